@@ -0,0 +1,221 @@
+//! Line diffs for version comparison and change markers.
+//!
+//! A plain Myers diff over the lines left after trimming the common prefix
+//! and suffix. Edits are usually small, so the search stays cheap; when two
+//! versions differ in more than [`MAX_EDIT_DISTANCE`] lines the middle is
+//! reported as one replacement instead of a minimal script.
+
+/// Edits beyond this fall back to delete-all/insert-all for the middle.
+pub const MAX_EDIT_DISTANCE: usize = 2000;
+
+/// One line of an edit script, by index into the old and new sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal { old: usize, new: usize },
+    Delete { old: usize },
+    Insert { new: usize },
+}
+
+/// How a line of the new version differs from the old one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineChange {
+    Added,
+    Modified,
+    /// Lines were removed just above this one (or below the last line).
+    Removed,
+}
+
+/// Edit script turning `old` into `new`, in order.
+pub fn diff_lines<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<DiffOp> = (0..prefix).map(|index| DiffOp::Equal { old: index, new: index }).collect();
+    match myers(old_middle, new_middle) {
+        Some(middle) => ops.extend(middle.into_iter().map(|op| match op {
+            DiffOp::Equal { old, new } => DiffOp::Equal {
+                old: old + prefix,
+                new: new + prefix,
+            },
+            DiffOp::Delete { old } => DiffOp::Delete { old: old + prefix },
+            DiffOp::Insert { new } => DiffOp::Insert { new: new + prefix },
+        })),
+        None => {
+            ops.extend((0..old_middle.len()).map(|index| DiffOp::Delete { old: index + prefix }));
+            ops.extend((0..new_middle.len()).map(|index| DiffOp::Insert { new: index + prefix }));
+        }
+    }
+    ops.extend((0..suffix).map(|index| DiffOp::Equal {
+        old: old.len() - suffix + index,
+        new: new.len() - suffix + index,
+    }));
+    ops
+}
+
+/// Per-line change markers for `new`, as shown in an editor gutter.
+pub fn line_changes<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Option<LineChange>> {
+    let mut changes = vec![None; new.len()];
+    let ops = diff_lines(old, new);
+    let mut index = 0;
+    while index < ops.len() {
+        if matches!(ops[index], DiffOp::Equal { .. }) {
+            index += 1;
+            continue;
+        }
+        let hunk_end = ops[index..]
+            .iter()
+            .position(|op| matches!(op, DiffOp::Equal { .. }))
+            .map_or(ops.len(), |offset| index + offset);
+        let hunk = &ops[index..hunk_end];
+        let deleted = hunk.iter().any(|op| matches!(op, DiffOp::Delete { .. }));
+        let inserted: Vec<usize> = hunk
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Insert { new } => Some(*new),
+                _ => None,
+            })
+            .collect();
+        if inserted.is_empty() {
+            // Mark the line that now sits where the removed lines were.
+            let row = match ops.get(hunk_end) {
+                Some(DiffOp::Equal { new, .. }) => Some(*new),
+                _ => new.len().checked_sub(1),
+            };
+            if let Some(row) = row {
+                changes[row].get_or_insert(LineChange::Removed);
+            }
+        } else {
+            let change = if deleted { LineChange::Modified } else { LineChange::Added };
+            for row in inserted {
+                changes[row] = Some(change);
+            }
+        }
+        index = hunk_end;
+    }
+    changes
+}
+
+/// Myers' O(ND) search. Returns `None` when the edit distance exceeds
+/// [`MAX_EDIT_DISTANCE`].
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Option<Vec<DiffOp>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (old.len() + new.len()).min(MAX_EDIT_DISTANCE) as isize;
+    // trace[d][k + d] is the furthest x reached on diagonal k after d edits.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    let snake = |mut x: isize, mut y: isize| {
+        while x < n && y < m && old[x as usize] == new[y as usize] {
+            x += 1;
+            y += 1;
+        }
+        x
+    };
+
+    'search: {
+        for d in 0..=max {
+            let mut furthest = vec![0isize; (2 * d + 1) as usize];
+            for k in (-d..=d).step_by(2) {
+                let x = if d == 0 {
+                    0
+                } else {
+                    let previous = &trace[(d - 1) as usize];
+                    let at = |k: isize| previous[(k + d - 1) as usize];
+                    if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+                        at(k + 1)
+                    } else {
+                        at(k - 1) + 1
+                    }
+                };
+                let x = snake(x, x - k);
+                furthest[(k + d) as usize] = x;
+                if x >= n && x - k >= m {
+                    trace.push(furthest);
+                    break 'search;
+                }
+            }
+            trace.push(furthest);
+        }
+        return None;
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[(d - 1) as usize];
+        let at = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+        let previous_x = at(previous_k);
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            ops.push(DiffOp::Equal {
+                old: x as usize,
+                new: y as usize,
+            });
+        }
+        if previous_k == k + 1 {
+            ops.push(DiffOp::Insert { new: previous_y as usize });
+        } else {
+            ops.push(DiffOp::Delete { old: previous_x as usize });
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        ops.push(DiffOp::Equal {
+            old: x as usize,
+            new: y as usize,
+        });
+    }
+    ops.reverse();
+    Some(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(old: &[&str], new: &[&str], ops: &[DiffOp]) -> Vec<String> {
+        ops.iter()
+            .filter_map(|op| match *op {
+                DiffOp::Equal { old: index, .. } => Some(old[index].to_string()),
+                DiffOp::Insert { new: index } => Some(new[index].to_string()),
+                DiffOp::Delete { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edit_script_rebuilds_the_new_version_minimally() {
+        let old = ["a", "b", "c", "a", "b", "b", "a"];
+        let new = ["c", "b", "a", "b", "a", "c"];
+        let ops = diff_lines(&old, &new);
+
+        assert_eq!(apply(&old, &new, &ops), new);
+        let edits = ops.iter().filter(|op| !matches!(op, DiffOp::Equal { .. })).count();
+        assert_eq!(edits, 5);
+        assert_eq!(diff_lines::<&str>(&[], &[]), []);
+        assert_eq!(diff_lines(&["x"], &[]), [DiffOp::Delete { old: 0 }]);
+    }
+
+    #[test]
+    fn line_changes_mark_added_modified_and_removed_lines() {
+        let old = ["# Title", "one", "two", "three", "four"];
+        let new = ["# Title", "one", "2", "three", "new", "four"];
+        assert_eq!(
+            line_changes(&old, &new),
+            [None, None, Some(LineChange::Modified), None, Some(LineChange::Added), None]
+        );
+
+        let new = ["# Title", "three", "four"];
+        assert_eq!(line_changes(&old, &new), [None, Some(LineChange::Removed), None]);
+        let new = ["# Title", "one"];
+        assert_eq!(line_changes(&old, &new), [None, Some(LineChange::Removed)]);
+    }
+}
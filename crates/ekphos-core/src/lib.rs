@@ -1,5 +1,6 @@
 //! Stable, platform-independent contracts shared by Ekphos subsystems.
 
+pub mod diff;
 pub mod markdown;
 
 use serde::{Deserialize, Serialize};
@@ -80,6 +81,8 @@ impl fmt::Display for VaultPath {
 pub struct FrontmatterSummary {
     pub title: Option<String>,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub date: Option<String>,
 }
 
@@ -42,6 +42,142 @@ pub fn frontmatter_end_in_lines<'a>(lines: impl IntoIterator<Item = &'a str>) ->
     lines.enumerate().find_map(|(index, line)| (line.trim() == "---").then_some(index + 1))
 }
 
+/// Rows of the section owned by the heading at or above `row`: the heading
+/// itself through the line before the next heading of the same or a higher
+/// level. Headings inside fenced code are ignored.
+pub fn heading_section(lines: &[&str], row: usize) -> Option<Range<usize>> {
+    let mut fence = None;
+    let mut owner: Option<(usize, usize)> = None;
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(marker) = fence_marker(line) {
+            if fence == Some(marker) {
+                fence = None;
+            } else if fence.is_none() {
+                fence = Some(marker);
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+        let Some(level) = heading(line).map(|heading| heading.level) else {
+            continue;
+        };
+        match owner {
+            Some((start, owner_level)) if index > row && level <= owner_level => return Some(start..index),
+            _ if index <= row => owner = Some((index, level)),
+            _ => {}
+        }
+    }
+    owner.map(|(start, _)| start..lines.len())
+}
+
+/// Every heading outside fenced code, with its row.
+pub fn headings<'a>(lines: &[&'a str]) -> Vec<(usize, Heading<'a>)> {
+    let mut fence = None;
+    let mut found = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(marker) = fence_marker(line) {
+            fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+        } else if fence.is_none() {
+            found.extend(heading(line).map(|heading| (index, heading)));
+        }
+    }
+    found
+}
+
+/// Headings whose text changed between two versions of a note, as
+/// `(old, new)` pairs. The outlines are compared position by position, so
+/// only edits that keep the number of headings are recognized, and a text
+/// that still appears elsewhere in the new outline counts as a reorder.
+pub fn renamed_headings(before: &[&str], after: &[&str]) -> Vec<(String, String)> {
+    let (before, after) = (headings(before), headings(after));
+    if before.len() != after.len() {
+        return Vec::new();
+    }
+    let present = |list: &[(usize, Heading)], text: &str| list.iter().any(|(_, heading)| heading.text.eq_ignore_ascii_case(text));
+    before
+        .iter()
+        .zip(&after)
+        .filter(|((_, old), (_, new))| {
+            old.level == new.level
+                && !old.text.eq_ignore_ascii_case(new.text)
+                && !old.text.is_empty()
+                && !new.text.is_empty()
+                && !present(&after, old.text)
+                && !present(&before, new.text)
+        })
+        .map(|((_, old), (_, new))| (old.text.to_string(), new.text.to_string()))
+        .collect()
+}
+
+/// A block ID ending a line, `text ^id` or a lone `^id`, returned with the
+/// byte offset of its caret. IDs are ASCII letters, digits and dashes.
+pub fn block_id(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim_end();
+    let caret = line.rfind('^')?;
+    let id = &line[caret + 1..];
+    let valid = !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-');
+    let separated = caret == 0 || line[..caret].ends_with(char::is_whitespace);
+    (valid && separated).then_some((caret, id))
+}
+
+/// A line with its block ID and the space before it removed.
+pub fn strip_block_id(line: &str) -> &str {
+    match block_id(line) {
+        Some((caret, _)) => line[..caret].trim_end(),
+        None => line,
+    }
+}
+
+/// Block IDs outside fenced code, with their rows.
+pub fn block_ids<'a>(lines: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let mut fence = None;
+    let mut found = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(marker) = fence_marker(line) {
+            fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+        } else if fence.is_none() {
+            found.extend(block_id(line).map(|(_, id)| (index, id)));
+        }
+    }
+    found
+}
+
+/// Rows of the block labelled `^id`. An ID ending a heading or list item
+/// labels that line alone; one ending a paragraph labels the paragraph; a
+/// lone `^id` line labels the paragraph above it.
+pub fn block_range(lines: &[&str], id: &str) -> Option<Range<usize>> {
+    let row = block_ids(lines).into_iter().find(|(_, found)| found.eq_ignore_ascii_case(id))?.0;
+    let line = lines[row];
+    let own_line = is_list_item(line) || heading(line).is_some();
+    let end = if line.trim_start().starts_with('^') { row } else { row + 1 };
+    if own_line {
+        return Some(row..end);
+    }
+    let mut start = end.min(row);
+    while start > 0 {
+        let above = lines[start - 1];
+        if above.trim().is_empty() || heading(above).is_some() || fence_marker(above).is_some() {
+            break;
+        }
+        start -= 1;
+        if is_list_item(above) {
+            break;
+        }
+    }
+    (start < end).then_some(start..end)
+}
+
+/// A bullet (`-`, `*`, `+`) or ordered (`1.`) list item.
+pub fn is_list_item(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let ordered = trimmed
+        .split_once(". ")
+        .is_some_and(|(number, _)| !number.is_empty() && number.bytes().all(|byte| byte.is_ascii_digit()));
+    ordered || ["- ", "* ", "+ "].iter().any(|marker| trimmed.starts_with(marker))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FenceMarker {
     Backtick,
@@ -271,6 +407,54 @@ pub fn bare_url_len(source: &str, start: usize) -> Option<usize> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn heading_section_stops_at_a_sibling_and_skips_fenced_headings() {
+        let lines = ["# Top", "intro", "## A", "a", "```", "# not a heading", "```", "### A.1", "## B", "b"];
+        assert_eq!(heading_section(&lines, 3), Some(2..8));
+        assert_eq!(heading_section(&lines, 7), Some(7..8));
+        assert_eq!(heading_section(&lines, 9), Some(8..10));
+        assert_eq!(heading_section(&lines, 1), Some(0..10));
+        assert_eq!(heading_section(&["no", "headings"], 1), None);
+    }
+
+    #[test]
+    fn renamed_headings_pair_changed_outline_entries() {
+        let before = ["# Top", "## Setup", "```", "## fenced", "```", "## Usage"];
+        let after = ["# Top", "## Installation", "```", "## changed", "```", "## Usage"];
+        assert_eq!(renamed_headings(&before, &after), [("Setup".to_string(), "Installation".to_string())]);
+        assert!(renamed_headings(&before, &["# Top", "## Usage", "## Setup"]).is_empty());
+        assert!(renamed_headings(&before, &["# Top", "## Usage"]).is_empty());
+    }
+
+    #[test]
+    fn block_ids_label_lines_paragraphs_and_the_block_above() {
+        assert_eq!(block_id("some text ^ab-12"), Some((10, "ab-12")));
+        assert_eq!(block_id("^lone"), Some((0, "lone")));
+        assert_eq!(block_id("x^2"), None);
+        assert_eq!(block_id("ends with ^"), None);
+        assert_eq!(strip_block_id("some text ^ab-12"), "some text");
+
+        let lines = [
+            "# Top",
+            "first line",
+            "second line ^para",
+            "",
+            "- item ^item",
+            "",
+            "quoted",
+            "more",
+            "^above",
+            "```",
+            "code ^fenced",
+            "```",
+        ];
+        assert_eq!(block_range(&lines, "para"), Some(1..3));
+        assert_eq!(block_range(&lines, "item"), Some(4..5));
+        assert_eq!(block_range(&lines, "above"), Some(6..8));
+        assert_eq!(block_range(&lines, "fenced"), None);
+        assert_eq!(block_ids(&lines).len(), 3);
+    }
+
     #[test]
     fn headings_match_supported_atx_boundaries() {
         assert_eq!(heading("# Title").unwrap(), Heading { level: 1, text: "Title" });
@@ -47,6 +47,12 @@ impl Editor {
             line_number_mode: LineNumberMode::Absolute,
             line_number_style: Style::default().fg(Color::DarkGray),
             line_number_width: 4, // Default width for line numbers
+            gutter_signs: None,
+            gutter_sign_styles: [
+                Style::default().fg(Color::Green),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Red),
+            ],
             scrolloff: 0,
             cursor_shape: CursorShape::Block,
         }
@@ -77,6 +83,24 @@ impl Editor {
         self.update_line_number_width();
     }
 
+    /// Show per-row change markers in a one-column gutter left of the line
+    /// numbers. `None` removes the column; rows past the end are unmarked.
+    pub fn set_gutter_signs(&mut self, signs: Option<Vec<Option<LineChange>>>) {
+        self.gutter_signs = signs;
+    }
+
+    pub fn set_gutter_sign_styles(&mut self, added: Style, modified: Style, removed: Style) {
+        self.gutter_sign_styles = [added, modified, removed];
+    }
+
+    pub fn gutter_sign(&self, row: usize) -> Option<LineChange> {
+        self.gutter_signs.as_ref()?.get(row).copied().flatten()
+    }
+
+    pub(super) fn sign_column_width(&self) -> u16 {
+        u16::from(self.gutter_signs.is_some())
+    }
+
     pub(super) fn update_line_number_width(&mut self) {
         if self.line_number_mode == LineNumberMode::None {
             self.line_number_width = 0;
@@ -247,6 +271,57 @@ impl Editor {
         }
     }
 
+    /// Replace rows `start_row..=end_row` with `lines` as a single undo step.
+    /// Empty `lines` deletes the rows.
+    pub fn replace_lines(&mut self, start_row: usize, end_row: usize, lines: Vec<String>) {
+        let end_row = end_row.min(self.buffer.line_count().saturating_sub(1));
+        if start_row > end_row {
+            return;
+        }
+        let old_lines: Vec<String> = (start_row..=end_row).filter_map(|row| self.buffer.line(row).map(str::to_string)).collect();
+        // The buffer always keeps at least one line.
+        let lines = if lines.is_empty() && old_lines.len() == self.buffer.line_count() {
+            vec![String::new()]
+        } else {
+            lines
+        };
+
+        // Insert before deleting so the buffer is never emptied midway.
+        let mut operations = Vec::new();
+        let inserted = lines.len();
+        if inserted > 0 {
+            operations.push(EditOperation::LineInsert { row: start_row, lines });
+        }
+        operations.push(EditOperation::LineDelete {
+            row: start_row + inserted,
+            lines: old_lines,
+        });
+        self.apply_grouped(operations, start_row);
+    }
+
+    /// Insert `lines` before `row` as a single undo step; `row` may equal the
+    /// line count to append.
+    pub fn insert_lines(&mut self, row: usize, lines: Vec<String>) {
+        if lines.is_empty() {
+            return;
+        }
+        let row = row.min(self.buffer.line_count());
+        self.apply_grouped(vec![EditOperation::LineInsert { row, lines }], row);
+    }
+
+    fn apply_grouped(&mut self, operations: Vec<EditOperation>, cursor_row: usize) {
+        let cursor_before = self.cursor.pos();
+        for operation in &operations {
+            self.apply_operation(operation);
+        }
+        let row = cursor_row.min(self.buffer.line_count().saturating_sub(1));
+        let cursor_after = Position::new(row, 0);
+        self.history.record_group(operations, cursor_before, cursor_after);
+        self.cursor.move_to(row, 0);
+        self.cursor.cancel_selection();
+        self.ensure_cursor_visible();
+    }
+
     pub fn visual_block_selected_text(&self) -> Option<String> {
         let (anchor, current) = self.visual_block_selection?;
         let (start_row, end_row) = if anchor.row <= current.row {
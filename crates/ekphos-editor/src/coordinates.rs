@@ -138,12 +138,14 @@ impl Editor {
         display_offset
     }
 
+    /// Width of the sign column and line numbers together.
     pub fn line_number_gutter_width(&self) -> u16 {
-        if self.line_number_mode != LineNumberMode::None {
+        let numbers = if self.line_number_mode != LineNumberMode::None {
             self.line_number_width
         } else {
             0
-        }
+        };
+        self.sign_column_width() + numbers
     }
     pub fn content_left_offset(&self) -> u16 {
         self.left_padding + self.line_number_gutter_width()
@@ -357,12 +359,7 @@ impl Editor {
     }
 
     pub fn content_x_offset(&self) -> u16 {
-        let gutter_width = if self.line_number_mode != LineNumberMode::None {
-            self.line_number_width
-        } else {
-            0
-        };
-        self.left_padding + gutter_width
+        self.left_padding + self.line_number_gutter_width()
     }
 
     pub fn visual_to_logical_coords(&self, visual_y: usize, visual_x: usize) -> (usize, usize) {
@@ -215,6 +215,25 @@ impl History {
         }
     }
 
+    /// Record several operations as one undo step that never merges with
+    /// neighbouring typing.
+    pub fn record_group(&mut self, operations: Vec<EditOperation>, cursor_before: Position, cursor_after: Position) {
+        if operations.is_empty() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push_back(HistoryEntry {
+            operations,
+            cursor_before,
+            cursor_after,
+            timestamp: Instant::now(),
+        });
+
+        while self.undo_stack.len() > self.max_entries {
+            self.undo_stack.pop_front();
+        }
+    }
+
     pub fn pop_undo(&mut self) -> Option<HistoryEntry> {
         if let Some(entry) = self.undo_stack.pop_back() {
             self.redo_stack.push(entry.clone());
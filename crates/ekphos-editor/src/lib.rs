@@ -16,6 +16,7 @@ use history::{EditOperation, History};
 use wrap::WrapCache;
 
 use crossterm::event::KeyEvent;
+pub use ekphos_core::diff::LineChange;
 use ratatui::{
     buffer::Buffer as RatatuiBuffer,
     layout::Rect,
@@ -406,6 +407,9 @@ pub struct Editor {
     line_number_mode: LineNumberMode,
     line_number_style: Style,
     line_number_width: u16,
+    // Change markers left of the line numbers; `Some` reserves the column
+    gutter_signs: Option<Vec<Option<LineChange>>>,
+    gutter_sign_styles: [Style; 3],
     // scrolloff, minimum lines above/below cursor
     scrolloff: usize,
     // Cursor shape for visual mode feedback
@@ -504,6 +508,43 @@ mod tests {
         assert_eq!(ed.selected_text().as_deref(), Some("Open "));
     }
 
+    #[test]
+    fn gutter_signs_reserve_a_column_before_line_numbers() {
+        let mut ed = Editor::new(vec!["a".to_string(), "b".to_string()]);
+        ed.set_line_number_mode(LineNumberMode::None);
+        assert_eq!(ed.content_left_offset(), 0);
+
+        ed.set_gutter_signs(Some(vec![None, Some(LineChange::Added)]));
+        assert_eq!(ed.content_left_offset(), 1);
+        let area = Rect::new(0, 0, 10, 2);
+        let mut buf = RatatuiBuffer::empty(area);
+        (&ed).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].symbol(), " ");
+        assert_eq!(buf[(0, 1)].symbol(), "▎");
+        assert_eq!(buf[(1, 1)].symbol(), "b");
+    }
+
+    #[test]
+    fn replace_and_insert_lines_are_single_undo_steps() {
+        let mut ed = Editor::new(vec!["c".to_string(), "b".to_string(), "a".to_string(), "end".to_string()]);
+        ed.replace_lines(0, 2, vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()]);
+        assert_eq!(ed.lines(), vec!["a", "b", "c", "d", "end"]);
+        ed.insert_lines(5, vec!["tail".to_string()]);
+        assert_eq!(ed.lines(), vec!["a", "b", "c", "d", "end", "tail"]);
+
+        ed.undo();
+        assert_eq!(ed.lines(), vec!["a", "b", "c", "d", "end"]);
+        ed.undo();
+        assert_eq!(ed.lines(), vec!["c", "b", "a", "end"]);
+        ed.redo();
+        assert_eq!(ed.lines(), vec!["a", "b", "c", "d", "end"]);
+
+        ed.replace_lines(0, 4, Vec::new());
+        assert_eq!(ed.lines(), vec![""]);
+        ed.undo();
+        assert_eq!(ed.lines(), vec!["a", "b", "c", "d", "end"]);
+    }
+
     /// Regression: deleting the LAST line with `dd` must be undoable. The recorded
     /// op used to target a row past the buffer end, so undo silently lost the text.
     #[test]
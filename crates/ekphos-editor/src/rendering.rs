@@ -48,6 +48,21 @@ impl Editor {
         }
     }
 
+    fn render_gutter_sign(&self, buf: &mut RatatuiBuffer, x: u16, y: u16, row: usize) {
+        let Some(sign) = self.gutter_sign(row) else {
+            return;
+        };
+        let (ch, style) = match sign {
+            LineChange::Added => ('▎', self.gutter_sign_styles[0]),
+            LineChange::Modified => ('▎', self.gutter_sign_styles[1]),
+            LineChange::Removed => ('▁', self.gutter_sign_styles[2]),
+        };
+        if let Some(cell) = buf.cell_mut((x, y)) {
+            cell.set_char(ch);
+            cell.set_style(style);
+        }
+    }
+
     /// Returns true if the cursor shape uses the terminal's native cursor (not rendered by editor)
     pub fn uses_native_cursor(&self) -> bool {
         matches!(self.cursor_shape, CursorShape::Bar)
@@ -55,12 +70,7 @@ impl Editor {
 
     fn render_wrapped(&self, area: Rect, buf: &mut RatatuiBuffer) {
         // Account for line number gutter
-        let gutter_width = if self.line_number_mode != LineNumberMode::None {
-            self.line_number_width
-        } else {
-            0
-        };
-        let content_start_x = area.x + self.left_padding + gutter_width;
+        let content_start_x = area.x + self.left_padding + self.line_number_gutter_width();
         let content_end_x = area.x + area.width.saturating_sub(self.right_padding);
         let content_width = content_end_x.saturating_sub(content_start_x) as usize;
         if content_width == 0 {
@@ -102,6 +112,8 @@ impl Editor {
             let is_cursor_line = row == cursor_pos.row;
             let chars: Vec<char> = line.chars().collect();
 
+            self.render_gutter_sign(buf, area.x + self.left_padding, screen_y, row);
+
             // Render line numbers if enabled (only for first visual line of a row)
             if let Some(ln_str) = self.get_line_number_str(row, cursor_pos.row) {
                 let ln_style = if is_cursor_line {
@@ -110,7 +122,7 @@ impl Editor {
                     self.line_number_style
                 };
                 for (i, ch) in ln_str.chars().enumerate() {
-                    if let Some(cell) = buf.cell_mut((area.x + self.left_padding + i as u16, screen_y)) {
+                    if let Some(cell) = buf.cell_mut((area.x + self.left_padding + self.sign_column_width() + i as u16, screen_y)) {
                         cell.set_char(ch);
                         cell.set_style(ln_style);
                     }
@@ -203,12 +215,7 @@ impl Editor {
 
     fn render_no_wrap(&self, area: Rect, buf: &mut RatatuiBuffer) {
         // Account for line number gutter
-        let gutter_width = if self.line_number_mode != LineNumberMode::None {
-            self.line_number_width
-        } else {
-            0
-        };
-        let content_start_x = area.x + self.left_padding + gutter_width;
+        let content_start_x = area.x + self.left_padding + self.line_number_gutter_width();
         let content_end_x = area.x + area.width.saturating_sub(self.right_padding);
 
         let cursor_pos = self.cursor.pos();
@@ -245,6 +252,8 @@ impl Editor {
             let chars: Vec<char> = line.chars().collect();
             let line_h_scroll = if is_cursor_line { h_scroll } else { 0 };
 
+            self.render_gutter_sign(buf, area.x + self.left_padding, y, row);
+
             // Render line numbers if enabled
             if let Some(ln_str) = self.get_line_number_str(row, cursor_pos.row) {
                 let ln_style = if is_cursor_line {
@@ -253,7 +262,7 @@ impl Editor {
                     self.line_number_style
                 };
                 for (i, ch) in ln_str.chars().enumerate() {
-                    if let Some(cell) = buf.cell_mut((area.x + self.left_padding + i as u16, y)) {
+                    if let Some(cell) = buf.cell_mut((area.x + self.left_padding + self.sign_column_width() + i as u16, y)) {
                         cell.set_char(ch);
                         cell.set_style(ln_style);
                     }
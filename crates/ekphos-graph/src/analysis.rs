@@ -0,0 +1,260 @@
+//! Whole-graph analysis over the compact adjacency: shortest paths,
+//! centrality rankings and community detection.
+//!
+//! Everything here treats links as undirected except PageRank, which follows
+//! link direction.  Tag and attachment nodes are skipped so that a popular tag
+//! does not become the "most central note" or glue every community together.
+
+use std::collections::{HashMap, VecDeque};
+
+use ekphos_core::NoteId;
+
+use crate::{CompactAdjacency, GraphIndex, GraphNodeKind};
+
+const PAGERANK_DAMPING: f32 = 0.85;
+const PAGERANK_MAX_ITERATIONS: usize = 64;
+const PAGERANK_TOLERANCE: f32 = 1.0e-6;
+/// Above this many notes betweenness is estimated from evenly spaced pivots.
+const BETWEENNESS_EXACT_LIMIT: usize = 2_000;
+const BETWEENNESS_PIVOTS: usize = 256;
+const LABEL_PROPAGATION_MAX_ROUNDS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CentralityMetric {
+    #[default]
+    PageRank,
+    Betweenness,
+}
+
+impl CentralityMetric {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PageRank => "PageRank",
+            Self::Betweenness => "betweenness",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::PageRank => Self::Betweenness,
+            Self::Betweenness => Self::PageRank,
+        }
+    }
+}
+
+impl GraphIndex {
+    /// Fewest-hops route between two notes, ignoring link direction.
+    /// Includes both endpoints; `None` when they are not connected.
+    pub fn shortest_path(&self, from: NoteId, to: NoteId) -> Option<Vec<NoteId>> {
+        let start = self.node_for_note(from)?;
+        let goal = self.node_for_note(to)?;
+        if start == goal {
+            return Some(vec![from]);
+        }
+        let mut parents = vec![u32::MAX; self.nodes.len()];
+        parents[start] = start as u32;
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &next in self.outgoing.get(node).iter().chain(self.incoming.get(node)) {
+                let next_idx = next as usize;
+                if parents[next_idx] != u32::MAX || self.nodes[next_idx].kind != GraphNodeKind::Note {
+                    continue;
+                }
+                parents[next_idx] = node as u32;
+                if next_idx == goal {
+                    let mut path = vec![self.nodes[goal].note_id];
+                    let mut current = goal;
+                    while current != start {
+                        current = parents[current] as usize;
+                        path.push(self.nodes[current].note_id);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next_idx);
+            }
+        }
+        None
+    }
+
+    /// Scores for every index node; non-note nodes score zero.
+    pub fn centrality(&self, metric: CentralityMetric) -> Vec<f32> {
+        match metric {
+            CentralityMetric::PageRank => self.pagerank(),
+            CentralityMetric::Betweenness => self.betweenness(),
+        }
+    }
+
+    /// Notes ordered by descending score, at most `limit` of them.
+    pub fn most_central(&self, metric: CentralityMetric, limit: usize) -> Vec<(NoteId, f32)> {
+        let scores = self.centrality(metric);
+        let mut ranked: Vec<_> = self
+            .nodes
+            .iter()
+            .zip(scores)
+            .filter(|(node, _)| node.kind == GraphNodeKind::Note)
+            .map(|(node, score)| (node.note_id, score))
+            .collect();
+        ranked.sort_by(|left, right| right.1.total_cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+        ranked.truncate(limit);
+        ranked
+    }
+
+    pub fn pagerank(&self) -> Vec<f32> {
+        let notes = self.note_nodes();
+        let mut scores = vec![0.0; self.nodes.len()];
+        if notes.is_empty() {
+            return scores;
+        }
+        let count = notes.len() as f32;
+        let out_counts: Vec<usize> = (0..self.nodes.len()).map(|idx| self.note_neighbors(&self.outgoing, idx).count()).collect();
+        for &idx in &notes {
+            scores[idx] = 1.0 / count;
+        }
+        let mut next = vec![0.0; self.nodes.len()];
+        for _ in 0..PAGERANK_MAX_ITERATIONS {
+            let dangling: f32 = notes.iter().filter(|&&idx| out_counts[idx] == 0).map(|&idx| scores[idx]).sum();
+            let base = (1.0 - PAGERANK_DAMPING) / count + PAGERANK_DAMPING * dangling / count;
+            for &idx in &notes {
+                let incoming: f32 = self
+                    .note_neighbors(&self.incoming, idx)
+                    .map(|source| scores[source] / out_counts[source] as f32)
+                    .sum();
+                next[idx] = base + PAGERANK_DAMPING * incoming;
+            }
+            let delta: f32 = notes.iter().map(|&idx| (next[idx] - scores[idx]).abs()).sum();
+            std::mem::swap(&mut scores, &mut next);
+            if delta < PAGERANK_TOLERANCE {
+                break;
+            }
+        }
+        scores
+    }
+
+    /// Brandes betweenness on the undirected note graph.  Large vaults use a
+    /// deterministic pivot sample scaled back up, so rankings stay stable.
+    pub fn betweenness(&self) -> Vec<f32> {
+        let notes = self.note_nodes();
+        let mut scores = vec![0.0f64; self.nodes.len()];
+        if notes.len() < 3 {
+            return vec![0.0; self.nodes.len()];
+        }
+        let pivots: Vec<usize> = if notes.len() <= BETWEENNESS_EXACT_LIMIT {
+            notes.clone()
+        } else {
+            (0..BETWEENNESS_PIVOTS).map(|step| notes[step * notes.len() / BETWEENNESS_PIVOTS]).collect()
+        };
+        let neighbors: Vec<Vec<usize>> = (0..self.nodes.len())
+            .map(|idx| {
+                let mut list: Vec<usize> = self
+                    .note_neighbors(&self.outgoing, idx)
+                    .chain(self.note_neighbors(&self.incoming, idx))
+                    .collect();
+                list.sort_unstable();
+                list.dedup();
+                list
+            })
+            .collect();
+
+        let mut stack = Vec::with_capacity(notes.len());
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        let mut paths = vec![0.0f64; self.nodes.len()];
+        let mut distance = vec![-1i64; self.nodes.len()];
+        let mut dependency = vec![0.0f64; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        for &source in &pivots {
+            for &idx in &notes {
+                predecessors[idx].clear();
+                paths[idx] = 0.0;
+                distance[idx] = -1;
+                dependency[idx] = 0.0;
+            }
+            paths[source] = 1.0;
+            distance[source] = 0;
+            queue.push_back(source);
+            while let Some(node) = queue.pop_front() {
+                stack.push(node);
+                for &next in &neighbors[node] {
+                    if distance[next] < 0 {
+                        distance[next] = distance[node] + 1;
+                        queue.push_back(next);
+                    }
+                    if distance[next] == distance[node] + 1 {
+                        paths[next] += paths[node];
+                        predecessors[next].push(node);
+                    }
+                }
+            }
+            while let Some(node) = stack.pop() {
+                for &previous in &predecessors[node] {
+                    dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    scores[node] += dependency[node];
+                }
+            }
+        }
+        // Every undirected path is counted from both ends.
+        let scale = notes.len() as f64 / pivots.len() as f64 / 2.0;
+        scores.into_iter().map(|score| (score * scale) as f32).collect()
+    }
+
+    fn note_nodes(&self) -> Vec<usize> {
+        (0..self.nodes.len()).filter(|&idx| self.nodes[idx].kind == GraphNodeKind::Note).collect()
+    }
+
+    fn note_neighbors<'a>(&'a self, adjacency: &'a CompactAdjacency, node: usize) -> impl Iterator<Item = usize> + 'a {
+        adjacency
+            .get(node)
+            .iter()
+            .map(|&next| next as usize)
+            .filter(|&next| self.nodes[next].kind == GraphNodeKind::Note)
+    }
+}
+
+/// Asynchronous label propagation over note-to-note links.  Ties go to the
+/// smallest label so the result is deterministic for a given index.  Clusters
+/// are numbered from 1 by descending size; non-note nodes get cluster 0.
+pub(crate) fn detect_clusters(kinds: &[GraphNodeKind], outgoing: &[Vec<usize>], incoming: &[Vec<usize>]) -> Vec<u32> {
+    let is_note = |idx: usize| kinds[idx] == GraphNodeKind::Note;
+    let mut labels: Vec<usize> = (0..kinds.len()).collect();
+    let mut counts: HashMap<usize, u32> = HashMap::new();
+    for _ in 0..LABEL_PROPAGATION_MAX_ROUNDS {
+        let mut changed = false;
+        for node in (0..kinds.len()).filter(|&idx| is_note(idx)) {
+            counts.clear();
+            for &next in outgoing[node].iter().chain(&incoming[node]) {
+                if is_note(next) {
+                    *counts.entry(labels[next]).or_default() += 1;
+                }
+            }
+            let best = counts
+                .iter()
+                .max_by(|left, right| left.1.cmp(right.1).then_with(|| right.0.cmp(left.0)))
+                .map(|(&label, _)| label);
+            if let Some(best) = best {
+                if best != labels[node] {
+                    labels[node] = best;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut sizes: HashMap<usize, (usize, usize)> = HashMap::new();
+    for node in (0..kinds.len()).filter(|&idx| is_note(idx)) {
+        let entry = sizes.entry(labels[node]).or_insert((0, node));
+        entry.0 += 1;
+    }
+    let mut order: Vec<_> = sizes.into_iter().collect();
+    order.sort_by(|left, right| right.1 .0.cmp(&left.1 .0).then(left.1 .1.cmp(&right.1 .1)));
+    let numbers: HashMap<usize, u32> = order
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (label, _))| (label, u32::try_from(rank + 1).unwrap_or(u32::MAX)))
+        .collect();
+    (0..kinds.len()).map(|idx| if is_note(idx) { numbers[&labels[idx]] } else { 0 }).collect()
+}
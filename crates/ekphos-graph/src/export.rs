@@ -0,0 +1,341 @@
+//! Serialize a graph projection for external tools such as Graphviz, Gephi
+//! and networkx.
+//!
+//! Every format is a directed graph keyed by vault path. A bidirectional link
+//! is written as two directed edges so in- and out-degrees survive the round
+//! trip.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{GraphEdge, GraphIndex, GraphNode, TERMINAL_X_ASPECT};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphExportFormat {
+    Dot,
+    GraphMl,
+    Json,
+}
+
+impl GraphExportFormat {
+    pub const ALL: [Self; 3] = [Self::Dot, Self::GraphMl, Self::Json];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "dot" | "gv" => Some(Self::Dot),
+            "graphml" => Some(Self::GraphMl),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dot => "DOT",
+            Self::GraphMl => "GraphML",
+            Self::Json => "JSON",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::GraphMl => "graphml",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphExportOptions {
+    pub format: GraphExportFormat,
+    /// Include layout coordinates. `x` is divided by the terminal cell aspect
+    /// so the exported layout has the proportions seen on screen.
+    pub positions: bool,
+}
+
+struct ExportNode<'a> {
+    id: &'a str,
+    kind: &'static str,
+    title: &'a str,
+    tags: &'a [String],
+    in_degree: u32,
+    out_degree: u32,
+    position: (f32, f32),
+}
+
+/// Render projected `nodes` and `edges` in the requested format. Nodes missing
+/// from `index` are skipped along with their edges.
+pub fn export_graph(index: &GraphIndex, nodes: &[GraphNode], edges: &[GraphEdge], options: GraphExportOptions) -> String {
+    let export_nodes: Vec<Option<ExportNode>> = nodes
+        .iter()
+        .map(|node| {
+            let metadata = index.metadata_for_note(node.note_id)?;
+            Some(ExportNode {
+                id: &metadata.path,
+                kind: metadata.kind.label(),
+                title: &metadata.title,
+                tags: &metadata.tags,
+                in_degree: node.in_degree,
+                out_degree: node.out_degree,
+                position: (node.x / TERMINAL_X_ASPECT, node.y),
+            })
+        })
+        .collect();
+    let mut directed = Vec::with_capacity(edges.len());
+    for edge in edges {
+        let (Some(Some(from)), Some(Some(to))) = (export_nodes.get(edge.from_index()), export_nodes.get(edge.to_index())) else {
+            continue;
+        };
+        directed.push((from.id, to.id));
+        if edge.bidirectional {
+            directed.push((to.id, from.id));
+        }
+    }
+    let nodes: Vec<_> = export_nodes.into_iter().flatten().collect();
+    match options.format {
+        GraphExportFormat::Dot => to_dot(&nodes, &directed, options.positions),
+        GraphExportFormat::GraphMl => to_graphml(&nodes, &directed, options.positions),
+        GraphExportFormat::Json => to_json(&nodes, &directed, options.positions),
+    }
+}
+
+fn to_dot(nodes: &[ExportNode], edges: &[(&str, &str)], positions: bool) -> String {
+    let mut out = String::from("digraph ekphos {\n");
+    for node in nodes {
+        let _ = write!(
+            out,
+            "  {} [label={}, kind={}, tags={}, in_degree={}, out_degree={}, degree={}",
+            dot_string(node.id),
+            dot_string(node.title),
+            node.kind,
+            dot_string(&node.tags.join(",")),
+            node.in_degree,
+            node.out_degree,
+            node.in_degree + node.out_degree,
+        );
+        if positions {
+            let _ = write!(out, ", pos=\"{},{}\"", node.position.0, node.position.1);
+        }
+        out.push_str("];\n");
+    }
+    for (from, to) in edges {
+        let _ = writeln!(out, "  {} -> {};", dot_string(from), dot_string(to));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn dot_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn to_graphml(nodes: &[ExportNode], edges: &[(&str, &str)], positions: bool) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    let mut keys = vec![
+        ("label", "string"),
+        ("kind", "string"),
+        ("tags", "string"),
+        ("in_degree", "int"),
+        ("out_degree", "int"),
+        ("degree", "int"),
+    ];
+    if positions {
+        keys.extend([("x", "float"), ("y", "float")]);
+    }
+    for (name, kind) in keys {
+        let _ = writeln!(out, "  <key id=\"{name}\" for=\"node\" attr.name=\"{name}\" attr.type=\"{kind}\"/>");
+    }
+    out.push_str("  <graph id=\"ekphos\" edgedefault=\"directed\">\n");
+    for node in nodes {
+        let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(node.id));
+        let _ = writeln!(out, "      <data key=\"label\">{}</data>", xml_escape(node.title));
+        let _ = writeln!(out, "      <data key=\"kind\">{}</data>", node.kind);
+        let _ = writeln!(out, "      <data key=\"tags\">{}</data>", xml_escape(&node.tags.join(",")));
+        let _ = writeln!(out, "      <data key=\"in_degree\">{}</data>", node.in_degree);
+        let _ = writeln!(out, "      <data key=\"out_degree\">{}</data>", node.out_degree);
+        let _ = writeln!(out, "      <data key=\"degree\">{}</data>", node.in_degree + node.out_degree);
+        if positions {
+            let _ = writeln!(out, "      <data key=\"x\">{}</data>", node.position.0);
+            let _ = writeln!(out, "      <data key=\"y\">{}</data>", node.position.1);
+        }
+        out.push_str("    </node>\n");
+    }
+    for (from, to) in edges {
+        let _ = writeln!(out, "    <edge source=\"{}\" target=\"{}\"/>", xml_escape(from), xml_escape(to));
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// networkx `node_link_data` layout, readable with `node_link_graph(data, edges="links")`.
+#[derive(Serialize)]
+struct JsonGraph<'a> {
+    directed: bool,
+    multigraph: bool,
+    graph: JsonGraphAttributes,
+    nodes: Vec<JsonNode<'a>>,
+    links: Vec<JsonLink<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonGraphAttributes {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    id: &'a str,
+    kind: &'static str,
+    title: &'a str,
+    tags: &'a [String],
+    in_degree: u32,
+    out_degree: u32,
+    degree: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct JsonLink<'a> {
+    source: &'a str,
+    target: &'a str,
+}
+
+fn to_json(nodes: &[ExportNode], edges: &[(&str, &str)], positions: bool) -> String {
+    let graph = JsonGraph {
+        directed: true,
+        multigraph: false,
+        graph: JsonGraphAttributes { name: "ekphos" },
+        nodes: nodes
+            .iter()
+            .map(|node| JsonNode {
+                id: node.id,
+                kind: node.kind,
+                title: node.title,
+                tags: node.tags,
+                in_degree: node.in_degree,
+                out_degree: node.out_degree,
+                degree: node.in_degree + node.out_degree,
+                x: positions.then_some(node.position.0),
+                y: positions.then_some(node.position.1),
+            })
+            .collect(),
+        links: edges.iter().map(|&(source, target)| JsonLink { source, target }).collect(),
+    };
+    let mut json = serde_json::to_string_pretty(&graph).unwrap_or_default();
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GraphFilter, GraphLinkScope, GraphMode, GraphSourceMetadata, GraphSources};
+    use ekphos_core::NoteId;
+
+    fn index() -> GraphIndex {
+        let notes = [
+            ("Home", "Home", "[[Topic]] and [[Q&A]]", vec!["start".to_string()]),
+            ("Topic", "sub/Topic", "[[Home]]", vec!["a".to_string(), "b".to_string()]),
+            ("Q&A", "Q&A", "", Vec::new()),
+        ];
+        let mut bodies = Vec::new();
+        let sources = notes
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (title, path, body, tags))| {
+                bodies.push(body.to_string());
+                GraphSourceMetadata {
+                    note_id: NoteId::from_index(idx).unwrap(),
+                    title: title.to_string(),
+                    path: path.to_string(),
+                    tags,
+                    aliases: Vec::new(),
+                    date: None,
+                }
+            })
+            .collect();
+        GraphIndex::build_from_loader(sources, GraphSources::default(), |note_id| bodies.get(note_id.as_index()).cloned())
+    }
+
+    fn export(format: GraphExportFormat, positions: bool) -> String {
+        let index = index();
+        let mut projection = index.project(GraphMode::Global, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::default(), true);
+        for (idx, node) in projection.nodes.iter_mut().enumerate() {
+            node.x = idx as f32 * 4.0;
+            node.y = idx as f32;
+        }
+        export_graph(&index, &projection.nodes, &projection.edges, GraphExportOptions { format, positions })
+    }
+
+    #[test]
+    fn formats_parse_from_cli_names() {
+        assert_eq!(GraphExportFormat::parse("DOT"), Some(GraphExportFormat::Dot));
+        assert_eq!(GraphExportFormat::parse("graphml"), Some(GraphExportFormat::GraphMl));
+        assert_eq!(GraphExportFormat::parse("json"), Some(GraphExportFormat::Json));
+        assert_eq!(GraphExportFormat::parse("gexf"), None);
+    }
+
+    #[test]
+    fn dot_expands_bidirectional_links_and_escapes_ids() {
+        let dot = export(GraphExportFormat::Dot, false);
+        assert!(dot.contains("\"Home\" -> \"sub/Topic\";"));
+        assert!(dot.contains("\"sub/Topic\" -> \"Home\";"));
+        assert!(dot.contains("\"Home\" -> \"Q&A\";"));
+        assert!(dot.contains("kind=note, tags=\"a,b\", in_degree=1, out_degree=1, degree=2"));
+        assert!(!dot.contains("pos="));
+        assert!(export(GraphExportFormat::Dot, true).contains("pos=\"2,1\""));
+    }
+
+    #[test]
+    fn graphml_escapes_markup_and_declares_position_keys() {
+        let graphml = export(GraphExportFormat::GraphMl, true);
+        assert!(graphml.contains("<node id=\"Q&amp;A\">"));
+        assert!(graphml.contains("<edge source=\"Home\" target=\"Q&amp;A\"/>"));
+        assert!(graphml.contains("attr.name=\"x\""));
+        assert_eq!(graphml.matches("<edge ").count(), 3);
+    }
+
+    #[test]
+    fn json_is_node_link_data() {
+        let value: serde_json::Value = serde_json::from_str(&export(GraphExportFormat::Json, false)).unwrap();
+        assert_eq!(value["directed"], true);
+        assert_eq!(value["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(value["links"].as_array().unwrap().len(), 3);
+        assert_eq!(value["nodes"][0]["tags"][0], "start");
+        assert!(value["nodes"][0].get("x").is_none());
+
+        let positioned: serde_json::Value = serde_json::from_str(&export(GraphExportFormat::Json, true)).unwrap();
+        assert_eq!(positioned["nodes"][1]["x"], 2.0);
+    }
+}
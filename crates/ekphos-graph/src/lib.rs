@@ -5,7 +5,15 @@
 //! separate: a vault-wide index is built once, cheap projections choose what is
 //! visible, and layout never runs on the render path.
 
+mod analysis;
+mod export;
+mod pins;
+mod timeline;
 mod worker;
+pub use analysis::CentralityMetric;
+pub use export::{export_graph, GraphExportFormat, GraphExportOptions};
+pub use pins::GraphPins;
+pub use timeline::{day_from_system_time, format_day, parse_day};
 pub use worker::{GraphResponse, GraphWorker};
 
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -17,7 +25,7 @@ use ekphos_core::NoteId;
 use serde::{Deserialize, Serialize};
 
 const LAYOUT_CACHE_VERSION: u32 = 3;
-const INDEX_CACHE_VERSION: u32 = 1;
+const INDEX_CACHE_VERSION: u32 = 2;
 const TERMINAL_X_ASPECT: f32 = 2.0;
 pub const GRAPH_MAX_ZOOM: f32 = 2.5;
 const GRAPH_MIN_ZOOM: f32 = 0.000_01;
@@ -84,6 +92,52 @@ pub enum GraphRelation {
     Bidirectional,
     #[default]
     Neutral,
+    /// A tag node; always reported as such, whatever the direction.
+    Tag,
+    /// An image or PDF node; always reported as such, whatever the direction.
+    Attachment,
+}
+
+/// What a graph node stands for. Tag and attachment nodes only exist when
+/// their [`GraphSources`] switch is on, and carry a synthetic [`NoteId`] that
+/// never matches a note in the vault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum GraphNodeKind {
+    #[default]
+    Note,
+    Tag,
+    Attachment,
+}
+
+impl GraphNodeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Tag => "tag",
+            Self::Attachment => "attachment",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "note" | "notes" => Some(Self::Note),
+            "tag" | "tags" => Some(Self::Tag),
+            "attachment" | "attachments" => Some(Self::Attachment),
+            _ => None,
+        }
+    }
+}
+
+/// Which links become edges besides `[[wiki]]` links between notes. All
+/// off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphSources {
+    /// `[text](other-note.md)` links, resolved relative to the linking note.
+    pub markdown_links: bool,
+    /// One node per tag, linked from every note carrying it.
+    pub tags: bool,
+    /// One node per linked or embedded image or PDF.
+    pub attachments: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -93,6 +147,11 @@ pub struct GraphSourceMetadata {
     /// Vault-relative path without the `.md` suffix.
     pub path: String,
     pub tags: Vec<String>,
+    /// Frontmatter aliases; links naming one resolve to this note.
+    pub aliases: Vec<String>,
+    /// Day number (see [`parse_day`]): frontmatter `date`, else the file's
+    /// creation or modification day.
+    pub date: Option<i32>,
 }
 
 #[cfg(test)]
@@ -102,6 +161,8 @@ struct GraphSourceNote {
     title: String,
     path: String,
     tags: Vec<String>,
+    aliases: Vec<String>,
+    date: Option<i32>,
     content: String,
 }
 
@@ -121,11 +182,17 @@ pub struct GraphSourceFile {
 #[derive(Debug, Clone)]
 pub struct GraphIndexNode {
     pub note_id: NoteId,
+    pub kind: GraphNodeKind,
     pub title: String,
     pub path: String,
     pub tags: Vec<String>,
     pub in_degree: usize,
     pub out_degree: usize,
+    /// Community from label propagation, numbered by size; 0 for non-notes.
+    pub cluster: u32,
+    /// Day number of a note; a tag or attachment takes the earliest date of
+    /// the notes linking to it.
+    pub date: Option<i32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -181,6 +248,11 @@ pub struct GraphIndex {
 impl GraphIndex {
     #[cfg(test)]
     fn build(sources: Vec<GraphSourceNote>) -> Self {
+        Self::build_with(sources, GraphSources::default())
+    }
+
+    #[cfg(test)]
+    fn build_with(sources: Vec<GraphSourceNote>, graph_sources: GraphSources) -> Self {
         let mut bodies = HashMap::with_capacity(sources.len());
         let metadata = sources
             .into_iter()
@@ -191,10 +263,12 @@ impl GraphIndex {
                     title: source.title,
                     path: source.path,
                     tags: source.tags,
+                    aliases: source.aliases,
+                    date: source.date,
                 }
             })
             .collect();
-        Self::build_from_loader(metadata, |note_id| bodies.remove(&note_id))
+        Self::build_from_loader(metadata, graph_sources, |note_id| bodies.remove(&note_id))
     }
 
     pub fn retained_bytes(&self) -> usize {
@@ -222,6 +296,7 @@ pub struct GraphNode {
     pub relation: GraphRelation,
     pub in_degree: u32,
     pub out_degree: u32,
+    pub cluster: u32,
 }
 
 impl GraphNode {
@@ -330,6 +405,14 @@ enum FilterKind {
     Text(String),
     Path(String),
     Tag(String),
+    Kind(GraphNodeKind),
+    Cluster(u32),
+    /// Dated before the first day of the period.
+    Before(i32),
+    /// Dated after the last day of the period.
+    After(i32),
+    /// Dated on or before the day, or undated: the timeline slider.
+    Until(i32),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -363,6 +446,14 @@ impl GraphFilter {
                         return None;
                     }
                     FilterKind::Path(path.to_lowercase())
+                } else if let Some(kind) = token.strip_prefix("kind:").and_then(GraphNodeKind::parse) {
+                    FilterKind::Kind(kind)
+                } else if let Some(cluster) = token.strip_prefix("cluster:").and_then(|value| value.parse().ok()) {
+                    FilterKind::Cluster(cluster)
+                } else if let Some((first, _)) = token.strip_prefix("before:").and_then(timeline::parse_period) {
+                    FilterKind::Before(first)
+                } else if let Some((_, last)) = token.strip_prefix("after:").and_then(timeline::parse_period) {
+                    FilterKind::After(last)
                 } else {
                     FilterKind::Text(token.to_lowercase())
                 };
@@ -375,6 +466,16 @@ impl GraphFilter {
         }
     }
 
+    /// Also hide nodes dated after `day`, as the timeline slider does.
+    /// Undated nodes stay; `before:` in the query still drops them.
+    pub fn dated_until(mut self, day: i32) -> Self {
+        self.terms.push(FilterTerm {
+            exclude: false,
+            kind: FilterKind::Until(day),
+        });
+        self
+    }
+
     pub fn matches(&self, node: &GraphIndexNode) -> bool {
         if self.terms.is_empty() {
             return true;
@@ -386,6 +487,11 @@ impl GraphFilter {
                 FilterKind::Text(value) => title.contains(value) || path.contains(value),
                 FilterKind::Path(value) => path.contains(value),
                 FilterKind::Tag(value) => node.tags.iter().any(|tag| tag.trim_start_matches('#').eq_ignore_ascii_case(value)),
+                FilterKind::Kind(kind) => node.kind == *kind,
+                FilterKind::Cluster(cluster) => node.cluster == *cluster,
+                FilterKind::Before(day) => node.date.is_some_and(|date| date < *day),
+                FilterKind::After(day) => node.date.is_some_and(|date| date > *day),
+                FilterKind::Until(day) => node.date.is_none_or(|date| date <= *day),
             };
             if term.exclude {
                 !matched
@@ -422,6 +528,8 @@ struct CachedFileSummary {
     normalized_path: String,
     fingerprint: GraphFileFingerprint,
     targets: Vec<String>,
+    /// Raw `[text](destination)` and `![alt](destination)` destinations.
+    markdown_targets: Vec<String>,
     tags: Vec<String>,
 }
 
@@ -440,25 +548,33 @@ pub struct GraphBuildOutcome {
 
 impl GraphIndex {
     /// Build an index while retaining at most one source body at a time.
-    pub fn build_from_loader(sources: Vec<GraphSourceMetadata>, mut load_body: impl FnMut(NoteId) -> Option<String>) -> Self {
+    pub fn build_from_loader(sources: Vec<GraphSourceMetadata>, options: GraphSources, mut load_body: impl FnMut(NoteId) -> Option<String>) -> Self {
         let summaries: Vec<_> = sources
             .iter()
             .map(|source| {
-                let targets = load_body(source.note_id).map_or_else(Vec::new, |content| extract_wiki_targets(&content));
+                let content = load_body(source.note_id).unwrap_or_default();
                 CachedFileSummary {
                     normalized_path: normalize_wiki_path(&source.path),
                     fingerprint: GraphFileFingerprint { size: 0, modified_nanos: 0 },
-                    targets,
+                    targets: extract_wiki_targets(&content),
+                    markdown_targets: extract_markdown_targets(&content),
                     tags: source.tags.clone(),
                 }
             })
             .collect();
-        Self::build_from_summaries(sources, &summaries)
+        Self::build_from_summaries(sources, &summaries, options)
     }
 
     /// Incrementally build from a durable per-file extraction cache. Only a
     /// changed file body is resident while Markdown links are extracted.
-    pub fn load_or_build(sources: Vec<GraphSourceFile>, cache_path: &Path, mut cancelled: impl FnMut() -> bool) -> Option<GraphBuildOutcome> {
+    /// The cache holds every link kind, so changing `options` never forces a
+    /// re-parse.
+    pub fn load_or_build(
+        sources: Vec<GraphSourceFile>,
+        options: GraphSources,
+        cache_path: &Path,
+        mut cancelled: impl FnMut() -> bool,
+    ) -> Option<GraphBuildOutcome> {
         let loaded = load_index_cache(cache_path);
         let cache_valid = loaded.is_some();
         let mut cached: HashMap<String, CachedFileSummary> = loaded
@@ -486,6 +602,7 @@ impl GraphIndex {
                         normalized_path,
                         fingerprint: source.fingerprint,
                         targets: extract_wiki_targets(&content),
+                        markdown_targets: extract_markdown_targets(&content),
                         tags: source.metadata.tags.clone(),
                     }
                 }
@@ -499,7 +616,7 @@ impl GraphIndex {
         if cancelled() {
             return None;
         }
-        let index = Self::build_from_summaries(metadata, &summaries);
+        let index = Self::build_from_summaries(metadata, &summaries, options);
         if parsed_files > 0 || !cached.is_empty() || !cache_valid {
             save_index_cache(
                 cache_path,
@@ -516,14 +633,14 @@ impl GraphIndex {
         })
     }
 
-    fn build_from_summaries(sources: Vec<GraphSourceMetadata>, summaries: &[CachedFileSummary]) -> Self {
-        let node_count = sources.len();
-        if node_count > u32::MAX as usize || summaries.len() != node_count {
+    fn build_from_summaries(sources: Vec<GraphSourceMetadata>, summaries: &[CachedFileSummary], options: GraphSources) -> Self {
+        let note_count = sources.len();
+        if note_count > u32::MAX as usize || summaries.len() != note_count {
             return Self::default();
         }
-        let mut root_titles = HashMap::with_capacity(node_count);
-        let mut all_titles = HashMap::with_capacity(node_count);
-        let mut paths = HashMap::with_capacity(node_count);
+        let mut root_titles = HashMap::with_capacity(note_count);
+        let mut all_titles = HashMap::with_capacity(note_count);
+        let mut paths = HashMap::with_capacity(note_count);
 
         for (idx, source) in sources.iter().enumerate() {
             let title_key = source.title.to_lowercase();
@@ -533,26 +650,75 @@ impl GraphIndex {
             }
             paths.entry(normalize_wiki_path(&source.path)).or_insert(idx);
         }
+        // Aliases only answer names no title claims, matching link navigation.
+        let mut aliases = HashMap::new();
+        for (idx, source) in sources.iter().enumerate() {
+            for alias in &source.aliases {
+                aliases.entry(alias.to_lowercase()).or_insert(idx);
+            }
+        }
 
         let mut directed = HashSet::new();
+        let mut virtual_links = Vec::new();
         for (from, summary) in summaries.iter().enumerate() {
             let mut targets_for_note = HashSet::new();
+            let mut link = |to: Option<usize>| {
+                if let Some(to) = to {
+                    if to != from && targets_for_note.insert(to) {
+                        directed.insert((from, to));
+                    }
+                }
+            };
             for target in &summary.targets {
                 let normalized = normalize_wiki_path(target);
-                let to = if normalized.contains('/') {
+                if is_attachment_path(&normalized) {
+                    if options.attachments {
+                        virtual_links.push((from, GraphNodeKind::Attachment, normalized));
+                    }
+                    continue;
+                }
+                link(if normalized.contains('/') {
                     paths.get(&normalized).copied()
                 } else {
                     let key = normalized.to_lowercase();
-                    root_titles.get(&key).or_else(|| all_titles.get(&key)).copied()
+                    root_titles.get(&key).or_else(|| all_titles.get(&key)).or_else(|| aliases.get(&key)).copied()
+                });
+            }
+            for destination in &summary.markdown_targets {
+                let Some(resolved) = resolve_markdown_destination(&summary.normalized_path, destination) else {
+                    continue;
                 };
-                if let Some(to) = to {
-                    if to != from && targets_for_note.insert(to) {
-                        directed.insert((from, to));
+                if is_attachment_path(&resolved) {
+                    if options.attachments {
+                        virtual_links.push((from, GraphNodeKind::Attachment, resolved));
+                    }
+                } else if options.markdown_links {
+                    link(paths.get(&normalize_wiki_path(&resolved)).copied());
+                }
+            }
+            if options.tags {
+                for tag in &summary.tags {
+                    let key = tag.trim().trim_start_matches('#').to_lowercase();
+                    if !key.is_empty() {
+                        virtual_links.push((from, GraphNodeKind::Tag, key));
                     }
                 }
             }
         }
 
+        // Tag and attachment nodes follow the notes in a stable order.
+        let mut virtual_nodes: Vec<(GraphNodeKind, String)> = virtual_links.iter().map(|(_, kind, key)| (*kind, key.clone())).collect();
+        virtual_nodes.sort_unstable();
+        virtual_nodes.dedup();
+        let node_count = note_count + virtual_nodes.len();
+        if node_count > u32::MAX as usize {
+            return Self::default();
+        }
+        let virtual_index: HashMap<_, _> = virtual_nodes.iter().enumerate().map(|(offset, node)| (node, note_count + offset)).collect();
+        for (from, kind, key) in &virtual_links {
+            directed.insert((*from, virtual_index[&(*kind, key.clone())]));
+        }
+
         let mut pairs: HashMap<(usize, usize), (bool, bool)> = HashMap::new();
         for &(from, to) in &directed {
             let key = if from < to { (from, to) } else { (to, from) };
@@ -601,18 +767,53 @@ impl GraphIndex {
             edges.push(edge);
         }
 
-        let nodes: Vec<_> = sources
+        let kinds: Vec<_> = std::iter::repeat_n(GraphNodeKind::Note, note_count)
+            .chain(virtual_nodes.iter().map(|(kind, _)| *kind))
+            .collect();
+        let clusters = analysis::detect_clusters(&kinds, &outgoing, &incoming);
+        let mut dates: Vec<Option<i32>> = sources.iter().map(|source| source.date).collect();
+        dates.resize(node_count, None);
+        for (from, kind, key) in &virtual_links {
+            let idx = virtual_index[&(*kind, key.clone())];
+            dates[idx] = match (dates[idx], dates[*from]) {
+                (Some(current), Some(linked)) => Some(current.min(linked)),
+                (current, linked) => current.or(linked),
+            };
+        }
+        let mut occupied_ids: HashSet<u32> = sources.iter().map(|source| source.note_id.get()).collect();
+        let mut nodes: Vec<_> = sources
             .into_iter()
             .enumerate()
             .map(|(idx, source)| GraphIndexNode {
                 note_id: source.note_id,
+                kind: GraphNodeKind::Note,
                 title: source.title,
                 path: source.path,
                 tags: source.tags,
                 in_degree: incoming[idx].len(),
                 out_degree: outgoing[idx].len(),
+                cluster: clusters[idx],
+                date: dates[idx],
             })
             .collect();
+        for (offset, (kind, key)) in virtual_nodes.into_iter().enumerate() {
+            let idx = note_count + offset;
+            let (title, path, tags) = match kind {
+                GraphNodeKind::Tag => (format!("#{key}"), format!("#{key}"), vec![key]),
+                _ => (key.rsplit('/').next().unwrap_or(&key).to_string(), key, Vec::new()),
+            };
+            nodes.push(GraphIndexNode {
+                note_id: synthetic_note_id(&path, &mut occupied_ids),
+                kind,
+                title,
+                path,
+                tags,
+                in_degree: incoming[idx].len(),
+                out_degree: outgoing[idx].len(),
+                cluster: clusters[idx],
+                date: dates[idx],
+            });
+        }
         let note_to_node = nodes.iter().enumerate().map(|(idx, node)| (node.note_id, idx as u32)).collect();
         let fingerprint = graph_fingerprint(&nodes, &edges);
 
@@ -634,6 +835,24 @@ impl GraphIndex {
         self.node_for_note(note_id).and_then(|node| self.nodes.get(node))
     }
 
+    /// Notes that `note_id` links to.
+    pub fn outgoing_notes(&self, note_id: NoteId) -> impl Iterator<Item = &GraphIndexNode> + '_ {
+        let neighbors = self.node_for_note(note_id).map_or(&[][..], |node| self.outgoing.get(node));
+        neighbors
+            .iter()
+            .map(|&node| &self.nodes[node as usize])
+            .filter(|node| node.kind == GraphNodeKind::Note)
+    }
+
+    /// Notes that link to `note_id`.
+    pub fn incoming_notes(&self, note_id: NoteId) -> impl Iterator<Item = &GraphIndexNode> + '_ {
+        let neighbors = self.node_for_note(note_id).map_or(&[][..], |node| self.incoming.get(node));
+        neighbors
+            .iter()
+            .map(|&node| &self.nodes[node as usize])
+            .filter(|node| node.kind == GraphNodeKind::Note)
+    }
+
     pub fn project(
         &self,
         mode: GraphMode,
@@ -717,9 +936,15 @@ impl GraphIndex {
                 } else {
                     0
                 },
-                relation: if mode == GraphMode::Local { relations[idx] } else { GraphRelation::Neutral },
+                relation: match indexed.kind {
+                    GraphNodeKind::Tag => GraphRelation::Tag,
+                    GraphNodeKind::Attachment => GraphRelation::Attachment,
+                    GraphNodeKind::Note if mode == GraphMode::Local => relations[idx],
+                    GraphNodeKind::Note => GraphRelation::Neutral,
+                },
                 in_degree: u32::try_from(indexed.in_degree).unwrap_or(u32::MAX),
                 out_degree: u32::try_from(indexed.out_degree).unwrap_or(u32::MAX),
+                cluster: indexed.cluster,
             });
         }
 
@@ -813,6 +1038,32 @@ fn normalize_wiki_path(path: &str) -> String {
     path.trim().trim_end_matches(".md").replace('\\', "/")
 }
 
+/// Markdown link and image destinations outside fenced code, as written.
+fn extract_markdown_targets(content: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut seen = HashSet::new();
+    let mut fence = None;
+    for line in content.lines() {
+        if let Some(marker) = ekphos_core::markdown::fence_marker(line) {
+            fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+            continue;
+        }
+        if fence.is_some() || !line.contains("](") {
+            continue;
+        }
+        for (start, _) in line.match_indices('[') {
+            let Some(link) = ekphos_core::markdown::markdown_link_at(line, start) else {
+                continue;
+            };
+            let destination = link.destination.trim();
+            if !destination.is_empty() && seen.insert(destination) {
+                targets.push(destination.to_string());
+            }
+        }
+    }
+    targets
+}
+
 fn extract_wiki_targets(content: &str) -> Vec<String> {
     let mut targets = Vec::new();
     let mut seen = HashSet::new();
@@ -825,6 +1076,82 @@ fn extract_wiki_targets(content: &str) -> Vec<String> {
     targets
 }
 
+/// Images and PDFs become attachment nodes rather than note links.
+fn is_attachment_path(path: &str) -> bool {
+    const EXTENSIONS: [&str; 9] = ["png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "avif", "pdf"];
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(extension)))
+}
+
+/// Resolve a Markdown link destination against the folder of the linking note
+/// (`note_path` is vault-relative, without `.md`). A leading `/` starts at the
+/// vault root. URLs, in-page anchors and paths leaving the vault give `None`.
+fn resolve_markdown_destination(note_path: &str, destination: &str) -> Option<String> {
+    let destination = destination.trim();
+    let destination = match destination.strip_prefix('<') {
+        Some(rest) => rest.split_once('>').map_or(rest, |(inner, _)| inner),
+        None => destination.split_whitespace().next()?,
+    };
+    if destination.is_empty() || destination.starts_with('#') || destination.contains("://") || destination.starts_with("mailto:") {
+        return None;
+    }
+    let destination = destination.split(['#', '?']).next()?;
+    let decoded = percent_decode(destination);
+    let (mut parts, relative) = match decoded.strip_prefix('/') {
+        Some(rooted) => (Vec::new(), rooted),
+        None => (note_path.split('/').collect::<Vec<_>>(), decoded.as_str()),
+    };
+    if !decoded.starts_with('/') {
+        parts.pop();
+    }
+    for part in relative.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let hex = bytes.get(idx + 1..idx + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match (bytes[idx], hex.and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| value.to_string())
+}
+
+/// Stable ID for a tag or attachment node, probing past IDs already taken by
+/// notes the same way the vault resolves note ID collisions.
+fn synthetic_note_id(path: &str, occupied: &mut HashSet<u32>) -> NoteId {
+    let mut candidate = 0x811c_9dc5u32;
+    for byte in b"\0graph:".iter().chain(path.as_bytes()) {
+        candidate ^= u32::from(*byte);
+        candidate = candidate.wrapping_mul(0x0100_0193);
+    }
+    while !occupied.insert(candidate) {
+        candidate = candidate.wrapping_add(1);
+    }
+    NoteId::new(candidate)
+}
+
 fn graph_fingerprint(nodes: &[GraphIndexNode], edges: &[GraphIndexEdge]) -> u64 {
     let mut hash = 0xcbf29ce484222325u64;
     let mut write = |bytes: &[u8]| {
@@ -866,6 +1193,8 @@ pub fn apply_local_layout(index: &GraphIndex, nodes: &mut [GraphNode]) {
         GraphRelation::Outgoing,
         GraphRelation::Bidirectional,
         GraphRelation::Neutral,
+        GraphRelation::Tag,
+        GraphRelation::Attachment,
     ] {
         let mut next_radius = 58.0;
         let mut previous_depth = 0u16;
@@ -901,6 +1230,8 @@ pub fn apply_local_layout(index: &GraphIndex, nodes: &mut [GraphNode]) {
                     ))
                 }
                 GraphRelation::Neutral => layout_radial_fan(nodes, &indices, next_radius, -std::f32::consts::FRAC_PI_2, std::f32::consts::TAU),
+                GraphRelation::Tag => layout_radial_fan(nodes, &indices, next_radius + 24.0, std::f32::consts::FRAC_PI_2, 1.2),
+                GraphRelation::Attachment => layout_radial_fan(nodes, &indices, next_radius + 24.0, -std::f32::consts::FRAC_PI_2, 1.2),
                 GraphRelation::Root => next_radius,
             };
             next_radius = outer_radius + 34.0;
@@ -1035,17 +1366,30 @@ fn visual_group_key(path: &str) -> &str {
 
 /// Refine a Global layout. Small graphs use exact repulsion; large graphs use
 /// Barnes-Hut approximation, keeping the hot loop near `O(N log N + E)`.
-pub fn apply_global_layout(index: &GraphIndex, nodes: &mut [GraphNode], edges: &[GraphEdge]) {
-    let _ = apply_global_layout_cancelable(index, nodes, edges, || false);
+/// Pinned nodes stay exactly where they were placed.
+pub fn apply_global_layout(index: &GraphIndex, nodes: &mut [GraphNode], edges: &[GraphEdge], pins: &GraphPins) {
+    let _ = apply_global_layout_cancelable(index, nodes, edges, pins, || false);
 }
 
 /// Returns `false` when a newer worker request cancels layout refinement.
-pub fn apply_global_layout_cancelable(index: &GraphIndex, nodes: &mut [GraphNode], edges: &[GraphEdge], mut cancelled: impl FnMut() -> bool) -> bool {
+pub fn apply_global_layout_cancelable(
+    index: &GraphIndex,
+    nodes: &mut [GraphNode],
+    edges: &[GraphEdge],
+    pins: &GraphPins,
+    mut cancelled: impl FnMut() -> bool,
+) -> bool {
     if nodes.len() <= 1 {
         apply_global_seed_layout(index, nodes);
+        pins.apply(index, nodes);
         return !cancelled();
     }
     apply_global_seed_layout(index, nodes);
+    let fixed = pins.resolve(index, nodes);
+    let has_pins = fixed.iter().any(Option::is_some);
+    if has_pins {
+        anchor_seed_to_pins(nodes, &fixed);
+    }
     let count = nodes.len();
     let iterations = if count <= 256 {
         100
@@ -1099,6 +1443,9 @@ pub fn apply_global_layout_cancelable(index: &GraphIndex, nodes: &mut [GraphNode
         let (center_x, center_y) = center(nodes);
         let anchor_strength = if count > 2_000 { 0.016 } else { 0.010 };
         for idx in 0..count {
+            if fixed[idx].is_some() {
+                continue;
+            }
             velocity[idx].0 += (center_x - nodes[idx].x) * 0.004;
             velocity[idx].1 += (center_y - nodes[idx].y) * 0.004;
             velocity[idx].0 += (nodes[idx].home_x - nodes[idx].x) * anchor_strength;
@@ -1112,12 +1459,42 @@ pub fn apply_global_layout_cancelable(index: &GraphIndex, nodes: &mut [GraphNode
             nodes[idx].y += velocity[idx].1 * 0.88;
         }
     }
-    resolve_collisions(nodes);
-    enforce_circular_aspect(nodes);
-    normalize_positions(nodes);
+    resolve_collisions(nodes, &fixed);
+    if has_pins {
+        // Pins are in view coordinates already; rescaling or shifting the
+        // layout would move them.
+        for node in nodes.iter_mut() {
+            node.home_x = node.x;
+            node.home_y = node.y;
+        }
+    } else {
+        enforce_circular_aspect(nodes);
+        normalize_positions(nodes);
+    }
     !cancelled()
 }
 
+/// Shift the seed so it is centred on the pinned nodes' targets, then put
+/// each pinned node on its pin. Springs only have to pull neighbours in
+/// rather than drag the whole vault across the canvas.
+fn anchor_seed_to_pins(nodes: &mut [GraphNode], fixed: &[Option<(f32, f32)>]) {
+    let pinned: Vec<_> = nodes
+        .iter()
+        .zip(fixed)
+        .filter_map(|(node, pin)| pin.map(|target| ((node.x, node.y), target)))
+        .collect();
+    let count = pinned.len() as f32;
+    let (seed_x, seed_y, target_x, target_y) = pinned
+        .iter()
+        .fold((0.0, 0.0, 0.0, 0.0), |(sx, sy, tx, ty), ((x, y), (px, py))| (sx + x, sy + y, tx + px, ty + py));
+    let (dx, dy) = ((target_x - seed_x) / count, (target_y - seed_y) / count);
+    for (node, pin) in nodes.iter_mut().zip(fixed) {
+        let (x, y) = pin.unwrap_or((node.x + dx, node.y + dy));
+        let (home_x, home_y) = pin.unwrap_or((node.home_x + dx, node.home_y + dy));
+        (node.x, node.y, node.home_x, node.home_y) = (x, y, home_x, home_y);
+    }
+}
+
 fn graph_node_path<'a>(index: &'a GraphIndex, node: &GraphNode) -> &'a str {
     index.metadata_for_note(node.note_id).map_or("", |metadata| metadata.path.as_str())
 }
@@ -1167,7 +1544,9 @@ fn apply_pair_repulsion(nodes: &[GraphNode], velocity: &mut [(f32, f32)], left:
     velocity[right].1 += fy;
 }
 
-fn resolve_collisions(nodes: &mut [GraphNode]) {
+/// Pinned nodes never move; an unpinned neighbour takes the whole push.
+fn resolve_collisions(nodes: &mut [GraphNode], fixed: &[Option<(f32, f32)>]) {
+    let is_fixed = |idx: usize| fixed.get(idx).is_some_and(Option::is_some);
     const CELL: f32 = 10.0;
     for _ in 0..3 {
         let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
@@ -1181,13 +1560,19 @@ fn resolve_collisions(nodes: &mut [GraphNode]) {
                             let dy = nodes[idx].y - nodes[other].y;
                             let distance = (dx * dx + dy * dy).sqrt();
                             if distance < 7.0 && distance > 0.001 {
-                                let push = (7.0 - distance) * 0.5;
+                                let (move_self, move_other) = match (is_fixed(idx), is_fixed(other)) {
+                                    (true, true) => continue,
+                                    (true, false) => (0.0, 1.0),
+                                    (false, true) => (1.0, 0.0),
+                                    (false, false) => (0.5, 0.5),
+                                };
+                                let push = 7.0 - distance;
                                 let px = dx / distance * push;
                                 let py = dy / distance * push;
-                                nodes[idx].x += px;
-                                nodes[idx].y += py;
-                                nodes[other].x -= px;
-                                nodes[other].y -= py;
+                                nodes[idx].x += px * move_self;
+                                nodes[idx].y += py * move_self;
+                                nodes[other].x -= px * move_other;
+                                nodes[other].y -= py * move_other;
                             }
                         }
                     }
@@ -1344,6 +1729,8 @@ mod tests {
             title: path.rsplit('/').next().unwrap_or(path).to_string(),
             path: path.to_string(),
             tags: Vec::new(),
+            aliases: Vec::new(),
+            date: None,
             content: content.to_string(),
         }
     }
@@ -1367,6 +1754,8 @@ mod tests {
                 title: path.rsplit('/').next().unwrap_or(path).to_string(),
                 path: path.to_string(),
                 tags: vec![format!("tag-{index}")],
+                aliases: Vec::new(),
+                date: None,
             },
             absolute_path,
             fingerprint: GraphFileFingerprint {
@@ -1378,7 +1767,9 @@ mod tests {
 
     fn assert_index_parity(legacy_sources: Vec<GraphSourceNote>, cached_sources: Vec<GraphSourceFile>, cache_path: &Path) {
         let legacy = GraphIndex::build(legacy_sources);
-        let cached = GraphIndex::load_or_build(cached_sources, cache_path, || false).unwrap().index;
+        let cached = GraphIndex::load_or_build(cached_sources, GraphSources::default(), cache_path, || false)
+            .unwrap()
+            .index;
         assert_eq!(cached.edges, legacy.edges);
         assert_eq!(cached.fingerprint, legacy.fingerprint);
         assert_eq!(
@@ -1437,12 +1828,307 @@ mod tests {
         assert_eq!(index.nodes[0].in_degree, 1);
     }
 
+    #[test]
+    fn neighbor_queries_follow_link_direction() {
+        let index = GraphIndex::build(vec![source(0, "A", "[[B]] [[C]]"), source(1, "B", "[[A]]"), source(2, "C", "")]);
+        let titles = |nodes: Vec<&GraphIndexNode>| nodes.into_iter().map(|node| node.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(index.outgoing_notes(NoteId::new(0)).collect()), ["B", "C"]);
+        assert_eq!(titles(index.incoming_notes(NoteId::new(0)).collect()), ["B"]);
+        assert_eq!(titles(index.incoming_notes(NoteId::new(2)).collect()), ["A"]);
+        assert!(index.outgoing_notes(NoteId::new(9)).next().is_none());
+    }
+
     #[test]
     fn index_ignores_code_unresolved_and_self_links() {
         let index = GraphIndex::build(vec![source(0, "A", "`[[B]]`\n```md\n[[B]]\n```\n[[Missing]]\n[[A]]"), source(1, "B", "")]);
         assert!(index.edges.is_empty());
     }
 
+    #[test]
+    fn markdown_links_resolve_relative_to_the_linking_note() {
+        let markdown = GraphSources {
+            markdown_links: true,
+            ..GraphSources::default()
+        };
+        let index = GraphIndex::build_with(
+            vec![
+                source(
+                    0,
+                    "notes/A",
+                    "[up](../B.md) [same](C.md#part) [spaced](<Old%20Note.md>) [web](https://example.com/B.md)",
+                ),
+                source(1, "B", "[root](/notes/A.md)\n```\n[code](C.md)\n```"),
+                source(2, "notes/C", ""),
+                source(3, "notes/Old Note", ""),
+            ],
+            markdown,
+        );
+        let titles = |note: usize| index.outgoing_notes(NoteId::new(note as u32)).map(|node| node.path.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(0), ["B", "notes/C", "notes/Old Note"]);
+        assert_eq!(titles(1), ["notes/A"]);
+
+        let wiki_only = GraphSources::default();
+        let sources = vec![
+            GraphSourceMetadata {
+                note_id: NoteId::new(0),
+                title: "A".to_string(),
+                path: "A".to_string(),
+                tags: Vec::new(),
+                aliases: Vec::new(),
+                date: None,
+            },
+            GraphSourceMetadata {
+                note_id: NoteId::new(1),
+                title: "B".to_string(),
+                path: "B".to_string(),
+                tags: Vec::new(),
+                aliases: Vec::new(),
+                date: None,
+            },
+        ];
+        assert!(GraphIndex::build_from_loader(sources, wiki_only, |_| Some("[b](B.md)".to_string()))
+            .edges
+            .is_empty());
+    }
+
+    #[test]
+    fn tags_and_attachments_become_filterable_nodes() {
+        let mut tagged = source(0, "projects/A", "![diagram](img/flow.png) ![[scan.pdf]] [[B]]");
+        tagged.tags = vec!["#Rust".to_string(), "graph".to_string()];
+        let mut other = source(1, "B", "![again](/projects/img/flow.png)");
+        other.tags = vec!["rust".to_string()];
+        let metadata: Vec<_> = [&tagged, &other]
+            .iter()
+            .map(|source| GraphSourceMetadata {
+                note_id: source.note_id,
+                title: source.title.clone(),
+                path: source.path.clone(),
+                tags: source.tags.clone(),
+                aliases: Vec::new(),
+                date: source.date,
+            })
+            .collect();
+        let bodies = [tagged.content.clone(), other.content.clone()];
+        let all = GraphSources {
+            markdown_links: true,
+            tags: true,
+            attachments: true,
+        };
+        let index = GraphIndex::build_from_loader(metadata.clone(), all, |note_id| bodies.get(note_id.as_index()).cloned());
+        let virtual_nodes: Vec<_> = index.nodes[2..].iter().map(|node| (node.kind, node.path.as_str(), node.in_degree)).collect();
+        assert_eq!(
+            virtual_nodes,
+            [
+                (GraphNodeKind::Tag, "#graph", 1),
+                (GraphNodeKind::Tag, "#rust", 2),
+                (GraphNodeKind::Attachment, "projects/img/flow.png", 2),
+                (GraphNodeKind::Attachment, "scan.pdf", 1),
+            ]
+        );
+        assert!(index.nodes.iter().map(|node| node.note_id).collect::<HashSet<_>>().len() == index.nodes.len());
+        assert_eq!(index.outgoing_notes(NoteId::new(0)).map(|node| node.title.as_str()).collect::<Vec<_>>(), ["B"]);
+
+        let local = index.project(GraphMode::Local, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::default(), true);
+        let relation = |path: &str| {
+            let idx = index.nodes.iter().position(|node| node.path == path).unwrap();
+            local
+                .nodes
+                .iter()
+                .find(|node| node.note_id == index.nodes[idx].note_id)
+                .map(|node| node.relation)
+        };
+        assert_eq!(relation("#rust"), Some(GraphRelation::Tag));
+        assert_eq!(relation("scan.pdf"), Some(GraphRelation::Attachment));
+        assert_eq!(relation("B"), Some(GraphRelation::Outgoing));
+
+        let tags_only = index.project(GraphMode::Global, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::parse("kind:tag"), true);
+        assert_eq!(tags_only.nodes.len(), 2);
+        let no_attachments = index.project(
+            GraphMode::Global,
+            NoteId::new(0),
+            1,
+            GraphLinkScope::All,
+            &GraphFilter::parse("-kind:attachment"),
+            true,
+        );
+        assert_eq!(no_attachments.nodes.len(), 4);
+
+        let notes_only = GraphIndex::build_from_loader(metadata, GraphSources::default(), |note_id| bodies.get(note_id.as_index()).cloned());
+        assert_eq!(notes_only.nodes.len(), 2);
+    }
+
+    #[test]
+    fn shortest_path_ignores_link_direction_and_virtual_nodes() {
+        let mut a = source(0, "A", "[[B]]");
+        a.tags = vec!["shared".to_string()];
+        let mut d = source(3, "D", "");
+        d.tags = vec!["shared".to_string()];
+        let index = GraphIndex::build(vec![a, source(1, "B", ""), source(2, "C", "[[B]] [[D]]"), d, source(4, "E", "")]);
+        let titles = |path: Option<Vec<NoteId>>| {
+            path.map(|ids| {
+                ids.into_iter()
+                    .map(|id| index.nodes[index.node_for_note(id).unwrap()].title.clone())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(
+            titles(index.shortest_path(NoteId::new(0), NoteId::new(3))),
+            Some(vec!["A".into(), "B".into(), "C".into(), "D".into()])
+        );
+        assert_eq!(titles(index.shortest_path(NoteId::new(1), NoteId::new(1))), Some(vec!["B".into()]));
+        assert_eq!(index.shortest_path(NoteId::new(0), NoteId::new(4)), None);
+    }
+
+    #[test]
+    fn centrality_ranks_hubs_and_bridges_first() {
+        // Two triangles joined through a single bridge note.
+        let index = GraphIndex::build(vec![
+            source(0, "A1", "[[A2]] [[A3]]"),
+            source(1, "A2", "[[A3]] [[Bridge]]"),
+            source(2, "A3", "[[A1]]"),
+            source(3, "Bridge", "[[B1]]"),
+            source(4, "B1", "[[B2]] [[B3]]"),
+            source(5, "B2", "[[B3]]"),
+            source(6, "B3", "[[B1]]"),
+        ]);
+        let top = |index: &GraphIndex, metric| index.nodes[index.node_for_note(index.most_central(metric, 1)[0].0).unwrap()].title.clone();
+        assert_eq!(top(&index, CentralityMetric::Betweenness), "Bridge");
+        assert_eq!(index.most_central(CentralityMetric::Betweenness, 3).len(), 3);
+
+        let star = GraphIndex::build(vec![
+            source(0, "S1", "[[Hub]]"),
+            source(1, "S2", "[[Hub]] [[S1]]"),
+            source(2, "Hub", ""),
+            source(3, "S3", "[[Hub]]"),
+        ]);
+        assert_eq!(top(&star, CentralityMetric::PageRank), "Hub");
+        let total: f32 = star.pagerank().iter().sum();
+        assert!((total - 1.0).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn clusters_follow_dense_groups_and_feed_the_filter() {
+        let mut notes = [
+            source(0, "A1", "[[A2]] [[A3]] [[A4]]"),
+            source(1, "A2", "[[A3]] [[A4]]"),
+            source(2, "A3", "[[A4]]"),
+            source(3, "A4", ""),
+            source(4, "B1", "[[B2]] [[B3]]"),
+            source(5, "B2", "[[B3]]"),
+            source(6, "B3", ""),
+        ];
+        notes[0].tags = vec!["everything".to_string()];
+        notes[4].tags = vec!["everything".to_string()];
+        let metadata: Vec<_> = notes
+            .iter()
+            .map(|source| GraphSourceMetadata {
+                note_id: source.note_id,
+                title: source.title.clone(),
+                path: source.path.clone(),
+                tags: source.tags.clone(),
+                aliases: Vec::new(),
+                date: source.date,
+            })
+            .collect();
+        let bodies: Vec<_> = notes.iter().map(|source| source.content.clone()).collect();
+        let sources = GraphSources {
+            tags: true,
+            ..GraphSources::default()
+        };
+        let index = GraphIndex::build_from_loader(metadata, sources, |note_id| bodies.get(note_id.as_index()).cloned());
+        let clusters: Vec<_> = index.nodes.iter().map(|node| node.cluster).collect();
+        assert_eq!(clusters, [1, 1, 1, 1, 2, 2, 2, 0]);
+
+        let second = index.project(
+            GraphMode::Global,
+            NoteId::new(0),
+            1,
+            GraphLinkScope::All,
+            &GraphFilter::parse("cluster:2"),
+            true,
+        );
+        assert_eq!(second.nodes.iter().map(|node| node.cluster).collect::<Vec<_>>(), [2, 2, 2]);
+        let rest = index.project(
+            GraphMode::Global,
+            NoteId::new(0),
+            1,
+            GraphLinkScope::All,
+            &GraphFilter::parse("-cluster:1"),
+            true,
+        );
+        assert_eq!(rest.nodes.len(), 4);
+    }
+
+    #[test]
+    fn dates_parse_into_day_numbers_and_drive_before_after_terms() {
+        assert_eq!(parse_day("1970-01-02"), Some(1));
+        assert_eq!(parse_day("'2024-03-01T09:30:00'"), parse_day("2024-03-01"));
+        assert_eq!(parse_day("2024-03-01x"), None);
+        assert_eq!(parse_day("March 2024"), None);
+        assert_eq!(format_day(parse_day("2024-02-29").unwrap()), "2024-02-29");
+        let day = |text: &str| parse_day(text).unwrap();
+        assert_eq!(timeline::parse_period("2024-02"), Some((day("2024-02-01"), day("2024-02-29"))));
+        assert_eq!(timeline::parse_period("2024"), Some((day("2024-01-01"), day("2024-12-31"))));
+        assert_eq!(timeline::parse_period("2024-13"), None);
+
+        let mut notes = [
+            source(0, "Old", "[[Mid]]"),
+            source(1, "Mid", "[[New]]"),
+            source(2, "New", ""),
+            source(3, "Undated", ""),
+        ];
+        notes[0].date = Some(day("2023-06-15"));
+        notes[1].date = Some(day("2024-02-10"));
+        notes[2].date = Some(day("2024-11-30"));
+        notes[1].tags = vec!["idea".to_string()];
+        notes[2].tags = vec!["idea".to_string()];
+        let metadata: Vec<_> = notes
+            .iter()
+            .map(|source| GraphSourceMetadata {
+                note_id: source.note_id,
+                title: source.title.clone(),
+                path: source.path.clone(),
+                tags: source.tags.clone(),
+                aliases: Vec::new(),
+                date: source.date,
+            })
+            .collect();
+        let bodies: Vec<_> = notes.iter().map(|source| source.content.clone()).collect();
+        let sources = GraphSources {
+            tags: true,
+            ..GraphSources::default()
+        };
+        let index = GraphIndex::build_from_loader(metadata, sources, |note_id| bodies.get(note_id.as_index()).cloned());
+        assert_eq!(index.nodes[4].date, Some(day("2024-02-10")), "a tag dates from its first note");
+
+        let titles = |query: &str| {
+            let filter = GraphFilter::parse(query);
+            index
+                .nodes
+                .iter()
+                .filter(|node| filter.matches(node))
+                .map(|node| node.title.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles("before:2024"), ["Old"]);
+        assert_eq!(titles("after:2024-01 before:2024-03"), ["Mid", "#idea"]);
+        assert_eq!(titles("after:2024-02-10 kind:note"), ["New"]);
+        assert_eq!(titles("-before:2024-11-30 kind:note"), ["New", "Undated"]);
+        assert_eq!(titles("before:someday"), Vec::<&str>::new());
+
+        let until = |query: &str, until: &str| {
+            let filter = GraphFilter::parse(query).dated_until(day(until));
+            index
+                .nodes
+                .iter()
+                .filter(|node| filter.matches(node))
+                .map(|node| node.title.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(until("kind:note", "2024-02-10"), ["Old", "Mid", "Undated"], "the slider keeps undated notes");
+        assert_eq!(until("kind:note before:2025", "2024-02-10"), ["Old", "Mid"]);
+    }
+
     #[test]
     fn bare_duplicate_title_prefers_vault_root_note() {
         let index = GraphIndex::build(vec![source(0, "A", ""), source(1, "folder/A", ""), source(2, "Source", "[[A]]")]);
@@ -1456,6 +2142,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alias_links_point_at_the_aliased_note_unless_a_title_claims_them() {
+        let mut target = source(0, "Remote Procedure Calls", "");
+        target.aliases = vec!["RPC".to_string(), "B".to_string()];
+        let index = GraphIndex::build(vec![target, source(1, "B", ""), source(2, "Source", "[[rpc]] [[B]]")]);
+        let targets: Vec<_> = index.edges.iter().map(|edge| (edge.from, edge.to)).collect();
+        assert_eq!(targets, [(2, 0), (2, 1)]);
+    }
+
     #[test]
     fn local_projection_honors_depth_and_direction() {
         let index = GraphIndex::build(vec![
@@ -1541,8 +2236,8 @@ mod tests {
         let index = GraphIndex::build(sources);
         let mut first = index.project(GraphMode::Global, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::default(), true);
         let mut second = first.clone();
-        apply_global_layout(&index, &mut first.nodes, &first.edges);
-        apply_global_layout(&index, &mut second.nodes, &second.edges);
+        apply_global_layout(&index, &mut first.nodes, &first.edges, &GraphPins::default());
+        apply_global_layout(&index, &mut second.nodes, &second.edges, &GraphPins::default());
         assert!(first.nodes.iter().all(|node| node.x.is_finite() && node.y.is_finite()));
         for (left, right) in first.nodes.iter().zip(second.nodes.iter()) {
             assert!((left.x - right.x).abs() < 0.001);
@@ -1618,6 +2313,39 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn pinned_nodes_hold_their_place_and_persist_by_path() {
+        let notes: Vec<_> = (0..12)
+            .map(|idx| source(idx, &format!("N{idx}"), &format!("[[N{}]] [[N{}]]", (idx + 1) % 12, (idx + 5) % 12)))
+            .collect();
+        let index = GraphIndex::build(notes);
+        let mut pins = GraphPins::default();
+        pins.insert("N3", 500.0, 240.0);
+        pins.insert("N7", 620.0, 300.0);
+        let mut projection = index.project(GraphMode::Global, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::default(), true);
+        apply_global_layout(&index, &mut projection.nodes, &projection.edges, &pins);
+        let position = |path: &str| {
+            let node = projection.nodes.iter().find(|node| graph_node_path(&index, node) == path).unwrap();
+            (node.x, node.y)
+        };
+        assert_eq!(position("N3"), (500.0, 240.0));
+        assert_eq!(position("N7"), (620.0, 300.0));
+        assert!(projection.nodes.iter().all(|node| node.x.is_finite() && node.y.is_finite()));
+        let (x, y) = position("N4");
+        assert!((x - 560.0).abs() < 400.0 && (y - 270.0).abs() < 400.0);
+
+        assert_eq!(GraphPins::default().layout_fingerprint(index.fingerprint), index.fingerprint);
+        assert_ne!(pins.layout_fingerprint(index.fingerprint), index.fingerprint);
+
+        let path = std::env::temp_dir().join(format!("ekphos-graph-pins-{}.json", std::process::id()));
+        pins.save(&path).unwrap();
+        assert_eq!(GraphPins::load(&path), pins);
+        pins.clear();
+        pins.save(&path).unwrap();
+        assert!(!path.exists());
+        assert!(GraphPins::load(&path).is_empty());
+    }
+
     #[test]
     fn cached_graph_matches_legacy_for_edge_case_vaults() {
         let cases: Vec<Vec<(&str, &str)>> = vec![
@@ -1675,9 +2403,9 @@ mod tests {
             cached_source(&root, 1, "B", "", 1),
             cached_source(&root, 2, "Old", "[[A]]", 1),
         ];
-        let built = GraphIndex::load_or_build(first.clone(), &cache, || false).unwrap();
+        let built = GraphIndex::load_or_build(first.clone(), GraphSources::default(), &cache, || false).unwrap();
         assert_eq!((built.parsed_files, built.reused_files), (3, 0));
-        let warm = GraphIndex::load_or_build(first, &cache, || false).unwrap();
+        let warm = GraphIndex::load_or_build(first, GraphSources::default(), &cache, || false).unwrap();
         assert_eq!((warm.parsed_files, warm.reused_files), (0, 3));
 
         let changed = vec![
@@ -1685,7 +2413,7 @@ mod tests {
             cached_source(&root, 1, "B", "", 1),
             cached_source(&root, 2, "Renamed", "[[A]]", 2),
         ];
-        let rebuilt = GraphIndex::load_or_build(changed, &cache, || false).unwrap();
+        let rebuilt = GraphIndex::load_or_build(changed, GraphSources::default(), &cache, || false).unwrap();
         assert_eq!((rebuilt.parsed_files, rebuilt.reused_files), (2, 1));
         assert_eq!(rebuilt.index.edges.len(), 2);
         assert!(rebuilt.index.edges.iter().any(|edge| edge.bidirectional));
@@ -1699,7 +2427,7 @@ mod tests {
         let cache = root.join("graph_index.bin");
         let sources = vec![cached_source(&root, 0, "A", "", 1)];
         fs::write(&cache, b"corrupt graph cache").unwrap();
-        let corrupt = GraphIndex::load_or_build(sources.clone(), &cache, || false).unwrap();
+        let corrupt = GraphIndex::load_or_build(sources.clone(), GraphSources::default(), &cache, || false).unwrap();
         assert_eq!(corrupt.parsed_files, 1);
 
         save_index_cache(
@@ -1709,7 +2437,7 @@ mod tests {
                 files: Vec::new(),
             },
         );
-        let stale = GraphIndex::load_or_build(sources, &cache, || false).unwrap();
+        let stale = GraphIndex::load_or_build(sources, GraphSources::default(), &cache, || false).unwrap();
         assert_eq!(stale.parsed_files, 1);
         assert!(load_index_cache(&cache).is_some());
         fs::remove_dir_all(root).unwrap();
@@ -1755,7 +2483,7 @@ mod tests {
 
         let global_started = std::time::Instant::now();
         let mut global = index.project(GraphMode::Global, NoteId::new(0), 1, GraphLinkScope::All, &GraphFilter::default(), true);
-        apply_global_layout(&index, &mut global.nodes, &global.edges);
+        apply_global_layout(&index, &mut global.nodes, &global.edges, &GraphPins::default());
         let global_elapsed = global_started.elapsed();
         eprintln!("10k/50k graph: index={index_elapsed:?}, local={local_elapsed:?}, global={global_elapsed:?}");
     }
@@ -0,0 +1,116 @@
+//! Manually placed Global graph nodes.
+//!
+//! Pins are keyed by note path rather than `NoteId`, so they survive
+//! re-indexing, and live in their own small JSON file: the layout cache is
+//! disposable and keyed by the index fingerprint, pins are user intent.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{graph_node_path, sibling_temp_path, sync_parent, GraphIndex, GraphNode};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphPins {
+    #[serde(default)]
+    positions: BTreeMap<String, (f32, f32)>,
+}
+
+impl GraphPins {
+    /// A missing or unreadable file is an empty set of pins.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok())
+            .map(|mut pins| {
+                pins.positions.retain(|_, (x, y)| x.is_finite() && y.is_finite());
+                pins
+            })
+            .unwrap_or_default()
+    }
+
+    /// Write atomically; an empty set removes the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if self.positions.is_empty() {
+            return match fs::remove_file(path) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        let temp_path = sibling_temp_path(path);
+        fs::write(&temp_path, contents)?;
+        fs::rename(&temp_path, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })?;
+        sync_parent(path);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn get(&self, path: &str) -> Option<(f32, f32)> {
+        self.positions.get(path).copied()
+    }
+
+    pub fn insert(&mut self, path: impl Into<String>, x: f32, y: f32) {
+        if x.is_finite() && y.is_finite() {
+            self.positions.insert(path.into(), (x, y));
+        }
+    }
+
+    pub fn remove(&mut self, path: &str) -> bool {
+        self.positions.remove(path).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.positions.clear();
+    }
+
+    /// Layout cache key for a layout computed with these pins held fixed.
+    /// Without pins it is the index fingerprint itself, so existing caches
+    /// stay valid.
+    pub fn layout_fingerprint(&self, index_fingerprint: u64) -> u64 {
+        if self.positions.is_empty() {
+            return index_fingerprint;
+        }
+        let mut hash = index_fingerprint ^ 0xcbf2_9ce4_8422_2325;
+        for (path, (x, y)) in &self.positions {
+            for byte in path.bytes().chain(x.to_bits().to_le_bytes()).chain(y.to_bits().to_le_bytes()) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    /// The pinned position of each node, in `nodes` order.
+    pub(crate) fn resolve(&self, index: &GraphIndex, nodes: &[GraphNode]) -> Vec<Option<(f32, f32)>> {
+        if self.positions.is_empty() {
+            return vec![None; nodes.len()];
+        }
+        nodes.iter().map(|node| self.get(graph_node_path(index, node))).collect()
+    }
+
+    /// Move pinned nodes onto their pins, leaving the rest untouched.
+    pub fn apply(&self, index: &GraphIndex, nodes: &mut [GraphNode]) {
+        let fixed = self.resolve(index, nodes);
+        for (node, pin) in nodes.iter_mut().zip(fixed) {
+            if let Some((x, y)) = pin {
+                (node.x, node.y, node.home_x, node.home_y) = (x, y, x, y);
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Note dates for the timeline and the `before:`/`after:` filter terms.
+//!
+//! A date is a day number, days since 1970-01-01, so the index stays small
+//! and comparisons are plain integer ones.  Times are taken in UTC.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Datelike, NaiveDate};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid epoch")
+}
+
+fn day_number(date: NaiveDate) -> Option<i32> {
+    i32::try_from(date.signed_duration_since(epoch()).num_days()).ok()
+}
+
+/// `2024-03-01`, optionally followed by a time as in `2024-03-01T09:30`.
+pub fn parse_day(text: &str) -> Option<i32> {
+    let text = text.trim().trim_matches(|ch| ch == '"' || ch == '\'');
+    let (date, rest) = if text.len() > 10 && text.is_char_boundary(10) {
+        text.split_at(10)
+    } else {
+        (text, "")
+    };
+    if !rest.is_empty() && !rest.starts_with(['T', 't', ' ']) {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok().and_then(day_number)
+}
+
+pub fn day_from_system_time(time: SystemTime) -> Option<i32> {
+    let seconds = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    i32::try_from(seconds / SECONDS_PER_DAY).ok()
+}
+
+pub fn format_day(day: i32) -> String {
+    let date = epoch() + chrono::Duration::days(i64::from(day));
+    format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day())
+}
+
+/// First and last day of `2024`, `2024-03` or `2024-03-01`.
+pub(crate) fn parse_period(text: &str) -> Option<(i32, i32)> {
+    let mut parts = text.split('-');
+    let year: i32 = parts.next().filter(|part| part.len() == 4)?.parse().ok()?;
+    let month: Option<u32> = match parts.next() {
+        Some(part) => Some(part.parse().ok()?),
+        None => None,
+    };
+    let day: Option<u32> = match parts.next() {
+        Some(part) => Some(part.parse().ok()?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    let (first, last) = match (month, day) {
+        (None, _) => (NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year, 12, 31)?),
+        (Some(month), None) => {
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            let next = if month == 12 {
+                NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+            } else {
+                NaiveDate::from_ymd_opt(year, month + 1, 1)?
+            };
+            (first, next.pred_opt()?)
+        }
+        (Some(month), Some(day)) => {
+            let date = NaiveDate::from_ymd_opt(year, month, day)?;
+            (date, date)
+        }
+    };
+    Some((day_number(first)?, day_number(last)?))
+}
@@ -1,4 +1,7 @@
-use crate::{apply_global_layout_cancelable, load_layout_cache, save_layout_cache, GraphBuildOutcome, GraphIndex, GraphProjection, GraphSourceFile};
+use crate::{
+    apply_global_layout_cancelable, load_layout_cache, save_layout_cache, CentralityMetric, GraphBuildOutcome, GraphIndex, GraphPins, GraphProjection,
+    GraphSourceFile, GraphSources,
+};
 use ekphos_core::NoteId;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -10,6 +13,7 @@ enum GraphRequest {
         ticket: u64,
         generation: u64,
         sources: Vec<GraphSourceFile>,
+        options: GraphSources,
         cache_path: PathBuf,
     },
     Layout {
@@ -17,20 +21,28 @@ enum GraphRequest {
         generation: u64,
         index: Arc<GraphIndex>,
         projection: GraphProjection,
+        pins: GraphPins,
         cache_path: PathBuf,
     },
+    Centrality {
+        ticket: u64,
+        generation: u64,
+        index: Arc<GraphIndex>,
+        metric: CentralityMetric,
+        limit: usize,
+    },
 }
 
 impl GraphRequest {
     fn ticket(&self) -> u64 {
         match self {
-            Self::Build { ticket, .. } | Self::Layout { ticket, .. } => *ticket,
+            Self::Build { ticket, .. } | Self::Layout { ticket, .. } | Self::Centrality { ticket, .. } => *ticket,
         }
     }
 
     fn generation(&self) -> u64 {
         match self {
-            Self::Build { generation, .. } | Self::Layout { generation, .. } => *generation,
+            Self::Build { generation, .. } | Self::Layout { generation, .. } | Self::Centrality { generation, .. } => *generation,
         }
     }
 }
@@ -46,6 +58,10 @@ pub enum GraphResponse {
         fingerprint: u64,
         positions: Vec<(NoteId, f32, f32)>,
     },
+    Centrality {
+        generation: u64,
+        entries: Vec<(NoteId, f32)>,
+    },
     Failed {
         generation: u64,
     },
@@ -65,8 +81,9 @@ struct Shared {
     pending: AtomicBool,
 }
 
-/// One replaceable request slot for graph extraction and global layout. A new
-/// generation cancels work at the next file or layout-iteration boundary.
+/// One replaceable request slot for graph extraction, global layout and
+/// centrality ranking. A new generation cancels work at the next file or
+/// layout-iteration boundary; a replaced ranking is discarded when it ends.
 pub struct GraphWorker {
     shared: Arc<Shared>,
     handle: Option<JoinHandle<()>>,
@@ -95,27 +112,41 @@ impl GraphWorker {
         Self { shared, handle: Some(handle) }
     }
 
-    pub fn submit_build(&self, generation: u64, sources: Vec<GraphSourceFile>, cache_path: PathBuf) {
+    pub fn submit_build(&self, generation: u64, sources: Vec<GraphSourceFile>, options: GraphSources, cache_path: PathBuf) {
         let ticket = self.next_ticket();
         self.replace_request(GraphRequest::Build {
             ticket,
             generation,
             sources,
+            options,
             cache_path,
         });
     }
 
-    pub fn submit_layout(&self, generation: u64, index: Arc<GraphIndex>, projection: GraphProjection, cache_path: PathBuf) {
+    pub fn submit_layout(&self, generation: u64, index: Arc<GraphIndex>, projection: GraphProjection, pins: GraphPins, cache_path: PathBuf) {
         let ticket = self.next_ticket();
         self.replace_request(GraphRequest::Layout {
             ticket,
             generation,
             index,
             projection,
+            pins,
             cache_path,
         });
     }
 
+    /// Rank the `limit` most central notes of `index`.
+    pub fn submit_centrality(&self, generation: u64, index: Arc<GraphIndex>, metric: CentralityMetric, limit: usize) {
+        let ticket = self.next_ticket();
+        self.replace_request(GraphRequest::Centrality {
+            ticket,
+            generation,
+            index,
+            metric,
+            limit,
+        });
+    }
+
     pub fn try_take(&self) -> Option<GraphResponse> {
         self.shared.result.lock().ok()?.take()
     }
@@ -207,9 +238,10 @@ fn execute(shared: &Shared, request: GraphRequest) -> Option<GraphResponse> {
             ticket,
             generation,
             sources,
+            options,
             cache_path,
         } => {
-            let outcome = GraphIndex::load_or_build(sources, &cache_path, || shared.current_ticket.load(Ordering::Acquire) != ticket)?;
+            let outcome = GraphIndex::load_or_build(sources, options, &cache_path, || shared.current_ticket.load(Ordering::Acquire) != ticket)?;
             Some(GraphResponse::Index { generation, outcome })
         }
         GraphRequest::Layout {
@@ -217,23 +249,25 @@ fn execute(shared: &Shared, request: GraphRequest) -> Option<GraphResponse> {
             generation,
             index,
             mut projection,
+            pins,
             cache_path,
         } => {
             let fingerprint = index.fingerprint;
-            if let Some(positions) = load_layout_cache(&cache_path, fingerprint, &projection.nodes) {
+            let cache_key = pins.layout_fingerprint(fingerprint);
+            if let Some(positions) = load_layout_cache(&cache_path, cache_key, &projection.nodes) {
                 return Some(GraphResponse::Layout {
                     generation,
                     fingerprint,
                     positions,
                 });
             }
-            let completed = apply_global_layout_cancelable(&index, &mut projection.nodes, &projection.edges, || {
+            let completed = apply_global_layout_cancelable(&index, &mut projection.nodes, &projection.edges, &pins, || {
                 shared.current_ticket.load(Ordering::Acquire) != ticket
             });
             if !completed {
                 return None;
             }
-            save_layout_cache(&cache_path, fingerprint, &projection.nodes);
+            save_layout_cache(&cache_path, cache_key, &projection.nodes);
             let positions = projection.nodes.into_iter().map(|node| (node.note_id, node.x, node.y)).collect();
             Some(GraphResponse::Layout {
                 generation,
@@ -241,6 +275,16 @@ fn execute(shared: &Shared, request: GraphRequest) -> Option<GraphResponse> {
                 positions,
             })
         }
+        GraphRequest::Centrality {
+            generation,
+            index,
+            metric,
+            limit,
+            ..
+        } => Some(GraphResponse::Centrality {
+            generation,
+            entries: index.most_central(metric, limit),
+        }),
     }
 }
 
@@ -257,28 +301,38 @@ mod tests {
                 title: format!("N{index}"),
                 path: format!("N{index}"),
                 tags: Vec::new(),
+                aliases: Vec::new(),
+                date: None,
             },
             absolute_path: root.join(format!("N{index}.md")),
             fingerprint: GraphFileFingerprint { size: 0, modified_nanos: 1 },
         }
     }
 
-    #[test]
-    fn latest_build_replaces_in_flight_work_and_shutdown_is_bounded() {
-        let root = std::env::temp_dir().join(format!("ekphos-graph-worker-{}", std::process::id()));
-        let worker = GraphWorker::new();
-        worker.submit_build(1, (0..5_000).map(|index| source(index, &root)).collect(), root.join("old.bin"));
-        worker.submit_build(2, vec![source(0, &root)], root.join("new.bin"));
-
+    fn wait_for_response(worker: &GraphWorker) -> GraphResponse {
         let started = Instant::now();
-        let response = loop {
+        loop {
             if let Some(response) = worker.try_take() {
-                break response;
+                return response;
             }
             assert!(started.elapsed() < Duration::from_secs(5));
             std::thread::yield_now();
-        };
-        match response {
+        }
+    }
+
+    #[test]
+    fn latest_build_replaces_in_flight_work_and_shutdown_is_bounded() {
+        let root = std::env::temp_dir().join(format!("ekphos-graph-worker-{}", std::process::id()));
+        let worker = GraphWorker::new();
+        worker.submit_build(
+            1,
+            (0..5_000).map(|index| source(index, &root)).collect(),
+            GraphSources::default(),
+            root.join("old.bin"),
+        );
+        worker.submit_build(2, vec![source(0, &root)], GraphSources::default(), root.join("new.bin"));
+
+        match wait_for_response(&worker) {
             GraphResponse::Index { generation, outcome } => {
                 assert_eq!(generation, 2);
                 assert_eq!(outcome.index.nodes.len(), 1);
@@ -290,4 +344,24 @@ mod tests {
         assert!(!worker.is_pending());
         let _ = std::fs::remove_dir_all(root);
     }
+
+    #[test]
+    fn centrality_is_ranked_on_the_worker() {
+        let root = std::env::temp_dir();
+        let metadata = (0..4).map(|index| source(index, &root).metadata).collect();
+        let index = GraphIndex::build_from_loader(metadata, GraphSources::default(), |note_id| {
+            Some(if note_id.as_index() == 0 { String::new() } else { "[[N0]]".to_string() })
+        });
+        let worker = GraphWorker::new();
+        worker.submit_centrality(7, Arc::new(index), CentralityMetric::PageRank, 2);
+
+        match wait_for_response(&worker) {
+            GraphResponse::Centrality { generation, entries } => {
+                assert_eq!(generation, 7);
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].0, NoteId::new(0));
+            }
+            response => panic!("unexpected graph response: {response:?}"),
+        }
+    }
 }
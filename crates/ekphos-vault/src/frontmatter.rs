@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
 
 #[allow(dead_code)]
@@ -7,6 +7,9 @@ pub struct Frontmatter {
     pub title: Option<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Other names links may use for this note; `alias: X` is accepted too.
+    #[serde(default, alias = "alias", deserialize_with = "string_or_list")]
+    pub aliases: Vec<String>,
     pub date: Option<String>,
     pub author: Option<String>,
     #[serde(flatten)]
@@ -32,6 +35,21 @@ impl Frontmatter {
     }
 }
 
+fn string_or_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+        Empty(()),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+        OneOrMany::Empty(()) => Vec::new(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +113,14 @@ Content"#;
         assert_eq!(fm.tags, vec!["rust", "cli", "tui"]);
         assert_eq!(start, 6);
     }
+
+    #[test]
+    fn test_parse_aliases_as_list_or_single_value() {
+        let (fm, _) = Frontmatter::parse("---\naliases: [RPC, Remote calls]\n---\nBody");
+        assert_eq!(fm.unwrap().aliases, vec!["RPC", "Remote calls"]);
+        let (fm, _) = Frontmatter::parse("---\nalias: RPC\n---\nBody");
+        assert_eq!(fm.unwrap().aliases, vec!["RPC"]);
+        let (fm, _) = Frontmatter::parse("---\naliases:\n---\nBody");
+        assert!(fm.unwrap().aliases.is_empty());
+    }
 }
@@ -4,8 +4,13 @@
 //! `<journal_dir>/<year>/journal.<date>.md`, creating the year directory and a
 //! small dated template when needed. Dates use the user's local timezone, so
 //! entries roll over at local midnight rather than UTC.
+//!
+//! Weekly, monthly, quarterly and yearly notes are periodic notes. Each kind
+//! has its own directory, strftime filename pattern and optional template, and
+//! a fresh entry links back to the notes of the periods it contains.
 
-use chrono::{Local, NaiveDate};
+use chrono::format::StrftimeItems;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 
@@ -33,14 +38,19 @@ pub fn filename_for_date(date: NaiveDate) -> String {
 
 /// Vault-relative path for a journal entry.
 pub fn entry_relative_path(journal_dir: &str, date: NaiveDate) -> Result<PathBuf, String> {
-    let journal_dir = journal_dir.trim();
-    let configured_path = Path::new(journal_dir);
+    let configured_path = vault_directory(journal_dir, "journal_dir")?;
+    Ok(configured_path.join(date.format("%Y").to_string()).join(filename_for_date(date)))
+}
 
-    if journal_dir.is_empty() || configured_path.is_absolute() || configured_path.components().any(|component| !matches!(component, Component::Normal(_))) {
-        return Err("journal_dir must be a non-empty relative path inside the notes directory".to_string());
+fn vault_directory<'a>(directory: &'a str, setting: &str) -> Result<&'a Path, String> {
+    let directory = directory.trim();
+    let configured_path = Path::new(directory);
+
+    if directory.is_empty() || configured_path.is_absolute() || configured_path.components().any(|component| !matches!(component, Component::Normal(_))) {
+        return Err(format!("{setting} must be a non-empty relative path inside the notes directory"));
     }
 
-    Ok(configured_path.join(date.format("%Y").to_string()).join(filename_for_date(date)))
+    Ok(configured_path)
 }
 
 /// Initial content for a fresh journal entry, with the date filled in.
@@ -75,6 +85,215 @@ pub fn open_or_create_entry(notes_dir: &Path, journal_dir: &str, date: NaiveDate
     })
 }
 
+/// A periodic note longer than one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PeriodKind {
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl PeriodKind {
+    pub const ALL: [Self; 4] = [Self::Week, Self::Month, Self::Quarter, Self::Year];
+
+    /// Config key and tag for this kind, e.g. `weekly`.
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Week => "weekly",
+            Self::Month => "monthly",
+            Self::Quarter => "quarterly",
+            Self::Year => "yearly",
+        }
+    }
+
+    /// Default filename pattern, relative to the configured directory.
+    pub const fn default_filename(self) -> &'static str {
+        match self {
+            Self::Week => "%G/week.%G-W%V.md",
+            Self::Month => "%Y/month.%Y-%m.md",
+            Self::Quarter => "%Y/quarter.%Y-Q%q.md",
+            Self::Year => "%Y/year.%Y.md",
+        }
+    }
+
+    /// First day of the period containing `date`. ISO weeks start on Monday.
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Week => date - Duration::days(i64::from(date.weekday().num_days_from_monday())),
+            Self::Month => date.with_day(1).expect("day one exists in every month"),
+            Self::Quarter => NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1).expect("quarter start is a valid date"),
+            Self::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).expect("January 1 is a valid date"),
+        }
+    }
+
+    /// Last day of the period containing `date`.
+    pub fn end(self, date: NaiveDate) -> NaiveDate {
+        let start = self.start(date);
+        let next = match self {
+            Self::Week => start + Duration::days(7),
+            Self::Month => start + Months::new(1),
+            Self::Quarter => start + Months::new(3),
+            Self::Year => start + Months::new(12),
+        };
+        next.pred_opt().expect("period end is after the minimum date")
+    }
+
+    /// Human-readable period label, e.g. `2026-W32` or `2026-Q3`.
+    pub fn label(self, date: NaiveDate) -> String {
+        let start = self.start(date);
+        match self {
+            Self::Week => start.format("%G-W%V").to_string(),
+            Self::Month => start.format("%Y-%m").to_string(),
+            Self::Quarter => start.format("%Y-Q%q").to_string(),
+            Self::Year => start.format("%Y").to_string(),
+        }
+    }
+}
+
+/// Where and how one periodic note kind is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodicNoteSpec<'a> {
+    pub kind: PeriodKind,
+    /// Vault-relative directory holding this kind of note.
+    pub directory: &'a str,
+    /// strftime pattern for the path below `directory`, formatted with the
+    /// first day of the period.
+    pub filename: &'a str,
+    /// Optional vault-relative template file.
+    pub template: Option<&'a str>,
+}
+
+/// Every configured path format a periodic entry can link to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodicLayout<'a> {
+    pub journal_dir: &'a str,
+    pub monthly: PeriodicNoteSpec<'a>,
+}
+
+/// Vault-relative path for a periodic entry.
+pub fn periodic_relative_path(spec: &PeriodicNoteSpec<'_>, date: NaiveDate) -> Result<PathBuf, String> {
+    let setting = spec.kind.id();
+    let directory = vault_directory(spec.directory, &format!("periodic.{setting}.directory"))?;
+    let pattern = spec.filename.trim();
+    let invalid = || format!("periodic.{setting}.filename must be a relative .md path pattern");
+    if pattern.is_empty() || StrftimeItems::new(pattern).parse().is_err() {
+        return Err(invalid());
+    }
+
+    let formatted = spec.kind.start(date).format(pattern).to_string();
+    let filename = Path::new(&formatted);
+    if filename.is_absolute()
+        || filename.components().any(|component| !matches!(component, Component::Normal(_)))
+        || filename.extension().is_none_or(|extension| extension != "md")
+    {
+        return Err(invalid());
+    }
+
+    Ok(directory.join(filename))
+}
+
+/// Titles of the notes directly contained by a period: days for weeks and
+/// months, months for quarters and years. Titles match the file stems that
+/// wiki links resolve against.
+pub fn contained_note_titles(kind: PeriodKind, date: NaiveDate, layout: &PeriodicLayout<'_>) -> Result<Vec<String>, String> {
+    let start = kind.start(date);
+    let end = kind.end(date);
+    let mut titles = Vec::new();
+    let mut cursor = start;
+    while cursor <= end {
+        let (path, next) = match kind {
+            PeriodKind::Week | PeriodKind::Month => (entry_relative_path(layout.journal_dir, cursor)?, cursor + Duration::days(1)),
+            PeriodKind::Quarter | PeriodKind::Year => (periodic_relative_path(&layout.monthly, cursor)?, cursor + Months::new(1)),
+        };
+        if let Some(stem) = path.file_stem() {
+            titles.push(stem.to_string_lossy().to_string());
+        }
+        cursor = next;
+    }
+    Ok(titles)
+}
+
+/// Initial content for a fresh periodic entry. A template may use
+/// `{{title}}`, `{{period}}`, `{{start}}`, `{{end}}` and `{{links}}`; the
+/// contained-note links are appended when `{{links}}` is absent.
+pub fn new_periodic_content(kind: PeriodKind, date: NaiveDate, links: &[String], template: Option<&str>) -> String {
+    let start = kind.start(date);
+    let end = kind.end(date);
+    let period = kind.label(date);
+    let title = match kind {
+        PeriodKind::Week => format!("Week {period}"),
+        PeriodKind::Month => format!("Month {period}"),
+        PeriodKind::Quarter => format!("Quarter {period}"),
+        PeriodKind::Year => format!("Year {period}"),
+    };
+    let heading = match kind {
+        PeriodKind::Week | PeriodKind::Month => "Days",
+        PeriodKind::Quarter | PeriodKind::Year => "Months",
+    };
+    let link_list: String = links.iter().map(|title| format!("- [[{title}]]\n")).collect();
+    let start_text = start.format("%Y-%m-%d").to_string();
+    let end_text = end.format("%Y-%m-%d").to_string();
+
+    let Some(template) = template else {
+        return format!(
+            "---\ntitle: {title}\ntags: [journal, {}]\ndate: {start_text}\n---\n\n# {title} · {start_text} – {end_text}\n\n## {heading}\n\n{link_list}",
+            kind.id()
+        );
+    };
+
+    let rendered = template
+        .replace("{{title}}", &title)
+        .replace("{{period}}", &period)
+        .replace("{{start}}", &start_text)
+        .replace("{{end}}", &end_text);
+    if rendered.contains("{{links}}") {
+        return rendered.replace("{{links}}", link_list.trim_end());
+    }
+    let separator = if rendered.is_empty() || rendered.ends_with("\n\n") {
+        ""
+    } else if rendered.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{rendered}{separator}## {heading}\n\n{link_list}")
+}
+
+/// Open the periodic entry containing `date`, or create it from the
+/// configured template. Existing files are never overwritten.
+pub fn open_or_create_periodic_entry(
+    notes_dir: &Path,
+    spec: &PeriodicNoteSpec<'_>,
+    layout: &PeriodicLayout<'_>,
+    date: NaiveDate,
+) -> Result<JournalEntry, String> {
+    let path = notes_dir.join(periodic_relative_path(spec, date)?);
+    if path.exists() {
+        return existing_entry(path);
+    }
+
+    let template = match spec.template.map(str::trim).filter(|template| !template.is_empty()) {
+        Some(template) => {
+            let relative = vault_directory(template, &format!("periodic.{}.template", spec.kind.id()))?;
+            let template_path = notes_dir.join(relative);
+            Some(fs::read_to_string(&template_path).map_err(|error| format!("failed to read template {}: {error}", template_path.display()))?)
+        }
+        None => None,
+    };
+    let links = contained_note_titles(spec.kind, date, layout)?;
+    let content = new_periodic_content(spec.kind, date, &links, template.as_deref());
+
+    let parent = path.parent().ok_or_else(|| "invalid periodic note path".to_string())?;
+    fs::create_dir_all(parent).map_err(|error| format!("failed to create directory {}: {error}", parent.display()))?;
+    fs::write(&path, content).map_err(|error| format!("failed to create {}: {error}", path.display()))?;
+
+    Ok(JournalEntry {
+        path,
+        action: JournalEntryAction::Created,
+    })
+}
+
 fn existing_entry(path: PathBuf) -> Result<JournalEntry, String> {
     if !path.is_file() {
         return Err(format!("path is not a file: {}", path.display()));
@@ -217,4 +436,132 @@ mod tests {
         let error = open_or_create_entry(&notes_dir, "Journal", test_date()).unwrap_err();
         assert!(error.contains("failed to create directory"));
     }
+
+    fn spec(kind: PeriodKind) -> PeriodicNoteSpec<'static> {
+        PeriodicNoteSpec {
+            kind,
+            directory: "Journal",
+            filename: kind.default_filename(),
+            template: None,
+        }
+    }
+
+    fn layout() -> PeriodicLayout<'static> {
+        PeriodicLayout {
+            journal_dir: "Journal",
+            monthly: spec(PeriodKind::Month),
+        }
+    }
+
+    #[test]
+    fn periods_cover_iso_weeks_months_quarters_and_years() {
+        let date = test_date();
+        assert_eq!(PeriodKind::Week.start(date), NaiveDate::from_ymd_opt(2026, 8, 3).unwrap());
+        assert_eq!(PeriodKind::Week.end(date), NaiveDate::from_ymd_opt(2026, 8, 9).unwrap());
+        assert_eq!(PeriodKind::Month.end(date), NaiveDate::from_ymd_opt(2026, 8, 31).unwrap());
+        assert_eq!(PeriodKind::Quarter.start(date), NaiveDate::from_ymd_opt(2026, 7, 1).unwrap());
+        assert_eq!(PeriodKind::Quarter.end(date), NaiveDate::from_ymd_opt(2026, 9, 30).unwrap());
+        assert_eq!(PeriodKind::Year.end(date), NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
+        assert_eq!(PeriodKind::Week.label(date), "2026-W32");
+        assert_eq!(PeriodKind::Quarter.label(date), "2026-Q3");
+    }
+
+    #[test]
+    fn periodic_paths_use_the_iso_week_year_at_year_boundaries() {
+        let date = NaiveDate::from_ymd_opt(2027, 1, 1).unwrap();
+        assert_eq!(
+            periodic_relative_path(&spec(PeriodKind::Week), date).unwrap(),
+            PathBuf::from("Journal/2026/week.2026-W53.md")
+        );
+        assert_eq!(
+            periodic_relative_path(&spec(PeriodKind::Quarter), test_date()).unwrap(),
+            PathBuf::from("Journal/2026/quarter.2026-Q3.md")
+        );
+    }
+
+    #[test]
+    fn periodic_paths_reject_patterns_outside_the_vault() {
+        for filename in ["", "../%Y.md", "/%Y.md", "%Y/notes.txt", "%Y-%!.md"] {
+            let spec = PeriodicNoteSpec {
+                filename,
+                ..spec(PeriodKind::Year)
+            };
+            let error = periodic_relative_path(&spec, test_date()).unwrap_err();
+            assert!(error.contains("periodic.yearly.filename"), "{filename}: {error}");
+        }
+        let spec = PeriodicNoteSpec {
+            directory: "../Reviews",
+            ..spec(PeriodKind::Year)
+        };
+        assert!(periodic_relative_path(&spec, test_date()).unwrap_err().contains("periodic.yearly.directory"));
+    }
+
+    #[test]
+    fn periodic_entries_link_to_contained_notes() {
+        let week = contained_note_titles(PeriodKind::Week, test_date(), &layout()).unwrap();
+        assert_eq!(week.len(), 7);
+        assert_eq!(week[0], "journal.2026-08-03");
+        assert_eq!(week[6], "journal.2026-08-09");
+
+        let quarter = contained_note_titles(PeriodKind::Quarter, test_date(), &layout()).unwrap();
+        assert_eq!(quarter, ["month.2026-07", "month.2026-08", "month.2026-09"]);
+
+        let content = new_periodic_content(PeriodKind::Quarter, test_date(), &quarter, None);
+        assert!(content.contains("title: Quarter 2026-Q3"));
+        assert!(content.contains("tags: [journal, quarterly]"));
+        assert!(content.contains("## Months\n\n- [[month.2026-07]]\n"));
+    }
+
+    #[test]
+    fn templates_fill_placeholders_and_place_links() {
+        let links = vec!["journal.2026-08-03".to_string()];
+        let placed = new_periodic_content(
+            PeriodKind::Week,
+            test_date(),
+            &links,
+            Some("# {{title}} ({{start}}..{{end}})\n{{links}}\nNotes"),
+        );
+        assert_eq!(placed, "# Week 2026-W32 (2026-08-03..2026-08-09)\n- [[journal.2026-08-03]]\nNotes");
+
+        let appended = new_periodic_content(PeriodKind::Week, test_date(), &links, Some("# {{period}}\n"));
+        assert_eq!(appended, "# 2026-W32\n\n## Days\n\n- [[journal.2026-08-03]]\n");
+    }
+
+    #[test]
+    fn creates_periodic_entry_from_template_without_overwriting_it() {
+        let workspace = TempWorkspace::new("periodic");
+        let notes_dir = workspace.path.join("vault");
+        fs::create_dir_all(notes_dir.join("Templates")).unwrap();
+        fs::write(notes_dir.join("Templates/month.md"), "# {{title}}\n").unwrap();
+        let spec = PeriodicNoteSpec {
+            template: Some("Templates/month.md"),
+            ..spec(PeriodKind::Month)
+        };
+
+        let created = open_or_create_periodic_entry(&notes_dir, &spec, &layout(), test_date()).unwrap();
+        assert_eq!(created.action, JournalEntryAction::Created);
+        assert_eq!(created.path, notes_dir.join("Journal/2026/month.2026-08.md"));
+        let content = fs::read_to_string(&created.path).unwrap();
+        assert!(content.starts_with("# Month 2026-08\n\n## Days\n"));
+        assert!(content.contains("- [[journal.2026-08-31]]"));
+
+        fs::write(&created.path, "keep").unwrap();
+        let opened = open_or_create_periodic_entry(&notes_dir, &spec, &layout(), test_date()).unwrap();
+        assert_eq!(opened.action, JournalEntryAction::Opened);
+        assert_eq!(fs::read_to_string(opened.path).unwrap(), "keep");
+    }
+
+    #[test]
+    fn missing_template_is_reported_before_creating_the_entry() {
+        let workspace = TempWorkspace::new("periodic-template");
+        let notes_dir = workspace.path.join("vault");
+        let spec = PeriodicNoteSpec {
+            template: Some("Templates/missing.md"),
+            ..spec(PeriodKind::Year)
+        };
+
+        let error = open_or_create_periodic_entry(&notes_dir, &spec, &layout(), test_date()).unwrap_err();
+        assert!(error.contains("failed to read template"));
+        assert!(!notes_dir.join("Journal").exists());
+    }
 }
@@ -2,6 +2,11 @@
 
 mod frontmatter;
 pub mod journal;
+pub mod properties;
+pub mod query;
+pub mod snapshots;
+pub mod tasks;
+pub mod trash;
 
 pub use frontmatter::Frontmatter;
 
@@ -137,6 +142,17 @@ impl Vault {
         Ok(Arc::<str>::from(body))
     }
 
+    /// Read only the frontmatter prefix of a note, keeping arbitrary keys.
+    pub fn load_frontmatter(&self, id: NoteId) -> Result<Option<Frontmatter>, VaultError> {
+        let note = self.notes.get(&id).ok_or(VaultError::NotFound(id))?;
+        let path = self.root.join(note.path.as_str());
+        match read_frontmatter(&path) {
+            Ok((frontmatter, _)) => Ok(frontmatter),
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => Err(VaultError::InvalidEncoding(path)),
+            Err(source) => Err(VaultError::Io { path, source }),
+        }
+    }
+
     /// Refresh one catalog record after an Ekphos save without rescanning the vault.
     pub fn refresh_note(&mut self, id: NoteId) -> Result<CatalogNote, VaultError> {
         let path = self.root.join(self.notes.get(&id).ok_or(VaultError::NotFound(id))?.path.as_str());
@@ -379,6 +395,7 @@ fn catalog_note(root: &Path, path: &Path) -> io::Result<CatalogNote> {
         .map(|value| FrontmatterSummary {
             title: value.title,
             tags: value.tags,
+            aliases: value.aliases,
             date: value.date,
         })
         .unwrap_or_default();
@@ -0,0 +1,523 @@
+//! Typed frontmatter properties.
+//!
+//! Values are read through [`Frontmatter`] and classified as text, list,
+//! date, number or bool. Writes are line based: only the lines belonging to
+//! the edited key are replaced, so key order, other keys' formatting and
+//! comments survive. Nested mappings are reported as [`PropertyValue::Raw`]
+//! and are never rewritten.
+
+use crate::Frontmatter;
+use chrono::NaiveDate;
+use ekphos_core::markdown::frontmatter_end;
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
+    Text,
+    List,
+    Date,
+    Number,
+    Bool,
+}
+
+impl PropertyKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::List => "list",
+            Self::Date => "date",
+            Self::Number => "number",
+            Self::Bool => "bool",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Text(String),
+    List(Vec<String>),
+    Date(NaiveDate),
+    /// Kept as written so `1.50` is not rewritten as `1.5`.
+    Number(String),
+    Bool(bool),
+    /// Nested mapping or tagged value, shown as YAML and left untouched.
+    Raw(String),
+}
+
+impl PropertyValue {
+    pub fn kind(&self) -> PropertyKind {
+        match self {
+            Self::Text(_) | Self::Raw(_) => PropertyKind::Text,
+            Self::List(_) => PropertyKind::List,
+            Self::Date(_) => PropertyKind::Date,
+            Self::Number(_) => PropertyKind::Number,
+            Self::Bool(_) => PropertyKind::Bool,
+        }
+    }
+
+    /// Empty value of `kind`; dates start at `today`.
+    pub fn empty(kind: PropertyKind, today: NaiveDate) -> Self {
+        match kind {
+            PropertyKind::Text => Self::Text(String::new()),
+            PropertyKind::List => Self::List(Vec::new()),
+            PropertyKind::Date => Self::Date(today),
+            PropertyKind::Number => Self::Number("0".to_owned()),
+            PropertyKind::Bool => Self::Bool(false),
+        }
+    }
+
+    fn from_yaml(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Text(String::new()),
+            Value::Bool(value) => Self::Bool(*value),
+            Value::Number(number) => Self::Number(number.to_string()),
+            Value::String(text) => NaiveDate::parse_from_str(text, DATE_FORMAT).map_or_else(|_| Self::Text(text.clone()), Self::Date),
+            Value::Sequence(items) => Self::List(items.iter().filter_map(scalar_text).collect()),
+            Value::Mapping(_) | Value::Tagged(_) => Self::Raw(serde_yaml::to_string(value).unwrap_or_default().trim_end().to_owned()),
+        }
+    }
+
+    /// Single-line rendering for display.
+    pub fn display(&self) -> String {
+        match self {
+            Self::Text(text) | Self::Number(text) => text.clone(),
+            Self::List(items) => items.join(", "),
+            Self::Date(date) => date.format(DATE_FORMAT).to_string(),
+            Self::Bool(value) => value.to_string(),
+            Self::Raw(yaml) => yaml.replace('\n', " "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub key: String,
+    pub value: PropertyValue,
+}
+
+/// Frontmatter properties in source order. `None` when the note has no
+/// frontmatter block or its YAML does not parse.
+pub fn parse_properties(content: &str) -> Option<Vec<Property>> {
+    let (frontmatter, _) = Frontmatter::parse(content);
+    let frontmatter = frontmatter?;
+    let end = frontmatter_end(content)?;
+    let properties = content
+        .lines()
+        .take(end)
+        .skip(1)
+        .filter_map(top_level_key)
+        .filter_map(|key| {
+            let value = match key {
+                "title" => PropertyValue::Text(frontmatter.title.clone().unwrap_or_default()),
+                "author" => PropertyValue::Text(frontmatter.author.clone().unwrap_or_default()),
+                "tags" => PropertyValue::List(frontmatter.tags.clone()),
+                "date" => frontmatter.date.as_deref().map_or(PropertyValue::Text(String::new()), |date| {
+                    NaiveDate::parse_from_str(date, DATE_FORMAT).map_or_else(|_| PropertyValue::Text(date.to_owned()), PropertyValue::Date)
+                }),
+                _ => PropertyValue::from_yaml(frontmatter.extra.get(key)?),
+            };
+            Some(Property { key: key.to_owned(), value })
+        })
+        .collect();
+    Some(properties)
+}
+
+/// Set `key` to `value`, replacing its existing lines or appending it to
+/// the frontmatter (which is created when missing).
+pub fn set_property(content: &str, key: &str, value: &PropertyValue) -> String {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = content.lines().map(str::to_owned).collect();
+    let Some(end) = frontmatter_end(content) else {
+        let mut out = format!("---{newline}");
+        for line in render_property(key, value, None, None) {
+            out.push_str(&line);
+            out.push_str(newline);
+        }
+        out.push_str("---");
+        out.push_str(newline);
+        out.push_str(content);
+        return out;
+    };
+
+    match key_block(&lines, end, key) {
+        Some(block) => {
+            let head = &lines[block.start];
+            let comment = trailing_comment(&head[key_prefix_len(head)..]).map(str::to_owned);
+            let item_indent = lines[block.start + 1..block.end]
+                .iter()
+                .find(|line| line.trim_start().starts_with('-'))
+                .map(|line| line[..line.len() - line.trim_start().len()].to_owned());
+            let (mut anchored, trailing) = block_comments(&lines[block.start + 1..block.end]);
+            let mut replacement = Vec::new();
+            for line in render_property(key, value, comment.as_deref(), item_indent.as_deref()) {
+                // Comments go back above the item they preceded.
+                let item = line.trim_start().strip_prefix("- ").and_then(item_text);
+                if let Some(position) = item.and_then(|item| anchored.iter().position(|(anchor, _)| *anchor == item)) {
+                    replacement.extend(anchored.remove(position).1);
+                }
+                replacement.push(line);
+            }
+            replacement.extend(trailing);
+            lines.splice(block, replacement);
+        }
+        None => {
+            let rendered = render_property(key, value, None, None);
+            lines.splice(end..end, rendered);
+        }
+    }
+    join_lines(&lines, newline, content)
+}
+
+/// Drop `key` and its continuation lines. Comments on their own lines stay,
+/// except those above a list item, which go with it.
+pub fn remove_property(content: &str, key: &str) -> String {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let Some(end) = frontmatter_end(content) else {
+        return content.to_owned();
+    };
+    let mut lines: Vec<String> = content.lines().map(str::to_owned).collect();
+    let Some(block) = key_block(&lines, end, key) else {
+        return content.to_owned();
+    };
+    let (_, trailing) = block_comments(&lines[block.start + 1..block.end]);
+    let kept: Vec<String> = trailing.iter().map(|line| line.trim_start().to_owned()).collect();
+    lines.splice(block, kept);
+    join_lines(&lines, newline, content)
+}
+
+/// Keys and values seen across the vault, for autocomplete.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyVocabulary {
+    keys: BTreeMap<String, KeyUsage>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct KeyUsage {
+    count: usize,
+    values: BTreeMap<String, usize>,
+    kinds: HashMap<PropertyKind, usize>,
+}
+
+impl PropertyVocabulary {
+    pub fn from_frontmatter<'a>(frontmatters: impl IntoIterator<Item = &'a Frontmatter>) -> Self {
+        let mut vocabulary = Self::default();
+        for frontmatter in frontmatters {
+            let builtin = [
+                ("title", frontmatter.title.as_ref().map(|title| PropertyValue::Text(title.clone()))),
+                ("author", frontmatter.author.as_ref().map(|author| PropertyValue::Text(author.clone()))),
+                (
+                    "date",
+                    frontmatter
+                        .date
+                        .as_deref()
+                        .map(|date| PropertyValue::from_yaml(&Value::String(date.to_owned()))),
+                ),
+                ("tags", (!frontmatter.tags.is_empty()).then(|| PropertyValue::List(frontmatter.tags.clone()))),
+            ];
+            for (key, value) in builtin {
+                if let Some(value) = value {
+                    vocabulary.record(key, &value);
+                }
+            }
+            for (key, value) in &frontmatter.extra {
+                vocabulary.record(key, &PropertyValue::from_yaml(value));
+            }
+        }
+        vocabulary
+    }
+
+    fn record(&mut self, key: &str, value: &PropertyValue) {
+        let usage = self.keys.entry(key.to_owned()).or_default();
+        usage.count += 1;
+        *usage.kinds.entry(value.kind()).or_default() += 1;
+        let values = match value {
+            PropertyValue::List(items) => items.clone(),
+            PropertyValue::Raw(_) => Vec::new(),
+            value => vec![value.display()],
+        };
+        for value in values.into_iter().filter(|value| !value.is_empty()) {
+            *usage.values.entry(value).or_default() += 1;
+        }
+    }
+
+    /// Known keys starting with `prefix`, most used first.
+    pub fn keys(&self, prefix: &str) -> Vec<&str> {
+        ranked(self.keys.iter().map(|(key, usage)| (key.as_str(), usage.count)), prefix)
+    }
+
+    /// Values used for `key` starting with `prefix`, most used first.
+    pub fn values(&self, key: &str, prefix: &str) -> Vec<&str> {
+        self.keys.get(key).map_or_else(Vec::new, |usage| {
+            ranked(usage.values.iter().map(|(value, count)| (value.as_str(), *count)), prefix)
+        })
+    }
+
+    /// The kind most notes use for `key`.
+    pub fn kind(&self, key: &str) -> Option<PropertyKind> {
+        let usage = self.keys.get(key)?;
+        usage
+            .kinds
+            .iter()
+            .max_by_key(|(kind, count)| (**count, std::cmp::Reverse(kind.label())))
+            .map(|(kind, _)| *kind)
+    }
+}
+
+fn ranked<'a>(candidates: impl Iterator<Item = (&'a str, usize)>, prefix: &str) -> Vec<&'a str> {
+    let prefix = prefix.to_lowercase();
+    let mut matches: Vec<(&str, usize)> = candidates
+        .filter(|(candidate, _)| candidate.to_lowercase().starts_with(&prefix) && !candidate.eq_ignore_ascii_case(&prefix))
+        .collect();
+    matches.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(right.0)));
+    matches.into_iter().map(|(candidate, _)| candidate).collect()
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// Key of a top-level `key: ...` line.
+fn top_level_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '#', '-']) {
+        return None;
+    }
+    let colon = line.find(':')?;
+    let key = line[..colon].trim().trim_matches(|character| character == '"' || character == '\'');
+    (!key.is_empty()).then_some(key)
+}
+
+fn key_prefix_len(line: &str) -> usize {
+    line.find(':').map_or(line.len(), |colon| colon + 1)
+}
+
+/// Lines `key:` spans inside the frontmatter: its own line plus indented or
+/// `- ` continuation lines.
+fn key_block(lines: &[String], end: usize, key: &str) -> Option<std::ops::Range<usize>> {
+    let continues = |line: &str| line.starts_with([' ', '\t']) || line.starts_with("- ") || line == "-";
+    let start = (1..end).find(|&index| top_level_key(&lines[index]) == Some(key))?;
+    let mut stop = start + 1;
+    while stop < end {
+        let line = &lines[stop];
+        if line.trim().is_empty() {
+            // Blank lines belong to the value only when it carries on after
+            // them, as inside a block scalar.
+            let next = (stop..end).find(|&index| !lines[index].trim().is_empty());
+            if !next.is_some_and(|index| continues(&lines[index])) {
+                break;
+            }
+        } else if !continues(line) {
+            break;
+        }
+        stop += 1;
+    }
+    Some(start..stop)
+}
+
+/// Own-line comments in a value's continuation lines: those directly above
+/// a `- item`, keyed by the item's text, and the rest.
+fn block_comments(lines: &[String]) -> (Vec<(String, Vec<String>)>, Vec<String>) {
+    let mut anchored = Vec::new();
+    let mut pending = Vec::new();
+    for line in lines {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            pending.push(line.clone());
+        } else if let Some(item) = trimmed.strip_prefix("- ").and_then(item_text) {
+            if !pending.is_empty() {
+                anchored.push((item, std::mem::take(&mut pending)));
+            }
+        }
+    }
+    (anchored, pending)
+}
+
+/// The string a block list item holds, as YAML reads it.
+fn item_text(item: &str) -> Option<String> {
+    scalar_text(&serde_yaml::from_str(item).ok()?)
+}
+
+/// ` # comment` after a value, ignoring `#` inside quotes.
+fn trailing_comment(value: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, character) in value.char_indices() {
+        match (quote, character) {
+            (None, '"' | '\'') => quote = Some(character),
+            (Some(open), _) if character == open => quote = None,
+            (None, '#') if previous.is_whitespace() => return Some(value[index..].trim_end()),
+            _ => {}
+        }
+        previous = character;
+    }
+    None
+}
+
+fn render_property(key: &str, value: &PropertyValue, comment: Option<&str>, item_indent: Option<&str>) -> Vec<String> {
+    let suffix = comment.map_or_else(String::new, |comment| format!(" {comment}"));
+    match value {
+        PropertyValue::List(items) if item_indent.is_some() && !items.is_empty() => {
+            let indent = item_indent.unwrap_or_default();
+            let mut lines = vec![format!("{key}:{suffix}")];
+            lines.extend(items.iter().map(|item| format!("{indent}- {}", yaml_scalar(item, false))));
+            lines
+        }
+        PropertyValue::List(items) => {
+            let items: Vec<String> = items.iter().map(|item| yaml_scalar(item, true)).collect();
+            vec![format!("{key}: [{}]{suffix}", items.join(", "))]
+        }
+        PropertyValue::Text(text) if text.is_empty() => vec![format!("{key}:{suffix}")],
+        PropertyValue::Text(text) if text.contains('\n') => {
+            // Literal block scalar; `+` keeps trailing newlines, `-` strips
+            // the one YAML would otherwise add.
+            let indicator = if text.starts_with(' ') { "2" } else { "" };
+            let chomping = if text.ends_with('\n') { '+' } else { '-' };
+            let body = text.strip_suffix('\n').unwrap_or(text);
+            let mut lines = vec![format!("{key}: |{indicator}{chomping}{suffix}")];
+            lines.extend(body.split('\n').map(|line| if line.is_empty() { String::new() } else { format!("  {line}") }));
+            lines
+        }
+        PropertyValue::Text(text) => vec![format!("{key}: {}{suffix}", yaml_scalar(text, false))],
+        PropertyValue::Date(date) => vec![format!("{key}: {}{suffix}", date.format(DATE_FORMAT))],
+        PropertyValue::Number(number) => vec![format!("{key}: {number}{suffix}")],
+        PropertyValue::Bool(value) => vec![format!("{key}: {value}{suffix}")],
+        PropertyValue::Raw(yaml) => {
+            let mut lines = vec![format!("{key}:{suffix}")];
+            lines.extend(yaml.lines().map(|line| format!("  {line}")));
+            lines
+        }
+    }
+}
+
+/// Plain scalar when YAML reads it back as the same string, single-quoted
+/// otherwise. Flow items also quote flow indicators.
+fn yaml_scalar(text: &str, in_flow: bool) -> String {
+    let reserved = matches!(
+        text.to_ascii_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+    );
+    let needs_quotes = text.is_empty()
+        || reserved
+        || text.parse::<f64>().is_ok()
+        || text.trim() != text
+        || text.starts_with(['-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`'])
+        || text.contains(": ")
+        || text.contains(" #")
+        || text.ends_with(':')
+        || (in_flow && text.contains([',', '[', ']', '{', '}']));
+    if needs_quotes {
+        format!("'{}'", text.replace('\'', "''"))
+    } else {
+        text.to_owned()
+    }
+}
+
+fn join_lines(lines: &[String], newline: &str, original: &str) -> String {
+    let mut out = lines.join(newline);
+    if original.ends_with('\n') {
+        out.push_str(newline);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "---\n# project card\ntitle: Launch plan\nstatus: active # keep short\npriority: 2\ndue: 2026-10-20\ndone: false\ntags:\n  - work\n  # owners below\n  - q4\nowner:\n  name: Ada\n---\n# Body\n";
+
+    #[test]
+    fn parses_properties_in_source_order_with_types() {
+        let properties = parse_properties(NOTE).unwrap();
+        let summary: Vec<(&str, PropertyKind, String)> = properties
+            .iter()
+            .map(|property| (property.key.as_str(), property.value.kind(), property.value.display()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("title", PropertyKind::Text, "Launch plan".to_owned()),
+                ("status", PropertyKind::Text, "active".to_owned()),
+                ("priority", PropertyKind::Number, "2".to_owned()),
+                ("due", PropertyKind::Date, "2026-10-20".to_owned()),
+                ("done", PropertyKind::Bool, "false".to_owned()),
+                ("tags", PropertyKind::List, "work, q4".to_owned()),
+                ("owner", PropertyKind::Text, "name: Ada".to_owned()),
+            ]
+        );
+        assert!(matches!(properties[6].value, PropertyValue::Raw(_)));
+        assert_eq!(parse_properties("# No frontmatter\n"), None);
+    }
+
+    #[test]
+    fn writes_preserve_order_comments_and_list_style() {
+        let updated = set_property(NOTE, "status", &PropertyValue::Text("on hold: waiting".to_owned()));
+        let updated = set_property(
+            &updated,
+            "tags",
+            &PropertyValue::List(vec!["work".to_owned(), "q1".to_owned(), "q4".to_owned()]),
+        );
+        let updated = set_property(&updated, "done", &PropertyValue::Bool(true));
+        let updated = set_property(&updated, "reviewed", &PropertyValue::Date(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()));
+        assert_eq!(
+            updated,
+            "---\n# project card\ntitle: Launch plan\nstatus: 'on hold: waiting' # keep short\npriority: 2\ndue: 2026-10-20\ndone: true\ntags:\n  - work\n  - q1\n  # owners below\n  - q4\nowner:\n  name: Ada\nreviewed: 2026-10-18\n---\n# Body\n"
+        );
+        let properties = parse_properties(&updated).unwrap();
+        assert_eq!(properties[1].value, PropertyValue::Text("on hold: waiting".to_owned()));
+
+        let note = "---\ntags:\n  - work\n  # owners below\n  - q4\n  # trailing note\ndone: false\n---\n";
+        let dropped = set_property(note, "tags", &PropertyValue::List(vec!["work".to_owned()]));
+        assert_eq!(dropped, "---\ntags:\n  - work\n  # trailing note\ndone: false\n---\n");
+        assert_eq!(remove_property(note, "tags"), "---\n# trailing note\ndone: false\n---\n");
+    }
+
+    #[test]
+    fn multi_line_text_round_trips_as_a_block_scalar() {
+        let note = "---\ndescription: |-\n  line one\n\n  line two\ntags: [a]\n---\nBody\n";
+        let properties = parse_properties(note).unwrap();
+        assert_eq!(properties[0].value, PropertyValue::Text("line one\n\nline two".to_owned()));
+
+        for text in ["line one\nline two edited", "  indented\nnext\n", "a\n\nb"] {
+            let updated = set_property(note, "description", &PropertyValue::Text(text.to_owned()));
+            let properties = parse_properties(&updated).unwrap();
+            assert_eq!(properties[0].value, PropertyValue::Text(text.to_owned()), "{updated}");
+            assert_eq!(properties[1].value, PropertyValue::List(vec!["a".to_owned()]));
+        }
+        let updated = set_property(note, "description", &PropertyValue::Text("line one\nline two edited".to_owned()));
+        assert_eq!(updated, "---\ndescription: |-\n  line one\n  line two edited\ntags: [a]\n---\nBody\n");
+    }
+
+    #[test]
+    fn creates_frontmatter_and_quotes_ambiguous_scalars() {
+        let updated = set_property("Body\r\n", "tags", &PropertyValue::List(vec!["a, b".to_owned(), "yes".to_owned()]));
+        assert_eq!(updated, "---\r\ntags: ['a, b', 'yes']\r\n---\r\nBody\r\n");
+        let properties = parse_properties(&updated).unwrap();
+        assert_eq!(properties[0].value, PropertyValue::List(vec!["a, b".to_owned(), "yes".to_owned()]));
+    }
+
+    #[test]
+    fn vocabulary_ranks_keys_and_values_by_use() {
+        let notes: Vec<Frontmatter> = [
+            "---\nstatus: active\ntags: [work]\n---\n",
+            "---\nstatus: done\ntags: [work, home]\n---\n",
+            "---\nstatus: active\nstage: 2\n---\n",
+        ]
+        .iter()
+        .filter_map(|content| Frontmatter::parse(content).0)
+        .collect();
+        let vocabulary = PropertyVocabulary::from_frontmatter(&notes);
+        assert_eq!(vocabulary.keys("st"), ["status", "stage"]);
+        assert_eq!(vocabulary.values("status", ""), ["active", "done"]);
+        assert_eq!(vocabulary.values("tags", "h"), ["home"]);
+        assert_eq!(vocabulary.kind("stage"), Some(PropertyKind::Number));
+        assert_eq!(vocabulary.kind("tags"), Some(PropertyKind::List));
+    }
+}
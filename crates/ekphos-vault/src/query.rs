@@ -0,0 +1,412 @@
+//! Query blocks.
+//!
+//! A fenced ```` ```query ```` block lists notes whose frontmatter matches a
+//! set of conditions. The language is line based, one clause per line:
+//!
+//! ```text
+//! table status, due      # project these fields as columns (or `list`)
+//! tag project            # frontmatter tag, repeatable
+//! path Projects/         # vault-relative path prefix, repeatable
+//! where status != done   # field condition, repeatable
+//! sort due desc          # sort key, repeatable
+//! limit 10
+//! ```
+//!
+//! All conditions must hold. `where` supports `=`, `!=`, `<`, `<=`, `>`,
+//! `>=`, `contains`, `exists` and `missing`; values compare numerically when
+//! both sides are numbers and case-insensitively otherwise. List fields such
+//! as `tags` match `=` and `contains` against any element. Besides arbitrary
+//! frontmatter keys, `file` is the note title and `path` its vault path.
+
+use crate::Frontmatter;
+use serde_yaml::Value;
+use std::cmp::Ordering;
+
+/// One note as seen by a query: its catalog identity plus full frontmatter.
+#[derive(Debug, Clone)]
+pub struct QueryNote {
+    pub title: String,
+    /// Vault-relative path with `/` separators, including the extension.
+    pub path: String,
+    /// Wiki link target used for the note column, e.g. `Projects/Alpha`.
+    pub link: String,
+    pub frontmatter: Option<Frontmatter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Contains,
+    Exists,
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    field: String,
+    operator: Operator,
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SortKey {
+    field: String,
+    descending: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    columns: Vec<String>,
+    tags: Vec<String>,
+    paths: Vec<String>,
+    conditions: Vec<Condition>,
+    sort: Vec<SortKey>,
+    limit: Option<usize>,
+}
+
+impl Query {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut query = Self::default();
+        let mut saw_projection = false;
+        for (index, raw) in source.lines().enumerate() {
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            let line_number = index + 1;
+            match keyword.to_ascii_lowercase().as_str() {
+                "table" | "list" if saw_projection => return Err(format!("line {line_number}: only one `table` or `list` clause is allowed")),
+                "table" => {
+                    saw_projection = true;
+                    query.columns = rest.split(',').map(str::trim).filter(|field| !field.is_empty()).map(str::to_owned).collect();
+                    if query.columns.is_empty() {
+                        return Err(format!("line {line_number}: `table` needs at least one field"));
+                    }
+                }
+                "list" if !rest.is_empty() => return Err(format!("line {line_number}: `list` takes no fields")),
+                "list" => saw_projection = true,
+                "tag" => query.tags.push(required(rest, "tag", line_number)?.trim_start_matches('#').to_owned()),
+                "path" => query
+                    .paths
+                    .push(unquote(required(rest, "path", line_number)?).trim_start_matches("./").to_owned()),
+                "where" => query.conditions.push(parse_condition(required(rest, "where", line_number)?, line_number)?),
+                "sort" => {
+                    let mut words = required(rest, "sort", line_number)?.split_whitespace();
+                    let field = words.next().unwrap_or_default().to_owned();
+                    let descending = match words.next().map(str::to_ascii_lowercase).as_deref() {
+                        None | Some("asc") => false,
+                        Some("desc") => true,
+                        Some(other) => return Err(format!("line {line_number}: sort direction must be `asc` or `desc`, not `{other}`")),
+                    };
+                    query.sort.push(SortKey { field, descending });
+                }
+                "limit" => {
+                    let limit = required(rest, "limit", line_number)?
+                        .parse()
+                        .map_err(|_| format!("line {line_number}: `limit` needs a whole number"))?;
+                    query.limit = Some(limit);
+                }
+                other => return Err(format!("line {line_number}: unknown clause `{other}`")),
+            }
+        }
+        Ok(query)
+    }
+
+    /// Evaluate against `notes`. Ties keep the note order given by the caller.
+    pub fn run(&self, notes: &[QueryNote]) -> QueryTable {
+        let mut matches: Vec<&QueryNote> = notes.iter().filter(|note| self.matches(note)).collect();
+        matches.sort_by(|left, right| {
+            self.sort.iter().fold(Ordering::Equal, |ordering, key| {
+                ordering.then_with(|| {
+                    let ordering = compare_optional(&field_text(left, &key.field), &field_text(right, &key.field));
+                    if key.descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+            })
+        });
+        if let Some(limit) = self.limit {
+            matches.truncate(limit);
+        }
+
+        let mut columns = vec!["Note".to_owned()];
+        columns.extend(self.columns.iter().cloned());
+        let rows = matches
+            .into_iter()
+            .map(|note| {
+                let mut row = vec![format!("[[{}]]", note.link)];
+                row.extend(self.columns.iter().map(|field| field_text(note, field).unwrap_or_default()));
+                row
+            })
+            .collect();
+        QueryTable { columns, rows }
+    }
+
+    fn matches(&self, note: &QueryNote) -> bool {
+        let tags = note.frontmatter.as_ref().map_or(&[][..], |frontmatter| frontmatter.tags.as_slice());
+        self.tags
+            .iter()
+            .all(|wanted| tags.iter().any(|tag| tag.trim_start_matches('#').eq_ignore_ascii_case(wanted)))
+            && self.paths.iter().all(|prefix| note.path.starts_with(prefix.as_str()))
+            && self.conditions.iter().all(|condition| condition.matches(note))
+    }
+}
+
+/// Parse and run a query block body.
+pub fn run_query(source: &str, notes: &[QueryNote]) -> Result<QueryTable, String> {
+    Query::parse(source).map(|query| query.run(notes))
+}
+
+impl Condition {
+    fn matches(&self, note: &QueryNote) -> bool {
+        let values = field_values(note, &self.field);
+        match self.operator {
+            Operator::Exists => !values.is_empty(),
+            Operator::Missing => values.is_empty(),
+            Operator::Equal => values.iter().any(|value| compare_values(value, &self.value) == Ordering::Equal),
+            Operator::NotEqual => !values.iter().any(|value| compare_values(value, &self.value) == Ordering::Equal),
+            Operator::Contains => {
+                let needle = self.value.to_lowercase();
+                values.iter().any(|value| value.to_lowercase().contains(&needle))
+            }
+            Operator::Less => values.iter().any(|value| compare_values(value, &self.value) == Ordering::Less),
+            Operator::LessOrEqual => values.iter().any(|value| compare_values(value, &self.value) != Ordering::Greater),
+            Operator::Greater => values.iter().any(|value| compare_values(value, &self.value) == Ordering::Greater),
+            Operator::GreaterOrEqual => values.iter().any(|value| compare_values(value, &self.value) != Ordering::Less),
+        }
+    }
+}
+
+fn required<'a>(rest: &'a str, keyword: &str, line_number: usize) -> Result<&'a str, String> {
+    if rest.is_empty() {
+        Err(format!("line {line_number}: `{keyword}` needs a value"))
+    } else {
+        Ok(rest)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    // `#` starts a comment only at the line start or after whitespace, so
+    // `tag #project` still works.
+    let mut previous_is_space = true;
+    for (index, ch) in line.char_indices() {
+        if ch == '#' && previous_is_space && !line[index + 1..].starts_with(|next: char| next.is_alphanumeric()) {
+            return &line[..index];
+        }
+        previous_is_space = ch.is_whitespace();
+    }
+    line
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|value| value.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+fn parse_condition(text: &str, line_number: usize) -> Result<Condition, String> {
+    const SYMBOLS: [(&str, Operator); 6] = [
+        ("!=", Operator::NotEqual),
+        ("<=", Operator::LessOrEqual),
+        (">=", Operator::GreaterOrEqual),
+        ("=", Operator::Equal),
+        ("<", Operator::Less),
+        (">", Operator::Greater),
+    ];
+    if let Some((field, operator)) = text.split_once(char::is_whitespace).and_then(|(field, rest)| {
+        match rest.trim().to_ascii_lowercase().as_str() {
+            "exists" => Some(Operator::Exists),
+            "missing" => Some(Operator::Missing),
+            _ => None,
+        }
+        .map(|operator| (field, operator))
+    }) {
+        return Ok(Condition {
+            field: field.to_owned(),
+            operator,
+            value: String::new(),
+        });
+    }
+    if let Some((field, value)) = text.split_once(" contains ") {
+        return Ok(Condition {
+            field: field.trim().to_owned(),
+            operator: Operator::Contains,
+            value: unquote(value.trim()).to_owned(),
+        });
+    }
+    let (position, symbol, operator) = SYMBOLS
+        .iter()
+        .filter_map(|(symbol, operator)| text.find(symbol).map(|position| (position, *symbol, *operator)))
+        .min_by_key(|(position, symbol, _)| (*position, std::cmp::Reverse(symbol.len())))
+        .ok_or_else(|| format!("line {line_number}: expected a comparison like `status = done`"))?;
+    let field = text[..position].trim();
+    if field.is_empty() {
+        return Err(format!("line {line_number}: condition is missing a field name"));
+    }
+    Ok(Condition {
+        field: field.to_owned(),
+        operator,
+        value: unquote(text[position + symbol.len()..].trim()).to_owned(),
+    })
+}
+
+/// Every scalar value of a field: lists contribute one entry per element.
+fn field_values(note: &QueryNote, field: &str) -> Vec<String> {
+    let frontmatter = note.frontmatter.as_ref();
+    match field.to_ascii_lowercase().as_str() {
+        "file" => vec![note.title.clone()],
+        "path" => vec![note.path.clone()],
+        "tags" => frontmatter.map(|frontmatter| frontmatter.tags.clone()).unwrap_or_default(),
+        "title" => frontmatter.and_then(|frontmatter| frontmatter.title.clone()).into_iter().collect(),
+        "date" => frontmatter.and_then(|frontmatter| frontmatter.date.clone()).into_iter().collect(),
+        "author" => frontmatter.and_then(|frontmatter| frontmatter.author.clone()).into_iter().collect(),
+        _ => match frontmatter.and_then(|frontmatter| extra_value(frontmatter, field)) {
+            Some(Value::Sequence(items)) => items.iter().filter_map(scalar_text).collect(),
+            Some(value) => scalar_text(value).into_iter().collect(),
+            None => Vec::new(),
+        },
+    }
+}
+
+/// A field rendered for a table cell or sort key.
+fn field_text(note: &QueryNote, field: &str) -> Option<String> {
+    let values = field_values(note, field);
+    (!values.is_empty()).then(|| values.join(", "))
+}
+
+fn extra_value<'a>(frontmatter: &'a Frontmatter, field: &str) -> Option<&'a Value> {
+    frontmatter.extra.get(field).or_else(|| {
+        frontmatter
+            .extra
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(field))
+            .map(|(_, value)| value)
+    })
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        Value::Tagged(tagged) => scalar_text(&tagged.value),
+        Value::Sequence(_) | Value::Mapping(_) => serde_yaml::to_string(value).ok().map(|text| text.trim().replace('\n', " ")),
+    }
+}
+
+fn compare_values(left: &str, right: &str) -> Ordering {
+    match (left.trim().parse::<f64>(), right.trim().parse::<f64>()) {
+        (Ok(left), Ok(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+        _ => left.to_lowercase().cmp(&right.to_lowercase()),
+    }
+}
+
+/// Missing values sort after present ones in ascending order.
+fn compare_optional(left: &Option<String>, right: &Option<String>) -> Ordering {
+    match (left, right) {
+        (Some(left), Some(right)) => compare_values(left, right),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(path: &str, yaml: &str) -> QueryNote {
+        let title = path.rsplit('/').next().unwrap().trim_end_matches(".md").to_owned();
+        QueryNote {
+            title,
+            path: path.to_owned(),
+            link: path.trim_end_matches(".md").to_owned(),
+            frontmatter: Some(serde_yaml::from_str(yaml).unwrap()),
+        }
+    }
+
+    fn corpus() -> Vec<QueryNote> {
+        vec![
+            note("Projects/Alpha.md", "tags: [project]\nstatus: active\npriority: 2\ndue: 2026-11-01"),
+            note("Projects/Beta.md", "tags: [project, client]\nstatus: done\npriority: 10\nowners: [ana, ben]"),
+            note("Projects/Gamma.md", "tags: [project]\nstatus: active\npriority: 1"),
+            note("Journal/Today.md", "tags: [journal]\nstatus: active"),
+            QueryNote {
+                title: "Plain".to_owned(),
+                path: "Plain.md".to_owned(),
+                link: "Plain".to_owned(),
+                frontmatter: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn filters_sorts_limits_and_projects_fields() {
+        let table = run_query(
+            "table status, priority, due\ntag project\npath \"Projects/\"\nwhere status != done  # skip finished\nsort priority desc\nlimit 5\n",
+            &corpus(),
+        )
+        .unwrap();
+        assert_eq!(table.columns, ["Note", "status", "priority", "due"]);
+        assert_eq!(
+            table.rows,
+            [["[[Projects/Alpha]]", "active", "2", "2026-11-01"], ["[[Projects/Gamma]]", "active", "1", ""],]
+        );
+    }
+
+    #[test]
+    fn list_fields_numbers_and_missing_values() {
+        let notes = corpus();
+        let owners = run_query("where owners = BEN", &notes).unwrap();
+        assert_eq!(owners.rows, [["[[Projects/Beta]]"]]);
+
+        let numeric = run_query("where priority >= 2\nsort priority", &notes).unwrap();
+        assert_eq!(numeric.rows, [["[[Projects/Alpha]]"], ["[[Projects/Beta]]"]]);
+
+        let missing = run_query("where status missing", &notes).unwrap();
+        assert_eq!(missing.rows, [["[[Plain]]"]]);
+
+        let sorted = run_query("table due\ntag #project\nsort due", &notes).unwrap();
+        assert_eq!(sorted.rows[0], ["[[Projects/Alpha]]", "2026-11-01"]);
+        assert_eq!(sorted.rows.len(), 3);
+
+        let contains = run_query("list\nwhere file contains amm", &notes).unwrap();
+        assert_eq!(contains.rows, [["[[Projects/Gamma]]"]]);
+    }
+
+    #[test]
+    fn reports_clause_errors_with_line_numbers() {
+        assert_eq!(run_query("tag project\nfrom x", &[]).unwrap_err(), "line 2: unknown clause `from`");
+        assert_eq!(run_query("limit many", &[]).unwrap_err(), "line 1: `limit` needs a whole number");
+        assert_eq!(
+            run_query("where status", &[]).unwrap_err(),
+            "line 1: expected a comparison like `status = done`"
+        );
+        assert_eq!(
+            run_query("sort due up", &[]).unwrap_err(),
+            "line 1: sort direction must be `asc` or `desc`, not `up`"
+        );
+        assert_eq!(
+            run_query("list\ntable a", &[]).unwrap_err(),
+            "line 2: only one `table` or `list` clause is allowed"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Local version snapshots.
+//!
+//! Each note gets a directory under `<cache>/snapshots/<vault>/` holding an
+//! `index` of versions and one gzip object per distinct version, named by the
+//! SHA-256 of its content. Saving a version that already exists only adds an
+//! index line. After every recording the index is pruned with a
+//! [`RetentionPolicy`] and unreferenced objects are removed.
+
+use chrono::{Local, TimeZone};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Which snapshots survive pruning. A snapshot is kept when any rule keeps
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// The most recent snapshots, regardless of age.
+    pub keep_last: usize,
+    /// The newest snapshot in each of this many most recent hours.
+    pub keep_hourly: usize,
+    /// The newest snapshot in each of this many most recent local days.
+    pub keep_daily: usize,
+}
+
+/// One stored version of a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Unix seconds when this version was written.
+    pub time: i64,
+    /// SHA-256 of the content, hex encoded.
+    pub hash: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    root: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Store for the vault at `vault_root`, kept under `cache_dir`.
+    pub fn new(cache_dir: &Path, vault_root: &Path) -> Self {
+        let vault_root = vault_root.canonicalize().unwrap_or_else(|_| vault_root.to_path_buf());
+        let key = &hex_digest(vault_root.to_string_lossy().as_bytes())[..16];
+        Self {
+            root: cache_dir.join("snapshots").join(key),
+        }
+    }
+
+    fn note_dir(&self, note: &str) -> PathBuf {
+        self.root.join(&hex_digest(note.as_bytes())[..32])
+    }
+
+    /// Record `content` as a version of the vault-relative `note` written at
+    /// `time`. Returns `false` when it matches the newest snapshot already.
+    pub fn record(&self, note: &str, content: &str, time: i64, policy: RetentionPolicy) -> io::Result<bool> {
+        let dir = self.note_dir(note);
+        let mut snapshots = self.list(note)?;
+        let hash = hex_digest(content.as_bytes());
+        if snapshots.first().is_some_and(|latest| latest.hash == hash) {
+            return Ok(false);
+        }
+
+        fs::create_dir_all(&dir)?;
+        let object = dir.join(format!("{hash}.gz"));
+        if !object.exists() {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content.as_bytes())?;
+            write_atomically(&object, &encoder.finish()?)?;
+        }
+        snapshots.insert(
+            0,
+            Snapshot {
+                time,
+                hash,
+                size: content.len() as u64,
+            },
+        );
+
+        let keep = retained(&snapshots.iter().map(|snapshot| snapshot.time).collect::<Vec<_>>(), policy);
+        let (kept, dropped): (Vec<_>, Vec<_>) = snapshots.into_iter().zip(keep).partition(|(_, keep)| *keep);
+        let kept: Vec<Snapshot> = kept.into_iter().map(|(snapshot, _)| snapshot).collect();
+
+        let mut index = format!("# {note}\n");
+        for snapshot in kept.iter().rev() {
+            index.push_str(&format!("{} {} {}\n", snapshot.time, snapshot.hash, snapshot.size));
+        }
+        write_atomically(&dir.join("index"), index.as_bytes())?;
+
+        let referenced: HashSet<&str> = kept.iter().map(|snapshot| snapshot.hash.as_str()).collect();
+        for (snapshot, _) in dropped {
+            if !referenced.contains(snapshot.hash.as_str()) {
+                let _ = fs::remove_file(dir.join(format!("{}.gz", snapshot.hash)));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Snapshots of `note`, newest first.
+    pub fn list(&self, note: &str) -> io::Result<Vec<Snapshot>> {
+        let index = match fs::read_to_string(self.note_dir(note).join("index")) {
+            Ok(index) => index,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut snapshots: Vec<Snapshot> = index
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split(' ');
+                Some(Snapshot {
+                    time: fields.next()?.parse().ok()?,
+                    hash: fields.next()?.to_string(),
+                    size: fields.next()?.parse().ok()?,
+                })
+            })
+            .collect();
+        snapshots.reverse();
+        Ok(snapshots)
+    }
+
+    /// Content of a snapshot of `note`.
+    pub fn read(&self, note: &str, snapshot: &Snapshot) -> io::Result<String> {
+        let compressed = fs::read(self.note_dir(note).join(format!("{}.gz", snapshot.hash)))?;
+        let mut content = String::new();
+        GzDecoder::new(compressed.as_slice()).read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    /// Carry the history of a renamed note over to its new path.
+    pub fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let source = self.note_dir(from);
+        if !source.exists() {
+            return Ok(());
+        }
+        let target = self.note_dir(to);
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(&source, &target)?;
+        let index = target.join("index");
+        let content = fs::read_to_string(&index)?;
+        let body = content.split_once('\n').map_or("", |(_, body)| body);
+        write_atomically(&index, format!("# {to}\n{body}").as_bytes())
+    }
+}
+
+/// Which of `times` (newest first) `policy` keeps.
+pub fn retained(times: &[i64], policy: RetentionPolicy) -> Vec<bool> {
+    let mut hours = HashSet::new();
+    let mut days = HashSet::new();
+    times
+        .iter()
+        .enumerate()
+        .map(|(index, &time)| {
+            let hour = time.div_euclid(3600);
+            let day = Local.timestamp_opt(time, 0).single().map(|time| time.date_naive());
+            // Newest first, so the first snapshot seen in a bucket is its newest.
+            let hourly = hours.len() < policy.keep_hourly && hours.insert(hour);
+            let daily = days.len() < policy.keep_daily && days.insert(day);
+            index < policy.keep_last || hourly || daily
+        })
+        .collect()
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temporary = path.with_extension("tmp");
+    fs::write(&temporary, bytes)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3600;
+    const DAY: i64 = 24 * HOUR;
+
+    #[test]
+    fn retention_keeps_recent_hourly_and_daily_snapshots() {
+        let policy = RetentionPolicy {
+            keep_last: 2,
+            keep_hourly: 2,
+            keep_daily: 2,
+        };
+        let now = 1_700_000_000 - 1_700_000_000 % DAY + 12 * HOUR + 1800;
+        // Newest first: three saves this hour, one an hour ago, one two hours
+        // ago, one yesterday and one the day before.
+        let times = [now, now - 60, now - 120, now - HOUR, now - 2 * HOUR, now - DAY, now - 2 * DAY];
+        assert_eq!(retained(&times, policy), [true, true, false, true, false, true, false]);
+    }
+
+    #[test]
+    fn records_deduplicate_compress_and_survive_renames() {
+        let cache = std::env::temp_dir().join(format!("ekphos-snapshot-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        let store = SnapshotStore::new(&cache, Path::new("/vault"));
+        let policy = RetentionPolicy {
+            keep_last: 3,
+            keep_hourly: 0,
+            keep_daily: 0,
+        };
+
+        assert!(store.record("a.md", "one", 10, policy).unwrap());
+        assert!(!store.record("a.md", "one", 20, policy).unwrap());
+        assert!(store.record("a.md", "two", 30, policy).unwrap());
+        assert!(store.record("a.md", "one", 40, policy).unwrap());
+        let objects = |store: &SnapshotStore, note: &str| {
+            fs::read_dir(store.note_dir(note))
+                .unwrap()
+                .filter(|entry| entry.as_ref().unwrap().path().extension().is_some_and(|ext| ext == "gz"))
+                .count()
+        };
+        assert_eq!(objects(&store, "a.md"), 2);
+
+        assert!(store.record("a.md", "three", 50, policy).unwrap());
+        let snapshots = store.list("a.md").unwrap();
+        assert_eq!(snapshots.iter().map(|snapshot| snapshot.time).collect::<Vec<_>>(), [50, 40, 30]);
+        assert_eq!(store.read("a.md", &snapshots[1]).unwrap(), "one");
+
+        store.rename("a.md", "b.md").unwrap();
+        assert!(store.list("a.md").unwrap().is_empty());
+        assert_eq!(store.read("b.md", &store.list("b.md").unwrap()[0]).unwrap(), "three");
+        let _ = fs::remove_dir_all(&cache);
+    }
+}
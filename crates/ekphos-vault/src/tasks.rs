@@ -0,0 +1,504 @@
+//! Vault-wide task collection for the agenda.
+//!
+//! Tasks are `- [ ]` / `- [x]` list items outside frontmatter and fenced code.
+//! Inline metadata follows the Tasks plugin conventions: a due date written as
+//! `📅 2026-10-20` or `due:2026-10-20`, and a priority written as one of
+//! `🔺 ⏫ 🔼 🔽 ⏬` or `priority:<level>`. Bodies are streamed one note at a
+//! time by [`TaskWorker`] so opening the agenda never retains the vault.
+
+use chrono::NaiveDate;
+use ekphos_core::markdown::{fence_marker, frontmatter_end};
+use ekphos_core::NoteId;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+const DUE_EMOJI: &str = "📅";
+const DUE_KEY: &str = "due:";
+const PRIORITY_KEY: &str = "priority:";
+const DATE_LEN: usize = "2026-10-20".len();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    Highest,
+    High,
+    Medium,
+    Low,
+    Lowest,
+}
+
+impl TaskPriority {
+    pub const ALL: [Self; 5] = [Self::Highest, Self::High, Self::Medium, Self::Low, Self::Lowest];
+
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Highest => "highest",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+            Self::Lowest => "lowest",
+        }
+    }
+
+    pub const fn emoji(self) -> &'static str {
+        match self {
+            Self::Highest => "🔺",
+            Self::High => "⏫",
+            Self::Medium => "🔼",
+            Self::Low => "🔽",
+            Self::Lowest => "⏬",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskSource {
+    pub note_id: NoteId,
+    pub absolute_path: PathBuf,
+}
+
+/// One task line with its inline metadata removed from `description`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultTask {
+    pub note_id: NoteId,
+    /// Zero-based source line.
+    pub line: u32,
+    pub checked: bool,
+    pub description: Box<str>,
+    pub due: Option<NaiveDate>,
+    pub priority: Option<TaskPriority>,
+    /// Inline `#tags`, without the leading `#`.
+    pub tags: Box<[Box<str>]>,
+}
+
+/// Recognize a task line, returning its checked state and the text after the
+/// checkbox. Matches the forms rendered as `ContentItem::TaskItem`.
+pub fn task_marker(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(text) = trimmed.strip_prefix("- [ ] ") {
+        Some((false, text))
+    } else {
+        trimmed
+            .strip_prefix("- [x] ")
+            .or_else(|| trimmed.strip_prefix("- [X] "))
+            .map(|text| (true, text))
+    }
+}
+
+/// Parse every task in a note body, skipping frontmatter and fenced code.
+pub fn parse_tasks(note_id: NoteId, content: &str) -> Vec<VaultTask> {
+    let skip_through = frontmatter_end(content);
+    let mut fence = None;
+    let mut tasks = Vec::new();
+
+    for (row, line) in content.lines().enumerate() {
+        if skip_through.is_some_and(|end| row <= end) {
+            continue;
+        }
+        if let Some(marker) = fence_marker(line) {
+            if fence == Some(marker) {
+                fence = None;
+            } else if fence.is_none() {
+                fence = Some(marker);
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+        let Some((checked, text)) = task_marker(line) else {
+            continue;
+        };
+        let Ok(line) = u32::try_from(row) else {
+            break;
+        };
+        tasks.push(parse_task_text(note_id, line, checked, text));
+    }
+    tasks
+}
+
+fn parse_task_text(note_id: NoteId, line: u32, checked: bool, text: &str) -> VaultTask {
+    let mut metadata: Vec<Range<usize>> = Vec::new();
+    let mut due = None;
+    let mut priority = None;
+
+    for (start, _) in text.match_indices(DUE_EMOJI) {
+        let date_start = start + DUE_EMOJI.len() + whitespace_len(&text[start + DUE_EMOJI.len()..]);
+        if let Some(date) = parse_date_at(text, date_start) {
+            due.get_or_insert(date);
+            metadata.push(start..date_start + DATE_LEN);
+        }
+    }
+    for (start, _) in text.match_indices(DUE_KEY) {
+        if !at_word_start(text, start) {
+            continue;
+        }
+        if let Some(date) = parse_date_at(text, start + DUE_KEY.len()) {
+            due.get_or_insert(date);
+            metadata.push(start..start + DUE_KEY.len() + DATE_LEN);
+        }
+    }
+    for level in TaskPriority::ALL {
+        for (start, emoji) in text.match_indices(level.emoji()) {
+            priority = Some(priority.map_or(level, |current: TaskPriority| current.min(level)));
+            metadata.push(start..start + emoji.len());
+        }
+    }
+    for (start, _) in text.match_indices(PRIORITY_KEY) {
+        if !at_word_start(text, start) {
+            continue;
+        }
+        let value_start = start + PRIORITY_KEY.len();
+        let value_len = text[value_start..].find(char::is_whitespace).unwrap_or(text.len() - value_start);
+        let value = &text[value_start..value_start + value_len];
+        if let Some(level) = TaskPriority::ALL.into_iter().find(|level| level.id().eq_ignore_ascii_case(value)) {
+            priority = Some(priority.map_or(level, |current: TaskPriority| current.min(level)));
+            metadata.push(start..value_start + value_len);
+        }
+    }
+
+    metadata.sort_by_key(|range| range.start);
+    let mut description = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for range in metadata {
+        if range.start >= cursor {
+            description.push_str(&text[cursor..range.start]);
+            cursor = range.end;
+        }
+    }
+    description.push_str(&text[cursor..]);
+    let description = description.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    VaultTask {
+        note_id,
+        line,
+        checked,
+        tags: inline_tags(&description).into_iter().map(Box::from).collect(),
+        description: description.into_boxed_str(),
+        due,
+        priority,
+    }
+}
+
+fn whitespace_len(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+fn at_word_start(text: &str, start: usize) -> bool {
+    text[..start].chars().next_back().is_none_or(char::is_whitespace)
+}
+
+fn parse_date_at(text: &str, start: usize) -> Option<NaiveDate> {
+    let candidate = text.get(start..start + DATE_LEN)?;
+    let followed_by_word = text[start + DATE_LEN..].chars().next().is_some_and(|ch| ch.is_alphanumeric());
+    if followed_by_word {
+        return None;
+    }
+    NaiveDate::parse_from_str(candidate, "%Y-%m-%d").ok()
+}
+
+/// Inline `#tag` tokens outside inline code. A tag needs at least one
+/// non-digit character so issue references like `#42` are not tags.
+fn inline_tags(text: &str) -> Vec<&str> {
+    let mut tags: Vec<&str> = Vec::new();
+    let mut in_code = false;
+    for (index, ch) in text.char_indices() {
+        if ch == '`' {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || ch != '#' || !at_word_start(text, index) {
+            continue;
+        }
+        let rest = &text[index + 1..];
+        let len = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '-' | '_' | '/')))
+            .unwrap_or(rest.len());
+        let tag = &rest[..len];
+        if tag.chars().any(|ch| !ch.is_ascii_digit()) && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Flip the checkbox on `line` when it is still a task in the `checked`
+/// state the caller saw. Returns `None` when the note changed underneath.
+pub fn toggle_task_line(content: &str, line: u32, checked: bool) -> Option<String> {
+    let mut offset = 0;
+    for (row, source) in content.split_inclusive('\n').enumerate() {
+        if row == line as usize {
+            let (current, _) = task_marker(source)?;
+            if current != checked {
+                return None;
+            }
+            let checkbox = offset + (source.len() - source.trim_start().len()) + 3;
+            let mut body = content.to_owned();
+            body.replace_range(checkbox..checkbox + 1, if checked { " " } else { "x" });
+            return Some(body);
+        }
+        offset += source.len();
+    }
+    None
+}
+
+/// Collect tasks from every source without retaining any body. `is_cancelled`
+/// is checked before every file so a replacement request bounds obsolete work.
+pub fn collect_tasks<F>(sources: &[TaskSource], mut is_cancelled: F) -> Option<Vec<VaultTask>>
+where
+    F: FnMut() -> bool,
+{
+    let mut tasks = Vec::new();
+    for source in sources {
+        if is_cancelled() {
+            return None;
+        }
+        let Ok(body) = std::fs::read_to_string(&source.absolute_path) else {
+            continue;
+        };
+        tasks.extend(parse_tasks(source.note_id, &body));
+    }
+    Some(tasks)
+}
+
+#[derive(Debug)]
+pub struct TaskResponse {
+    pub request_id: u64,
+    pub tasks: Vec<VaultTask>,
+}
+
+struct TaskRequest {
+    ticket: u64,
+    request_id: u64,
+    sources: Arc<[TaskSource]>,
+}
+
+#[derive(Default)]
+struct RequestState {
+    request: Option<TaskRequest>,
+    stop: bool,
+}
+
+struct Shared {
+    request: Mutex<RequestState>,
+    request_ready: Condvar,
+    result: Mutex<Option<TaskResponse>>,
+    current_ticket: AtomicU64,
+    pending: AtomicBool,
+}
+
+/// Background task collector with the same replaceable request/result slots
+/// as the content search worker. Submitting again drops the previous scan.
+pub struct TaskWorker {
+    shared: Arc<Shared>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Default for TaskWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskWorker {
+    pub fn new() -> Self {
+        let shared = Arc::new(Shared {
+            request: Mutex::new(RequestState::default()),
+            request_ready: Condvar::new(),
+            result: Mutex::new(None),
+            current_ticket: AtomicU64::new(0),
+            pending: AtomicBool::new(false),
+        });
+        let worker_shared = Arc::clone(&shared);
+        let handle = thread::Builder::new()
+            .name("ekphos-tasks".to_string())
+            .spawn(move || run_worker(worker_shared))
+            .expect("failed to start task worker");
+        Self { shared, handle: Some(handle) }
+    }
+
+    pub fn submit(&self, request_id: u64, sources: Arc<[TaskSource]>) {
+        let ticket = self.shared.current_ticket.fetch_add(1, Ordering::AcqRel).wrapping_add(1);
+        self.shared.pending.store(true, Ordering::Release);
+        if let Ok(mut result) = self.shared.result.lock() {
+            *result = None;
+        }
+        if let Ok(mut state) = self.shared.request.lock() {
+            state.request = Some(TaskRequest { ticket, request_id, sources });
+            self.shared.request_ready.notify_one();
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.shared.current_ticket.fetch_add(1, Ordering::AcqRel);
+        self.shared.pending.store(false, Ordering::Release);
+        if let Ok(mut state) = self.shared.request.lock() {
+            state.request = None;
+        }
+        if let Ok(mut result) = self.shared.result.lock() {
+            *result = None;
+        }
+    }
+
+    pub fn try_take(&self) -> Option<TaskResponse> {
+        self.shared.result.lock().ok()?.take()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.shared.pending.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for TaskWorker {
+    fn drop(&mut self) {
+        self.shared.current_ticket.fetch_add(1, Ordering::AcqRel);
+        if let Ok(mut state) = self.shared.request.lock() {
+            state.stop = true;
+            state.request = None;
+            self.shared.request_ready.notify_one();
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run_worker(shared: Arc<Shared>) {
+    loop {
+        let request = {
+            let mut state = match shared.request.lock() {
+                Ok(state) => state,
+                Err(_) => return,
+            };
+            while state.request.is_none() && !state.stop {
+                state = match shared.request_ready.wait(state) {
+                    Ok(state) => state,
+                    Err(_) => return,
+                };
+            }
+            if state.stop {
+                return;
+            }
+            state.request.take().expect("request checked above")
+        };
+
+        let tasks = collect_tasks(&request.sources, || shared.current_ticket.load(Ordering::Acquire) != request.ticket);
+        if shared.current_ticket.load(Ordering::Acquire) != request.ticket {
+            continue;
+        }
+        if let Some(tasks) = tasks {
+            if let Ok(mut result) = shared.result.lock() {
+                *result = Some(TaskResponse {
+                    request_id: request.request_id,
+                    tasks,
+                });
+            }
+        }
+        if shared.current_ticket.load(Ordering::Acquire) == request.ticket {
+            shared.pending.store(false, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn parses_due_dates_priorities_and_tags() {
+        let content = "\
+---
+tags: [work]
+- [ ] not a task inside frontmatter
+---
+- [ ] Ship release 📅 2026-10-20 ⏫ #work
+  - [x] Write notes due:2026-10-18 priority:low #docs/api
+- [ ] Plain follow-up with #42 and `#code`
+```
+- [ ] fenced example
+```
+- [ ]missing space
+";
+        let tasks = parse_tasks(NoteId::new(3), content);
+        assert_eq!(tasks.len(), 3);
+
+        assert_eq!(tasks[0].line, 4);
+        assert!(!tasks[0].checked);
+        assert_eq!(&*tasks[0].description, "Ship release #work");
+        assert_eq!(tasks[0].due, Some(date(20)));
+        assert_eq!(tasks[0].priority, Some(TaskPriority::High));
+        assert_eq!(&*tasks[0].tags, [Box::from("work")]);
+
+        assert_eq!(tasks[1].line, 5);
+        assert!(tasks[1].checked);
+        assert_eq!(&*tasks[1].description, "Write notes #docs/api");
+        assert_eq!(tasks[1].due, Some(date(18)));
+        assert_eq!(tasks[1].priority, Some(TaskPriority::Low));
+        assert_eq!(&*tasks[1].tags, [Box::from("docs/api")]);
+
+        assert_eq!(tasks[2].due, None);
+        assert_eq!(tasks[2].priority, None);
+        assert!(tasks[2].tags.is_empty());
+    }
+
+    #[test]
+    fn malformed_metadata_stays_in_the_description() {
+        let tasks = parse_tasks(NoteId::new(1), "- [ ] Review 📅 2026-13-40 overdue:2026-10-01 priority:urgent\n");
+        assert_eq!(tasks[0].due, None);
+        assert_eq!(tasks[0].priority, None);
+        assert_eq!(&*tasks[0].description, "Review 📅 2026-13-40 overdue:2026-10-01 priority:urgent");
+    }
+
+    #[test]
+    fn toggle_only_rewrites_the_expected_checkbox() {
+        let content = "# Tasks\r\n  - [ ] First\r\n- [X] Second\r\n";
+        assert_eq!(toggle_task_line(content, 1, false).unwrap(), "# Tasks\r\n  - [x] First\r\n- [X] Second\r\n");
+        assert_eq!(toggle_task_line(content, 2, true).unwrap(), "# Tasks\r\n  - [ ] First\r\n- [ ] Second\r\n");
+        assert_eq!(toggle_task_line(content, 1, true), None);
+        assert_eq!(toggle_task_line(content, 0, false), None);
+        assert_eq!(toggle_task_line(content, 9, false), None);
+    }
+
+    #[test]
+    fn worker_returns_latest_collection() {
+        let root = std::env::temp_dir().join(format!("ekphos-tasks-worker-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let first = root.join("first.md");
+        let second = root.join("second.md");
+        fs::write(&first, "- [ ] one\n").unwrap();
+        fs::write(&second, "- [ ] two\n- [x] three\n").unwrap();
+        let sources: Arc<[TaskSource]> = vec![
+            TaskSource {
+                note_id: NoteId::new(1),
+                absolute_path: first,
+            },
+            TaskSource {
+                note_id: NoteId::new(2),
+                absolute_path: second,
+            },
+        ]
+        .into();
+
+        let worker = TaskWorker::new();
+        worker.submit(1, Arc::from(&sources[..1]));
+        worker.submit(2, sources);
+        let started = std::time::Instant::now();
+        let response = loop {
+            if let Some(response) = worker.try_take() {
+                break response;
+            }
+            assert!(started.elapsed() < std::time::Duration::from_secs(2));
+            std::thread::yield_now();
+        };
+        assert_eq!(response.request_id, 2);
+        assert_eq!(response.tasks.len(), 3);
+        let _ = fs::remove_dir_all(root);
+    }
+}
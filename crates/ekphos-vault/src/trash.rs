@@ -0,0 +1,233 @@
+//! Vault trash.
+//!
+//! Deleted notes and folders move to `<vault>/.trash/<id>/`, keeping their
+//! vault-relative path below that directory, next to a `.trashinfo` file in
+//! the freedesktop format (`Path=` and `DeletionDate=`, here in Unix
+//! seconds). The scanner skips dot-directories, so trashed notes drop out of
+//! the catalog, search and graph until they are restored.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+pub const TRASH_DIR: &str = ".trash";
+const INFO_FILE: &str = ".trashinfo";
+
+/// One deleted note or folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    /// Directory name under `.trash/`.
+    pub id: String,
+    /// Vault-relative path the item was deleted from.
+    pub original: PathBuf,
+    /// Unix seconds.
+    pub deleted_at: i64,
+    pub is_folder: bool,
+}
+
+impl TrashEntry {
+    /// Name shown in the trash browser.
+    pub fn display_name(&self) -> String {
+        let path = self.original.to_string_lossy().replace('\\', "/");
+        if self.is_folder {
+            format!("{path}/")
+        } else {
+            path.strip_suffix(".md").unwrap_or(&path).to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Trash {
+    vault_root: PathBuf,
+}
+
+impl Trash {
+    pub fn new(vault_root: &Path) -> Self {
+        Self {
+            vault_root: vault_root.to_path_buf(),
+        }
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.vault_root.join(TRASH_DIR)
+    }
+
+    /// Move `path`, a note or folder inside the vault, to the trash.
+    pub fn trash(&self, path: &Path, deleted_at: i64) -> io::Result<TrashEntry> {
+        let original = path
+            .strip_prefix(&self.vault_root)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path is outside the vault"))?
+            .to_path_buf();
+        if original.as_os_str().is_empty() || original.starts_with(TRASH_DIR) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "cannot trash this path"));
+        }
+        let is_folder = path.is_dir();
+
+        fs::create_dir_all(self.dir())?;
+        let (id, entry_dir) = (0u32..)
+            .map(|attempt| {
+                let id = if attempt == 0 {
+                    deleted_at.to_string()
+                } else {
+                    format!("{deleted_at}-{attempt}")
+                };
+                let dir = self.dir().join(&id);
+                (id, dir)
+            })
+            .find(|(_, dir)| fs::create_dir(dir).is_ok())
+            .ok_or_else(|| io::Error::other("could not create a trash entry"))?;
+
+        let destination = entry_dir.join(&original);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Err(error) = fs::rename(path, &destination) {
+            let _ = fs::remove_dir_all(&entry_dir);
+            return Err(error);
+        }
+        let info = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={deleted_at}\n",
+            original.to_string_lossy().replace('\\', "/")
+        );
+        fs::write(entry_dir.join(INFO_FILE), info)?;
+        Ok(TrashEntry {
+            id,
+            original,
+            deleted_at,
+            is_folder,
+        })
+    }
+
+    /// Everything in the trash, most recently deleted first.
+    pub fn entries(&self) -> io::Result<Vec<TrashEntry>> {
+        let read_dir = match fs::read_dir(self.dir()) {
+            Ok(read_dir) => read_dir,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut entries: Vec<TrashEntry> = read_dir
+            .filter_map(Result::ok)
+            .filter_map(|dir_entry| {
+                let id = dir_entry.file_name().to_string_lossy().into_owned();
+                let info = fs::read_to_string(dir_entry.path().join(INFO_FILE)).ok()?;
+                let field = |name: &str| info.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix('='));
+                let original = PathBuf::from(field("Path")?);
+                // Never trust a path that would escape the vault on restore.
+                if original.components().any(|component| !matches!(component, Component::Normal(_))) {
+                    return None;
+                }
+                let deleted_at = field("DeletionDate")?.parse().ok()?;
+                let is_folder = dir_entry.path().join(&original).is_dir();
+                Some(TrashEntry {
+                    id,
+                    original,
+                    deleted_at,
+                    is_folder,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(entries)
+    }
+
+    /// Put an entry back where it was deleted from. When that path is taken
+    /// again, the item comes back as "name (restored)". Returns the restored
+    /// absolute path.
+    pub fn restore(&self, entry: &TrashEntry) -> io::Result<PathBuf> {
+        let entry_dir = self.dir().join(&entry.id);
+        let source = entry_dir.join(&entry.original);
+        let target = self.vault_root.join(&entry.original);
+        let target = (0u32..)
+            .map(|attempt| match attempt {
+                0 => target.clone(),
+                _ => {
+                    let stem = target.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+                    let suffix = if attempt == 1 {
+                        " (restored)".to_string()
+                    } else {
+                        format!(" (restored {attempt})")
+                    };
+                    let name = match target.extension() {
+                        Some(extension) if !entry.is_folder => format!("{stem}{suffix}.{}", extension.to_string_lossy()),
+                        _ => format!(
+                            "{}{suffix}",
+                            target.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+                        ),
+                    };
+                    target.with_file_name(name)
+                }
+            })
+            .find(|candidate| !candidate.exists())
+            .ok_or_else(|| io::Error::other("no free name to restore to"))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&source, &target)?;
+        fs::remove_dir_all(&entry_dir)?;
+        Ok(target)
+    }
+
+    /// Delete an entry for good.
+    pub fn purge(&self, entry: &TrashEntry) -> io::Result<()> {
+        fs::remove_dir_all(self.dir().join(&entry.id))
+    }
+
+    /// Vault-relative paths of the notes inside an entry, for flagging links
+    /// that point into the trash.
+    pub fn notes_in(&self, entry: &TrashEntry) -> Vec<PathBuf> {
+        if !entry.is_folder {
+            return vec![entry.original.clone()];
+        }
+        let entry_dir = self.dir().join(&entry.id);
+        let mut notes = Vec::new();
+        let mut pending = vec![entry_dir.join(&entry.original)];
+        while let Some(dir) = pending.pop() {
+            let Ok(read_dir) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for path in read_dir.filter_map(Result::ok).map(|dir_entry| dir_entry.path()) {
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|extension| extension == "md") {
+                    if let Ok(relative) = path.strip_prefix(&entry_dir) {
+                        notes.push(relative.to_path_buf());
+                    }
+                }
+            }
+        }
+        notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trashed_items_keep_their_path_and_restore_without_clobbering() {
+        let root = std::env::temp_dir().join(format!("ekphos-trash-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("projects/old")).unwrap();
+        fs::write(root.join("projects/plan.md"), "plan").unwrap();
+        fs::write(root.join("projects/old/a.md"), "a").unwrap();
+        let trash = Trash::new(&root);
+
+        let note = trash.trash(&root.join("projects/plan.md"), 100).unwrap();
+        let folder = trash.trash(&root.join("projects/old"), 200).unwrap();
+        assert!(!root.join("projects/plan.md").exists());
+        assert_eq!(trash.entries().unwrap(), [folder.clone(), note.clone()]);
+        assert_eq!(note.display_name(), "projects/plan");
+        assert_eq!(trash.notes_in(&folder), [PathBuf::from("projects/old/a.md")]);
+
+        fs::write(root.join("projects/plan.md"), "new plan").unwrap();
+        let restored = trash.restore(&note).unwrap();
+        assert_eq!(restored, root.join("projects/plan (restored).md"));
+        assert_eq!(fs::read_to_string(restored).unwrap(), "plan");
+        assert_eq!(fs::read_to_string(root.join("projects/plan.md")).unwrap(), "new plan");
+
+        trash.purge(&folder).unwrap();
+        assert!(trash.entries().unwrap().is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
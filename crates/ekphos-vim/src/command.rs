@@ -1,4 +1,6 @@
-//! Vim command mode (:w, :q, :%s)
+//! Vim command mode (:w, :q, :%s, :map, :!, :extract)
+
+use crate::mapping::{map_command, unmap_command, MapMode};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Command {
@@ -12,6 +14,142 @@ pub enum Command {
         replacement: String,
         flags: SubstituteFlags,
     },
+    Map {
+        modes: &'static [MapMode],
+        lhs: String,
+        rhs: String,
+        recursive: bool,
+    },
+    Unmap {
+        modes: &'static [MapMode],
+        lhs: String,
+    },
+    /// `:map` with no rhs lists mappings, optionally only those starting with `prefix`.
+    ListMappings {
+        modes: &'static [MapMode],
+        prefix: String,
+    },
+    /// `:{range}!cmd` pipes the lines through `command` and replaces them.
+    Filter {
+        range: LineRange,
+        command: String,
+    },
+    /// `:r !cmd` inserts the output of `command` below the cursor line.
+    ReadCommand(String),
+    /// `:{range}extract [title]` moves the lines, or the heading section under
+    /// the cursor when no range is given, into a new note.
+    Extract {
+        range: Option<LineRange>,
+        title: Option<String>,
+    },
+}
+
+/// One end of an ex range: a base line plus an offset (`.`, `$`, `12`, `.+3`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineAddress {
+    pub base: AddressBase,
+    pub offset: isize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressBase {
+    Current,
+    Last,
+    /// 1-based line number.
+    Line(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineRange {
+    pub start: LineAddress,
+    pub end: LineAddress,
+}
+
+impl LineAddress {
+    pub const fn current() -> Self {
+        Self {
+            base: AddressBase::Current,
+            offset: 0,
+        }
+    }
+
+    /// 0-based row for a buffer of `line_count` lines, clamped into it.
+    pub fn resolve(&self, cursor_row: usize, line_count: usize) -> usize {
+        let base = match self.base {
+            AddressBase::Current => cursor_row as isize,
+            AddressBase::Last => line_count as isize - 1,
+            AddressBase::Line(line) => line as isize - 1,
+        };
+        (base + self.offset).clamp(0, line_count.saturating_sub(1) as isize) as usize
+    }
+}
+
+impl LineRange {
+    /// Inclusive 0-based rows; a backwards range is swapped into order.
+    pub fn resolve(&self, cursor_row: usize, line_count: usize) -> (usize, usize) {
+        let start = self.start.resolve(cursor_row, line_count);
+        let end = self.end.resolve(cursor_row, line_count);
+        (start.min(end), start.max(end))
+    }
+}
+
+/// Parse a leading range (`%`, `.`, `$`, `N`, `.+N`, `A,B`) and return it with
+/// the rest of the input.
+fn parse_range(input: &str) -> Option<(LineRange, &str)> {
+    if let Some(rest) = input.strip_prefix('%') {
+        let range = LineRange {
+            start: LineAddress {
+                base: AddressBase::Line(1),
+                offset: 0,
+            },
+            end: LineAddress {
+                base: AddressBase::Last,
+                offset: 0,
+            },
+        };
+        return Some((range, rest));
+    }
+    let (start, rest) = parse_address(input)?;
+    match rest.strip_prefix(',') {
+        Some(rest) => {
+            let (end, rest) = parse_address(rest)?;
+            Some((LineRange { start, end }, rest))
+        }
+        None => Some((LineRange { start, end: start }, rest)),
+    }
+}
+
+fn parse_address(input: &str) -> Option<(LineAddress, &str)> {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (base, mut rest) = if let Some(rest) = input.strip_prefix('.') {
+        (Some(AddressBase::Current), rest)
+    } else if let Some(rest) = input.strip_prefix('$') {
+        (Some(AddressBase::Last), rest)
+    } else {
+        let len = digits(input);
+        match input[..len].parse() {
+            Ok(line) => (Some(AddressBase::Line(line)), &input[len..]),
+            Err(_) => (None, input),
+        }
+    };
+
+    // A bare offset (`+2`) is relative to the cursor line.
+    let mut offset = 0isize;
+    let mut has_offset = false;
+    while let Some(sign) = rest.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let after = &rest[1..];
+        let len = digits(after);
+        let amount: isize = if len == 0 { 1 } else { after[..len].parse().ok()? };
+        offset += if sign == '+' { amount } else { -amount };
+        rest = &after[len..];
+        has_offset = true;
+    }
+    let base = match base {
+        Some(base) => base,
+        None if has_offset => AddressBase::Current,
+        None => return None,
+    };
+    Some((LineAddress { base, offset }, rest))
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -64,7 +202,57 @@ pub fn parse_command(input: &str) -> Option<Command> {
         return Some(Command::GoToLine(line));
     }
 
-    None
+    if let Some(command) = ["r", "read"].iter().find_map(|name| input.strip_prefix(name)?.trim_start().strip_prefix('!')) {
+        let command = command.trim();
+        return (!command.is_empty()).then(|| Command::ReadCommand(command.to_string()));
+    }
+    let (extract_range, rest) = match parse_range(input) {
+        Some((range, rest)) => (Some(range), rest),
+        None => (None, input),
+    };
+    if let Some(title) = rest
+        .strip_prefix("extract")
+        .filter(|title| title.is_empty() || title.starts_with(char::is_whitespace))
+    {
+        let title = title.trim();
+        return Some(Command::Extract {
+            range: extract_range,
+            title: (!title.is_empty()).then(|| title.to_string()),
+        });
+    }
+    if let Some((range, rest)) = parse_range(input) {
+        let command = rest.strip_prefix('!')?.trim();
+        return (!command.is_empty()).then(|| Command::Filter {
+            range,
+            command: command.to_string(),
+        });
+    }
+
+    parse_map(input)
+}
+
+fn parse_map(input: &str) -> Option<Command> {
+    let (name, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let rest = rest.trim();
+    if let Some(modes) = unmap_command(name) {
+        if rest.is_empty() {
+            return None;
+        }
+        return Some(Command::Unmap { modes, lhs: rest.to_string() });
+    }
+    let (modes, recursive) = map_command(name)?;
+    match rest.split_once(char::is_whitespace) {
+        Some((lhs, rhs)) => Some(Command::Map {
+            modes,
+            lhs: lhs.to_string(),
+            rhs: rhs.trim().to_string(),
+            recursive,
+        }),
+        None => Some(Command::ListMappings {
+            modes,
+            prefix: rest.to_string(),
+        }),
+    }
 }
 
 fn parse_substitute(input: &str) -> Option<Command> {
@@ -169,6 +357,44 @@ mod tests {
         assert_eq!(parse_command("%s€only"), None);
     }
 
+    #[test]
+    fn test_parse_map_commands() {
+        assert_eq!(
+            parse_command("inoremap jk <Esc>"),
+            Some(Command::Map {
+                modes: &[MapMode::Insert],
+                lhs: "jk".to_string(),
+                rhs: "<Esc>".to_string(),
+                recursive: false,
+            })
+        );
+        assert_eq!(
+            parse_command("nmap <leader>g   <Cmd>open_graph<CR>"),
+            Some(Command::Map {
+                modes: &[MapMode::Normal],
+                lhs: "<leader>g".to_string(),
+                rhs: "<Cmd>open_graph<CR>".to_string(),
+                recursive: true,
+            })
+        );
+        assert_eq!(
+            parse_command("map"),
+            Some(Command::ListMappings {
+                modes: &[MapMode::Normal, MapMode::Visual],
+                prefix: String::new()
+            })
+        );
+        assert_eq!(
+            parse_command("vunmap H"),
+            Some(Command::Unmap {
+                modes: &[MapMode::Visual],
+                lhs: "H".to_string()
+            })
+        );
+        assert_eq!(parse_command("unmap"), None);
+        assert_eq!(parse_command("mapx a b"), None);
+    }
+
     #[test]
     fn test_parse_empty() {
         assert_eq!(parse_command(""), None);
@@ -188,4 +414,39 @@ mod tests {
         assert!(flags.case_insensitive);
         assert!(flags.confirm);
     }
+
+    #[test]
+    fn test_parse_filter_ranges() {
+        let filter = |input| match parse_command(input) {
+            Some(Command::Filter { range, command }) => Some((range.resolve(4, 10), command)),
+            _ => None,
+        };
+        assert_eq!(filter(".!sort"), Some(((4, 4), "sort".to_string())));
+        assert_eq!(filter(".,.+2!sort -r"), Some(((4, 6), "sort -r".to_string())));
+        assert_eq!(filter("%!jq ."), Some(((0, 9), "jq .".to_string())));
+        assert_eq!(filter("2,$!fmt"), Some(((1, 9), "fmt".to_string())));
+        assert_eq!(filter("5,2! tac"), Some(((1, 4), "tac".to_string())));
+        assert_eq!(filter(".,+100!tac"), Some(((4, 9), "tac".to_string())));
+        assert_eq!(filter(".!"), None);
+        assert_eq!(parse_command("5,2"), None);
+    }
+
+    #[test]
+    fn test_parse_extract() {
+        let extract = |input| match parse_command(input) {
+            Some(Command::Extract { range, title }) => Some((range.map(|range| range.resolve(4, 10)), title)),
+            _ => None,
+        };
+        assert_eq!(extract("extract"), Some((None, None)));
+        assert_eq!(extract("extract Meeting notes"), Some((None, Some("Meeting notes".to_string()))));
+        assert_eq!(extract(".,.+2extract Ideas"), Some((Some((4, 6)), Some("Ideas".to_string()))));
+        assert_eq!(extract("extractor"), None);
+    }
+
+    #[test]
+    fn test_parse_read_command() {
+        assert_eq!(parse_command("r !date"), Some(Command::ReadCommand("date".to_string())));
+        assert_eq!(parse_command("read !ls -1"), Some(Command::ReadCommand("ls -1".to_string())));
+        assert_eq!(parse_command("r !"), None);
+    }
 }
@@ -10,10 +10,12 @@
 //! - Macros (q to record, @ to play)
 //! - Marks (m to set, ` or ' to jump)
 //! - Command mode (:w, :q, :wq, :%s/pat/rep/g)
+//! - User key mappings (:map, :nnoremap, :inoremap, <leader>)
 
 pub mod command;
 pub mod find;
 pub mod macro_record;
+pub mod mapping;
 pub mod marks;
 pub mod mode;
 pub mod motion;
@@ -23,6 +25,7 @@ pub mod text_object;
 
 pub use find::{FindState, PendingFind};
 pub use macro_record::MacroState;
+pub use mapping::{KeyMapper, MapAction, MapMode};
 pub use marks::MarkMap;
 pub use mode::VimMode;
 pub use motion::Motion;
@@ -179,6 +182,21 @@ impl VimState {
         }
     }
 
+    /// True while a command waits for its argument key (`f{char}`, `r{char}`,
+    /// `"{reg}`, `m{mark}`, `q{reg}`, an operator's motion); user mappings
+    /// are not applied to that key.
+    pub fn awaiting_argument(&self) -> bool {
+        self.pending_g
+            || self.pending_z
+            || self.pending_find.is_some()
+            || self.awaiting_replace
+            || self.pending_text_object_scope.is_some()
+            || self.pending_mark.is_some()
+            || self.pending_macro.is_some()
+            || self.pending_register
+            || matches!(self.mode, VimMode::OperatorPending { .. })
+    }
+
     pub fn reset_pending(&mut self) {
         self.count = None;
         self.pending_g = false;
@@ -0,0 +1,525 @@
+//! User key mappings (:map, :nnoremap, :inoremap, :vnoremap, :unmap)
+//!
+//! Mappings are written in Vim key notation (`jk`, `<leader>g`, `<C-s>`,
+//! `<Esc>`). `<leader>` is expanded when the mapping is defined. A right-hand
+//! side of the form `<Cmd>name<CR>` runs an application command instead of
+//! replaying keys.
+//!
+//! [`KeyMapper`] only decides what typed keys turn into; the caller feeds the
+//! resulting [`MapAction`]s to the mode handlers one at a time, so a mapping
+//! that switches modes (`jk` -> `<Esc>`) sees the right table for the keys
+//! that follow.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Default wait for the rest of an ambiguous sequence, like Vim's `timeoutlen`.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapMode {
+    Normal,
+    Insert,
+    Visual,
+}
+
+impl MapMode {
+    pub const fn prefix(self) -> char {
+        match self {
+            Self::Normal => 'n',
+            Self::Insert => 'i',
+            Self::Visual => 'v',
+        }
+    }
+}
+
+const NORMAL_VISUAL: &[MapMode] = &[MapMode::Normal, MapMode::Visual];
+const NORMAL: &[MapMode] = &[MapMode::Normal];
+const INSERT: &[MapMode] = &[MapMode::Insert];
+const VISUAL: &[MapMode] = &[MapMode::Visual];
+
+/// Modes and recursiveness of a `:map`-family command name.
+pub fn map_command(name: &str) -> Option<(&'static [MapMode], bool)> {
+    Some(match name {
+        "map" => (NORMAL_VISUAL, true),
+        "noremap" | "no" => (NORMAL_VISUAL, false),
+        "nmap" | "nm" => (NORMAL, true),
+        "nnoremap" | "nn" => (NORMAL, false),
+        "imap" | "im" => (INSERT, true),
+        "inoremap" | "ino" => (INSERT, false),
+        "vmap" | "vm" | "xmap" | "xm" => (VISUAL, true),
+        "vnoremap" | "vn" | "xnoremap" | "xn" => (VISUAL, false),
+        _ => return None,
+    })
+}
+
+/// Modes of an `:unmap`-family command name.
+pub fn unmap_command(name: &str) -> Option<&'static [MapMode]> {
+    Some(match name {
+        "unmap" | "unm" => NORMAL_VISUAL,
+        "nunmap" | "nun" => NORMAL,
+        "iunmap" | "iu" => INSERT,
+        "vunmap" | "vu" | "xunmap" | "xu" => VISUAL,
+        _ => return None,
+    })
+}
+
+/// One key as mappings see it: shifted letters are carried by the character
+/// itself, so `J` and `Shift+j` compare equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapKey {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl MapKey {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(ch) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(if modifiers.contains(KeyModifiers::CONTROL) {
+                    ch.to_ascii_lowercase()
+                } else {
+                    ch
+                })
+            }
+            KeyCode::BackTab => {
+                modifiers.insert(KeyModifiers::SHIFT);
+                KeyCode::Tab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+
+    pub fn to_event(self) -> KeyEvent {
+        match (self.code, self.modifiers) {
+            (KeyCode::Tab, modifiers) if modifiers.contains(KeyModifiers::SHIFT) => KeyEvent::new(KeyCode::BackTab, modifiers),
+            (KeyCode::Char(ch), modifiers) if ch.is_uppercase() => KeyEvent::new(KeyCode::Char(ch), modifiers | KeyModifiers::SHIFT),
+            (code, modifiers) => KeyEvent::new(code, modifiers),
+        }
+    }
+}
+
+impl fmt::Display for MapKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => Some("Space".to_owned()),
+            KeyCode::Char('<') => Some("lt".to_owned()),
+            KeyCode::Char(ch) => (!self.modifiers.is_empty()).then(|| ch.to_string()),
+            KeyCode::Esc => Some("Esc".to_owned()),
+            KeyCode::Enter => Some("CR".to_owned()),
+            KeyCode::Tab => Some("Tab".to_owned()),
+            KeyCode::Backspace => Some("BS".to_owned()),
+            KeyCode::Delete => Some("Del".to_owned()),
+            KeyCode::Up => Some("Up".to_owned()),
+            KeyCode::Down => Some("Down".to_owned()),
+            KeyCode::Left => Some("Left".to_owned()),
+            KeyCode::Right => Some("Right".to_owned()),
+            KeyCode::Home => Some("Home".to_owned()),
+            KeyCode::End => Some("End".to_owned()),
+            KeyCode::PageUp => Some("PageUp".to_owned()),
+            KeyCode::PageDown => Some("PageDown".to_owned()),
+            KeyCode::F(number) => Some(format!("F{number}")),
+            code => Some(format!("{code:?}")),
+        };
+        let Some(name) = name else {
+            if let KeyCode::Char(ch) = self.code {
+                return write!(f, "{ch}");
+            }
+            return Ok(());
+        };
+        write!(f, "<")?;
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        write!(f, "{name}>")
+    }
+}
+
+/// Parse Vim key notation. `leader` replaces `<leader>`.
+pub fn parse_keys(notation: &str, leader: &[MapKey]) -> Result<Vec<MapKey>, String> {
+    let mut keys = Vec::new();
+    let mut rest = notation;
+    while let Some(ch) = rest.chars().next() {
+        if ch == '<' {
+            if let Some(end) = rest.find('>') {
+                let name = &rest[1..end];
+                if name.eq_ignore_ascii_case("leader") {
+                    keys.extend_from_slice(leader);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+                if let Some(key) = parse_special(name) {
+                    keys.push(key);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        keys.push(MapKey::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        rest = &rest[ch.len_utf8()..];
+    }
+    if keys.is_empty() {
+        return Err("empty key sequence".to_owned());
+    }
+    Ok(keys)
+}
+
+fn parse_special(name: &str) -> Option<MapKey> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut base = name;
+    while base.len() > 2 && base.as_bytes()[1] == b'-' {
+        match base.as_bytes()[0].to_ascii_lowercase() {
+            b'c' => modifiers.insert(KeyModifiers::CONTROL),
+            b'a' | b'm' => modifiers.insert(KeyModifiers::ALT),
+            b's' => modifiers.insert(KeyModifiers::SHIFT),
+            _ => return None,
+        }
+        base = &base[2..];
+    }
+    let lower = base.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "esc" => KeyCode::Esc,
+        "cr" | "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "bs" | "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "bar" => KeyCode::Char('|'),
+        "bslash" => KeyCode::Char('\\'),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        _ => {
+            if let Some(number) = lower.strip_prefix('f').and_then(|number| number.parse::<u8>().ok()) {
+                KeyCode::F(number)
+            } else {
+                let mut chars = base.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) if !modifiers.is_empty() => KeyCode::Char(ch),
+                    _ => return None,
+                }
+            }
+        }
+    };
+    Some(MapKey::new(code, modifiers))
+}
+
+/// Application command named by a `<Cmd>name<CR>` right-hand side.
+pub fn command_target(rhs: &str) -> Option<&str> {
+    let head = rhs.get(..5)?;
+    if !head.eq_ignore_ascii_case("<cmd>") {
+        return None;
+    }
+    let body = &rhs[5..];
+    let body = match body.len().checked_sub(4).and_then(|end| body.get(end..)) {
+        Some(tail) if tail.eq_ignore_ascii_case("<cr>") => &body[..body.len() - 4],
+        _ => body,
+    };
+    Some(body.trim())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapTarget {
+    Keys(Vec<MapKey>),
+    Command(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub mode: MapMode,
+    pub lhs: Vec<MapKey>,
+    pub target: MapTarget,
+    pub recursive: bool,
+    /// Right-hand side as written, for listing.
+    pub rhs_text: String,
+}
+
+impl Mapping {
+    fn actions(&self) -> Vec<MapAction> {
+        match &self.target {
+            MapTarget::Command(name) => vec![MapAction::Command(name.clone())],
+            MapTarget::Keys(keys) => {
+                // As in Vim, a recursive rhs that starts with its own lhs does
+                // not remap that prefix, so `nmap j jzz` cannot loop.
+                let guarded = if keys.starts_with(&self.lhs) { self.lhs.len() } else { 0 };
+                keys.iter()
+                    .enumerate()
+                    .map(|(index, key)| {
+                        if self.recursive && index >= guarded {
+                            MapAction::Remap(key.to_event())
+                        } else {
+                            MapAction::Key(key.to_event())
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// What a typed key turns into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapAction {
+    /// Hand to the mode handler without consulting mappings.
+    Key(KeyEvent),
+    /// Feed back through the mapper (recursive mappings).
+    Remap(KeyEvent),
+    /// Run the named application command.
+    Command(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMapper {
+    mappings: Vec<Mapping>,
+    leader: Vec<MapKey>,
+    timeout: Duration,
+    pending: Vec<MapKey>,
+    pending_mode: Option<MapMode>,
+    pending_since: Option<Instant>,
+}
+
+impl Default for KeyMapper {
+    fn default() -> Self {
+        Self {
+            mappings: Vec::new(),
+            leader: vec![MapKey::new(KeyCode::Char('\\'), KeyModifiers::NONE)],
+            timeout: DEFAULT_TIMEOUT,
+            pending: Vec::new(),
+            pending_mode: None,
+            pending_since: None,
+        }
+    }
+}
+
+impl KeyMapper {
+    /// Mapper with the given `<leader>` (in key notation) and sequence timeout.
+    pub fn new(leader: &str, timeout: Duration) -> Result<Self, String> {
+        let leader = parse_keys(leader, &[]).map_err(|error| format!("invalid leader: {error}"))?;
+        Ok(Self {
+            leader,
+            timeout,
+            ..Self::default()
+        })
+    }
+
+    pub fn leader(&self) -> &[MapKey] {
+        &self.leader
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Define or replace a mapping in each of `modes`.
+    pub fn map(&mut self, modes: &[MapMode], lhs: &str, rhs: &str, recursive: bool) -> Result<(), String> {
+        let lhs_keys = parse_keys(lhs, &self.leader)?;
+        let target = match command_target(rhs) {
+            Some("") => return Err(format!("missing command name in `{rhs}`")),
+            Some(name) => MapTarget::Command(name.to_owned()),
+            None => MapTarget::Keys(parse_keys(rhs, &self.leader)?),
+        };
+        for &mode in modes {
+            self.mappings.retain(|mapping| mapping.mode != mode || mapping.lhs != lhs_keys);
+            self.mappings.push(Mapping {
+                mode,
+                lhs: lhs_keys.clone(),
+                target: target.clone(),
+                recursive,
+                rhs_text: rhs.to_owned(),
+            });
+        }
+        self.cancel();
+        Ok(())
+    }
+
+    /// Remove the mapping for `lhs` from each of `modes`.
+    pub fn unmap(&mut self, modes: &[MapMode], lhs: &str) -> Result<(), String> {
+        let lhs_keys = parse_keys(lhs, &self.leader)?;
+        let before = self.mappings.len();
+        self.mappings.retain(|mapping| !(modes.contains(&mapping.mode) && mapping.lhs == lhs_keys));
+        self.cancel();
+        if self.mappings.len() == before {
+            Err(format!("No such mapping: {lhs}"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// `:map`-style listing of mappings in `modes` whose lhs starts with `prefix`.
+    pub fn list(&self, modes: &[MapMode], prefix: &str) -> Vec<String> {
+        let prefix = if prefix.is_empty() {
+            Vec::new()
+        } else {
+            parse_keys(prefix, &self.leader).unwrap_or_default()
+        };
+        self.mappings
+            .iter()
+            .filter(|mapping| modes.contains(&mapping.mode) && mapping.lhs.starts_with(&prefix))
+            .map(|mapping| {
+                let lhs: String = mapping.lhs.iter().map(ToString::to_string).collect();
+                let marker = if mapping.recursive { ' ' } else { '*' };
+                format!("{} {lhs} {marker}{}", mapping.mode.prefix(), mapping.rhs_text)
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// When the pending sequence stops waiting for more keys.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending_since.map(|since| since + self.timeout)
+    }
+
+    /// Drop any partially typed sequence.
+    pub fn cancel(&mut self) {
+        self.pending.clear();
+        self.pending_mode = None;
+        self.pending_since = None;
+    }
+
+    /// Feed one typed key. `None` means the key is held while a longer
+    /// mapping may still match.
+    pub fn feed(&mut self, mode: MapMode, key: KeyEvent, now: Instant) -> Option<Vec<MapAction>> {
+        if self.pending_mode.is_some_and(|pending| pending != mode) {
+            self.cancel();
+        }
+        self.pending.push(MapKey::from_event(key));
+        self.pending_mode = Some(mode);
+        self.pending_since = Some(now);
+        self.resolve(mode, false)
+    }
+
+    /// Resolve a sequence whose timeout has passed.
+    pub fn expire(&mut self, now: Instant) -> Option<Vec<MapAction>> {
+        let mode = self.pending_mode?;
+        if self.deadline().is_some_and(|deadline| now < deadline) {
+            return None;
+        }
+        self.resolve(mode, true)
+    }
+
+    fn resolve(&mut self, mode: MapMode, timed_out: bool) -> Option<Vec<MapAction>> {
+        let pending = &self.pending;
+        let longer = self
+            .mappings
+            .iter()
+            .any(|mapping| mapping.mode == mode && mapping.lhs.len() > pending.len() && mapping.lhs.starts_with(pending));
+        if longer && !timed_out {
+            return None;
+        }
+
+        let keys = std::mem::take(&mut self.pending);
+        self.cancel();
+        let best = self
+            .mappings
+            .iter()
+            .filter(|mapping| mapping.mode == mode && keys.starts_with(&mapping.lhs))
+            .max_by_key(|mapping| mapping.lhs.len());
+        let (mut actions, consumed) = match best {
+            Some(mapping) => (mapping.actions(), mapping.lhs.len()),
+            // Nothing maps: the first key is taken literally and the rest is
+            // looked at again.
+            None => (vec![MapAction::Key(keys[0].to_event())], 1),
+        };
+        actions.extend(keys[consumed..].iter().map(|key| MapAction::Remap(key.to_event())));
+        Some(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(ch: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE)
+    }
+
+    fn keys(actions: &[MapAction]) -> Vec<String> {
+        actions
+            .iter()
+            .map(|action| match action {
+                MapAction::Key(event) => format!("{}", MapKey::from_event(*event)),
+                MapAction::Remap(event) => format!("~{}", MapKey::from_event(*event)),
+                MapAction::Command(name) => format!(":{name}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_vim_key_notation_with_leader() {
+        let leader = parse_keys("<Space>", &[]).unwrap();
+        let parsed = parse_keys("<leader>g<C-S><esc>J<lt>", &leader).unwrap();
+        let rendered: String = parsed.iter().map(ToString::to_string).collect();
+        assert_eq!(rendered, "<Space>g<C-s><Esc>J<lt>");
+        assert_eq!(parse_keys("<nope>", &[]).unwrap().len(), 6);
+        assert_eq!(command_target("<Cmd>open_graph<CR>"), Some("open_graph"));
+        assert_eq!(command_target("<cmd> show_help"), Some("show_help"));
+        assert_eq!(command_target("jk"), None);
+    }
+
+    #[test]
+    fn multi_key_sequences_wait_for_more_input_or_the_timeout() {
+        let mut mapper = KeyMapper::new("<Space>", Duration::from_millis(500)).unwrap();
+        mapper.map(&[MapMode::Insert], "jk", "<Esc>", false).unwrap();
+        let start = Instant::now();
+
+        assert_eq!(mapper.feed(MapMode::Insert, key('j'), start), None);
+        assert_eq!(keys(&mapper.feed(MapMode::Insert, key('k'), start).unwrap()), ["<Esc>"]);
+
+        assert_eq!(mapper.feed(MapMode::Insert, key('j'), start), None);
+        assert_eq!(keys(&mapper.feed(MapMode::Insert, key('x'), start).unwrap()), ["j", "~x"]);
+
+        assert_eq!(mapper.feed(MapMode::Insert, key('j'), start), None);
+        assert_eq!(mapper.expire(start + Duration::from_millis(100)), None);
+        assert_eq!(keys(&mapper.expire(start + Duration::from_millis(500)).unwrap()), ["j"]);
+
+        assert_eq!(keys(&mapper.feed(MapMode::Normal, key('j'), start).unwrap()), ["j"]);
+    }
+
+    #[test]
+    fn recursive_and_non_recursive_targets() {
+        let mut mapper = KeyMapper::new("<Space>", DEFAULT_TIMEOUT).unwrap();
+        mapper.map(&[MapMode::Normal], "<leader>g", "<Cmd>open_graph<CR>", false).unwrap();
+        mapper.map(&[MapMode::Normal], "Q", "dd", true).unwrap();
+        mapper.map(&[MapMode::Normal], "j", "jzz", true).unwrap();
+        mapper.map(&[MapMode::Normal, MapMode::Visual], "H", "^", false).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(mapper.feed(MapMode::Normal, key(' '), now), None);
+        assert_eq!(keys(&mapper.feed(MapMode::Normal, key('g'), now).unwrap()), [":open_graph"]);
+        assert_eq!(keys(&mapper.feed(MapMode::Normal, key('Q'), now).unwrap()), ["~d", "~d"]);
+        assert_eq!(keys(&mapper.feed(MapMode::Normal, key('j'), now).unwrap()), ["j", "~z", "~z"]);
+        assert_eq!(keys(&mapper.feed(MapMode::Visual, key('H'), now).unwrap()), ["^"]);
+
+        assert_eq!(mapper.list(&[MapMode::Normal], "<leader>"), ["n <Space>g *<Cmd>open_graph<CR>"]);
+        mapper.unmap(&[MapMode::Normal, MapMode::Visual], "H").unwrap();
+        assert!(mapper.unmap(&[MapMode::Normal], "H").is_err());
+        assert_eq!(keys(&mapper.feed(MapMode::Visual, key('H'), now).unwrap()), ["H"]);
+    }
+}
@@ -15,19 +15,22 @@ use ratatui::{
 };
 use ratatui_image::{picker::Picker, sliced::SlicedProtocol};
 
-use crate::config::{Config, Theme, ThemeEntry, ThemeFile};
+use crate::config::{Config, GraphColorGroup, HookEvent, Theme, ThemeEntry, ThemeFile};
 use crate::highlight::Highlighter;
 use crate::highlight_worker::{HighlightColors, HighlightResult, HighlightWorker};
-use crate::keybindings::{KeybindingFallback, KeybindingWarning, Keymap};
+use crate::keybindings::{AppCommand, KeybindingFallback, KeybindingWarning, Keymap};
 use ekphos_editor::{Editor, Position};
 use ekphos_graph as graph;
 use ekphos_graph::{
-    GraphEdge, GraphFileFingerprint, GraphFilter, GraphIndex, GraphLinkScope, GraphMode, GraphNode, GraphResponse, GraphSourceFile, GraphSourceMetadata,
-    GraphWorker,
+    CentralityMetric, GraphEdge, GraphExportFormat, GraphExportOptions, GraphFileFingerprint, GraphFilter, GraphIndex, GraphLinkScope, GraphMode, GraphNode,
+    GraphPins, GraphProjection, GraphResponse, GraphSourceFile, GraphSourceMetadata, GraphWorker,
 };
 use ekphos_search as search;
 use ekphos_search::{SearchHit, SearchIndex, SearchWorker};
-use ekphos_vim::VimState;
+use ekphos_vault::properties::{Property, PropertyVocabulary};
+use ekphos_vault::query::{QueryNote, QueryTable};
+use ekphos_vault::tasks::{TaskSource, TaskWorker, VaultTask};
+use ekphos_vim::{KeyMapper, VimState};
 
 mod session_types;
 pub use session_types::*;
@@ -203,6 +206,8 @@ impl AppBuilder {
             Ok(keymap) => (keymap, None),
             Err(error) => (Keymap::default(), Some(KeybindingWarning::new(error, KeybindingFallback::Defaults))),
         };
+        let (vim_mappings, vim_issues) = config.vim.mapper();
+        let keybinding_warning = KeybindingWarning::with_vim_issues(keybinding_warning, vim_issues);
 
         let theme = Theme::from_name_in(&config.theme, &Config::themes_dir_in(&dependencies.config_dir));
         let mut list_state = ListState::default();
@@ -290,6 +295,7 @@ impl AppBuilder {
             outline_state: ListState::default(),
             vim_mode: VimMode::Normal,
             vim: VimState::new(),
+            vim_mappings,
             visual_line_anchor: None,
             visual_line_current: None,
             visual_block_anchor: None,
@@ -300,6 +306,25 @@ impl AppBuilder {
             floating_cursor_mode: config.floating_cursor,
             content_items: Vec::new(),
             document_tables: Vec::new(),
+            document_queries: Vec::new(),
+            document_embeds: Vec::new(),
+            frontmatter_corpus: None,
+            note_aliases: HashMap::new(),
+            properties: PropertiesState::default(),
+            command_palette: CommandPaletteState::default(),
+            palette_usage: palette::read_palette_usage(&dependencies.cache_dir),
+            filter_job: None,
+            command_runner: CommandRunner::default(),
+            command_log: std::collections::VecDeque::new(),
+            command_log_scroll: 0,
+            git: None,
+            git_history: None,
+            note_history: None,
+            trash: None,
+            merge: None,
+            heading_rename: None,
+            doctor: None,
+            trashed_notes: HashSet::new(),
             document_links: Vec::new(),
             document_link_ranges: Vec::new(),
             content_render_scratch: ContentRenderScratch::default(),
@@ -360,6 +385,8 @@ impl AppBuilder {
             graph_index_generation: 0,
             graph_indexing: false,
             graph_layout_generation: 0,
+            graph_centrality_generation: 0,
+            local_graph: LocalGraphPane::default(),
             graph_last_reused_files: 0,
             graph_last_parsed_files: 0,
             sort_mode: SortMode::default(),
@@ -367,6 +394,8 @@ impl AppBuilder {
             navigation_index: 0,
             frontmatter_hidden,
             theme_picker: ThemePicker::default(),
+            agenda: AgendaState::default(),
+            task_worker: None,
             search_picker: SearchPickerState::Closed,
             search_picker_area: Rect::default(),
             search_picker_results_area: Rect::default(),
@@ -401,15 +430,37 @@ impl AppBuilder {
     }
 }
 
+mod agenda;
+mod blocks;
+mod doctor;
 mod document;
 mod editing;
+mod extract;
+mod filter;
+pub(crate) use filter::FilterJob;
+mod git_state;
+pub(crate) use git_state::GitState;
 mod graph_state;
+mod heading_rename;
+mod hooks;
+pub(crate) use hooks::CommandRunner;
 mod lifecycle;
 mod links;
+mod local_graph;
 mod memory;
+mod merge;
+mod palette;
+pub use palette::PALETTE_VISIBLE_ITEMS;
+mod properties;
 pub use memory::*;
+pub(crate) use properties::month_grid;
 mod search_state;
 mod services;
+mod snapshots;
+pub use snapshots::format_snapshot_time;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod trash;
 mod ui_state;
 mod vault;
 fn fetch_remote_image_blocking(url: &str) -> Option<DynamicImage> {
@@ -0,0 +1,397 @@
+use super::*;
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BTreeMap;
+
+use ekphos_vault::tasks::{self, TaskPriority};
+
+/// Rows of context kept above a task opened from the agenda.
+const AGENDA_SCROLL_MARGIN: usize = 3;
+
+impl App {
+    /// Open the agenda and collect tasks in the background. Grouping and
+    /// filters persist for the session.
+    pub fn open_agenda(&mut self) {
+        if self.mode != Mode::Normal {
+            return;
+        }
+        self.agenda = AgendaState {
+            grouping: self.agenda.grouping,
+            filter: self.agenda.filter,
+            show_completed: self.agenda.show_completed,
+            request_id: self.agenda.request_id,
+            ..AgendaState::default()
+        };
+        self.dialog = DialogState::Agenda;
+        self.refresh_agenda();
+    }
+
+    pub fn refresh_agenda(&mut self) {
+        let sources: Arc<[TaskSource]> = self
+            .notes
+            .iter()
+            .filter_map(|note| {
+                Some(TaskSource {
+                    note_id: note.id,
+                    absolute_path: note.file_path.clone()?,
+                })
+            })
+            .collect::<Vec<_>>()
+            .into();
+        self.agenda.request_id = self.agenda.request_id.wrapping_add(1);
+        self.agenda.loading = true;
+        let worker = self.task_worker.get_or_insert_with(TaskWorker::new);
+        worker.submit(self.agenda.request_id, sources);
+    }
+
+    pub fn close_agenda(&mut self) {
+        if let Some(worker) = &self.task_worker {
+            worker.cancel();
+        }
+        self.task_worker = None;
+        self.agenda = AgendaState {
+            grouping: self.agenda.grouping,
+            filter: self.agenda.filter,
+            show_completed: self.agenda.show_completed,
+            request_id: self.agenda.request_id,
+            ..AgendaState::default()
+        };
+        self.dialog = DialogState::None;
+    }
+
+    /// Install a finished collection. Returns true when the agenda changed.
+    pub fn poll_agenda(&mut self) -> bool {
+        let Some(response) = self.task_worker.as_ref().and_then(TaskWorker::try_take) else {
+            return false;
+        };
+        if self.dialog != DialogState::Agenda || response.request_id != self.agenda.request_id {
+            return false;
+        }
+        self.agenda.tasks = response.tasks;
+        self.agenda.loading = false;
+        self.rebuild_agenda_rows();
+        true
+    }
+
+    pub fn agenda_has_background_work(&self) -> bool {
+        self.agenda.loading
+    }
+
+    pub fn agenda_cycle_grouping(&mut self) {
+        self.agenda.grouping = self.agenda.grouping.next();
+        self.rebuild_agenda_rows();
+    }
+
+    pub fn agenda_set_filter(&mut self, filter: AgendaFilter) {
+        self.agenda.filter = filter;
+        self.rebuild_agenda_rows();
+    }
+
+    pub fn agenda_cycle_filter(&mut self) {
+        let next = match self.agenda.filter {
+            AgendaFilter::All => AgendaFilter::Overdue,
+            AgendaFilter::Overdue => AgendaFilter::Today,
+            AgendaFilter::Today => AgendaFilter::Upcoming,
+            AgendaFilter::Upcoming => AgendaFilter::All,
+        };
+        self.agenda_set_filter(next);
+    }
+
+    pub fn agenda_toggle_completed(&mut self) {
+        self.agenda.show_completed = !self.agenda.show_completed;
+        self.rebuild_agenda_rows();
+    }
+
+    pub fn agenda_select_next(&mut self) {
+        let start = self.agenda.selected + 1;
+        if let Some(offset) = self.agenda.rows[start.min(self.agenda.rows.len())..]
+            .iter()
+            .position(|row| matches!(row, AgendaRow::Task(_)))
+        {
+            self.agenda.selected = start + offset;
+        }
+    }
+
+    pub fn agenda_select_prev(&mut self) {
+        if let Some(index) = self.agenda.rows[..self.agenda.selected.min(self.agenda.rows.len())]
+            .iter()
+            .rposition(|row| matches!(row, AgendaRow::Task(_)))
+        {
+            self.agenda.selected = index;
+        }
+    }
+
+    pub fn agenda_selected_task(&self) -> Option<&VaultTask> {
+        match self.agenda.rows.get(self.agenda.selected)? {
+            AgendaRow::Task(index) => self.agenda.tasks.get(*index),
+            AgendaRow::Group { .. } => None,
+        }
+    }
+
+    /// Flip the selected task's checkbox in its source file. The agenda keeps
+    /// the row in place so an accidental toggle can be undone immediately.
+    pub fn agenda_toggle_selected(&mut self) {
+        let Some(AgendaRow::Task(task_index)) = self.agenda.rows.get(self.agenda.selected).cloned() else {
+            return;
+        };
+        let Some(task) = self.agenda.tasks.get(task_index).cloned() else {
+            return;
+        };
+        // The worker read the file on disk, so the line number is only
+        // meaningful against the file on disk.
+        let body = self
+            .note_index_for_id(task.note_id)
+            .and_then(|index| self.notes[index].file_path.as_ref())
+            .and_then(|path| fs::read_to_string(path).ok());
+        let Some(updated) = body.and_then(|body| tasks::toggle_task_line(&body, task.line, task.checked)) else {
+            self.show_error_toast("Task changed on disk; refreshing agenda".to_string());
+            self.refresh_agenda();
+            return;
+        };
+        if !self.persist_note_body(task.note_id, updated) {
+            return;
+        }
+        if self.current_note().is_some_and(|note| note.id == task.note_id) {
+            let saved_cursor = self.content_cursor;
+            self.update_content_items();
+            self.content_cursor = saved_cursor.min(self.content_items.len().saturating_sub(1));
+        }
+        if let Some(task) = self.agenda.tasks.get_mut(task_index) {
+            task.checked = !task.checked;
+        }
+    }
+
+    /// Close the agenda and jump to the selected task in its note.
+    pub fn agenda_open_selected(&mut self) {
+        let Some((note_id, line)) = self.agenda_selected_task().map(|task| (task.note_id, task.line as usize)) else {
+            return;
+        };
+        self.close_agenda();
+        let Some(note_index) = self.note_index_for_id(note_id) else {
+            return;
+        };
+        if !self.navigate_to_note(note_index) {
+            return;
+        }
+        if let Some(index) = self.content_items.iter().position(|item| item.source_line() >= line) {
+            self.content_cursor = index;
+            self.content_scroll_offset = index.saturating_sub(AGENDA_SCROLL_MARGIN);
+        }
+        self.focus = Focus::Content;
+    }
+
+    /// Rebuild grouped rows from the collected tasks, keeping the selected
+    /// task selected when it is still visible.
+    pub(super) fn rebuild_agenda_rows(&mut self) {
+        let today = self.dependencies.clock.today();
+        let previous = match self.agenda.rows.get(self.agenda.selected) {
+            Some(AgendaRow::Task(index)) => Some(*index),
+            _ => None,
+        };
+        let note_indices: HashMap<NoteId, usize> = self.notes.iter().enumerate().map(|(index, note)| (note.id, index)).collect();
+
+        let mut visible: Vec<usize> = self
+            .agenda
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| {
+                note_indices.contains_key(&task.note_id) && (self.agenda.show_completed || !task.checked) && self.agenda.filter.matches(task.due, today)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        visible.sort_by(|&left, &right| self.compare_agenda_tasks(&self.agenda.tasks[left], &self.agenda.tasks[right], &note_indices));
+
+        let mut groups: BTreeMap<(u8, String), (String, Vec<usize>)> = BTreeMap::new();
+        for index in visible {
+            let task = &self.agenda.tasks[index];
+            let note_index = note_indices[&task.note_id];
+            match self.agenda.grouping {
+                AgendaGrouping::Note => {
+                    let label = self.get_wiki_path_for_note(note_index).unwrap_or_else(|| self.notes[note_index].title.clone());
+                    groups.entry((0, label.to_lowercase())).or_insert_with(|| (label, Vec::new())).1.push(index);
+                }
+                AgendaGrouping::Tag => {
+                    let mut tags: Vec<&str> = task.tags.iter().map(AsRef::as_ref).collect();
+                    if let Some(frontmatter) = &self.notes[note_index].frontmatter {
+                        for tag in frontmatter.tags.iter() {
+                            if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                                tags.push(tag);
+                            }
+                        }
+                    }
+                    if tags.is_empty() {
+                        groups
+                            .entry((1, String::new()))
+                            .or_insert_with(|| ("Untagged".to_string(), Vec::new()))
+                            .1
+                            .push(index);
+                    }
+                    for tag in tags {
+                        groups
+                            .entry((0, tag.to_lowercase()))
+                            .or_insert_with(|| (format!("#{tag}"), Vec::new()))
+                            .1
+                            .push(index);
+                    }
+                }
+                AgendaGrouping::DueDate => {
+                    let (key, label) = match task.due {
+                        Some(due) => {
+                            let suffix = match due.cmp(&today) {
+                                CmpOrdering::Less => " · overdue",
+                                CmpOrdering::Equal => " · today",
+                                CmpOrdering::Greater => "",
+                            };
+                            ((0, due.format("%Y-%m-%d").to_string()), format!("{}{}", due.format("%Y-%m-%d %a"), suffix))
+                        }
+                        None => ((1, String::new()), "No due date".to_string()),
+                    };
+                    groups.entry(key).or_insert_with(|| (label, Vec::new())).1.push(index);
+                }
+            }
+        }
+
+        let mut rows = Vec::new();
+        for (label, members) in groups.into_values() {
+            rows.push(AgendaRow::Group { label, count: members.len() });
+            rows.extend(members.into_iter().map(AgendaRow::Task));
+        }
+        self.agenda.rows = rows;
+        self.agenda.selected = previous
+            .and_then(|previous| self.agenda.rows.iter().position(|row| *row == AgendaRow::Task(previous)))
+            .or_else(|| self.agenda.rows.iter().position(|row| matches!(row, AgendaRow::Task(_))))
+            .unwrap_or(0);
+        self.agenda.scroll_offset = 0;
+    }
+
+    /// Priority first (unprioritised tasks rank between medium and low, as in
+    /// the Tasks plugin), then earliest due date, then source order.
+    fn compare_agenda_tasks(&self, left: &VaultTask, right: &VaultTask, note_indices: &HashMap<NoteId, usize>) -> CmpOrdering {
+        fn priority_rank(priority: Option<TaskPriority>) -> u8 {
+            match priority {
+                Some(TaskPriority::Highest) => 0,
+                Some(TaskPriority::High) => 1,
+                Some(TaskPriority::Medium) => 2,
+                None => 3,
+                Some(TaskPriority::Low) => 4,
+                Some(TaskPriority::Lowest) => 5,
+            }
+        }
+        let title = |task: &VaultTask| note_indices.get(&task.note_id).map(|&index| self.notes[index].title.to_lowercase());
+        priority_rank(left.priority)
+            .cmp(&priority_rank(right.priority))
+            .then_with(|| match (left.due, right.due) {
+                (Some(left), Some(right)) => left.cmp(&right),
+                (Some(_), None) => CmpOrdering::Less,
+                (None, Some(_)) => CmpOrdering::Greater,
+                (None, None) => CmpOrdering::Equal,
+            })
+            .then_with(|| title(left).cmp(&title(right)))
+            .then_with(|| left.line.cmp(&right.line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn today() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    fn wait_for_agenda(app: &mut App) {
+        let started = std::time::Instant::now();
+        while !app.poll_agenda() {
+            assert!(started.elapsed() < std::time::Duration::from_secs(2));
+            std::thread::yield_now();
+        }
+    }
+
+    fn task_labels(app: &App) -> Vec<String> {
+        app.agenda
+            .rows
+            .iter()
+            .map(|row| match row {
+                AgendaRow::Group { label, count } => format!("{label} ({count})"),
+                AgendaRow::Task(index) => format!("- {}", app.agenda.tasks[*index].description),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_filters_and_toggles_tasks_across_the_vault() {
+        let vault = TempVault::with_files(&[
+            (
+                "Work.md",
+                "---\ntags: [job]\n---\n- [ ] Late 📅 2026-10-17\n- [ ] Due today due:2026-10-18 ⏫\n- [x] Done already\n",
+            ),
+            ("Home.md", "- [ ] Groceries #errand\n- [ ] Plan trip 📅 2026-11-02\n"),
+        ]);
+        let notes = vault.notes();
+        let mut app = vault.app_on(today());
+
+        app.open_agenda();
+        assert_eq!(app.dialog, DialogState::Agenda);
+        wait_for_agenda(&mut app);
+        assert_eq!(
+            task_labels(&app),
+            ["Home (2)", "- Plan trip", "- Groceries #errand", "Work (2)", "- Due today", "- Late"]
+        );
+
+        app.agenda_cycle_grouping();
+        assert_eq!(
+            task_labels(&app),
+            [
+                "#errand (1)",
+                "- Groceries #errand",
+                "#job (2)",
+                "- Due today",
+                "- Late",
+                "Untagged (1)",
+                "- Plan trip"
+            ]
+        );
+
+        app.agenda_cycle_grouping();
+        app.agenda_set_filter(AgendaFilter::Overdue);
+        assert_eq!(task_labels(&app), ["2026-10-17 Sat · overdue (1)", "- Late"]);
+        app.agenda_set_filter(AgendaFilter::Today);
+        assert_eq!(task_labels(&app), ["2026-10-18 Sun · today (1)", "- Due today"]);
+        app.agenda_set_filter(AgendaFilter::Upcoming);
+        assert_eq!(task_labels(&app), ["2026-11-02 Mon (1)", "- Plan trip"]);
+
+        app.agenda_set_filter(AgendaFilter::Today);
+        app.agenda_toggle_selected();
+        assert_eq!(
+            fs::read_to_string(notes.join("Work.md")).unwrap(),
+            "---\ntags: [job]\n---\n- [ ] Late 📅 2026-10-17\n- [x] Due today due:2026-10-18 ⏫\n- [x] Done already\n"
+        );
+        assert_eq!(task_labels(&app), ["2026-10-18 Sun · today (1)", "- Due today"]);
+        app.agenda_set_filter(AgendaFilter::All);
+        assert!(!task_labels(&app).contains(&"- Due today".to_string()));
+        app.agenda_toggle_completed();
+        assert!(task_labels(&app).contains(&"- Done already".to_string()));
+
+        app.close_agenda();
+        assert!(app.task_worker.is_none());
+        assert_eq!(app.agenda.grouping, AgendaGrouping::DueDate);
+    }
+
+    #[test]
+    fn stale_task_line_refreshes_instead_of_writing() {
+        let vault = TempVault::with_files(&[("Note.md", "- [ ] Original\n")]);
+        let notes = vault.notes();
+        let mut app = vault.app_on(today());
+        app.open_agenda();
+        wait_for_agenda(&mut app);
+
+        fs::write(notes.join("Note.md"), "# Inserted heading\n- [ ] Original\n").unwrap();
+        app.agenda_toggle_selected();
+        assert_eq!(fs::read_to_string(notes.join("Note.md")).unwrap(), "# Inserted heading\n- [ ] Original\n");
+        assert!(app.agenda.loading);
+        wait_for_agenda(&mut app);
+        assert_eq!(app.agenda.tasks[0].line, 1);
+    }
+}
@@ -32,6 +32,7 @@ pub struct App {
     pub outline_state: ListState,
     pub vim_mode: VimMode,
     pub vim: VimState,
+    pub vim_mappings: KeyMapper,
     pub visual_line_anchor: Option<usize>,
     pub visual_line_current: Option<usize>,
     pub visual_block_anchor: Option<Position>,
@@ -42,6 +43,14 @@ pub struct App {
     pub floating_cursor_mode: bool,
     pub content_items: Vec<ContentItem>,
     pub document_tables: Vec<TableMetadata>,
+    pub(crate) document_queries: Vec<QueryBlock>,
+    pub(crate) document_embeds: Vec<EmbedBlock>,
+    /// Frontmatter of every note for query blocks and property autocomplete,
+    /// tagged with the catalog generation it was read at.
+    pub(crate) frontmatter_corpus: Option<(u64, Arc<[QueryNote]>)>,
+    /// Frontmatter aliases from the catalog, kept beside `notes` so notes
+    /// without aliases stay compact.
+    pub(crate) note_aliases: HashMap<NoteId, Box<[Box<str>]>>,
     pub(crate) document_links: Vec<LinkInfo>,
     pub(crate) document_link_ranges: Vec<DocumentLinkRange>,
     pub(crate) content_render_scratch: ContentRenderScratch,
@@ -105,6 +114,8 @@ pub struct App {
     pub graph_index_generation: u64,
     pub graph_indexing: bool,
     pub graph_layout_generation: u64,
+    pub graph_centrality_generation: u64,
+    pub local_graph: LocalGraphPane,
     #[doc(hidden)]
     pub graph_last_reused_files: usize,
     #[doc(hidden)]
@@ -118,6 +129,31 @@ pub struct App {
     pub frontmatter_hidden: bool,
     // Theme selector modal (Ctrl+T)
     pub theme_picker: ThemePicker,
+    // Vault-wide task agenda
+    pub agenda: AgendaState,
+    pub properties: PropertiesState,
+    pub command_palette: CommandPaletteState,
+    /// How often each palette entry was chosen, persisted in the cache dir.
+    pub palette_usage: HashMap<String, u32>,
+    /// External command filtering editor lines (`!`, `:r !`).
+    pub(crate) filter_job: Option<FilterJob>,
+    /// Hooks and palette actions, run one at a time.
+    pub(crate) command_runner: CommandRunner,
+    /// Finished hooks and actions, newest first.
+    pub command_log: std::collections::VecDeque<CommandLogEntry>,
+    pub command_log_scroll: usize,
+    /// Set when the vault lives inside a git repository.
+    pub(crate) git: Option<GitState>,
+    pub git_history: Option<GitHistoryState>,
+    pub note_history: Option<NoteHistoryState>,
+    pub trash: Option<TrashBrowserState>,
+    pub merge: Option<MergeState>,
+    pub heading_rename: Option<HeadingRenameState>,
+    pub doctor: Option<DoctorState>,
+    /// Lowercase titles and vault-relative paths (without `.md`) of trashed
+    /// notes, so links to them can be flagged.
+    pub trashed_notes: HashSet<String>,
+    pub(crate) task_worker: Option<TaskWorker>,
     // Global search picker (file/content search)
     pub search_picker: SearchPickerState,
     pub search_picker_area: ratatui::layout::Rect,
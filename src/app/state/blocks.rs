@@ -0,0 +1,217 @@
+use super::*;
+
+use ekphos_core::markdown::{block_id, block_ids, block_range, heading, is_list_item, strip_block_id};
+
+impl App {
+    /// Body of the note a link target names; the current note for `""`.
+    fn linked_note_body(&self, target: &str) -> Option<(usize, Arc<str>)> {
+        let index = if target.is_empty() {
+            self.notes.iter().position(|note| Some(note.id) == self.active_note_id)?
+        } else {
+            self.resolve_wiki_link(target)?
+        };
+        let note = &self.notes[index];
+        let body = if self.active_note_id == Some(note.id) {
+            self.active_document.as_ref().map(DocumentSnapshot::body_arc)
+        } else {
+            self.vault.load_body(note.id).ok()
+        }?;
+        Some((index, body))
+    }
+
+    /// Lines of block `^id` for an `![[target#^id]]` embed.
+    pub(super) fn embedded_block(&self, target: &str, id: &str) -> Result<Vec<String>, String> {
+        let Some((_, body)) = self.linked_note_body(target) else {
+            return Err(format!("No note named \"{target}\""));
+        };
+        let lines: Vec<&str> = body.lines().collect();
+        let range = block_range(&lines, id).ok_or_else(|| format!("No block ^{id} in {target}"))?;
+        Ok(lines[range].iter().map(|line| strip_block_id(line).to_string()).collect())
+    }
+
+    /// Move the content cursor to the first line of block `^id`.
+    pub(super) fn navigate_to_block(&mut self, id: &str) {
+        let Some(start) = self.active_document.as_ref().and_then(|document| {
+            let lines: Vec<&str> = document.body().lines().collect();
+            block_range(&lines, id).map(|range| range.start)
+        }) else {
+            return;
+        };
+        if let Some(index) = self.content_items.iter().position(|item| item.source_line() >= start) {
+            self.content_cursor = index;
+            self.content_scroll_offset = index.saturating_sub(2);
+        }
+    }
+
+    /// `^id` completions for `[[target#^`, previewing each block's text.
+    pub(super) fn block_suggestions(&self, target: &str, query: &str) -> Vec<WikiSuggestion> {
+        let Some((index, body)) = self.linked_note_body(target) else {
+            return Vec::new();
+        };
+        let wiki_path = self.get_wiki_path_for_note(index).unwrap_or_default();
+        let lines: Vec<&str> = body.lines().collect();
+        block_ids(&lines)
+            .into_iter()
+            .filter_map(|(row, id)| {
+                let preview = match strip_block_id(lines[row]).trim() {
+                    "" => row.checked_sub(1).map_or("", |above| lines[above].trim()),
+                    text => text,
+                };
+                let score = if query.is_empty() {
+                    1000
+                } else {
+                    fuzzy_match(id, query).or_else(|| fuzzy_match(preview, query))?
+                };
+                let preview: String = preview.chars().take(60).collect();
+                Some(WikiSuggestion {
+                    display_name: format!("^{id}  {preview}"),
+                    insert_text: format!("^{id}"),
+                    is_folder: false,
+                    path: format!("{wiki_path}#^{id}"),
+                    score,
+                    folder_hint: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Copy a link to the block under the content cursor, adding a `^id`
+    /// to the note first when the block has none. Headings copy a plain
+    /// section link.
+    pub fn copy_block_link(&mut self) {
+        let Some((note_id, path)) = self.current_note().and_then(|note| Some((note.id, note.file_path.clone()?))) else {
+            return;
+        };
+        let Some(row) = self.content_items.get(self.content_cursor).and_then(|item| match item {
+            ContentItem::TextLine { .. } | ContentItem::TaskItem { .. } | ContentItem::TableRow { .. } => Some(item.source_line()),
+            _ => None,
+        }) else {
+            self.show_error_toast("Move to a paragraph or list item to link to it");
+            return;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return;
+        };
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let Some(line) = lines.get(row).filter(|line| !line.trim().is_empty()) else {
+            self.show_error_toast("Move to a paragraph or list item to link to it");
+            return;
+        };
+        let wiki_path = Self::calculate_wiki_path(&path, &self.config.notes_path());
+        if let Some(heading) = heading(line) {
+            let link = format!("[[{wiki_path}#{}]]", heading.text);
+            self.copy_link_to_clipboard(link);
+            return;
+        }
+
+        // The ID belongs on the block's last line, which may already carry one.
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let last = block_end(&refs, row);
+        let id = match block_id(&lines[last]) {
+            Some((_, id)) => id.to_string(),
+            None => {
+                let taken: Vec<String> = block_ids(&refs).into_iter().map(|(_, id)| id.to_lowercase()).collect();
+                let id = new_block_id(&lines[last], &taken);
+                lines[last] = format!("{} ^{id}", lines[last].trim_end());
+                let mut body = lines.join("\n");
+                if content.ends_with('\n') {
+                    body.push('\n');
+                }
+                let cursor = self.content_cursor;
+                if !self.persist_note_body(note_id, body) {
+                    return;
+                }
+                self.content_cursor = cursor.min(self.content_items.len().saturating_sub(1));
+                id
+            }
+        };
+        self.copy_link_to_clipboard(format!("[[{wiki_path}#^{id}]]"));
+    }
+
+    fn copy_link_to_clipboard(&mut self, link: String) {
+        match self.clipboard().set_text(&link) {
+            Ok(()) => self.show_toast(format!("Copied {link}"), ToastKind::Success),
+            Err(error) => self.show_error_toast(format!("Could not copy link: {error}")),
+        }
+    }
+}
+
+/// Last row of the paragraph containing `row`; list items and table rows
+/// stand alone.
+fn block_end(lines: &[&str], row: usize) -> usize {
+    let standalone = |line: &str| is_list_item(line) || line.trim_start().starts_with('|');
+    if standalone(lines[row]) {
+        return row;
+    }
+    let mut end = row;
+    while let Some(next) = lines.get(end + 1) {
+        if next.trim().is_empty() || heading(next).is_some() || standalone(next) || next.trim_start().starts_with("```") {
+            break;
+        }
+        end += 1;
+    }
+    end
+}
+
+/// A short ID derived from the line, unique within the note.
+fn new_block_id(line: &str, taken: &[String]) -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    (0u64..)
+        .map(|attempt| {
+            let mut hash = 0xcbf2_9ce4_8422_2325u64 ^ attempt;
+            for byte in line.bytes() {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+            (0..6)
+                .map(|position| ALPHABET[((hash >> (position * 6)) % ALPHABET.len() as u64) as usize] as char)
+                .collect::<String>()
+        })
+        .find(|id| !taken.contains(id))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[
+            ("source.md", "# Source\n\nfirst line\nquote me ^quote\n\nplain paragraph\nsecond line\n"),
+            ("reader.md", "# Reader\n\n![[source#^quote]]\n![[source#^gone]]\n"),
+        ])
+    }
+
+    #[test]
+    fn block_embeds_resolve_and_links_navigate_to_the_block() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("reader.md")));
+
+        let lines: Vec<_> = app.document_embeds.iter().map(|block| block.lines.clone()).collect();
+        assert_eq!(lines[0].as_deref().unwrap(), [Box::from("first line"), Box::from("quote me")]);
+        assert!(lines[1].is_err());
+        assert_eq!(app.block_suggestions("source", "quo")[0].insert_text, "^quote");
+
+        assert!(app.navigate_to_wiki_link_with_heading("source", Some("^quote")));
+        assert_eq!(app.content_items[app.content_cursor].source_line(), 2);
+    }
+
+    #[test]
+    fn copying_a_block_link_adds_an_id_once() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        let source = vault.notes().join("source.md");
+        assert!(app.select_note_by_path(&source));
+        app.content_cursor = app.content_items.iter().position(|item| item.source_line() == 5).unwrap();
+
+        app.copy_block_link();
+        let content = fs::read_to_string(&source).unwrap();
+        let (_, id) = block_id(content.lines().nth(6).unwrap()).unwrap();
+        let link = app.clipboard().get_text().unwrap().unwrap();
+        assert_eq!(link, format!("[[source#^{id}]]"));
+
+        app.copy_block_link();
+        assert_eq!(fs::read_to_string(&source).unwrap(), content);
+    }
+}
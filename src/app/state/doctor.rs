@@ -0,0 +1,295 @@
+use super::*;
+
+use std::path::Path;
+
+use ekphos_core::markdown::{fence_marker, frontmatter_end, markdown_link_at, visit_document_wiki_links_with_tilde_fences, MarkdownLinkKind};
+use ekphos_graph::{GraphIndex, GraphSources};
+use ekphos_vault::{Frontmatter, VaultError};
+
+impl App {
+    /// Audit the whole vault. Issues are grouped by kind, errors first,
+    /// then ordered by path and line.
+    pub fn vault_doctor_issues(&self) -> Vec<DoctorIssue> {
+        let mut issues = Vec::new();
+        let mut unreadable = HashSet::new();
+        for note in &self.notes {
+            let Some(path) = &note.file_path else {
+                continue;
+            };
+            match self.vault.load_body(note.id) {
+                Ok(body) => self.check_note_body(path, &body, &mut issues),
+                Err(VaultError::InvalidEncoding(_)) => {
+                    unreadable.insert(note.id);
+                    issues.push(DoctorIssue {
+                        kind: DoctorIssueKind::InvalidEncoding,
+                        path: path.clone(),
+                        line: None,
+                        detail: "file is not valid UTF-8 text".to_string(),
+                    });
+                }
+                Err(_) => {
+                    unreadable.insert(note.id);
+                }
+            }
+        }
+
+        // Bare links to a shared title only ever reach the first match.
+        let mut titles: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+        for note in &self.notes {
+            if let Some(path) = &note.file_path {
+                titles.entry(note.title.to_lowercase()).or_default().push(path);
+            }
+        }
+        for paths in titles.values().filter(|paths| paths.len() > 1) {
+            for &path in paths {
+                let others: Vec<String> = paths.iter().filter(|other| **other != path).map(|other| self.vault_relative(other)).collect();
+                issues.push(DoctorIssue {
+                    kind: DoctorIssueKind::DuplicateTitle,
+                    path: path.clone(),
+                    line: None,
+                    detail: format!("same title as {}", others.join(", ")),
+                });
+            }
+        }
+
+        let sources = (0..self.notes.len())
+            .filter(|&index| self.notes[index].file_path.is_some() && !unreadable.contains(&self.notes[index].id))
+            .map(|index| self.graph_source_metadata(index))
+            .collect();
+        // A note is an orphan when no other note links to or from it; shared
+        // tags and attachments do not count.
+        let note_links = GraphSources {
+            tags: false,
+            attachments: false,
+            ..self.config.graph.sources()
+        };
+        let index = GraphIndex::build_from_loader(sources, note_links, |note_id| self.vault.load_body(note_id).ok().map(|body| body.to_string()));
+        for node in index.nodes.iter().filter(|node| node.in_degree == 0 && node.out_degree == 0) {
+            if let Some(path) = self.note_index_for_id(node.note_id).and_then(|index| self.notes[index].file_path.clone()) {
+                issues.push(DoctorIssue {
+                    kind: DoctorIssueKind::Orphan,
+                    path,
+                    line: None,
+                    detail: "no links in or out".to_string(),
+                });
+            }
+        }
+
+        issues.sort_by(|a, b| (a.kind, &a.path, a.line).cmp(&(b.kind, &b.path, b.line)));
+        issues
+    }
+
+    fn check_note_body(&self, path: &Path, body: &str, issues: &mut Vec<DoctorIssue>) {
+        let mut push = |kind, line, detail| {
+            issues.push(DoctorIssue {
+                kind,
+                path: path.to_path_buf(),
+                line,
+                detail,
+            })
+        };
+        let body_start = frontmatter_end(body);
+        if body_start.is_some() && Frontmatter::parse(body).0.is_none() {
+            push(
+                DoctorIssueKind::InvalidFrontmatter,
+                Some(0),
+                "YAML between the --- lines does not parse".to_string(),
+            );
+        }
+        if body.lines().skip(body_start.map_or(0, |end| end + 1)).all(|line| line.trim().is_empty()) {
+            push(DoctorIssueKind::EmptyNote, None, "nothing after the frontmatter".to_string());
+        }
+
+        visit_document_wiki_links_with_tilde_fences(body, body_start, true, |located| {
+            let target = located.link.target.trim();
+            let state = self.wiki_link_state(target);
+            if state != WikiLinkState::Exists {
+                let reason = if state == WikiLinkState::Trashed { "in the trash" } else { "no such note" };
+                push(DoctorIssueKind::BrokenLink, Some(located.row), format!("[[{target}]] ({reason})"));
+            }
+        });
+
+        let note_dir = path.parent().unwrap_or(path);
+        let mut fence = None;
+        for (row, line) in body.lines().enumerate().skip(body_start.map_or(0, |end| end + 1)) {
+            if let Some(marker) = fence_marker(line) {
+                fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+                continue;
+            }
+            if fence.is_some() {
+                continue;
+            }
+            for (start, _) in line.match_indices("![") {
+                let Some(link) = markdown_link_at(line, start).filter(|link| link.kind == MarkdownLinkKind::Image) else {
+                    continue;
+                };
+                if !local_image_exists(note_dir, link.destination) {
+                    push(DoctorIssueKind::MissingImage, Some(row), link.destination.to_string());
+                }
+            }
+        }
+    }
+
+    pub fn open_vault_doctor(&mut self) {
+        self.doctor = Some(DoctorState {
+            issues: self.vault_doctor_issues(),
+            selected: 0,
+            scroll: 0,
+        });
+        self.dialog = DialogState::VaultDoctor;
+    }
+
+    pub fn close_vault_doctor(&mut self) {
+        self.doctor = None;
+        self.dialog = DialogState::None;
+    }
+
+    /// Re-run the checks, keeping the selection in range.
+    pub fn refresh_vault_doctor(&mut self) {
+        let issues = self.vault_doctor_issues();
+        if let Some(doctor) = &mut self.doctor {
+            doctor.selected = doctor.selected.min(issues.len().saturating_sub(1));
+            doctor.issues = issues;
+        }
+    }
+
+    /// Jump to the selected issue's note and line.
+    pub fn open_doctor_issue(&mut self) {
+        let Some(issue) = self.doctor.as_ref().and_then(|doctor| doctor.issues.get(doctor.selected)).cloned() else {
+            return;
+        };
+        let Some(index) = self.notes.iter().position(|note| note.file_path.as_ref() == Some(&issue.path)) else {
+            return;
+        };
+        if !self.navigate_to_note(index) {
+            self.show_error_toast(format!("Could not open {}", self.vault_relative(&issue.path)));
+            return;
+        }
+        self.close_vault_doctor();
+        if let Some(line) = issue.line {
+            if let Some(item) = self.content_items.iter().position(|item| item.source_line() >= line) {
+                self.content_cursor = item;
+                self.content_scroll_offset = item.saturating_sub(2);
+            }
+        }
+    }
+
+    /// Apply the quick fix for the selected issue: create the note a broken
+    /// link names, or move an empty note to the trash.
+    pub fn fix_doctor_issue(&mut self) {
+        let Some(issue) = self.doctor.as_ref().and_then(|doctor| doctor.issues.get(doctor.selected)).cloned() else {
+            return;
+        };
+        match issue.kind {
+            DoctorIssueKind::BrokenLink => {
+                let target = issue.detail.trim_start_matches("[[").split("]]").next().unwrap_or_default().to_string();
+                if self.trashed_notes.contains(&target.to_lowercase()) {
+                    self.show_error_toast(format!("\"{target}\" is in the trash; restore it from there"));
+                    return;
+                }
+                if !self.create_note_from_wiki_target(&target) {
+                    self.show_error_toast(format!("Could not create \"{target}\""));
+                    return;
+                }
+                self.dialog = DialogState::VaultDoctor;
+                self.show_toast(format!("Created {target}"), ToastKind::Success);
+            }
+            DoctorIssueKind::EmptyNote => {
+                if !self.move_to_trash(&issue.path) {
+                    return;
+                }
+                self.load_notes_from_dir();
+                self.show_toast(format!("Moved {} to the trash", self.vault_relative(&issue.path)), ToastKind::Success);
+            }
+            _ => {
+                self.show_toast("No quick fix for this issue; press enter to open it", ToastKind::Info);
+                return;
+            }
+        }
+        self.refresh_vault_doctor();
+    }
+}
+
+/// Whether a Markdown image destination points at an existing file, looking
+/// beside the note first. Remote images are not fetched.
+fn local_image_exists(note_dir: &Path, destination: &str) -> bool {
+    let destination = normalize_image_destination(destination);
+    if destination.starts_with("http://") || destination.starts_with("https://") || destination.starts_with("data:") {
+        return true;
+    }
+    let path = match destination.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map_or_else(|| PathBuf::from(&destination), |home| home.join(rest)),
+        None => PathBuf::from(&destination),
+    };
+    note_dir.join(path).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn sample_vault() -> TempVault {
+        let vault = TempVault::with_files(&[
+            ("pic.png", "png"),
+            (
+                "index.md",
+                "# Index\n\n[[sub/topic]] [[Missing]]\n\n![ok](pic.png) ![gone](lost.png)\n\n```\n[[Code]] ![x](nope.png)\n```\n",
+            ),
+            ("sub/topic.md", "---\ntitle: [broken\n---\n# Topic\n"),
+            ("topic.md", "---\ntags: [x]\n---\n\n"),
+        ]);
+        vault.write("binary.md", b"\xff\xfe not text");
+        vault
+    }
+
+    #[test]
+    fn doctor_reports_each_kind_of_issue() {
+        let vault = sample_vault();
+        let app = vault.app();
+        let issues: Vec<_> = app
+            .vault_doctor_issues()
+            .into_iter()
+            .map(|issue| (issue.kind, app.vault_relative(&issue.path), issue.line, issue.detail))
+            .collect();
+        let row = |kind, path: &str, line, detail: &str| (kind, path.to_string(), line, detail.to_string());
+        assert_eq!(
+            issues,
+            [
+                row(DoctorIssueKind::BrokenLink, "index.md", Some(2), "[[Missing]] (no such note)"),
+                row(DoctorIssueKind::MissingImage, "index.md", Some(4), "lost.png"),
+                row(
+                    DoctorIssueKind::InvalidFrontmatter,
+                    "sub/topic.md",
+                    Some(0),
+                    "YAML between the --- lines does not parse"
+                ),
+                row(DoctorIssueKind::InvalidEncoding, "binary.md", None, "file is not valid UTF-8 text"),
+                row(DoctorIssueKind::DuplicateTitle, "sub/topic.md", None, "same title as topic.md"),
+                row(DoctorIssueKind::DuplicateTitle, "topic.md", None, "same title as sub/topic.md"),
+                row(DoctorIssueKind::EmptyNote, "topic.md", None, "nothing after the frontmatter"),
+                row(DoctorIssueKind::Orphan, "topic.md", None, "no links in or out"),
+            ]
+        );
+    }
+
+    #[test]
+    fn quick_fixes_create_missing_notes_and_trash_empty_ones() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        app.open_vault_doctor();
+
+        app.fix_doctor_issue();
+        assert!(vault.notes().join("Missing.md").exists());
+        assert_eq!(app.dialog, DialogState::VaultDoctor);
+        let doctor = app.doctor.as_ref().unwrap();
+        assert!(doctor.issues.iter().all(|issue| issue.kind != DoctorIssueKind::BrokenLink));
+
+        let empty = doctor.issues.iter().position(|issue| issue.kind == DoctorIssueKind::EmptyNote).unwrap();
+        app.doctor.as_mut().unwrap().selected = empty;
+        app.fix_doctor_issue();
+        assert!(!vault.notes().join("topic.md").exists());
+        let doctor = app.doctor.as_ref().unwrap();
+        assert!(doctor.issues.iter().all(|issue| issue.kind != DoctorIssueKind::DuplicateTitle));
+    }
+}
@@ -4,6 +4,8 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 struct ParsedDocument {
     items: Vec<ContentItem>,
     tables: Vec<TableMetadata>,
+    queries: Vec<QueryBlock>,
+    embeds: Vec<EmbedBlock>,
     outline: Vec<OutlineItem>,
     links: Vec<LinkInfo>,
     link_ranges: Vec<DocumentLinkRange>,
@@ -27,6 +29,29 @@ impl ParsedDocument {
                     .map(|(text, url, start_col, end_col)| LinkInfo::Markdown { text, url, start_col, end_col })
                     .collect()
             }),
+            ContentItem::QueryRow {
+                query,
+                row: row @ (QueryRowKind::Header | QueryRowKind::Row(_)),
+                ..
+            } => self.queries.get(*query as usize).map_or_else(Vec::new, |block| {
+                let cells = block.cells(*row);
+                let widths: Vec<usize> = block.metadata.column_widths.iter().map(|width| *width as usize).collect();
+                let mut links: Vec<LinkInfo> = App::extract_table_links(&cells, &widths, &block.metadata.alignments)
+                    .into_iter()
+                    .map(|(text, url, start_col, end_col)| LinkInfo::Markdown { text, url, start_col, end_col })
+                    .collect();
+                links.extend(table_wiki_links(&cells, &widths, wiki_exists));
+                links
+            }),
+            ContentItem::EmbedLine { embed, line: 0, .. } => self.embeds.get(*embed as usize).map_or_else(Vec::new, |block| {
+                vec![LinkInfo::Wiki {
+                    target: block.target.to_string(),
+                    heading: Some(format!("^{}", block.id)),
+                    start_col: 0,
+                    end_col: 2 + block.caption().width(),
+                    is_valid: wiki_exists(&block.target),
+                }]
+            }),
             _ => Vec::new(),
         };
         links.sort_by_key(LinkInfo::start_col);
@@ -65,12 +90,142 @@ fn parse_text_links(text: &str, wiki_exists: &dyn Fn(&str) -> bool) -> Vec<LinkI
             heading: link.heading.map(str::to_owned),
             start_col,
             end_col: start_col + link.display_text().width(),
-            is_valid: wiki_exists(link.target),
+            is_valid: wiki_exists(link.target) || (link.target.is_empty() && link.heading.is_some()),
         }
     }));
     links
 }
 
+type EmbedResolver<'a> = &'a dyn Fn(&str, &str) -> Result<Vec<String>, String>;
+
+/// Blocks whose content is computed rather than read from the document.
+struct ComputedBlocks<'a> {
+    query: &'a dyn Fn(&str) -> Result<QueryTable, String>,
+    /// Lines of block `^id` in the note a link target names.
+    embed: EmbedResolver<'a>,
+}
+
+/// `![[note#^id]]` alone on a line.
+fn standalone_block_embed(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    let link = ekphos_core::markdown::wiki_link_at(trimmed.strip_prefix('!')?, 0)?;
+    let id = link.heading?.strip_prefix('^')?;
+    (link.range.end == trimmed.len() - 1).then_some((link.target, id))
+}
+
+fn push_embed(
+    parsed: &mut ParsedDocument,
+    document: &DocumentSnapshot,
+    source_line: usize,
+    (target, id): (&str, &str),
+    resolve: EmbedResolver,
+    wiki_exists: &dyn Fn(&str) -> bool,
+) {
+    let embed = parsed.embeds.len() as u32;
+    parsed.embeds.push(EmbedBlock {
+        target: target.into(),
+        id: id.into(),
+        lines: resolve(target, id)
+            .map(|lines| lines.into_iter().map(String::into_boxed_str).collect())
+            .map_err(String::into_boxed_str),
+    });
+    for line in 0..parsed.embeds[embed as usize].line_count() as u32 {
+        parsed.push_item(
+            ContentItem::EmbedLine {
+                embed,
+                line,
+                source_line: source_line as u32,
+            },
+            document,
+            wiki_exists,
+        );
+    }
+}
+
+/// Wiki links inside left-aligned table cells, positioned the way
+/// `App::extract_table_links` positions markdown links.
+fn table_wiki_links(cells: &[&str], column_widths: &[usize], wiki_exists: &dyn Fn(&str) -> bool) -> Vec<LinkInfo> {
+    let mut links = Vec::new();
+    let mut col_cursor = 0usize;
+    for (i, cell) in cells.iter().enumerate() {
+        let width = column_widths.get(i).copied().unwrap_or_else(|| crate::ui::cell_visible_width(cell));
+        for link in ekphos_core::markdown::wiki_links(cell) {
+            let start_col = col_cursor + 1 + crate::ui::cell_visible_width(&cell[..link.range.start]);
+            links.push(LinkInfo::Wiki {
+                target: link.target.to_owned(),
+                heading: link.heading.map(str::to_owned),
+                start_col,
+                end_col: start_col + link.display_text().width(),
+                is_valid: wiki_exists(link.target),
+            });
+        }
+        col_cursor += 1 + width + 1;
+        if i + 1 < cells.len() {
+            col_cursor += 1;
+        }
+    }
+    links
+}
+
+/// Evaluate one ```` ```query ```` block and push its rows. Every row points at
+/// the opening fence so editing jumps to the query source.
+fn push_query_block(
+    parsed: &mut ParsedDocument,
+    document: &DocumentSnapshot,
+    fence_line: usize,
+    source: &str,
+    run_query: &dyn Fn(&str) -> Result<QueryTable, String>,
+    wiki_exists: &dyn Fn(&str) -> bool,
+) {
+    let query_id = parsed.queries.len() as u32;
+    let source_line = fence_line as u32;
+    let (columns, rows, message) = match run_query(source) {
+        Ok(table) if table.rows.is_empty() => (table.columns, Vec::new(), Some("No matching notes".to_owned())),
+        Ok(table) => (table.columns, table.rows, None),
+        Err(error) => (Vec::new(), Vec::new(), Some(format!("query error: {error}"))),
+    };
+    let mut widths = vec![3u16; columns.len()];
+    for cells in std::iter::once(&columns).chain(rows.iter()) {
+        for (column, cell) in cells.iter().enumerate().take(widths.len()) {
+            let width = crate::ui::cell_visible_width(cell);
+            widths[column] = widths[column].max(u16::try_from(width).unwrap_or(u16::MAX));
+        }
+    }
+    let row_count = rows.len();
+    let has_table = !columns.is_empty();
+    let has_message = message.is_some();
+    parsed.queries.push(QueryBlock {
+        metadata: TableMetadata {
+            alignments: vec![Alignment::Left; widths.len()].into_boxed_slice(),
+            column_widths: widths.into_boxed_slice(),
+        },
+        columns: columns.into_iter().map(String::into_boxed_str).collect(),
+        rows: rows.into_iter().map(|cells| cells.into_iter().map(String::into_boxed_str).collect()).collect(),
+        message: message.map(String::into_boxed_str),
+    });
+
+    let mut kinds = Vec::with_capacity(row_count + 3);
+    if has_table {
+        kinds.push(QueryRowKind::Header);
+        kinds.push(QueryRowKind::Separator);
+        kinds.extend((0..row_count as u32).map(QueryRowKind::Row));
+    }
+    if has_message {
+        kinds.push(QueryRowKind::Message);
+    }
+    for row in kinds {
+        parsed.push_item(
+            ContentItem::QueryRow {
+                query: query_id,
+                row,
+                source_line,
+            },
+            document,
+            wiki_exists,
+        );
+    }
+}
+
 fn range_for_slice(document: &DocumentSnapshot, source_line: usize, slice: &str) -> DocumentRange {
     let line = document.line(source_line).unwrap_or("");
     let relative_start = slice.as_ptr() as usize - line.as_ptr() as usize;
@@ -109,10 +264,13 @@ fn parse_document(
     frontmatter_hidden: bool,
     show_tags: bool,
     wiki_exists: &dyn Fn(&str) -> bool,
+    computed: &ComputedBlocks,
 ) -> ParsedDocument {
     let mut parsed = ParsedDocument {
         items: Vec::with_capacity(document.line_count()),
         tables: Vec::new(),
+        queries: Vec::new(),
+        embeds: Vec::new(),
         outline: Vec::new(),
         links: Vec::new(),
         link_ranges: Vec::with_capacity(document.line_count()),
@@ -158,6 +316,19 @@ fn parse_document(
     while line_index < document.line_count() {
         let line = document.line(line_index).unwrap_or("");
 
+        if !in_code_block && line.starts_with("```") && line.trim_start_matches('`').trim() == "query" {
+            let closing = (line_index + 1..document.line_count()).find(|&index| document.line(index).unwrap_or("").starts_with("```"));
+            if let Some(closing) = closing {
+                let source = (line_index + 1..closing)
+                    .map(|index| document.line(index).unwrap_or(""))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                push_query_block(&mut parsed, document, line_index, &source, computed.query, wiki_exists);
+                line_index = closing + 1;
+                continue;
+            }
+        }
+
         if line.starts_with("```") {
             let language = line.trim_start_matches('`');
             parsed.push_item(
@@ -186,6 +357,12 @@ fn parse_document(
             continue;
         }
 
+        if let Some(embed) = standalone_block_embed(line) {
+            push_embed(&mut parsed, document, line_index, embed, computed.embed, wiki_exists);
+            line_index += 1;
+            continue;
+        }
+
         if let Some(path) = standalone_image_path(line) {
             parsed.push_item(
                 ContentItem::Image {
@@ -355,6 +532,66 @@ impl App {
         self.document_tables.get(table as usize)
     }
 
+    pub(crate) fn query_block(&self, query: u32) -> Option<&QueryBlock> {
+        self.document_queries.get(query as usize)
+    }
+
+    pub(crate) fn embed_block(&self, embed: u32) -> Option<&EmbedBlock> {
+        self.document_embeds.get(embed as usize)
+    }
+
+    /// Read every note's frontmatter once per catalog generation so query
+    /// blocks see the vault as of the last catalog change.
+    pub(crate) fn ensure_frontmatter_corpus(&mut self) {
+        if self
+            .frontmatter_corpus
+            .as_ref()
+            .is_some_and(|(generation, _)| *generation == self.catalog_generation)
+        {
+            return;
+        }
+        let root = self.vault.root();
+        let notes: Arc<[QueryNote]> = self
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(index, note)| QueryNote {
+                title: note.title.clone(),
+                path: note
+                    .file_path
+                    .as_deref()
+                    .map(|path| path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_default(),
+                link: self.get_wiki_path_for_note(index).unwrap_or_else(|| note.title.clone()),
+                frontmatter: self.vault.load_frontmatter(note.id).ok().flatten(),
+            })
+            .collect();
+        self.frontmatter_corpus = Some((self.catalog_generation, notes));
+    }
+
+    /// Carry a corpus built at `previous_generation` forward after a save,
+    /// re-reading only the saved note's frontmatter.
+    pub(crate) fn refresh_frontmatter_corpus_entry(&mut self, note_id: NoteId, previous_generation: u64) {
+        if self
+            .frontmatter_corpus
+            .as_ref()
+            .is_none_or(|(generation, _)| *generation != previous_generation)
+        {
+            return;
+        }
+        let index = self.note_index_for_id(note_id);
+        let frontmatter = self.vault.load_frontmatter(note_id).ok().flatten();
+        let Some((generation, notes)) = self.frontmatter_corpus.as_mut() else {
+            return;
+        };
+        let Some(entry) = index.and_then(|index| Arc::make_mut(notes).get_mut(index)) else {
+            self.frontmatter_corpus = None;
+            return;
+        };
+        entry.frontmatter = frontmatter;
+        *generation = self.catalog_generation;
+    }
+
     pub fn update_outline(&mut self) {
         if !self.outline.is_empty() {
             self.outline_state.select(Some(0));
@@ -373,6 +610,8 @@ impl App {
         }
         self.content_items.clear();
         self.document_tables.clear();
+        self.document_queries.clear();
+        self.document_embeds.clear();
         self.document_links.clear();
         self.document_link_ranges.clear();
         self.outline.clear();
@@ -382,6 +621,9 @@ impl App {
         self.details_open_states.clear();
         self.heading_fold_states.clear();
 
+        if self.active_document.as_ref().is_some_and(|document| document.body().contains("```query")) {
+            self.ensure_frontmatter_corpus();
+        }
         if let Some(document) = self.active_document.as_ref() {
             let (frontmatter, content_start_line) = self
                 .current_note()
@@ -394,9 +636,18 @@ impl App {
                 self.frontmatter_hidden,
                 self.config.show_tags,
                 &|target| self.wiki_link_exists(target),
+                &ComputedBlocks {
+                    query: &|source| match self.frontmatter_corpus.as_ref() {
+                        Some((_, notes)) => ekphos_vault::query::run_query(source, notes),
+                        None => Err("vault catalog unavailable".to_owned()),
+                    },
+                    embed: &|target, id| self.embedded_block(target, id),
+                },
             );
             self.content_items = parsed.items;
             self.document_tables = parsed.tables;
+            self.document_queries = parsed.queries;
+            self.document_embeds = parsed.embeds;
             self.outline = parsed.outline;
             self.document_links = parsed.links;
             self.document_link_ranges = parsed.link_ranges;
@@ -1386,11 +1637,16 @@ impl App {
 mod phase6_tests {
     use super::*;
 
+    const NO_COMPUTED: ComputedBlocks = ComputedBlocks {
+        query: &|_| Err(String::new()),
+        embed: &|_, _| Err(String::new()),
+    };
+
     #[test]
     fn shared_pass_emits_ranges_outline_links_and_one_table_metadata_owner() {
         let source = "# Head e\u{301}\nText [link](https://example.test) and [[Head]].\n- [x] task 😀\n| name | value |\n|:-----|------:|\n| 日本 | [open](target.md) |\n```rust\nlet x = 1;\n```\n<details>\n<summary>More</summary>\ninside\n</details>\n![image](image.png)\n";
         let document = DocumentSnapshot::new(Arc::from(source));
-        let parsed = parse_document(&document, None, 0, true, true, &|target| target == "Head");
+        let parsed = parse_document(&document, None, 0, true, true, &|target| target == "Head", &NO_COMPUTED);
 
         assert_eq!(parsed.outline.len(), 1);
         assert_eq!(parsed.outline[0].source_line, 0);
@@ -1430,7 +1686,7 @@ mod phase6_tests {
             tags: vec![Box::<str>::from("one")].into_boxed_slice(),
             date: Some(Box::from("2026-08-21")),
         };
-        let parsed = parse_document(&document, Some(&frontmatter), 4, false, true, &|_| false);
+        let parsed = parse_document(&document, Some(&frontmatter), 4, false, true, &|_| false, &NO_COMPUTED);
         let values: Vec<(&str, &str)> = parsed
             .items
             .iter()
@@ -1442,3 +1698,69 @@ mod phase6_tests {
         assert_eq!(values, [("tags", "[one]"), ("date", "2026-08-21")]);
     }
 }
+
+#[cfg(test)]
+mod query_tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn query_rows(app: &App) -> Vec<String> {
+        app.content_items
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::QueryRow { query, row, .. } => {
+                    let block = app.query_block(*query)?;
+                    Some(match row {
+                        QueryRowKind::Separator => "---".to_owned(),
+                        QueryRowKind::Message => block.message.as_deref().unwrap_or_default().to_owned(),
+                        _ => block.cells(*row).join(" | "),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn query_block_renders_matching_notes_and_follows_catalog_changes() {
+        let vault = TempVault::with_files(&[
+            ("Alpha.md", "---\ntags: [project]\nstatus: active\npriority: 2\n---\nAlpha\n"),
+            ("Beta.md", "---\ntags: [project]\nstatus: done\npriority: 1\n---\nBeta\n"),
+            (
+                "Dashboard.md",
+                "# Projects\n```query\ntable status, priority\ntag project\nwhere status != done\nsort priority\n```\n\n```query\nfrom nowhere\n```\n",
+            ),
+        ]);
+        let notes = vault.notes();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&notes.join("Dashboard.md")));
+        app.update_content_items();
+
+        assert_eq!(
+            query_rows(&app),
+            [
+                "Note | status | priority",
+                "---",
+                "[[Alpha]] | active | 2",
+                "query error: line 1: unknown clause `from`"
+            ]
+        );
+        assert!(app
+            .document_links
+            .iter()
+            .any(|link| matches!(link, LinkInfo::Wiki { target, is_valid: true, .. } if target == "Alpha")));
+        assert!(app.content_items.iter().all(|item| !matches!(item, ContentItem::CodeLine { .. })));
+
+        // A save re-reads only the saved note, so this edit from outside
+        // stays unseen until the next catalog reload.
+        vault.write("Alpha.md", "---\ntags: [project]\nstatus: done\npriority: 2\n---\nAlpha\n");
+        let beta = app.notes.iter().find(|note| note.title == "Beta").unwrap().id;
+        assert!(app.persist_note_body(beta, "---\ntags: [project]\nstatus: active\npriority: 1\n---\nBeta\n".to_owned()));
+        assert!(app.select_note_by_path(&notes.join("Dashboard.md")));
+        app.update_content_items();
+        assert_eq!(
+            query_rows(&app)[..4],
+            ["Note | status | priority", "---", "[[Beta]] | active | 1", "[[Alpha]] | active | 2"]
+        );
+    }
+}
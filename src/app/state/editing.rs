@@ -36,6 +36,10 @@ impl App {
             self.content_items.shrink_to_fit();
             self.document_tables.clear();
             self.document_tables.shrink_to_fit();
+            self.document_queries.clear();
+            self.document_queries.shrink_to_fit();
+            self.document_embeds.clear();
+            self.document_embeds.shrink_to_fit();
             self.document_links.clear();
             self.document_links.shrink_to_fit();
             self.document_link_ranges.clear();
@@ -84,6 +88,7 @@ impl App {
                 Some(self.theme.editor.italic),
             );
             self.editor.set_frontmatter_color(self.theme.content.frontmatter);
+            self.load_git_baseline();
 
             self.editor.set_cursor(target_row, 0);
             for source_line in 0..self.editor.line_count() {
@@ -216,6 +221,26 @@ impl App {
         self.editor.set_cursor_line_style(Style::default());
     }
 
+    /// Replace the whole buffer of `note_id` with an earlier version as one
+    /// undoable edit, entering edit mode if needed. Nothing is written until
+    /// the note is saved.
+    pub(super) fn restore_note_lines(&mut self, note_id: NoteId, lines: Vec<String>, label: &str) {
+        if self.current_note().map(|note| note.id) != Some(note_id) {
+            return;
+        }
+        if self.mode != Mode::Edit {
+            self.enter_edit_mode();
+            if self.mode != Mode::Edit {
+                return;
+            }
+        }
+        let last = self.editor.line_count().saturating_sub(1);
+        self.editor.replace_lines(0, last, lines);
+        self.editor.set_cursor(0, 0);
+        self.update_editor_highlights();
+        self.vim.status_message = Some(format!("Restored {label} — :w to save, u to undo"));
+    }
+
     pub fn save_edit(&mut self) {
         // Clear search state and vim state when exiting edit mode
         self.end_buffer_search();
@@ -231,7 +256,12 @@ impl App {
         let cursor_offset_from_top = cursor_row.saturating_sub(editor_scroll);
 
         let content = self.editor.lines().join("\n");
-        if !self.persist_active_body(content) {
+        let note_id = self.current_note().map(|note| note.id);
+        let previous = self
+            .current_note()
+            .and_then(|note| note.file_path.as_ref())
+            .and_then(|path| fs::read_to_string(path).ok());
+        if !self.persist_active_body(content.clone()) {
             return;
         }
 
@@ -250,6 +280,10 @@ impl App {
         self.content_cursor = self.content_cursor_for_source_line(cursor_row);
         let preview_scroll = self.content_cursor.saturating_sub(cursor_offset_from_top);
         self.content_scroll_offset = preview_scroll + 1;
+
+        if let (Some(note_id), Some(previous)) = (note_id, previous) {
+            self.offer_heading_link_updates(note_id, &previous, &content);
+        }
     }
 
     pub fn cancel_edit(&mut self) {
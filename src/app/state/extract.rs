@@ -0,0 +1,269 @@
+use super::*;
+
+use ekphos_core::markdown::{fence_marker, heading, heading_section};
+
+impl App {
+    /// Extract the heading section under the cursor into a new note titled
+    /// after the heading.
+    pub fn extract_section_to_note(&mut self) {
+        let was_editing = self.mode == Mode::Edit;
+        if !was_editing {
+            self.enter_edit_mode();
+            if self.mode != Mode::Edit {
+                return;
+            }
+        }
+        if !self.extract_to_note(None, None) && !was_editing {
+            self.cancel_edit();
+        }
+    }
+
+    /// Move `rows` (inclusive), or the heading section under the cursor, into
+    /// a new note next to the current one and leave a link in their place.
+    /// Links to headings inside the moved text follow it. The source note is
+    /// saved so the vault stays consistent. Returns `false` after reporting
+    /// why nothing happened.
+    pub fn extract_to_note(&mut self, rows: Option<(usize, usize)>, title: Option<String>) -> bool {
+        let lines: Vec<String> = self.editor.lines().into_iter().map(str::to_string).collect();
+        let (start, end) = match rows {
+            Some((start, end)) => (start.min(end), start.max(end).min(lines.len().saturating_sub(1))),
+            None => {
+                let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+                match heading_section(&refs, self.editor.cursor().0) {
+                    Some(section) => (section.start, section.end - 1),
+                    None => {
+                        self.show_error_toast("No heading above the cursor; select lines and use :extract Title");
+                        return false;
+                    }
+                }
+            }
+        };
+        let Some((source_title, source_path)) = self.current_note().and_then(|note| Some((note.title.clone(), note.file_path.clone()?))) else {
+            return false;
+        };
+
+        let top = heading(&lines[start]).map(|heading| (heading.level, heading.text.to_string()));
+        let Some(title) = title
+            .or_else(|| top.as_ref().map(|(_, text)| text.clone()))
+            .filter(|title| !title.trim().is_empty())
+        else {
+            self.show_error_toast("Name the new note: :extract Title");
+            return false;
+        };
+        let title = title.trim().to_string();
+        if title.contains(['/', '\\', '[', ']', '#', '|']) {
+            self.show_error_toast(format!("\"{title}\" cannot be used in a wiki link"));
+            return false;
+        }
+        let new_path = source_path.parent().unwrap_or(self.vault.root()).join(format!("{title}.md"));
+        if new_path.exists() {
+            self.show_error_toast(format!("A note named \"{title}\" already exists"));
+            return false;
+        }
+
+        let content = extracted_body(&lines[start..=end], top.as_ref().map(|(level, _)| *level));
+        let note = match self.extract_note_content(&title, &source_title, &content) {
+            Ok(note) => note,
+            Err(error) => {
+                self.show_error_toast(error);
+                return false;
+            }
+        };
+        if let Err(error) = fs::write(&new_path, note) {
+            self.show_error_toast(format!("Could not create {title}: {error}"));
+            return false;
+        }
+
+        let link = if self.config.extract.embed {
+            format!("![[{title}]]")
+        } else {
+            format!("[[{title}]]")
+        };
+        let mut replacement = vec![link];
+        if end + 1 < lines.len() && lines[end].trim().is_empty() {
+            replacement.push(String::new());
+        }
+
+        // Headings that moved, lowercased; the top one becomes the note itself.
+        let mut moved_headings: Vec<String> = Vec::new();
+        let mut fence = None;
+        for line in &lines[start..=end] {
+            if let Some(marker) = fence_marker(line) {
+                fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+            } else if fence.is_none() {
+                moved_headings.extend(heading(line).map(|heading| heading.text.to_lowercase()));
+            }
+        }
+        let top_heading = top.as_ref().map(|(_, text)| text.to_lowercase());
+        let source_wiki = Self::calculate_wiki_path(&source_path, &self.config.notes_path()).to_lowercase();
+        let source_title_lower = source_title.to_lowercase();
+        let retarget = |target: &str, suffix: &str, own_note: bool| -> Option<String> {
+            let target = target.to_lowercase();
+            let points_at_source = target == source_wiki || target == source_title_lower || (own_note && target.is_empty());
+            if !points_at_source {
+                return None;
+            }
+            let fragment = suffix.strip_prefix('#')?;
+            let (anchor, alias) = fragment
+                .split_once('|')
+                .map_or((fragment, ""), |(anchor, _)| (anchor, &fragment[anchor.len()..]));
+            let anchor_lower = anchor.trim().to_lowercase();
+            if !moved_headings.contains(&anchor_lower) {
+                return None;
+            }
+            Some(if top_heading.as_deref() == Some(anchor_lower.as_str()) {
+                format!("{title}{alias}")
+            } else {
+                format!("{title}#{anchor}{alias}")
+            })
+        };
+
+        // Retarget links left in this note too, then swap in the extracted
+        // rows and any rewritten ones around them as a single undo step.
+        let buffer = [&lines[..start], &replacement[..], &lines[end + 1..]].concat().join("\n");
+        let rewritten = Self::rewrite_wiki_links(&buffer, |target, suffix| retarget(target, suffix, true));
+        let new_lines: Vec<String> = rewritten.split('\n').map(str::to_string).collect();
+        let (first, last, rows) = changed_rows(&lines, &new_lines, start, end);
+        self.editor.replace_lines(first, last, rows);
+        self.save_edit();
+        self.run_hook(HookEvent::Create, &new_path, None);
+        self.rewrite_wiki_links_in_vault(Some(&source_path), |target, suffix| retarget(target, suffix, false));
+
+        self.load_notes_from_dir();
+        self.show_toast(format!("Extracted to [[{title}]]"), ToastKind::Success);
+        true
+    }
+
+    /// The new note's text, from the `[extract]` template when one is set.
+    fn extract_note_content(&self, title: &str, source: &str, content: &str) -> Result<String, String> {
+        let Some(template) = self.config.extract.template.as_deref().map(str::trim).filter(|template| !template.is_empty()) else {
+            return Ok(format!("# {title}\n\n{content}\n"));
+        };
+        let template_path = self.config.notes_path().join(template);
+        let template = fs::read_to_string(&template_path).map_err(|error| format!("Could not read template {}: {error}", template_path.display()))?;
+        let rendered = template
+            .replace("{{title}}", title)
+            .replace("{{source}}", source)
+            .replace("{{date}}", &self.dependencies.clock.today().format("%Y-%m-%d").to_string());
+        if rendered.contains("{{content}}") {
+            return Ok(rendered.replace("{{content}}", content));
+        }
+        let separator = if rendered.is_empty() || rendered.ends_with("\n\n") {
+            ""
+        } else if rendered.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        Ok(format!("{rendered}{separator}{content}\n"))
+    }
+}
+
+/// The rows `first..=last` of `old` and what replaces them to give `new`.
+/// Always covers `start..=end`; beyond that only rows that differ.
+fn changed_rows(old: &[String], new: &[String], start: usize, end: usize) -> (usize, usize, Vec<String>) {
+    let first = old.iter().zip(new).take(start).take_while(|(old, new)| old == new).count();
+    let kept_tail = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(old.len() - 1 - end)
+        .take_while(|(old, new)| old == new)
+        .count();
+    (first, old.len() - 1 - kept_tail, new[first..new.len() - kept_tail].to_vec())
+}
+
+/// Moved lines without the section heading, which becomes the new title.
+/// Subheadings are promoted so the section's children become `##`.
+fn extracted_body(lines: &[String], top_level: Option<usize>) -> String {
+    let (lines, promote) = match top_level {
+        Some(level) => (&lines[1..], level - 1),
+        None => (lines, 0),
+    };
+    let mut fence = None;
+    let body: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            if let Some(marker) = fence_marker(line) {
+                fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+                return line.clone();
+            }
+            match heading(line) {
+                Some(heading) if fence.is_none() && promote > 0 => {
+                    format!("{}{}", "#".repeat(heading.level.saturating_sub(promote).max(1)), &line[heading.level..])
+                }
+                _ => line.clone(),
+            }
+        })
+        .collect();
+    let first = body.iter().position(|line| !line.trim().is_empty()).unwrap_or(body.len());
+    let last = body.iter().rposition(|line| !line.trim().is_empty()).map_or(first, |last| last + 1);
+    body[first..last].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[
+            (
+                "big.md",
+                "# Big\n\nSee [[#Ideas]].\n\n## Ideas\n\nfirst\n\n### Later\n\nsecond\n\n## Other\n\nrest\n",
+            ),
+            ("index.md", "[[Big#Ideas|ideas]] and [[big#Later]] and [[Big#Other]]\n"),
+        ])
+    }
+
+    #[test]
+    fn extracting_a_section_moves_it_and_follows_heading_links() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("big.md")));
+        app.enter_edit_mode();
+        app.editor.set_cursor(6, 0);
+
+        assert!(app.extract_to_note(None, None));
+        assert_eq!(app.mode, Mode::Normal);
+        let read = |name: &str| fs::read_to_string(vault.notes().join(name)).unwrap();
+        assert_eq!(read("Ideas.md"), "# Ideas\n\nfirst\n\n## Later\n\nsecond\n");
+        assert_eq!(read("big.md"), "# Big\n\nSee [[Ideas]].\n\n[[Ideas]]\n\n## Other\n\nrest");
+        assert_eq!(read("index.md"), "[[Ideas|ideas]] and [[Ideas#Later]] and [[Big#Other]]\n");
+        assert!(app.wiki_link_exists("Ideas"));
+    }
+
+    #[test]
+    fn extracting_lines_needs_a_title_and_keeps_headings() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("big.md")));
+        app.enter_edit_mode();
+
+        assert!(!app.extract_to_note(Some((14, 14)), None));
+        assert!(app.extract_to_note(Some((14, 14)), Some("Rest".to_string())));
+        assert_eq!(fs::read_to_string(vault.notes().join("Rest.md")).unwrap(), "# Rest\n\nrest\n");
+    }
+
+    #[test]
+    fn extraction_is_one_undo_step_and_keeps_trailing_blank_lines() {
+        let vault = TempVault::with_files(&[("big.md", "# Big\n\nSee [[#Ideas]].\n\n## Ideas\n\nfirst\n\n## Other\n\nrest\n\n")]);
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("big.md")));
+        app.enter_edit_mode();
+        let original: Vec<String> = app.editor.lines().into_iter().map(str::to_string).collect();
+        app.editor.set_cursor(4, 0);
+        assert!(app.extract_to_note(None, None));
+        let extracted = fs::read_to_string(vault.notes().join("big.md")).unwrap();
+        assert_eq!(extracted, "# Big\n\nSee [[Ideas]].\n\n[[Ideas]]\n\n## Other\n\nrest\n");
+
+        let extracted: Vec<String> = extracted.split('\n').map(str::to_string).collect();
+        let (first, last, rows) = changed_rows(&original, &extracted, 4, 7);
+        assert_eq!((first, last), (2, 7));
+        let mut editor = Editor::new(original.clone());
+        editor.replace_lines(first, last, rows);
+        assert_eq!(editor.lines(), extracted);
+        editor.undo();
+        assert_eq!(editor.lines(), original);
+    }
+}
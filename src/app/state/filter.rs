@@ -0,0 +1,157 @@
+use super::*;
+
+use crate::shell::{ShellCommand, ShellJob};
+
+/// Where a finished filter writes its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterTarget {
+    /// Replace rows `start..=end` (`!{motion}`, `:{range}!`).
+    Replace { start: usize, end: usize },
+    /// Insert before `row` (`:r !`).
+    Insert { row: usize },
+}
+
+/// An external command running on behalf of the editor.
+pub(crate) struct FilterJob {
+    job: ShellJob,
+    target: FilterTarget,
+    command: String,
+}
+
+/// Output lines without the final newline most commands print.
+fn output_lines(stdout: &str) -> Vec<String> {
+    let stdout = stdout.strip_suffix('\n').unwrap_or(stdout);
+    if stdout.is_empty() {
+        return Vec::new();
+    }
+    stdout.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line).to_string()).collect()
+}
+
+impl App {
+    /// Pipe rows `start..=end` through `command`, replacing them once it
+    /// finishes.
+    pub fn start_filter(&mut self, start: usize, end: usize, command: String) {
+        let lines = self.editor.lines();
+        let end = end.min(lines.len().saturating_sub(1));
+        let mut input = lines[start.min(end)..=end].join("\n");
+        input.push('\n');
+        self.spawn_filter(FilterTarget::Replace { start, end }, command, Some(input));
+    }
+
+    /// Insert the output of `command` below the cursor line (`:r !cmd`).
+    pub fn start_read_command(&mut self, command: String) {
+        let row = self.editor.cursor().0 + 1;
+        self.spawn_filter(FilterTarget::Insert { row }, command, None);
+    }
+
+    fn spawn_filter(&mut self, target: FilterTarget, command: String, stdin: Option<String>) {
+        if self.filter_job.is_some() {
+            self.vim.status_message = Some("A command is already running".to_string());
+            return;
+        }
+        let job = ShellJob::spawn(ShellCommand {
+            stdin,
+            cwd: Some(self.vault.root().to_path_buf()),
+            ..ShellCommand::new(command.clone())
+        });
+        self.vim.status_message = Some(format!("Running `{command}`… (Esc/Ctrl+C to cancel)"));
+        self.filter_job = Some(FilterJob { job, target, command });
+    }
+
+    pub fn cancel_filter_job(&mut self) {
+        if let Some(filter) = &self.filter_job {
+            filter.job.cancel();
+        }
+    }
+
+    /// Apply a finished filter. Returns `true` when the screen needs a redraw.
+    pub fn poll_filter_job(&mut self) -> bool {
+        let Some(output) = self.filter_job.as_ref().and_then(|filter| filter.job.try_finish()) else {
+            return false;
+        };
+        let Some(FilterJob { target, command, .. }) = self.filter_job.take() else {
+            return false;
+        };
+        self.vim.status_message = None;
+
+        if let Some(reason) = output.failure_message() {
+            if output.status == crate::shell::ShellStatus::Cancelled {
+                self.vim.status_message = Some(format!("Cancelled `{command}`"));
+            } else {
+                self.show_error_toast(format!("`{command}`: {reason}"));
+            }
+            return true;
+        }
+        // The user may have left the editor while the command ran.
+        if self.mode != Mode::Edit {
+            return true;
+        }
+
+        let lines = output_lines(&output.stdout);
+        match target {
+            FilterTarget::Replace { start, end } => {
+                let count = lines.len();
+                self.editor.replace_lines(start, end, lines);
+                self.vim.status_message = Some(format!("{count} lines filtered"));
+            }
+            FilterTarget::Insert { row } => self.editor.insert_lines(row, lines),
+        }
+        self.update_editor_highlights();
+        let stderr = output.stderr.trim();
+        if !stderr.is_empty() {
+            self.show_error_toast(format!("`{command}`: {stderr}"));
+        }
+        true
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+    use std::time::{Duration, Instant};
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[("list.md", "# List\npear\napple\nfig\n")])
+    }
+
+    fn finish(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !app.poll_filter_job() {
+            assert!(Instant::now() < deadline, "filter did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn filter_replaces_lines_as_one_undo_step() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        app.enter_edit_mode();
+        app.start_filter(1, 3, "sort".to_string());
+        finish(&mut app);
+        assert_eq!(app.editor.lines()[..4], ["# List", "apple", "fig", "pear"]);
+
+        app.start_read_command("echo added".to_string());
+        finish(&mut app);
+        assert_eq!(app.editor.lines()[..3], ["# List", "apple", "added"]);
+
+        app.editor.undo();
+        app.editor.undo();
+        assert_eq!(app.editor.lines()[..4], ["# List", "pear", "apple", "fig"]);
+    }
+
+    #[test]
+    fn failing_filter_leaves_buffer_and_reports_stderr() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        app.enter_edit_mode();
+        app.start_filter(1, 3, "echo broken >&2; exit 1".to_string());
+        finish(&mut app);
+        assert_eq!(app.editor.lines()[..4], ["# List", "pear", "apple", "fig"]);
+        assert_eq!(
+            app.toast.as_ref().map(|toast| toast.message.as_str()),
+            Some("`echo broken >&2; exit 1`: broken")
+        );
+    }
+}
@@ -0,0 +1,602 @@
+use super::*;
+
+use std::path::Path;
+use std::sync::mpsc::TryRecvError;
+
+use crate::git::{FileStatus, GitCommit, GitRepo};
+use ekphos_core::diff::{diff_lines, line_changes};
+use ekphos_editor::LineChange;
+
+type StatusResult = Result<HashMap<PathBuf, FileStatus>, String>;
+
+/// A finished git command that was run off the event loop.
+enum GitReply {
+    Committed {
+        message: String,
+        result: Result<String, String>,
+    },
+    Log {
+        note_id: NoteId,
+        title: String,
+        current: Vec<String>,
+        result: Result<Vec<GitCommit>, String>,
+    },
+    Version {
+        hash: String,
+        result: Result<String, String>,
+    },
+    Baseline {
+        generation: u64,
+        lines: Option<Vec<String>>,
+    },
+    Signs {
+        generation: u64,
+        signs: Vec<Option<LineChange>>,
+    },
+}
+
+/// Git integration for a vault that lives inside a repository.
+pub(crate) struct GitState {
+    repo: GitRepo,
+    /// Canonical vault root. `git` reports canonical paths; they are rebased
+    /// onto `vault.root()` so they compare equal to note paths.
+    vault_root: PathBuf,
+    status: HashMap<PathBuf, FileStatus>,
+    /// Folders containing at least one changed file.
+    changed_folders: HashSet<PathBuf>,
+    refresh: Option<Receiver<StatusResult>>,
+    /// A refresh was requested while another was running.
+    refresh_queued: bool,
+    /// `HEAD` lines of the note in the editor, compared for gutter signs.
+    baseline: Option<Arc<[String]>>,
+    /// Bumped per baseline load so a reply for a note no longer being edited
+    /// is dropped.
+    baseline_generation: u64,
+    replies: Receiver<GitReply>,
+    reply_sender: Sender<GitReply>,
+    /// Commands started and not yet replied to.
+    running: usize,
+    committing: bool,
+    /// Gutter signs are being diffed; at most one diff runs at a time.
+    signs_running: bool,
+    /// The buffer changed while signs were being diffed.
+    signs_queued: bool,
+}
+
+impl GitState {
+    /// Run `job` on a background thread; `poll_git_jobs` applies its reply.
+    fn spawn(&mut self, job: impl FnOnce(GitRepo) -> GitReply + Send + 'static) {
+        let repo = self.repo.clone();
+        let sender = self.reply_sender.clone();
+        self.running += 1;
+        std::thread::spawn(move || {
+            let _ = sender.send(job(repo));
+        });
+    }
+}
+
+impl App {
+    /// Look for a repository around the vault. Called on every vault load;
+    /// the existing state is kept when the vault has not moved.
+    pub(super) fn discover_git_repo(&mut self) {
+        if !self.config.git_integration {
+            self.git = None;
+            return;
+        }
+        let Ok(vault_root) = self.vault.root().canonicalize() else {
+            self.git = None;
+            return;
+        };
+        if self.git.as_ref().is_none_or(|git| git.vault_root != vault_root) {
+            self.git = GitRepo::discover(&vault_root).map(|repo| {
+                let (reply_sender, replies) = mpsc::channel();
+                GitState {
+                    repo,
+                    vault_root,
+                    status: HashMap::new(),
+                    changed_folders: HashSet::new(),
+                    refresh: None,
+                    refresh_queued: false,
+                    baseline: None,
+                    baseline_generation: 0,
+                    replies,
+                    reply_sender,
+                    running: 0,
+                    committing: false,
+                    signs_running: false,
+                    signs_queued: false,
+                }
+            });
+        }
+        self.refresh_git_status();
+    }
+
+    /// Re-read `git status` on a background thread.
+    pub fn refresh_git_status(&mut self) {
+        let Some(git) = &mut self.git else {
+            return;
+        };
+        if git.refresh.is_some() {
+            git.refresh_queued = true;
+            return;
+        }
+        let repo = git.repo.clone();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(repo.status());
+        });
+        git.refresh = Some(receiver);
+    }
+
+    /// Apply a finished status refresh. Returns `true` when the sidebar needs
+    /// a redraw.
+    pub fn poll_git_status(&mut self) -> bool {
+        let vault_root = self.vault.root().to_path_buf();
+        let Some(git) = &mut self.git else {
+            return false;
+        };
+        let result = match git.refresh.as_ref().map(Receiver::try_recv) {
+            Some(Ok(result)) => result,
+            Some(Err(TryRecvError::Disconnected)) => Err("git status stopped unexpectedly".to_string()),
+            Some(Err(TryRecvError::Empty)) | None => return false,
+        };
+        git.refresh = None;
+
+        let updated = match result {
+            Ok(status) => {
+                git.status = status
+                    .into_iter()
+                    .filter_map(|(path, status)| Some((vault_root.join(path.strip_prefix(&git.vault_root).ok()?), status)))
+                    .collect();
+                git.changed_folders = git
+                    .status
+                    .keys()
+                    .flat_map(|path| {
+                        path.ancestors()
+                            .skip(1)
+                            .take_while(|folder| folder.starts_with(&vault_root) && *folder != vault_root)
+                    })
+                    .map(Path::to_path_buf)
+                    .collect();
+                true
+            }
+            // Status is advisory; keep the last known markers.
+            Err(_) => false,
+        };
+        if std::mem::take(&mut git.refresh_queued) {
+            self.refresh_git_status();
+        }
+        updated
+    }
+
+    /// Apply finished commit, history and baseline commands. Returns `true`
+    /// when something on screen may have changed.
+    pub fn poll_git_jobs(&mut self) -> bool {
+        let Some(git) = &mut self.git else {
+            return false;
+        };
+        let replies: Vec<GitReply> = git.replies.try_iter().collect();
+        git.running -= replies.len();
+        let changed = !replies.is_empty();
+        for reply in replies {
+            self.apply_git_reply(reply);
+        }
+        changed
+    }
+
+    fn apply_git_reply(&mut self, reply: GitReply) {
+        match reply {
+            GitReply::Committed { message, result } => {
+                if let Some(git) = &mut self.git {
+                    git.committing = false;
+                }
+                match result {
+                    Ok(hash) => {
+                        if self.dialog == DialogState::GitCommit {
+                            self.input_buffer.clear();
+                            self.dialog_error = None;
+                            self.dialog = DialogState::None;
+                        }
+                        self.show_toast(format!("Committed {hash}: {message}"), ToastKind::Success);
+                        self.refresh_git_status();
+                        if self.mode == Mode::Edit {
+                            self.load_git_baseline();
+                        }
+                    }
+                    Err(error) if self.dialog == DialogState::GitCommit => self.dialog_error = Some(error),
+                    Err(error) => self.show_error_toast(format!("git commit failed: {error}")),
+                }
+            }
+            GitReply::Log {
+                note_id,
+                title,
+                current,
+                result,
+            } => {
+                // Dropped when the user has moved on while `git log` ran.
+                if self.dialog != DialogState::None || self.current_note().map(|note| note.id) != Some(note_id) {
+                    return;
+                }
+                let commits = match result {
+                    Ok(commits) => commits,
+                    Err(error) => {
+                        self.show_error_toast(format!("git log failed: {error}"));
+                        return;
+                    }
+                };
+                if commits.is_empty() {
+                    self.show_toast(format!("\"{title}\" has no commits yet"), ToastKind::Info);
+                    return;
+                }
+                self.git_history = Some(GitHistoryState {
+                    note_id,
+                    title,
+                    commits,
+                    selected: 0,
+                    list_scroll: 0,
+                    diff_scroll: 0,
+                    version: Vec::new(),
+                    current,
+                    diff: Vec::new(),
+                    error: None,
+                    loading: false,
+                });
+                self.select_git_history_commit(0);
+                self.dialog = DialogState::GitHistory;
+            }
+            GitReply::Version { hash, result } => {
+                let Some(history) = &mut self.git_history else {
+                    return;
+                };
+                if history.commits.get(history.selected).is_none_or(|commit| commit.hash != hash) {
+                    return;
+                }
+                history.loading = false;
+                match result {
+                    Ok(content) => {
+                        history.version = content.lines().map(str::to_string).collect();
+                        history.diff = diff_lines(&history.version, &history.current);
+                    }
+                    Err(error) => history.error = Some(error),
+                }
+            }
+            GitReply::Baseline { generation, lines } => {
+                let Some(git) = &mut self.git else {
+                    return;
+                };
+                if git.baseline_generation != generation {
+                    return;
+                }
+                git.baseline = lines.map(Arc::from);
+                self.update_git_signs();
+            }
+            GitReply::Signs { generation, signs } => {
+                let Some(git) = &mut self.git else {
+                    return;
+                };
+                git.signs_running = false;
+                let queued = std::mem::take(&mut git.signs_queued);
+                if git.baseline_generation == generation {
+                    self.editor.set_gutter_signs(Some(signs));
+                }
+                if queued {
+                    self.update_git_signs();
+                }
+            }
+        }
+    }
+
+    pub fn git_has_background_work(&self) -> bool {
+        self.git.as_ref().is_some_and(|git| git.refresh.is_some() || git.running > 0)
+    }
+
+    /// A commit from the dialog is still running.
+    pub fn git_committing(&self) -> bool {
+        self.git.as_ref().is_some_and(|git| git.committing)
+    }
+
+    pub fn git_file_status(&self, path: &Path) -> Option<FileStatus> {
+        self.git.as_ref()?.status.get(path).copied()
+    }
+
+    /// Changed files inside the vault, as of the last status refresh.
+    pub fn git_change_count(&self) -> usize {
+        self.git.as_ref().map_or(0, |git| git.status.len())
+    }
+
+    pub fn git_folder_changed(&self, path: &Path) -> bool {
+        self.git.as_ref().is_some_and(|git| git.changed_folders.contains(path))
+    }
+
+    /// Load the committed version of the note being edited in the background
+    /// and show gutter signs against it once it arrives. Notes git does not
+    /// track get no signs, except new ones, which are all additions.
+    pub(super) fn load_git_baseline(&mut self) {
+        let path = self.current_note().and_then(|note| note.file_path.clone());
+        let status = path.as_deref().and_then(|path| self.git_file_status(path));
+        let Some(git) = &mut self.git else {
+            return;
+        };
+        git.baseline = None;
+        git.baseline_generation = git.baseline_generation.wrapping_add(1);
+        if let Some(path) = path {
+            let generation = git.baseline_generation;
+            git.spawn(move |repo| GitReply::Baseline {
+                generation,
+                lines: match repo.head_content(&path) {
+                    Some(content) => Some(content.lines().map(str::to_string).collect()),
+                    None => matches!(status, Some(FileStatus::Untracked | FileStatus::Added)).then(Vec::new),
+                },
+            });
+        }
+        self.editor.set_gutter_sign_styles(
+            Style::default().fg(self.theme.success),
+            Style::default().fg(self.theme.warning),
+            Style::default().fg(self.theme.error),
+        );
+        self.update_git_signs();
+    }
+
+    /// Recompute gutter signs after an edit. The diff runs in the
+    /// background; edits made meanwhile are folded into one more run.
+    pub(super) fn update_git_signs(&mut self) {
+        let Some(git) = &mut self.git else {
+            return;
+        };
+        let Some(baseline) = git.baseline.clone() else {
+            self.editor.set_gutter_signs(None);
+            return;
+        };
+        if git.signs_running {
+            git.signs_queued = true;
+            return;
+        }
+        git.signs_running = true;
+        let generation = git.baseline_generation;
+        let lines: Vec<String> = self.editor.lines().into_iter().map(str::to_string).collect();
+        git.spawn(move |_| GitReply::Signs {
+            generation,
+            signs: line_changes(&baseline, &lines),
+        });
+    }
+
+    pub fn open_git_commit_dialog(&mut self) {
+        let Some(git) = &self.git else {
+            self.show_error_toast("The vault is not in a git repository");
+            return;
+        };
+        if git.refresh.is_none() && git.status.is_empty() {
+            self.show_toast("Nothing to commit", ToastKind::Info);
+            return;
+        }
+        self.input_buffer.clear();
+        self.dialog_error = None;
+        self.dialog = DialogState::GitCommit;
+    }
+
+    /// Stage every change in the vault and commit it with `message` in the
+    /// background. Changes elsewhere in an enclosing repository are left
+    /// alone. The dialog closes once the commit lands, or shows its error.
+    pub fn commit_all(&mut self, message: &str) {
+        let message = message.trim().to_string();
+        if message.is_empty() {
+            self.dialog_error = Some("Commit message cannot be empty".to_string());
+            return;
+        }
+        let Some(git) = &mut self.git else {
+            return;
+        };
+        if git.committing {
+            return;
+        }
+        git.committing = true;
+        let vault_root = git.vault_root.clone();
+        git.spawn(move |repo| GitReply::Committed {
+            result: repo.commit_under(&vault_root, &message),
+            message,
+        });
+        self.dialog_error = None;
+    }
+
+    /// Open the history browser for the current note once `git log` returns.
+    pub fn open_git_history(&mut self) {
+        if self.git.is_none() {
+            self.show_error_toast("The vault is not in a git repository");
+            return;
+        }
+        let Some(note) = self.current_note() else {
+            return;
+        };
+        let Some(path) = note.file_path.clone() else {
+            return;
+        };
+        let (note_id, title) = (note.id, note.title.clone());
+        let current: Vec<String> = if self.mode == Mode::Edit {
+            self.editor.lines().into_iter().map(str::to_string).collect()
+        } else {
+            fs::read_to_string(&path).unwrap_or_default().lines().map(str::to_string).collect()
+        };
+        if let Some(git) = &mut self.git {
+            git.spawn(move |repo| GitReply::Log {
+                note_id,
+                title,
+                current,
+                result: repo.log(&path),
+            });
+        }
+    }
+
+    /// Select the `index`th commit and load its version in the background,
+    /// to be diffed against the note.
+    pub fn select_git_history_commit(&mut self, index: usize) {
+        let (Some(git), Some(history)) = (&mut self.git, &mut self.git_history) else {
+            return;
+        };
+        let Some(commit) = history.commits.get(index) else {
+            return;
+        };
+        history.selected = index;
+        history.diff_scroll = 0;
+        history.version.clear();
+        history.diff.clear();
+        history.error = None;
+        history.loading = true;
+        let (hash, path) = (commit.hash.clone(), commit.path.clone());
+        git.spawn(move |repo| GitReply::Version {
+            result: repo.content_at(&hash, &path),
+            hash,
+        });
+    }
+
+    pub fn close_git_history(&mut self) {
+        self.git_history = None;
+        self.dialog = DialogState::None;
+    }
+
+    /// Replace the editor buffer with the selected version as one undoable
+    /// edit. Nothing is written until the note is saved.
+    pub fn restore_git_version(&mut self) {
+        let Some(history) = self.git_history.take() else {
+            return;
+        };
+        self.dialog = DialogState::None;
+        if history.error.is_some() || history.loading {
+            return;
+        }
+        let Some(commit) = history.commits.get(history.selected) else {
+            return;
+        };
+        let label = format!("{} from {}", history.title, commit.short_hash);
+        self.restore_note_lines(history.note_id, history.version, &label);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+    use ekphos_editor::LineChange;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    /// A vault in `notes/` of a repository rooted one level up.
+    struct TempRepo {
+        vault: TempVault,
+    }
+
+    impl TempRepo {
+        /// A repository with a committed `notes/alpha.md`, or `None` when no
+        /// git binary is available.
+        fn new() -> Option<Self> {
+            let vault = TempVault::with_files(&[("alpha.md", "# Alpha\none\ntwo\n")]);
+            fs::create_dir_all(vault.notes().join("sub")).unwrap();
+            let repo = Self { vault };
+            repo.git(&["init", "--quiet"]).then_some(())?;
+            repo.git(&["config", "user.name", "Test"]);
+            repo.git(&["config", "user.email", "test@example.com"]);
+            repo.git(&["config", "commit.gpgsign", "false"]);
+            repo.git(&["add", "--all"]);
+            repo.git(&["commit", "--quiet", "-m", "Initial"]).then_some(repo)
+        }
+
+        fn git(&self, args: &[&str]) -> bool {
+            Command::new("git")
+                .arg("-C")
+                .arg(&self.vault.path)
+                .args(args)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        }
+
+        fn app(&self) -> App {
+            self.vault.app()
+        }
+    }
+
+    fn wait_for_git(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.git_has_background_work() {
+            assert!(Instant::now() < deadline, "git did not finish");
+            app.poll_git_status();
+            app.poll_git_jobs();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn status_marks_changed_notes_and_their_folders() {
+        let Some(repo) = TempRepo::new() else {
+            return;
+        };
+        fs::write(repo.vault.notes().join("sub/new.md"), "# New\n").unwrap();
+        let mut app = repo.app();
+        wait_for_git(&mut app);
+
+        let root = app.vault.root().to_path_buf();
+        assert_eq!(app.git_file_status(&root.join("sub/new.md")), Some(FileStatus::Untracked));
+        assert_eq!(app.git_file_status(&root.join("alpha.md")), None);
+        assert!(app.git_folder_changed(&root.join("sub")));
+
+        app.open_git_commit_dialog();
+        app.commit_all("Add new note");
+        wait_for_git(&mut app);
+        assert_eq!(app.dialog, DialogState::None);
+        assert_eq!(app.git_file_status(&root.join("sub/new.md")), None);
+        assert!(!app.git_folder_changed(&root.join("sub")));
+    }
+
+    #[test]
+    fn gutter_signs_follow_edits_and_restore_is_undoable() {
+        let Some(repo) = TempRepo::new() else {
+            return;
+        };
+        let mut app = repo.app();
+        wait_for_git(&mut app);
+        app.enter_edit_mode();
+        wait_for_git(&mut app);
+        app.editor.set_cursor(2, 0);
+        app.editor.insert_lines(3, vec!["three".to_string()]);
+        app.request_highlight_update();
+        wait_for_git(&mut app);
+        assert_eq!(app.editor.gutter_sign(3), Some(LineChange::Added));
+        assert_eq!(app.editor.gutter_sign(1), None);
+        app.save_edit();
+
+        app.open_git_history();
+        wait_for_git(&mut app);
+        let history = app.git_history.as_ref().unwrap();
+        assert_eq!(history.commits.len(), 1);
+        assert!(history.diff.contains(&ekphos_core::diff::DiffOp::Insert { new: 3 }));
+
+        app.restore_git_version();
+        assert_eq!(app.mode, Mode::Edit);
+        assert_eq!(app.editor.lines(), ["# Alpha", "one", "two"]);
+        app.editor.undo();
+        assert_eq!(app.editor.lines(), ["# Alpha", "one", "two", "three"]);
+    }
+
+    #[test]
+    fn commit_leaves_changes_outside_the_vault_alone() {
+        let Some(repo) = TempRepo::new() else {
+            return;
+        };
+        fs::write(repo.vault.path.join("outside.md"), "elsewhere\n").unwrap();
+        assert!(repo.git(&["add", "outside.md"]));
+        fs::remove_file(repo.vault.notes().join("alpha.md")).unwrap();
+        fs::write(repo.vault.notes().join("sub/new.md"), "# New\n").unwrap();
+        let mut app = repo.app();
+        wait_for_git(&mut app);
+
+        app.open_git_commit_dialog();
+        app.commit_all("Vault only");
+        wait_for_git(&mut app);
+        assert_eq!(app.dialog, DialogState::None);
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&repo.vault.path)
+            .args(["status", "--porcelain"])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "A  outside.md\n");
+    }
+}
@@ -1,29 +1,45 @@
 use super::*;
 
 const RETAINED_GRAPH_INDEX_BUDGET: usize = 16 * 1024 * 1024;
+const GRAPH_CENTRALITY_LIMIT: usize = 50;
+const LEGACY_GRAPH_PINS_FILE: &str = "graph_pins.json";
+const GRAPH_TIMELINE_FRAME: std::time::Duration = std::time::Duration::from_millis(60);
 
 impl App {
     pub fn start_graph_index_build(&mut self) {
         self.graph_index_generation = self.graph_index_generation.wrapping_add(1);
         let generation = self.graph_index_generation;
-        let sources: Vec<GraphSourceFile> = self
-            .notes
+        let sources = self.graph_source_files();
+        let cache_path = self.graph_cache_path("graph_index.bin");
+        self.graph_worker
+            .get_or_insert_with(GraphWorker::new)
+            .submit_build(generation, sources, self.config.graph.sources(), cache_path);
+
+        self.graph_index = None;
+        self.graph_layout_generation = self.graph_layout_generation.wrapping_add(1);
+        self.graph_view.global_positions = Vec::new();
+        self.graph_view.global_fingerprint = None;
+        self.graph_view.layout_pending = false;
+        self.graph_indexing = true;
+        self.graph_view.index_pending = true;
+    }
+
+    /// Build the link index on the calling thread, reusing and refreshing the
+    /// same on-disk cache as the background worker.
+    pub fn build_graph_index_blocking(&self) -> Option<GraphIndex> {
+        let cache_path = self.graph_cache_path("graph_index.bin");
+        GraphIndex::load_or_build(self.graph_source_files(), self.config.graph.sources(), &cache_path, || false).map(|outcome| outcome.index)
+    }
+
+    fn graph_source_files(&self) -> Vec<GraphSourceFile> {
+        self.notes
             .iter()
             .enumerate()
             .filter_map(|(note_index, note)| {
                 let absolute_path = note.file_path.clone()?;
                 let fingerprint = self.vault.fingerprint(note.id)?;
                 Some(GraphSourceFile {
-                    metadata: GraphSourceMetadata {
-                        note_id: note.id,
-                        title: note.title.clone(),
-                        path: self.get_wiki_path_for_note(note_index).unwrap_or_else(|| note.title.clone()),
-                        tags: note
-                            .frontmatter
-                            .as_ref()
-                            .map(|frontmatter| frontmatter.tags.iter().map(|tag| tag.to_string()).collect())
-                            .unwrap_or_default(),
-                    },
+                    metadata: self.graph_source_metadata(note_index),
                     absolute_path,
                     fingerprint: GraphFileFingerprint {
                         size: fingerprint.size,
@@ -31,23 +47,37 @@ impl App {
                     },
                 })
             })
-            .collect();
-        let cache_path = self.graph_cache_path("graph_index.bin");
-        self.graph_worker
-            .get_or_insert_with(GraphWorker::new)
-            .submit_build(generation, sources, cache_path);
+            .collect()
+    }
 
-        self.graph_index = None;
-        self.graph_layout_generation = self.graph_layout_generation.wrapping_add(1);
-        self.graph_view.global_positions = Vec::new();
-        self.graph_view.global_fingerprint = None;
-        self.graph_view.layout_pending = false;
-        self.graph_indexing = true;
-        self.graph_view.index_pending = true;
+    /// Graph index input for one note: its identity, wiki path, tags and aliases.
+    pub(super) fn graph_source_metadata(&self, note_index: usize) -> GraphSourceMetadata {
+        let note = &self.notes[note_index];
+        GraphSourceMetadata {
+            note_id: note.id,
+            title: note.title.clone(),
+            path: self.get_wiki_path_for_note(note_index).unwrap_or_else(|| note.title.clone()),
+            tags: note
+                .frontmatter
+                .as_ref()
+                .map(|frontmatter| frontmatter.tags.iter().map(|tag| tag.to_string()).collect())
+                .unwrap_or_default(),
+            aliases: self.note_aliases(note.id).iter().map(|alias| alias.to_string()).collect(),
+            date: note
+                .frontmatter
+                .as_ref()
+                .and_then(|frontmatter| frontmatter.date.as_deref())
+                .and_then(graph::parse_day)
+                .or_else(|| note.created_time.or(note.modified_time).and_then(graph::day_from_system_time)),
+        }
     }
 
     pub fn graph_has_background_work(&self) -> bool {
-        self.graph_indexing || self.graph_view.layout_pending || self.graph_worker.as_ref().is_some_and(GraphWorker::is_pending)
+        self.graph_indexing
+            || self.graph_view.layout_pending
+            || self.graph_view.centrality.as_ref().is_some_and(|panel| panel.loading)
+            || self.graph_view.timeline.as_ref().is_some_and(|timeline| timeline.playing)
+            || self.graph_worker.as_ref().is_some_and(GraphWorker::is_pending)
     }
 
     /// Poll the single managed graph worker. Generation checks prevent a reload
@@ -56,6 +86,16 @@ impl App {
         let Some(response) = self.graph_worker.as_ref().and_then(GraphWorker::try_take) else {
             return false;
         };
+        let ranked = matches!(response, GraphResponse::Centrality { .. });
+        let changed = self.apply_graph_response(response);
+        // Any other reply means a ranking waiting for the worker can start.
+        if !ranked {
+            self.submit_graph_centrality();
+        }
+        changed
+    }
+
+    fn apply_graph_response(&mut self, response: GraphResponse) -> bool {
         match response {
             GraphResponse::Index { generation, outcome } => {
                 if generation != self.graph_index_generation {
@@ -102,7 +142,22 @@ impl App {
                 }
                 true
             }
+            GraphResponse::Centrality { generation, entries } => {
+                if generation != self.graph_centrality_generation {
+                    return false;
+                }
+                let Some(panel) = self.graph_view.centrality.as_mut().filter(|panel| panel.loading) else {
+                    return false;
+                };
+                panel.entries = entries;
+                panel.loading = false;
+                true
+            }
             GraphResponse::Failed { generation } => {
+                if generation == self.graph_centrality_generation && self.graph_view.centrality.as_ref().is_some_and(|panel| panel.loading) {
+                    self.graph_view.centrality = None;
+                    self.show_error_toast("Could not rank the notes");
+                }
                 if generation == self.graph_index_generation {
                     self.graph_indexing = false;
                     self.graph_view.index_pending = false;
@@ -136,7 +191,7 @@ impl App {
         let cache_path = self.graph_cache_path("graph_layout.bin");
         self.graph_worker
             .get_or_insert_with(GraphWorker::new)
-            .submit_layout(generation, index, projection, cache_path);
+            .submit_layout(generation, index, projection, self.graph_view.pins.clone(), cache_path);
         self.graph_view.layout_pending = true;
     }
 
@@ -149,6 +204,7 @@ impl App {
         self.graph_view.index_pending = self.graph_index.is_none();
         self.graph_view.global_positions = Vec::new();
         self.graph_view.global_fingerprint = None;
+        self.graph_view.pins = self.load_graph_pins();
         if self.graph_index.is_none() && !self.graph_indexing {
             self.start_graph_index_build();
         }
@@ -163,7 +219,10 @@ impl App {
             self.graph_view.total_edges = 0;
             return;
         };
-        let filter = GraphFilter::parse(&self.graph_view.filter_query);
+        let mut filter = GraphFilter::parse(&self.graph_view.filter_query);
+        if let Some(timeline) = &self.graph_view.timeline {
+            filter = filter.dated_until(timeline.cursor);
+        }
         let root_note_id = self
             .notes
             .get(self.graph_view.root_note_index)
@@ -192,6 +251,7 @@ impl App {
                 if self.graph_view.global_positions.is_empty() {
                     graph::apply_global_seed_layout(&index, &mut projection.nodes);
                 }
+                self.graph_view.pins.apply(&index, &mut projection.nodes);
             }
         }
         let preferred = self
@@ -211,6 +271,184 @@ impl App {
         self.graph_view.edges = projection.edges;
         self.graph_view.dirty = refit;
         self.graph_view.needs_center = false;
+        self.update_graph_path();
+        self.assign_graph_groups();
+    }
+
+    /// Match every projected node against the colour groups, first match wins.
+    pub fn assign_graph_groups(&mut self) {
+        let groups: Vec<_> = self.config.graph.groups.iter().map(|group| GraphFilter::parse(&group.query)).collect();
+        let index = self.graph_index.clone();
+        self.graph_view.node_groups = self
+            .graph_view
+            .nodes
+            .iter()
+            .map(|node| {
+                let metadata = index.as_ref()?.metadata_for_note(node.note_id)?;
+                groups.iter().position(|filter| !filter.query.trim().is_empty() && filter.matches(metadata))
+            })
+            .collect();
+    }
+
+    pub fn open_graph_groups_editor(&mut self) {
+        self.graph_view.groups_editor = Some(GraphGroupsEditor::default());
+    }
+
+    pub fn add_graph_group(&mut self) {
+        const COLORS: [&str; 8] = ["info", "success", "secondary", "warning", "tag", "link", "primary", "error"];
+        let color = COLORS[self.config.graph.groups.len() % COLORS.len()].to_string();
+        self.config.graph.groups.push(GraphColorGroup { query: String::new(), color });
+        self.graph_view.groups_editor = Some(GraphGroupsEditor {
+            selected: self.config.graph.groups.len() - 1,
+            editing: Some(GraphGroupField::Query),
+            draft: String::new(),
+        });
+    }
+
+    pub fn edit_graph_group(&mut self, field: GraphGroupField) {
+        let Some(editor) = self.graph_view.groups_editor.as_mut() else {
+            return;
+        };
+        let Some(group) = self.config.graph.groups.get(editor.selected) else {
+            return;
+        };
+        editor.draft = match field {
+            GraphGroupField::Query => group.query.clone(),
+            GraphGroupField::Color => group.color.clone(),
+        };
+        editor.editing = Some(field);
+    }
+
+    /// Apply the field being edited. Invalid colours and empty queries keep
+    /// the field open so the draft is not lost.
+    pub fn commit_graph_group_edit(&mut self) {
+        let Some(editor) = self.graph_view.groups_editor.as_mut() else {
+            return;
+        };
+        let (Some(field), Some(group)) = (editor.editing, self.config.graph.groups.get_mut(editor.selected)) else {
+            return;
+        };
+        let value = editor.draft.trim().to_string();
+        match field {
+            GraphGroupField::Query if value.is_empty() => {
+                self.show_error_toast("A colour group needs a filter query");
+                return;
+            }
+            GraphGroupField::Query => group.query = value,
+            GraphGroupField::Color => {
+                let candidate = GraphColorGroup {
+                    query: group.query.clone(),
+                    color: value,
+                };
+                if candidate.resolve_color(&self.theme).is_none() {
+                    let message = format!("Unknown colour '{}'; use a theme colour, a name like blue or #rrggbb", candidate.color);
+                    self.show_error_toast(message);
+                    return;
+                }
+                *group = candidate;
+            }
+        }
+        editor.editing = None;
+        self.save_graph_groups();
+    }
+
+    /// Leave the field untouched; a just-added group without a query is dropped.
+    pub fn cancel_graph_group_edit(&mut self) {
+        let Some(editor) = self.graph_view.groups_editor.as_mut() else {
+            return;
+        };
+        editor.editing = None;
+        if self.config.graph.groups.get(editor.selected).is_some_and(|group| group.query.is_empty()) {
+            self.config.graph.groups.remove(editor.selected);
+            editor.selected = editor.selected.min(self.config.graph.groups.len().saturating_sub(1));
+        }
+    }
+
+    pub fn delete_graph_group(&mut self) {
+        let Some(editor) = self.graph_view.groups_editor.as_mut() else {
+            return;
+        };
+        if editor.selected >= self.config.graph.groups.len() {
+            return;
+        }
+        self.config.graph.groups.remove(editor.selected);
+        editor.selected = editor.selected.min(self.config.graph.groups.len().saturating_sub(1));
+        self.save_graph_groups();
+    }
+
+    /// Move the selected group earlier or later; order decides precedence.
+    pub fn move_graph_group(&mut self, delta: isize) {
+        let Some(editor) = self.graph_view.groups_editor.as_mut() else {
+            return;
+        };
+        let target = editor.selected as isize + delta;
+        if editor.selected >= self.config.graph.groups.len() || target < 0 || target as usize >= self.config.graph.groups.len() {
+            return;
+        }
+        self.config.graph.groups.swap(editor.selected, target as usize);
+        editor.selected = target as usize;
+        self.save_graph_groups();
+    }
+
+    fn save_graph_groups(&mut self) {
+        self.assign_graph_groups();
+        if let Err(error) = self.config.save_to_dir(&self.dependencies.config_dir) {
+            self.show_error_toast(format!("Could not save graph groups: {}", error));
+        }
+    }
+
+    /// Project `index` with `view`'s mode, root, depth, scope, filter and
+    /// orphan settings and lay it out on the calling thread. Global positions
+    /// come from the layout cache when it matches the index.
+    pub fn project_graph_blocking(&self, index: &GraphIndex, view: &GraphViewState) -> GraphProjection {
+        let root_note_id = self.notes.get(view.root_note_index).map(|note| note.id).unwrap_or_else(|| NoteId::new(0));
+        let filter = GraphFilter::parse(&view.filter_query);
+        let mut projection = index.project(view.mode, root_note_id, view.depth, view.link_scope, &filter, view.show_orphans);
+        match view.mode {
+            GraphMode::Local => graph::apply_local_layout(index, &mut projection.nodes),
+            GraphMode::Global => {
+                let mut full = index.project(GraphMode::Global, root_note_id, 1, GraphLinkScope::All, &GraphFilter::default(), true);
+                let cache_path = self.graph_cache_path("graph_layout.bin");
+                let pins = self.load_graph_pins();
+                let cache_key = pins.layout_fingerprint(index.fingerprint);
+                let positions = graph::load_layout_cache(&cache_path, cache_key, &full.nodes).unwrap_or_else(|| {
+                    graph::apply_global_layout(index, &mut full.nodes, &full.edges, &pins);
+                    graph::save_layout_cache(&cache_path, cache_key, &full.nodes);
+                    full.nodes.iter().map(|node| (node.note_id, node.x, node.y)).collect()
+                });
+                let positions: HashMap<_, _> = positions.into_iter().map(|(note_id, x, y)| (note_id, (x, y))).collect();
+                for node in &mut projection.nodes {
+                    if let Some(&(x, y)) = positions.get(&node.note_id) {
+                        (node.x, node.y, node.home_x, node.home_y) = (x, y, x, y);
+                    }
+                }
+            }
+        }
+        projection
+    }
+
+    /// Write the graph view as shown, including dragged node positions, to
+    /// `graph-<mode>.<ext>` in the vault root.
+    pub fn export_graph_view(&mut self, format: GraphExportFormat) {
+        self.graph_view.export_prompt = false;
+        let Some(index) = self.graph_index.clone() else {
+            self.show_error_toast("The link index is still building");
+            return;
+        };
+        let options = GraphExportOptions {
+            format,
+            positions: self.graph_view.export_positions,
+        };
+        let contents = graph::export_graph(&index, &self.graph_view.nodes, &self.graph_view.edges, options);
+        let file_name = format!("graph-{}.{}", self.graph_view.mode.label().to_lowercase(), format.extension());
+        let path = self.config.notes_path().join(&file_name);
+        match fs::write(&path, contents) {
+            Ok(()) => self.show_toast(
+                format!("Exported {} notes as {} to {file_name}", self.graph_view.nodes.len(), format.label()),
+                ToastKind::Success,
+            ),
+            Err(error) => self.show_error_toast(format!("Could not write {file_name}: {error}")),
+        }
     }
 
     pub fn toggle_graph_mode(&mut self) {
@@ -232,6 +470,7 @@ impl App {
             if let Some(worker) = &self.graph_worker {
                 worker.cancel();
             }
+            self.submit_graph_centrality();
         }
     }
 
@@ -270,6 +509,8 @@ impl App {
         self.graph_view.show_orphans = true;
         self.graph_view.filter_query.clear();
         self.graph_view.filter_draft.clear();
+        self.graph_view.path_source = None;
+        self.graph_view.color_clusters = false;
         self.rebuild_graph_projection(true);
     }
 
@@ -282,12 +523,292 @@ impl App {
         self.rebuild_graph_projection(true);
     }
 
+    /// Mark the selected node as the start of a shortest path, or clear the
+    /// mark when it is already the start.
+    pub fn toggle_graph_path_mark(&mut self) {
+        let Some(note_id) = self
+            .graph_view
+            .selected_node
+            .and_then(|idx| self.graph_view.nodes.get(idx))
+            .map(|node| node.note_id)
+        else {
+            return;
+        };
+        if self.graph_view.path_source == Some(note_id) {
+            self.graph_view.path_source = None;
+            self.show_toast("Path cleared", ToastKind::Info);
+        } else {
+            self.graph_view.path_source = Some(note_id);
+            self.show_toast("Path start marked; select another note to trace the route", ToastKind::Info);
+        }
+        self.update_graph_path();
+    }
+
+    /// Recompute the shortest path from the marked start to the selection.
+    pub fn update_graph_path(&mut self) {
+        let target = self
+            .graph_view
+            .selected_node
+            .and_then(|idx| self.graph_view.nodes.get(idx))
+            .map(|node| node.note_id);
+        self.graph_view.path = match (self.graph_view.path_source, target, self.graph_index.as_ref()) {
+            (Some(source), Some(target), Some(index)) => index.shortest_path(source, target).unwrap_or_default(),
+            _ => Vec::new(),
+        };
+    }
+
+    pub fn toggle_graph_cluster_colors(&mut self) {
+        self.graph_view.color_clusters = !self.graph_view.color_clusters;
+    }
+
+    /// Open the most-central list; the ranking runs on the graph worker.
+    pub fn open_graph_centrality(&mut self, metric: CentralityMetric) {
+        if self.graph_index.is_none() {
+            self.show_error_toast("The link index is still building");
+            return;
+        }
+        self.graph_view.centrality = Some(GraphCentralityPanel {
+            metric,
+            entries: Vec::new(),
+            selected: 0,
+            loading: true,
+        });
+        self.submit_graph_centrality();
+    }
+
+    /// Start ranking for a loading centrality panel, unless the worker's one
+    /// slot holds an index build or layout; their replies call this again.
+    fn submit_graph_centrality(&mut self) {
+        let Some(metric) = self.graph_view.centrality.as_ref().filter(|panel| panel.loading).map(|panel| panel.metric) else {
+            return;
+        };
+        if self.graph_indexing || self.graph_view.layout_pending {
+            return;
+        }
+        let Some(index) = self.graph_index.clone() else {
+            return;
+        };
+        self.graph_centrality_generation = self.graph_centrality_generation.wrapping_add(1);
+        self.graph_worker
+            .get_or_insert_with(GraphWorker::new)
+            .submit_centrality(self.graph_centrality_generation, index, metric, GRAPH_CENTRALITY_LIMIT);
+    }
+
+    /// Select the chosen central note, re-rooting the Local graph on it when
+    /// the current projection does not include it.
+    pub fn select_graph_centrality_entry(&mut self) {
+        let Some(panel) = self.graph_view.centrality.take() else {
+            return;
+        };
+        let Some(&(note_id, _)) = panel.entries.get(panel.selected) else {
+            return;
+        };
+        let Some(note_index) = self.note_index_for_id(note_id) else {
+            return;
+        };
+        self.graph_view.selected_note_index = Some(note_index);
+        if !self.graph_view.nodes.iter().any(|node| node.note_id == note_id) {
+            self.graph_view.root_note_index = note_index;
+            self.graph_view.mode = GraphMode::Local;
+            self.rebuild_graph_projection(true);
+        }
+        if let Some(idx) = self.graph_view.nodes.iter().position(|node| node.note_id == note_id) {
+            self.graph_view.selected_node = Some(idx);
+            self.graph_view.needs_center = true;
+        }
+        self.update_graph_path();
+    }
+
+    /// Show or hide the timeline slider. It opens at the newest date, so
+    /// nothing disappears until the cursor moves.
+    pub fn toggle_graph_timeline(&mut self) {
+        if self.graph_view.timeline.take().is_some() {
+            self.rebuild_graph_projection(false);
+            return;
+        }
+        let Some(index) = self.graph_index.clone() else {
+            self.show_error_toast("The link index is still building");
+            return;
+        };
+        let dates = index.nodes.iter().filter_map(|node| node.date);
+        let (Some(first), Some(last)) = (dates.clone().min(), dates.max()) else {
+            self.show_toast("No dated notes to put on a timeline", ToastKind::Info);
+            return;
+        };
+        self.graph_view.timeline = Some(GraphTimeline {
+            first,
+            last,
+            cursor: last,
+            playing: false,
+            last_frame: None,
+        });
+    }
+
+    /// Move the cursor by whole steps of the sweep, pausing playback.
+    pub fn step_graph_timeline(&mut self, steps: i32) {
+        let Some(timeline) = self.graph_view.timeline.as_mut() else {
+            return;
+        };
+        timeline.playing = false;
+        let cursor = (timeline.cursor + steps * timeline.step()).clamp(timeline.first, timeline.last);
+        self.set_graph_timeline_cursor(cursor);
+    }
+
+    pub fn jump_graph_timeline(&mut self, to_end: bool) {
+        let Some(timeline) = self.graph_view.timeline.as_mut() else {
+            return;
+        };
+        timeline.playing = false;
+        let cursor = if to_end { timeline.last } else { timeline.first };
+        self.set_graph_timeline_cursor(cursor);
+    }
+
+    /// Play the vault's growth from the cursor, restarting from the first
+    /// date when the cursor is already at the end.
+    pub fn toggle_graph_timeline_play(&mut self) {
+        if self.graph_view.timeline.is_none() {
+            self.toggle_graph_timeline();
+        }
+        let Some(timeline) = self.graph_view.timeline.as_mut() else {
+            return;
+        };
+        timeline.playing = !timeline.playing;
+        timeline.last_frame = Some(std::time::Instant::now());
+        if timeline.playing && timeline.cursor >= timeline.last {
+            let first = timeline.first;
+            self.set_graph_timeline_cursor(first);
+        }
+    }
+
+    /// Advance a playing timeline by one frame once the frame time has
+    /// passed. Returns whether the view changed.
+    pub fn tick_graph_timeline(&mut self) -> bool {
+        if self.dialog != DialogState::GraphView {
+            return false;
+        }
+        let Some(timeline) = self.graph_view.timeline.as_mut().filter(|timeline| timeline.playing) else {
+            return false;
+        };
+        if timeline.last_frame.is_some_and(|frame| frame.elapsed() < GRAPH_TIMELINE_FRAME) {
+            return false;
+        }
+        timeline.last_frame = Some(std::time::Instant::now());
+        let cursor = (timeline.cursor + timeline.step()).min(timeline.last);
+        timeline.playing = cursor < timeline.last;
+        self.set_graph_timeline_cursor(cursor);
+        true
+    }
+
+    fn set_graph_timeline_cursor(&mut self, cursor: i32) {
+        let Some(timeline) = self.graph_view.timeline.as_mut() else {
+            return;
+        };
+        if timeline.cursor != cursor {
+            timeline.cursor = cursor;
+            self.rebuild_graph_projection(false);
+        }
+    }
+
+    /// Pin the selected Global node where it is, or release its pin.
+    pub fn toggle_graph_pin(&mut self) {
+        let Some(idx) = self.graph_view.selected_node else {
+            return;
+        };
+        if self.graph_view.mode != GraphMode::Global {
+            self.show_toast("Pins place nodes in the Global graph; press v to switch", ToastKind::Info);
+            return;
+        }
+        let Some(path) = self.graph_node_path(idx) else {
+            return;
+        };
+        if self.graph_view.pins.remove(&path) {
+            self.save_graph_pins();
+            self.show_toast("Unpinned; the next layout may move it", ToastKind::Info);
+        } else {
+            self.pin_graph_node(idx);
+        }
+    }
+
+    /// Pin a Global node at its current position, e.g. after dragging it.
+    pub fn pin_graph_node(&mut self, idx: usize) {
+        if self.hold_graph_node(idx) {
+            self.save_graph_pins();
+        }
+    }
+
+    /// Move the selected node from the keyboard. Its pin is only written out
+    /// by `flush_graph_pins`, so holding a key does not rewrite the file.
+    pub fn nudge_graph_node(&mut self, idx: usize, dx: f32, dy: f32) {
+        let Some(node) = self.graph_view.nodes.get_mut(idx) else {
+            return;
+        };
+        node.x += dx;
+        node.y += dy;
+        if self.hold_graph_node(idx) {
+            self.graph_view.pins_dirty = true;
+        }
+    }
+
+    pub fn flush_graph_pins(&mut self) {
+        if self.graph_view.pins_dirty {
+            self.save_graph_pins();
+        }
+    }
+
+    /// Record a Global node's position as its pin, in memory only.
+    fn hold_graph_node(&mut self, idx: usize) -> bool {
+        if self.graph_view.mode != GraphMode::Global {
+            return false;
+        }
+        let (Some(path), Some(node)) = (self.graph_node_path(idx), self.graph_view.nodes.get_mut(idx)) else {
+            return false;
+        };
+        (node.home_x, node.home_y) = (node.x, node.y);
+        let (note_id, x, y) = (node.note_id, node.x, node.y);
+        self.graph_view.pins.insert(path, x, y);
+        if let Ok(position) = self.graph_view.global_positions.binary_search_by_key(&note_id, |(note_id, _, _)| *note_id) {
+            self.graph_view.global_positions[position] = (note_id, x, y);
+        }
+        true
+    }
+
+    /// Drop every pin and lay the Global graph out from scratch.
+    pub fn reset_graph_pins(&mut self) {
+        if self.graph_view.pins.is_empty() {
+            self.show_toast("No pinned nodes", ToastKind::Info);
+            return;
+        }
+        let count = self.graph_view.pins.len();
+        self.graph_view.pins.clear();
+        self.save_graph_pins();
+        self.show_toast(format!("Released {count} pinned nodes"), ToastKind::Success);
+        if self.graph_view.mode == GraphMode::Global {
+            self.graph_view.global_positions = Vec::new();
+            self.graph_view.global_fingerprint = None;
+            self.rebuild_graph_projection(true);
+            self.start_global_graph_layout();
+        }
+    }
+
+    fn save_graph_pins(&mut self) {
+        self.graph_view.pins_dirty = false;
+        if let Err(error) = self.graph_view.pins.save(&self.graph_pins_path()) {
+            self.show_error_toast(format!("Could not save graph pins: {}", error));
+        }
+    }
+
+    fn graph_node_path(&self, idx: usize) -> Option<String> {
+        let note_id = self.graph_view.nodes.get(idx)?.note_id;
+        Some(self.graph_index.as_ref()?.metadata_for_note(note_id)?.path.clone())
+    }
+
     pub fn close_graph_view(&mut self) {
         self.dialog = DialogState::None;
         self.release_graph_session();
     }
 
     pub fn release_graph_session(&mut self) {
+        self.flush_graph_pins();
         self.graph_index_generation = self.graph_index_generation.wrapping_add(1);
         self.graph_layout_generation = self.graph_layout_generation.wrapping_add(1);
         self.graph_indexing = false;
@@ -304,11 +825,21 @@ impl App {
         self.graph_view.layout_pending = false;
         self.graph_view.drag_start = None;
         self.graph_view.dragging_node = None;
+        self.graph_view.node_dragged = false;
         self.graph_view.is_panning = false;
-        if self
-            .graph_index
-            .as_ref()
-            .is_some_and(|index| index.retained_bytes() > RETAINED_GRAPH_INDEX_BUDGET)
+        self.graph_view.export_prompt = false;
+        self.graph_view.path_source = None;
+        self.graph_view.path = Vec::new();
+        self.graph_view.centrality = None;
+        self.graph_view.node_groups = Vec::new();
+        self.graph_view.groups_editor = None;
+        self.graph_view.timeline = None;
+        // The docked local graph keeps using the index after the view closes.
+        if !self.local_graph_visible()
+            && self
+                .graph_index
+                .as_ref()
+                .is_some_and(|index| index.retained_bytes() > RETAINED_GRAPH_INDEX_BUDGET)
         {
             self.graph_index = None;
         }
@@ -322,4 +853,21 @@ impl App {
     fn graph_cache_path(&self, file_name: &str) -> PathBuf {
         search::get_index_path_in(&self.dependencies.cache_dir, &self.config.notes_path()).with_file_name(file_name)
     }
+
+    /// Pins are user data, so they live beside the config, one file per
+    /// vault, where `--clean-cache` cannot reach them.
+    fn graph_pins_path(&self) -> PathBuf {
+        let identity = search::vault_identity(&self.config.notes_path());
+        self.dependencies.config_dir.join("graph_pins").join(format!("{identity:016x}.json"))
+    }
+
+    /// Falls back to the old location in the cache until the pins are next saved.
+    fn load_graph_pins(&self) -> GraphPins {
+        let path = self.graph_pins_path();
+        if path.exists() {
+            GraphPins::load(&path)
+        } else {
+            GraphPins::load(&self.graph_cache_path(LEGACY_GRAPH_PINS_FILE))
+        }
+    }
 }
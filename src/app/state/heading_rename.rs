@@ -0,0 +1,236 @@
+use super::*;
+
+use std::path::Path;
+
+use ekphos_core::markdown::{heading, heading_section, renamed_headings};
+
+impl App {
+    /// Start renaming the heading that owns the content cursor.
+    pub fn open_heading_rename(&mut self) {
+        let Some((note, path)) = self.current_note().and_then(|note| Some((note.id, note.file_path.clone()?))) else {
+            return;
+        };
+        let row = self.content_items.get(self.content_cursor).map(ContentItem::source_line).unwrap_or(0);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let Some(section) = heading_section(&lines, row) else {
+            self.show_error_toast("No heading above the cursor");
+            return;
+        };
+        let Some(text) = heading(lines[section.start]).map(|heading| heading.text.to_string()) else {
+            return;
+        };
+        self.heading_rename = Some(HeadingRenameState {
+            note,
+            heading_row: Some(section.start),
+            renames: vec![(text.clone(), text.clone())],
+            input: Some(text),
+            affected: Vec::new(),
+        });
+        self.dialog = DialogState::HeadingRename;
+    }
+
+    pub fn close_heading_rename(&mut self) {
+        self.heading_rename = None;
+        self.dialog = DialogState::None;
+    }
+
+    /// Offer to fix section links after a save renamed headings. Only asks
+    /// when some link in the vault still uses an old name.
+    pub(super) fn offer_heading_link_updates(&mut self, note: NoteId, before: &str, after: &str) {
+        let before: Vec<&str> = before.lines().collect();
+        let after: Vec<&str> = after.lines().collect();
+        let renames = renamed_headings(&before, &after);
+        if renames.is_empty() {
+            return;
+        }
+        let affected = self.heading_link_rewrites(note, &renames);
+        if affected.is_empty() {
+            return;
+        }
+        self.heading_rename = Some(HeadingRenameState {
+            note,
+            heading_row: None,
+            renames,
+            input: None,
+            affected,
+        });
+        self.dialog = DialogState::HeadingRename;
+    }
+
+    /// Take the typed name and show which links would change.
+    pub fn submit_heading_rename(&mut self) {
+        let Some(state) = &self.heading_rename else {
+            return;
+        };
+        let Some(name) = state.input.as_deref().map(str::trim) else {
+            return;
+        };
+        if name.is_empty() || name.contains(['[', ']', '|', '#']) {
+            self.show_error_toast("Heading names cannot be empty or contain [ ] | #");
+            return;
+        }
+        let old = state.renames[0].0.clone();
+        if name == old {
+            self.close_heading_rename();
+            return;
+        }
+        let renames = vec![(old, name.to_string())];
+        let affected = self.heading_link_rewrites(state.note, &renames);
+        if let Some(state) = &mut self.heading_rename {
+            state.renames = renames;
+            state.input = None;
+            state.affected = affected;
+        }
+    }
+
+    /// Rename the heading when asked to, then rewrite the section links.
+    pub fn apply_heading_rename(&mut self) {
+        let Some(state) = self.heading_rename.take() else {
+            return;
+        };
+        self.dialog = DialogState::None;
+        let Some(path) = self.note_index_for_id(state.note).and_then(|index| self.notes[index].file_path.clone()) else {
+            return;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return;
+        };
+
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        if let (Some(row), Some((_, new))) = (state.heading_row, state.renames.first()) {
+            let Some(level) = lines.get(row).and_then(|line| heading(line)).map(|heading| heading.level) else {
+                self.show_error_toast("The heading moved; nothing was renamed");
+                return;
+            };
+            lines[row] = format!("{} {new}", "#".repeat(level));
+        }
+        let mut body = lines.join("\n");
+        if content.ends_with('\n') {
+            body.push('\n');
+        }
+        let body = Self::rewrite_wiki_links(&body, |target, suffix| {
+            retarget_section_link(target, suffix, target.is_empty() || self.is_link_to(target, &path), &state.renames)
+        });
+        if body != content && !self.persist_note_body(state.note, body) {
+            return;
+        }
+        let renames = state.renames.clone();
+        self.rewrite_wiki_links_in_vault(Some(&path), |target, suffix| {
+            let points_here = self.is_link_to(target, &path);
+            retarget_section_link(target, suffix, points_here, &renames)
+        });
+
+        let links: usize = state.affected.iter().map(|(_, count)| count).sum();
+        self.show_toast(
+            format!("Updated {links} section link(s) in {} file(s)", state.affected.len()),
+            ToastKind::Success,
+        );
+    }
+
+    /// Files with links to `note#old` for any of `renames`, and how many.
+    fn heading_link_rewrites(&self, note: NoteId, renames: &[(String, String)]) -> Vec<(String, usize)> {
+        let Some(path) = self.note_index_for_id(note).and_then(|index| self.notes[index].file_path.clone()) else {
+            return Vec::new();
+        };
+        let mut affected: Vec<(String, usize)> = Self::collect_markdown_files(&self.config.notes_path())
+            .into_iter()
+            .filter_map(|file| {
+                let content = fs::read_to_string(&file).ok()?;
+                let own = file == path;
+                let mut count = 0;
+                Self::rewrite_wiki_links(&content, |target, suffix| {
+                    let points_here = (own && target.is_empty()) || self.is_link_to(target, &path);
+                    let replacement = retarget_section_link(target, suffix, points_here, renames);
+                    count += usize::from(replacement.is_some());
+                    replacement
+                });
+                (count > 0).then(|| (self.vault_relative(&file), count))
+            })
+            .collect();
+        affected.sort();
+        affected
+    }
+
+    /// Whether a wiki link target names the note at `path`.
+    fn is_link_to(&self, target: &str, path: &Path) -> bool {
+        let target = target.to_lowercase();
+        !target.is_empty()
+            && (Self::calculate_wiki_path(path, &self.config.notes_path()).to_lowercase() == target
+                || path.file_stem().is_some_and(|stem| stem.to_string_lossy().to_lowercase() == target))
+    }
+}
+
+/// `target#old[|alias]` becomes `target#new[|alias]` when the link points at
+/// the renamed note. Anchors compare by slug, like link navigation does.
+fn retarget_section_link(target: &str, suffix: &str, points_here: bool, renames: &[(String, String)]) -> Option<String> {
+    if !points_here {
+        return None;
+    }
+    let fragment = suffix.strip_prefix('#')?;
+    let (anchor, alias) = fragment
+        .split_once('|')
+        .map_or((fragment, ""), |(anchor, _)| (anchor, &fragment[anchor.len()..]));
+    let slug = slugify_heading(anchor);
+    let (_, new) = renames.iter().find(|(old, _)| slugify_heading(old) == slug)?;
+    Some(format!("{target}#{new}{alias}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[
+            ("guide.md", "# Guide\n\nsee [[#Setup]]\n\n## Setup\n\nsteps\n"),
+            ("index.md", "[[Guide#Setup|setup]] and [[guide#setup]] and [[Guide#Guide]]\n"),
+        ])
+    }
+
+    #[test]
+    fn renaming_a_heading_by_command_rewrites_section_links() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("guide.md")));
+        app.content_cursor = app.content_items.iter().position(|item| item.source_line() == 6).unwrap();
+
+        app.open_heading_rename();
+        assert_eq!(app.heading_rename.as_ref().unwrap().input.as_deref(), Some("Setup"));
+        app.heading_rename.as_mut().unwrap().input = Some("Installation".to_string());
+        app.submit_heading_rename();
+        assert_eq!(
+            app.heading_rename.as_ref().unwrap().affected,
+            [("guide.md".to_string(), 1), ("index.md".to_string(), 2)]
+        );
+
+        app.apply_heading_rename();
+        let read = |name: &str| fs::read_to_string(vault.notes().join(name)).unwrap();
+        assert_eq!(read("guide.md"), "# Guide\n\nsee [[#Installation]]\n\n## Installation\n\nsteps\n");
+        assert_eq!(
+            read("index.md"),
+            "[[Guide#Installation|setup]] and [[guide#Installation]] and [[Guide#Guide]]\n"
+        );
+    }
+
+    #[test]
+    fn saving_a_renamed_heading_offers_to_update_links() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        assert!(app.select_note_by_path(&vault.notes().join("guide.md")));
+        app.enter_edit_mode();
+        app.editor.replace_lines(4, 4, vec!["## Install".to_string()]);
+        app.save_edit();
+
+        assert_eq!(app.dialog, DialogState::HeadingRename);
+        let state = app.heading_rename.as_ref().unwrap();
+        assert_eq!(state.renames, [("Setup".to_string(), "Install".to_string())]);
+        app.apply_heading_rename();
+        assert_eq!(
+            fs::read_to_string(vault.notes().join("index.md")).unwrap(),
+            "[[Guide#Install|setup]] and [[guide#Install]] and [[Guide#Guide]]\n"
+        );
+    }
+}
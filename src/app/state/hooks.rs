@@ -0,0 +1,302 @@
+use super::*;
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::shell::{ShellCommand, ShellJob, ShellStatus};
+
+/// Finished commands kept in the log; older entries are dropped.
+const COMMAND_LOG_CAPACITY: usize = 200;
+/// Output lines kept per log entry.
+const COMMAND_LOG_OUTPUT_LINES: usize = 200;
+
+/// A hook or action waiting for, or holding, the single runner slot. Commands
+/// run one at a time so hooks such as auto-commits never race each other.
+pub(crate) struct QueuedCommand {
+    shell: ShellCommand,
+    source: String,
+    note: Option<String>,
+    /// Actions report success with a toast; hooks only report failures.
+    is_action: bool,
+}
+
+pub(crate) struct RunningCommand {
+    job: ShellJob,
+    queued: QueuedCommand,
+}
+
+#[derive(Default)]
+pub(crate) struct CommandRunner {
+    queue: VecDeque<QueuedCommand>,
+    running: Option<RunningCommand>,
+}
+
+impl CommandRunner {
+    pub(crate) fn is_busy(&self) -> bool {
+        self.running.is_some() || !self.queue.is_empty()
+    }
+}
+
+impl App {
+    /// Queue the `[hooks]` command for `event`, if one is configured.
+    /// `old_path` is the previous location of a renamed note.
+    pub fn run_hook(&mut self, event: HookEvent, path: &Path, old_path: Option<&Path>) {
+        let Some(command) = self.config.hooks.command(event) else {
+            return;
+        };
+        let mut env = vec![("EKPHOS_EVENT".to_string(), event.key().to_string())];
+        if let Some(old_path) = old_path {
+            env.push(("EKPHOS_OLD_NOTE".to_string(), old_path.display().to_string()));
+        }
+        let shell = self.command_for_note(command, Some(path), env, None);
+        self.queue_command(QueuedCommand {
+            shell,
+            source: event.key().to_string(),
+            note: Some(self.vault_relative(path)),
+            is_action: false,
+        });
+    }
+
+    /// Run the `index`th configured action. It receives `selection` on stdin,
+    /// or the whole current note when nothing is selected.
+    pub fn run_action(&mut self, index: usize, selection: Option<String>) {
+        let Some(action) = self.config.actions.get(index).cloned() else {
+            return;
+        };
+        let path = self.current_note().and_then(|note| note.file_path.clone());
+        let stdin = selection.or_else(|| {
+            if self.mode == Mode::Edit {
+                Some(self.editor.lines().join("\n"))
+            } else {
+                path.as_ref().and_then(|path| fs::read_to_string(path).ok())
+            }
+        });
+        let env = vec![("EKPHOS_ACTION".to_string(), action.name.clone())];
+        let shell = self.command_for_note(&action.command, path.as_deref(), env, stdin);
+        let note = path.as_deref().map(|path| self.vault_relative(path));
+        self.queue_command(QueuedCommand {
+            shell,
+            source: action.name,
+            note,
+            is_action: true,
+        });
+    }
+
+    fn command_for_note(&self, command: &str, path: Option<&Path>, mut env: Vec<(String, String)>, stdin: Option<String>) -> ShellCommand {
+        let root = self.vault.root().to_path_buf();
+        env.push(("EKPHOS_VAULT".to_string(), root.display().to_string()));
+        if let Some(path) = path {
+            env.push(("EKPHOS_NOTE".to_string(), path.display().to_string()));
+        }
+        ShellCommand {
+            stdin,
+            env,
+            cwd: Some(root),
+            timeout: Some(Duration::from_secs(self.config.hooks.timeout_secs.max(1))),
+            ..ShellCommand::new(command)
+        }
+    }
+
+    pub(crate) fn vault_relative(&self, path: &Path) -> String {
+        path.strip_prefix(self.vault.root()).unwrap_or(path).display().to_string()
+    }
+
+    fn queue_command(&mut self, command: QueuedCommand) {
+        self.command_runner.queue.push_back(command);
+        self.start_next_command();
+    }
+
+    fn start_next_command(&mut self) {
+        if self.command_runner.running.is_some() {
+            return;
+        }
+        if let Some(queued) = self.command_runner.queue.pop_front() {
+            let job = ShellJob::spawn(queued.shell.clone());
+            self.command_runner.running = Some(RunningCommand { job, queued });
+        }
+    }
+
+    /// Log a finished hook or action and start the next one. Returns `true`
+    /// when the screen needs a redraw.
+    pub fn poll_command_runner(&mut self) -> bool {
+        let Some(output) = self.command_runner.running.as_ref().and_then(|running| running.job.try_finish()) else {
+            return false;
+        };
+        let Some(RunningCommand { queued, .. }) = self.command_runner.running.take() else {
+            return false;
+        };
+
+        if let Some(reason) = output.failure_message() {
+            self.show_error_toast(format!("{} failed: {reason}", queued.source));
+        } else if queued.is_action {
+            let summary = output.stdout.lines().find(|line| !line.trim().is_empty()).unwrap_or("done");
+            self.show_toast(format!("{}: {}", queued.source, summary.trim()), ToastKind::Success);
+        }
+
+        let mut lines: Vec<String> = output.stdout.lines().chain(output.stderr.lines()).map(str::to_string).collect();
+        if lines.len() > COMMAND_LOG_OUTPUT_LINES {
+            lines.drain(..lines.len() - COMMAND_LOG_OUTPUT_LINES);
+        }
+        self.command_log.push_front(CommandLogEntry {
+            source: queued.source,
+            command: queued.shell.command,
+            note: queued.note,
+            status: output.status,
+            output: lines,
+            finished_at: self.dependencies.clock.now(),
+        });
+        self.command_log.truncate(COMMAND_LOG_CAPACITY);
+
+        self.start_next_command();
+        true
+    }
+
+    /// Block until every queued hook has finished, returning the failures.
+    /// Command-line subcommands exit right after their work, so they cannot
+    /// leave hooks to the event loop.
+    pub fn wait_for_commands(&mut self) -> Vec<String> {
+        let mut failures = Vec::new();
+        while self.command_runner.is_busy() {
+            if !self.poll_command_runner() {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            } else if let Some(toast) = self.toast.take().filter(|toast| toast.kind == ToastKind::Error) {
+                failures.push(toast.message);
+            }
+        }
+        failures
+    }
+
+    pub fn open_command_log(&mut self) {
+        self.command_log_scroll = 0;
+        self.dialog = DialogState::CommandLog;
+    }
+
+    pub fn close_command_log(&mut self) {
+        self.dialog = DialogState::None;
+    }
+
+    /// Commands still queued or running, for the log title.
+    pub fn pending_command_count(&self) -> usize {
+        self.command_runner.queue.len() + usize::from(self.command_runner.running.is_some())
+    }
+}
+
+impl CommandLogEntry {
+    pub fn succeeded(&self) -> bool {
+        self.status == ShellStatus::Exited(Some(0))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+    use std::time::Instant;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[("alpha.md", "# Alpha\none two three\n")])
+    }
+
+    fn drain(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.command_runner.is_busy() {
+            assert!(Instant::now() < deadline, "commands did not finish");
+            app.poll_command_runner();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn save_hook_receives_note_and_vault_in_environment() {
+        let vault = sample_vault();
+        let mut config = Config::default();
+        config.hooks.on_save = Some("echo \"$EKPHOS_EVENT ${EKPHOS_NOTE#$EKPHOS_VAULT/}\" > saved.log".to_string());
+        let mut app = vault.app_with(config);
+
+        app.enter_edit_mode();
+        app.save_edit();
+        drain(&mut app);
+
+        assert_eq!(fs::read_to_string(vault.notes().join("saved.log")).unwrap(), "on_save alpha.md\n");
+        let entry = &app.command_log[0];
+        assert_eq!((entry.source.as_str(), entry.note.as_deref()), ("on_save", Some("alpha.md")));
+        assert!(entry.succeeded());
+    }
+
+    #[test]
+    fn actions_read_stdin_and_failures_reach_the_log() {
+        let vault = sample_vault();
+        let mut config = Config::default();
+        config.hooks.timeout_secs = 1;
+        config.actions = vec![
+            crate::config::ActionConfig {
+                name: "Words".to_string(),
+                command: "wc -w | tr -d ' '".to_string(),
+            },
+            crate::config::ActionConfig {
+                name: "Slow".to_string(),
+                command: "sleep 5".to_string(),
+            },
+        ];
+        let mut app = vault.app_with(config);
+
+        app.run_action(0, None);
+        app.run_action(0, Some("just two".to_string()));
+        app.run_action(1, None);
+        drain(&mut app);
+
+        let log: Vec<_> = app.command_log.iter().map(|entry| (entry.source.as_str(), entry.output.clone())).collect();
+        assert_eq!(log[2], ("Words", vec!["5".to_string()]));
+        assert_eq!(log[1], ("Words", vec!["2".to_string()]));
+        assert_eq!(app.command_log[0].status, ShellStatus::TimedOut);
+        assert_eq!(app.toast.as_ref().map(|toast| toast.kind), Some(ToastKind::Error));
+    }
+
+    #[test]
+    fn moving_and_folder_operations_fire_a_hook_per_note() {
+        let vault = TempVault::with_files(&[
+            ("alpha.md", "# Alpha\n"),
+            ("projects/plan.md", "# Plan\n"),
+            ("projects/deep/idea.md", "# Idea\n"),
+            ("archive/old.md", "# Old\n"),
+        ]);
+        let mut config = Config::default();
+        config.hooks.on_rename = Some("echo \"${EKPHOS_OLD_NOTE#$EKPHOS_VAULT/} -> ${EKPHOS_NOTE#$EKPHOS_VAULT/}\"".to_string());
+        config.hooks.on_delete = Some("echo \"deleted ${EKPHOS_NOTE#$EKPHOS_VAULT/}\"".to_string());
+        let mut app = vault.app_with(config);
+        let notes = vault.notes();
+        let select_folder = |app: &mut App, relative: &str| {
+            app.selected_sidebar_index = app
+                .sidebar_items
+                .iter()
+                .position(|item| matches!(&item.kind, SidebarItemKind::Folder(folder) if folder.path == notes.join(relative)))
+                .unwrap();
+        };
+
+        app.move_note(&notes.join("alpha.md"), &notes.join("archive"), "alpha").unwrap();
+        select_folder(&mut app, "projects");
+        app.rename_folder("work");
+        app.move_folder(&notes.join("work"), &notes.join("archive"), "work").unwrap();
+        select_folder(&mut app, "archive");
+        app.delete_current_folder();
+        drain(&mut app);
+
+        let mut outputs: Vec<_> = app.command_log.iter().flat_map(|entry| entry.output.clone()).collect();
+        outputs.sort();
+        assert_eq!(
+            outputs,
+            [
+                "alpha.md -> archive/alpha.md",
+                "deleted archive/alpha.md",
+                "deleted archive/old.md",
+                "deleted archive/work/deep/idea.md",
+                "deleted archive/work/plan.md",
+                "projects/deep/idea.md -> work/deep/idea.md",
+                "projects/plan.md -> work/plan.md",
+                "work/deep/idea.md -> archive/work/deep/idea.md",
+                "work/plan.md -> archive/work/plan.md",
+            ]
+        );
+    }
+}
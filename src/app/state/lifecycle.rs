@@ -1,4 +1,5 @@
 use super::*;
+use ekphos_vault::journal::PeriodKind;
 
 impl App {
     pub fn new() -> Self {
@@ -37,6 +38,15 @@ impl App {
         AppBuilder::injected(config, target_file, dependencies).build()
     }
 
+    /// Open a vault for command-line subcommands: the user's config and
+    /// caches, but no clipboard, network or launch dialogs.
+    pub fn new_headless(vault_path: PathBuf) -> Self {
+        let mut config = Config::load();
+        config.check_updates = false;
+        let dependencies = AppDependencies::headless(Config::config_dir(), default_cache_dir());
+        Self::new_injected(config, vault_path, None, dependencies)
+    }
+
     /// Select a note by its file path, expanding collapsed ancestors as needed.
     pub fn select_note_by_path(&mut self, target_path: &PathBuf) -> bool {
         let Some(note_idx) = self.notes.iter().position(|note| note.file_path.as_ref() == Some(target_path)) else {
@@ -79,6 +89,9 @@ impl App {
                 self.keybinding_warning = Some(KeybindingWarning::new(error, KeybindingFallback::Previous));
             }
         }
+        let (vim_mappings, vim_issues) = config.vim.mapper();
+        self.vim_mappings = vim_mappings;
+        self.keybinding_warning = KeybindingWarning::with_vim_issues(self.keybinding_warning.take(), vim_issues);
         self.config = config;
 
         self.theme = Theme::from_name_in(&self.config.theme, &Config::themes_dir_in(&self.dependencies.config_dir));
@@ -202,15 +215,54 @@ impl App {
         }
         let notes_dir = self.config.notes_path();
         let date = self.dependencies.clock.today();
-        let entry = match ekphos_vault::journal::open_or_create_entry(&notes_dir, &self.config.journal_dir, date) {
-            Ok(entry) => entry,
-            Err(error) => {
-                self.status_message = Some(format!("Journal failed: {error}"));
-                return;
-            }
+        match ekphos_vault::journal::open_or_create_entry(&notes_dir, &self.config.journal_dir, date) {
+            Ok(entry) => self.select_journal_entry(entry, "Journal"),
+            Err(error) => self.status_message = Some(format!("Journal failed: {error}")),
+        }
+    }
+
+    /// Make sure the daily note for `date` exists, without selecting it.
+    pub fn ensure_journal_entry(&mut self, date: chrono::NaiveDate) -> Result<ekphos_vault::journal::JournalEntry, String> {
+        let entry = ekphos_vault::journal::open_or_create_entry(&self.config.notes_path(), &self.config.journal_dir, date)?;
+        if entry.action == ekphos_vault::journal::JournalEntryAction::Created {
+            self.run_hook(HookEvent::Create, &entry.path, None);
+            self.load_notes_from_dir();
+        }
+        Ok(entry)
+    }
+
+    /// Open the weekly, monthly, quarterly or yearly note containing today,
+    /// creating it from the configured `[periodic.<kind>]` settings.
+    pub fn open_or_create_periodic_note(&mut self, kind: PeriodKind) {
+        if self.mode != Mode::Normal {
+            return;
+        }
+        let notes_dir = self.config.notes_path();
+        let date = self.dependencies.clock.today();
+        let layout = ekphos_vault::journal::PeriodicLayout {
+            journal_dir: &self.config.journal_dir,
+            monthly: self.config.periodic_spec(PeriodKind::Month),
         };
+        let spec = self.config.periodic_spec(kind);
+        let label = match kind {
+            PeriodKind::Week => "Weekly note",
+            PeriodKind::Month => "Monthly note",
+            PeriodKind::Quarter => "Quarterly note",
+            PeriodKind::Year => "Yearly note",
+        };
+        match ekphos_vault::journal::open_or_create_periodic_entry(&notes_dir, &spec, &layout, date) {
+            Ok(entry) => self.select_journal_entry(entry, label),
+            Err(error) => self.status_message = Some(format!("{label} failed: {error}")),
+        }
+    }
 
+    fn select_journal_entry(&mut self, entry: ekphos_vault::journal::JournalEntry, label: &str) {
+        let notes_dir = self.config.notes_path();
         let display_path = entry.path.strip_prefix(&notes_dir).unwrap_or(&entry.path).display().to_string();
+        if entry.action == ekphos_vault::journal::JournalEntryAction::Created {
+            self.run_hook(HookEvent::Create, &entry.path, None);
+        }
+        self.run_hook(HookEvent::JournalOpen, &entry.path, None);
 
         self.load_notes_from_dir();
         if self.select_note_by_path(&entry.path) {
@@ -221,7 +273,7 @@ impl App {
             self.status_message = Some(format!("{action} {display_path}"));
             self.focus = Focus::Content;
         } else {
-            self.status_message = Some(format!("Journal failed to load: {display_path}"));
+            self.status_message = Some(format!("{label} failed to load: {display_path}"));
         }
     }
 }
@@ -4,7 +4,8 @@ impl App {
     // ==================== Wiki Link Support ====================
 
     /// Resolve a wiki link target to a note index
-    /// "note" -> searches all notes recursively for matching title (root first, then subfolders)
+    /// "note" -> searches all notes recursively for matching title (root first, then subfolders),
+    /// then for a note listing it in frontmatter `aliases`
     /// "folder/note" -> searches for note in specific folder
     pub fn resolve_wiki_link(&self, target: &str) -> Option<usize> {
         if target.is_empty() {
@@ -41,10 +42,23 @@ impl App {
                     return Some(idx);
                 }
             }
+            // Titles win over aliases, so an alias cannot shadow a real note
+            if let Some(idx) = self
+                .notes
+                .iter()
+                .position(|note| self.note_aliases(note.id).iter().any(|alias| alias.eq_ignore_ascii_case(target)))
+            {
+                return Some(idx);
+            }
         }
         None
     }
 
+    /// Frontmatter `aliases` of a note, empty when it declares none.
+    pub(super) fn note_aliases(&self, note_id: NoteId) -> &[Box<str>] {
+        self.note_aliases.get(&note_id).map_or(&[], |aliases| aliases)
+    }
+
     /// Check if a wiki link target exists
     pub fn wiki_link_exists(&self, target: &str) -> bool {
         self.resolve_wiki_link(target).is_some()
@@ -329,6 +343,13 @@ impl App {
     }
 
     pub fn navigate_to_wiki_link_with_heading(&mut self, target: &str, heading: Option<&str>) -> bool {
+        // `[[#heading]]` and `[[#^block]]` stay in the current note.
+        if target.is_empty() {
+            if let Some(heading) = heading {
+                self.navigate_to_heading(heading);
+            }
+            return heading.is_some();
+        }
         if let Some(note_idx) = self.resolve_wiki_link(target) {
             if let Some(note) = self.notes.get(note_idx) {
                 if let Some(ref file_path) = note.file_path {
@@ -388,6 +409,10 @@ impl App {
     /// Matches against the GitHub-style heading slug (lowercased, whitespace
     /// to dashes, punctuation stripped). Also handles `%`-encoded fragments.
     pub(super) fn navigate_to_heading(&mut self, heading: &str) {
+        if let Some(id) = heading.strip_prefix('^') {
+            self.navigate_to_block(id);
+            return;
+        }
         let decoded = percent_decode(heading);
         let target_slug = slugify_heading(&decoded);
         if target_slug.is_empty() {
@@ -576,21 +601,32 @@ impl App {
                     }
                 }
 
+                let folder_hint = wiki_path.rfind('/').map(|last_slash| wiki_path[..last_slash].to_string());
+                let path = note.file_path.as_ref().map(|p| p.display().to_string()).unwrap_or_default();
                 if let Some(score) = fuzzy_match(&note.title, note_query) {
-                    let folder_hint = if let Some(last_slash) = wiki_path.rfind('/') {
-                        Some(wiki_path[..last_slash].to_string())
-                    } else {
-                        None
-                    };
                     suggestions.push(WikiSuggestion {
                         display_name: note.title.clone(),
                         insert_text: note.title.clone(),
                         is_folder: false,
-                        path: note.file_path.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+                        path: path.clone(),
                         score,
-                        folder_hint,
+                        folder_hint: folder_hint.clone(),
                     });
                 }
+
+                // Aliases link to the real title and keep the alias as display text
+                for alias in self.note_aliases(note.id) {
+                    if let Some(score) = fuzzy_match(alias, note_query) {
+                        suggestions.push(WikiSuggestion {
+                            display_name: format!("{alias} → {}", note.title),
+                            insert_text: format!("{}|{alias}", note.title),
+                            is_folder: false,
+                            path: path.clone(),
+                            score,
+                            folder_hint: folder_hint.clone(),
+                        });
+                    }
+                }
             }
         }
 
@@ -638,6 +674,11 @@ impl App {
     /// Build heading suggestions for a note target
     /// This extracts headings from the note's content and filters by query
     pub fn build_heading_suggestions(&self, note_target: &str, query: &str) -> Vec<WikiSuggestion> {
+        if let Some(block_query) = query.strip_prefix('^') {
+            let mut suggestions = self.block_suggestions(note_target, block_query);
+            suggestions.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.score));
+            return suggestions;
+        }
         let mut suggestions = Vec::new();
 
         for (idx, note) in self.notes.iter().enumerate() {
@@ -714,6 +755,7 @@ impl App {
         if fs::write(&file_path, &content).is_err() {
             return false;
         }
+        self.run_hook(HookEvent::Create, &file_path, None);
 
         self.load_notes_from_dir();
 
@@ -746,3 +788,33 @@ impl App {
         let _ = Command::new("cmd").args(["/c", "start", "", &open_path]).spawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::app::state::test_support::TempVault;
+
+    #[test]
+    fn aliases_resolve_links_and_appear_in_suggestions() {
+        let vault = TempVault::with_files(&[
+            ("net/Remote Procedure Calls.md", "---\naliases: [RPC, Index]\n---\n# Remote Procedure Calls\n"),
+            ("Index.md", "# Index\n\n[[rpc]]\n"),
+        ]);
+        let app = vault.app();
+        let title = |index: Option<usize>| index.map(|index| app.notes[index].title.as_str());
+
+        assert_eq!(title(app.resolve_wiki_link("rpc")), Some("Remote Procedure Calls"));
+        assert_eq!(title(app.resolve_wiki_link("Index")), Some("Index"));
+
+        let suggestion = app
+            .build_wiki_suggestions("RPC")
+            .into_iter()
+            .find(|suggestion| suggestion.display_name.starts_with("RPC"))
+            .unwrap();
+        assert_eq!(suggestion.display_name, "RPC → Remote Procedure Calls");
+        assert_eq!(suggestion.insert_text, "Remote Procedure Calls|RPC");
+        assert_eq!(suggestion.folder_hint.as_deref(), Some("net"));
+
+        let results: Vec<_> = app.build_file_picker_results("index").into_iter().map(|result| result.display_name).collect();
+        assert_eq!(results, ["Index", "Index → Remote Procedure Calls"]);
+    }
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+impl App {
+    /// The docked pane shares the outline column, so it hides with it.
+    pub fn local_graph_visible(&self) -> bool {
+        self.config.graph.local_pane && !self.zen_mode && !self.is_outline_minimized()
+    }
+
+    pub fn toggle_local_graph(&mut self) {
+        self.config.graph.local_pane = !self.config.graph.local_pane;
+        if self.config.graph.local_pane {
+            if self.is_outline_minimized() {
+                self.show_toast("The local graph docks under the outline; expand it with the outline toggle", ToastKind::Info);
+            }
+        } else {
+            self.local_graph = LocalGraphPane::default();
+            if self.focus == Focus::LocalGraph {
+                self.focus = Focus::Outline;
+            }
+        }
+        if let Err(error) = self.config.save_to_dir(&self.dependencies.config_dir) {
+            self.show_error_toast(format!("Could not save the local graph setting: {}", error));
+        }
+    }
+
+    /// Reproject the pane when the current note, the link index or the depth
+    /// changed. Starts an index build when there is none, at most once per
+    /// index generation. Returns whether the pane needs a redraw.
+    pub fn sync_local_graph(&mut self) -> bool {
+        if !self.local_graph_visible() {
+            if self.focus == Focus::LocalGraph {
+                self.focus = Focus::Outline;
+            }
+            return false;
+        }
+        let Some(index) = self.graph_index.clone() else {
+            if !self.graph_indexing && self.local_graph.requested_generation != Some(self.graph_index_generation) {
+                self.start_graph_index_build();
+                self.local_graph.requested_generation = Some(self.graph_index_generation);
+            }
+            return false;
+        };
+        let root = self.current_note().map(|note| note.id);
+        let depth = self.config.graph.local_pane_depth();
+        let pane = &self.local_graph;
+        if pane.root == root && pane.fingerprint == Some(index.fingerprint) && pane.depth == depth {
+            return false;
+        }
+
+        let mut projection = match root {
+            Some(root) => index.project(GraphMode::Local, root, depth, GraphLinkScope::All, &GraphFilter::default(), true),
+            None => GraphProjection::default(),
+        };
+        graph::apply_local_layout(&index, &mut projection.nodes);
+        let titles: Vec<String> = projection
+            .nodes
+            .iter()
+            .map(|node| index.metadata_for_note(node.note_id).map(|metadata| metadata.title.clone()).unwrap_or_default())
+            .collect();
+        let mut order: Vec<usize> = (0..projection.nodes.len()).filter(|&idx| Some(idx) != projection.root_node).collect();
+        order.sort_by_cached_key(|&idx| (projection.nodes[idx].depth, titles[idx].to_lowercase()));
+        let selected = self
+            .local_graph
+            .selected
+            .filter(|note_id| self.local_graph.root == root && projection.nodes.iter().any(|node| node.note_id == *note_id));
+
+        self.local_graph = LocalGraphPane {
+            root,
+            fingerprint: Some(index.fingerprint),
+            depth,
+            nodes: projection.nodes,
+            edges: projection.edges,
+            titles,
+            order,
+            selected,
+            cells: Vec::new(),
+            area: self.local_graph.area,
+            requested_generation: self.local_graph.requested_generation,
+        };
+        true
+    }
+
+    /// Step through the neighbours, wrapping at either end.
+    pub fn move_local_graph_selection(&mut self, delta: isize) {
+        let pane = &mut self.local_graph;
+        if pane.order.is_empty() {
+            return;
+        }
+        let current = pane
+            .selected
+            .and_then(|note_id| pane.order.iter().position(|&idx| pane.nodes[idx].note_id == note_id));
+        let next = match current {
+            Some(position) => (position as isize + delta).rem_euclid(pane.order.len() as isize) as usize,
+            None if delta < 0 => pane.order.len() - 1,
+            None => 0,
+        };
+        pane.selected = Some(pane.nodes[pane.order[next]].note_id);
+    }
+
+    pub fn select_local_graph_edge(&mut self, last: bool) {
+        let pane = &mut self.local_graph;
+        let idx = if last { pane.order.last() } else { pane.order.first() };
+        pane.selected = idx.map(|&idx| pane.nodes[idx].note_id);
+    }
+
+    pub fn open_local_graph_selection(&mut self) {
+        if let Some(note_id) = self.local_graph.selected {
+            self.open_local_graph_note(note_id);
+        }
+    }
+
+    /// Jump to a neighbour; tag and attachment nodes have no note to open.
+    pub fn open_local_graph_note(&mut self, note_id: NoteId) {
+        if self.local_graph.root == Some(note_id) {
+            return;
+        }
+        let Some(note_index) = self.note_index_for_id(note_id) else {
+            return;
+        };
+        if self.navigate_to_note(note_index) {
+            self.sync_local_graph();
+        }
+    }
+
+    /// The node drawn nearest to a clicked cell, within one cell.
+    pub fn local_graph_node_at(&self, x: u16, y: u16) -> Option<NoteId> {
+        self.local_graph
+            .cells
+            .iter()
+            .map(|&(idx, cx, cy)| (idx, cx.abs_diff(x) + cy.abs_diff(y)))
+            .filter(|&(_, distance)| distance <= 1)
+            .min_by_key(|&(_, distance)| distance)
+            .map(|(idx, _)| self.local_graph.nodes[idx].note_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    #[test]
+    fn local_graph_follows_the_current_note_and_jumps_to_neighbours() {
+        let vault = TempVault::with_files(&[
+            ("hub.md", "[[alpha]] and [[beta]]\n"),
+            ("alpha.md", "[[hub]] and [[far]]\n"),
+            ("beta.md", "# Beta\n"),
+            ("far.md", "# Far\n"),
+        ]);
+        let mut config = Config::default();
+        config.graph.local_pane = true;
+        let mut app = vault.app_with(config);
+        let note_index = |app: &App, name: &str| app.notes.iter().position(|note| note.title == name).unwrap();
+        let hub = note_index(&app, "hub");
+        assert!(app.navigate_to_note(hub));
+        app.graph_index = app.build_graph_index_blocking().map(Arc::new);
+        assert!(app.sync_local_graph());
+        assert!(!app.sync_local_graph());
+
+        let titles = |app: &App| app.local_graph.order.iter().map(|&idx| app.local_graph.titles[idx].clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&app), ["alpha", "beta"]);
+
+        app.focus = Focus::Outline;
+        app.toggle_focus(false);
+        assert_eq!(app.focus, Focus::LocalGraph);
+        app.move_local_graph_selection(-1);
+        assert_eq!(app.local_graph.selected, Some(app.notes[note_index(&app, "beta")].id));
+        app.move_local_graph_selection(1);
+        app.open_local_graph_selection();
+        assert_eq!(app.selected_note, note_index(&app, "alpha"));
+        assert_eq!(app.local_graph.root, Some(app.notes[note_index(&app, "alpha")].id));
+        assert_eq!(app.local_graph.selected, None);
+        assert_eq!(titles(&app), ["far", "hub"]);
+
+        app.config.graph.local_pane_depth = 2;
+        assert!(app.sync_local_graph());
+        assert_eq!(titles(&app), ["far", "hub", "beta"]);
+    }
+}
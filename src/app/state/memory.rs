@@ -47,6 +47,10 @@ impl App {
                 .iter()
                 .map(|table| table.column_widths.len() * std::mem::size_of::<u16>() + table.alignments.len() * std::mem::size_of::<Alignment>())
                 .sum::<usize>()
+            + self.document_queries.capacity() * std::mem::size_of::<QueryBlock>()
+            + self.document_queries.iter().map(query_block_bytes).sum::<usize>()
+            + self.document_embeds.capacity() * std::mem::size_of::<EmbedBlock>()
+            + self.document_embeds.iter().map(embed_block_bytes).sum::<usize>()
             + self.document_link_ranges.capacity() * std::mem::size_of::<DocumentLinkRange>()
             + self.document_links.capacity() * std::mem::size_of::<LinkInfo>()
             + self.document_links.iter().map(link_info_bytes).sum::<usize>()
@@ -114,11 +118,13 @@ impl App {
                 + usize::from(self.search_worker.is_some())
                 + usize::from(self.indexing_in_progress)
                 + usize::from(self.graph_worker.is_some())
+                + usize::from(self.task_worker.is_some())
                 + usize::from(self.highlighter_loading),
             pending_requests: usize::from(self.highlight_pending)
                 + usize::from(self.search_worker.as_ref().is_some_and(SearchWorker::is_pending))
                 + usize::from(self.indexing_in_progress)
                 + usize::from(self.graph_worker.as_ref().is_some_and(GraphWorker::is_pending))
+                + usize::from(self.task_worker.as_ref().is_some_and(TaskWorker::is_pending))
                 + self.pending_images.len(),
         }
     }
@@ -143,6 +149,25 @@ fn content_item_bytes(item: &ContentItem) -> usize {
         | ContentItem::TaskItem { .. }
         | ContentItem::FrontmatterLine { .. }
         | ContentItem::TagBadges
-        | ContentItem::FrontmatterDelimiter { .. } => 0,
+        | ContentItem::FrontmatterDelimiter { .. }
+        | ContentItem::QueryRow { .. }
+        | ContentItem::EmbedLine { .. } => 0,
     }
 }
+
+fn embed_block_bytes(block: &EmbedBlock) -> usize {
+    let lines = match &block.lines {
+        Ok(lines) => std::mem::size_of_val(lines.as_ref()) + lines.iter().map(|line| line.len()).sum::<usize>(),
+        Err(message) => message.len(),
+    };
+    block.target.len() + block.id.len() + lines
+}
+
+fn query_block_bytes(block: &QueryBlock) -> usize {
+    let cells = |cells: &[Box<str>]| std::mem::size_of_val(cells) + cells.iter().map(|cell| cell.len()).sum::<usize>();
+    cells(&block.columns)
+        + block.rows.iter().map(|row| std::mem::size_of::<Box<[Box<str>]>>() + cells(row)).sum::<usize>()
+        + block.message.as_ref().map_or(0, |message| message.len())
+        + block.metadata.column_widths.len() * std::mem::size_of::<u16>()
+        + block.metadata.alignments.len() * std::mem::size_of::<Alignment>()
+}
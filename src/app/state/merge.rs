@@ -0,0 +1,336 @@
+use super::*;
+
+use ekphos_core::markdown::{fence_marker, heading};
+use ekphos_vault::properties::{set_property, PropertyValue};
+use ekphos_vault::Frontmatter;
+
+impl App {
+    /// Start "merge into…" for the note selected in the sidebar.
+    pub fn open_merge_for_selected_note(&mut self) {
+        let Some(SidebarItemKind::Note { note_id }) = self.sidebar_items.get(self.selected_sidebar_index).map(|item| item.kind.clone()) else {
+            return;
+        };
+        self.open_merge_dialog(note_id);
+    }
+
+    /// Start "merge into…" for the note highlighted in the file picker.
+    pub fn open_merge_from_search_picker(&mut self) {
+        let SearchPickerState::Open {
+            mode: SearchPickerMode::Files,
+            file_results,
+            selected_index,
+            ..
+        } = &self.search_picker
+        else {
+            return;
+        };
+        let Some(note_id) = file_results
+            .get(*selected_index)
+            .and_then(|result| self.notes.get(result.note_index))
+            .map(|note| note.id)
+        else {
+            return;
+        };
+        self.close_search_picker();
+        self.open_merge_dialog(note_id);
+    }
+
+    pub fn open_merge_dialog(&mut self, source: NoteId) {
+        let Some(index) = self.note_index_for_id(source) else {
+            return;
+        };
+        self.merge = Some(MergeState {
+            source,
+            source_title: self.notes[index].title.clone(),
+            query: String::new(),
+            results: Vec::new(),
+            selected: 0,
+            plan: None,
+        });
+        self.update_merge_results();
+        self.dialog = DialogState::MergeNote;
+    }
+
+    pub fn close_merge_dialog(&mut self) {
+        self.merge = None;
+        self.dialog = DialogState::None;
+    }
+
+    pub fn merge_query_push(&mut self, c: char) {
+        if let Some(merge) = &mut self.merge {
+            merge.query.push(c);
+        }
+        self.update_merge_results();
+    }
+
+    pub fn merge_query_pop(&mut self) {
+        if let Some(merge) = &mut self.merge {
+            merge.query.pop();
+        }
+        self.update_merge_results();
+    }
+
+    fn update_merge_results(&mut self) {
+        let Some(merge) = &self.merge else {
+            return;
+        };
+        let source = merge.source;
+        let results: Vec<FilePickerResult> = self
+            .build_file_picker_results(&merge.query)
+            .into_iter()
+            .filter(|result| self.notes.get(result.note_index).is_some_and(|note| note.id != source))
+            .collect();
+        if let Some(merge) = &mut self.merge {
+            merge.results = results;
+            merge.selected = 0;
+        }
+    }
+
+    pub fn merge_select(&mut self, down: bool) {
+        if let Some(merge) = &mut self.merge {
+            let last = merge.results.len().saturating_sub(1);
+            merge.selected = if down {
+                (merge.selected + 1).min(last)
+            } else {
+                merge.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Dry run for the highlighted destination.
+    pub fn preview_merge(&mut self) {
+        let Some(merge) = &self.merge else {
+            return;
+        };
+        let source = merge.source;
+        let Some(destination) = merge
+            .results
+            .get(merge.selected)
+            .and_then(|result| self.notes.get(result.note_index))
+            .map(|note| note.id)
+        else {
+            return;
+        };
+        match self.plan_merge(source, destination) {
+            Ok(plan) => {
+                if let Some(merge) = &mut self.merge {
+                    merge.plan = Some(plan);
+                }
+            }
+            Err(error) => self.show_error_toast(error),
+        }
+    }
+
+    pub fn cancel_merge_preview(&mut self) {
+        if let Some(merge) = &mut self.merge {
+            merge.plan = None;
+        }
+    }
+
+    /// Work out the merged destination and every link that changes.
+    pub fn plan_merge(&self, source: NoteId, destination: NoteId) -> Result<MergePlan, String> {
+        let note = |id: NoteId| {
+            self.note_index_for_id(id)
+                .and_then(|index| Some((self.notes[index].title.clone(), self.notes[index].file_path.clone()?)))
+                .ok_or_else(|| "Note no longer exists".to_string())
+        };
+        let (source_title, source_path) = note(source)?;
+        let (destination_title, destination_path) = note(destination)?;
+        let read = |path: &PathBuf| fs::read_to_string(path).map_err(|error| format!("Could not read {}: {error}", self.vault_relative(path)));
+        let source_text = read(&source_path)?;
+        let destination_text = read(&destination_path)?;
+
+        let notes_root = self.config.notes_path();
+        let source_wiki = Self::calculate_wiki_path(&source_path, &notes_root).to_lowercase();
+        let source_title_lower = source_title.to_lowercase();
+        let retarget = |target: &str, suffix: &str| {
+            let target = target.to_lowercase();
+            (target == source_wiki || target == source_title_lower).then(|| format!("{destination_title}{suffix}"))
+        };
+
+        let (source_frontmatter, body_start) = Frontmatter::parse(&source_text);
+        let (heading, body) = merged_section(&source_text.lines().skip(body_start).collect::<Vec<_>>(), &source_title);
+        let body = Self::rewrite_wiki_links(&body, retarget);
+
+        let (destination_frontmatter, _) = Frontmatter::parse(&destination_text);
+        let mut tags = destination_frontmatter.map(|frontmatter| frontmatter.tags).unwrap_or_default();
+        let new_tags: Vec<String> = source_frontmatter
+            .map(|frontmatter| frontmatter.tags)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tag| !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)))
+            .collect();
+        let destination_text = if new_tags.is_empty() {
+            destination_text
+        } else {
+            tags.extend(new_tags.iter().cloned());
+            set_property(&destination_text, "tags", &PropertyValue::List(tags))
+        };
+        let mut merged = format!("{}\n\n## {heading}\n", destination_text.trim_end());
+        if !body.is_empty() {
+            merged.push_str(&format!("\n{body}\n"));
+        }
+
+        let mut affected = Vec::new();
+        for path in Self::collect_markdown_files(&notes_root) {
+            if path == source_path {
+                continue;
+            }
+            let content = if path == destination_path {
+                merged.clone()
+            } else {
+                match fs::read_to_string(&path) {
+                    Ok(content) => content,
+                    Err(_) => continue,
+                }
+            };
+            let mut count = 0;
+            let rewritten = Self::rewrite_wiki_links(&content, |target, suffix| {
+                let replacement = retarget(target, suffix);
+                count += usize::from(replacement.is_some());
+                replacement
+            });
+            if path == destination_path {
+                merged = rewritten;
+            }
+            if count > 0 {
+                affected.push((self.vault_relative(&path), count));
+            }
+        }
+        affected.sort();
+
+        Ok(MergePlan {
+            destination,
+            destination_title,
+            heading,
+            new_tags,
+            affected,
+            merged,
+        })
+    }
+
+    /// Apply the previewed merge: write the destination, rewrite links and
+    /// move the source to the trash.
+    pub fn apply_merge(&mut self) {
+        let Some(merge) = self.merge.take() else {
+            return;
+        };
+        self.dialog = DialogState::None;
+        let Some(preview) = merge.plan else {
+            return;
+        };
+        // Recompute so edits made since the preview are not lost.
+        let plan = match self.plan_merge(merge.source, preview.destination) {
+            Ok(plan) => plan,
+            Err(error) => {
+                self.show_error_toast(error);
+                return;
+            }
+        };
+        let Some(source_path) = self.note_index_for_id(merge.source).and_then(|index| self.notes[index].file_path.clone()) else {
+            return;
+        };
+        if !self.persist_note_body(plan.destination, plan.merged) {
+            return;
+        }
+        let notes_root = self.config.notes_path();
+        let source_wiki = Self::calculate_wiki_path(&source_path, &notes_root).to_lowercase();
+        let source_title = merge.source_title.to_lowercase();
+        let destination_title = plan.destination_title.clone();
+        self.rewrite_wiki_links_in_vault(Some(&source_path), |target, suffix| {
+            let target = target.to_lowercase();
+            (target == source_wiki || target == source_title).then(|| format!("{destination_title}{suffix}"))
+        });
+        if self.move_to_trash(&source_path) {
+            self.run_hook(HookEvent::Delete, &source_path, None);
+        }
+
+        let destination_path = self.note_index_for_id(plan.destination).and_then(|index| self.notes[index].file_path.clone());
+        self.load_notes_from_dir();
+        if let Some(path) = destination_path {
+            self.select_note_by_path(&path);
+            self.select_current_note_in_sidebar();
+        }
+        self.show_toast(format!("Merged {} into {}", merge.source_title, plan.destination_title), ToastKind::Success);
+    }
+}
+
+/// The heading a merged note goes under and its body. A leading heading in
+/// the source names the section; otherwise its title does. Remaining
+/// headings are demoted to sit below the new `##` section.
+fn merged_section(lines: &[&str], title: &str) -> (String, String) {
+    let first = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let (section, rest) = match lines.get(first).and_then(|line| heading(line)) {
+        Some(heading) => (heading.text.to_string(), &lines[first + 1..]),
+        None => (title.to_string(), &lines[first..]),
+    };
+
+    let mut fence = None;
+    let headings: Vec<Option<usize>> = rest
+        .iter()
+        .map(|line| {
+            if let Some(marker) = fence_marker(line) {
+                fence = if fence == Some(marker) { None } else { fence.or(Some(marker)) };
+                return None;
+            }
+            fence.is_none().then(|| heading(line).map(|heading| heading.level)).flatten()
+        })
+        .collect();
+    let shallowest = headings.iter().flatten().min().copied().unwrap_or(3);
+    let demote = 3usize.saturating_sub(shallowest);
+    let body: Vec<String> = rest
+        .iter()
+        .zip(&headings)
+        .map(|(line, level)| match level {
+            Some(level) if demote > 0 => format!("{}{}", "#".repeat((level + demote).min(6)), &line[*level..]),
+            _ => line.to_string(),
+        })
+        .collect();
+    let start = body.iter().position(|line| !line.trim().is_empty()).unwrap_or(body.len());
+    let end = body.iter().rposition(|line| !line.trim().is_empty()).map_or(start, |end| end + 1);
+    (section, body[start..end].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    #[test]
+    fn merge_previews_then_appends_tags_links_and_trashes_the_source() {
+        let vault = TempVault::with_files(&[
+            ("draft.md", "---\ntags: [idea, rust]\n---\n# Draft idea\n\n## Detail\n\nbody\n"),
+            ("main.md", "---\ntags: [rust]\n---\n# Main\n\nintro\n"),
+            ("index.md", "[[draft]] and [[Draft#Detail|detail]] and [[main]]\n"),
+        ]);
+        let mut app = vault.app();
+        let id = |app: &App, name: &str| app.notes.iter().find(|note| note.title == name).unwrap().id;
+        let (draft, main) = (id(&app, "draft"), id(&app, "main"));
+
+        app.open_merge_dialog(draft);
+        app.merge_query_push('m');
+        app.merge_query_push('a');
+        assert_eq!(app.merge.as_ref().unwrap().results[0].display_name, "main");
+        app.preview_merge();
+        let plan = app.merge.as_ref().unwrap().plan.clone().unwrap();
+        assert_eq!(plan.destination, main);
+        assert_eq!(plan.heading, "Draft idea");
+        assert_eq!(plan.new_tags, ["idea"]);
+        assert_eq!(plan.affected, [("index.md".to_string(), 2)]);
+        assert_eq!(
+            fs::read_to_string(vault.notes().join("index.md")).unwrap(),
+            "[[draft]] and [[Draft#Detail|detail]] and [[main]]\n"
+        );
+
+        app.apply_merge();
+        let read = |name: &str| fs::read_to_string(vault.notes().join(name)).unwrap();
+        assert_eq!(
+            read("main.md"),
+            "---\ntags: [rust, idea]\n---\n# Main\n\nintro\n\n## Draft idea\n\n### Detail\n\nbody\n"
+        );
+        assert_eq!(read("index.md"), "[[main]] and [[main#Detail|detail]] and [[main]]\n");
+        assert!(!vault.notes().join("draft.md").exists());
+        assert_eq!(app.wiki_link_state("draft"), WikiLinkState::Trashed);
+        assert_eq!(app.current_note().map(|note| note.id), Some(main));
+    }
+}
@@ -0,0 +1,310 @@
+use super::*;
+
+use std::path::Path;
+
+/// Result rows shown at once; the list scrolls past this.
+pub const PALETTE_VISIBLE_ITEMS: usize = 12;
+/// Recent notes offered from the navigation history.
+const PALETTE_RECENT_NOTES: usize = 10;
+/// Score added per recorded use. The cap keeps the bonus well under the gap
+/// between match kinds, so usage reorders entries that match alike.
+const PALETTE_USAGE_WEIGHT: i32 = 20;
+const PALETTE_USAGE_CAP: u32 = 4;
+/// The fuzzy score of an exact match; no usage bonus reaches it.
+const PALETTE_EXACT_SCORE: i32 = 1000;
+/// Keys remembered before old counts start to age out.
+const PALETTE_USAGE_ENTRIES: usize = 256;
+
+/// Ex commands offered when the palette is opened from the editor.
+const PALETTE_EX_COMMANDS: &[(&str, &str, bool)] = &[
+    ("w", "Write note", false),
+    ("wq", "Write note and close editor", false),
+    ("q", "Close editor", false),
+    ("q!", "Discard changes and close editor", false),
+    ("%s/", "Substitute in note", true),
+    ("%!", "Filter note through a command", true),
+    ("r !", "Insert command output", true),
+    ("map", "List key mappings", false),
+    ("nnoremap ", "Map normal-mode keys", true),
+    ("inoremap ", "Map insert-mode keys", true),
+    ("vnoremap ", "Map visual-mode keys", true),
+    ("unmap ", "Remove a key mapping", true),
+];
+
+fn palette_usage_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("command_palette_usage")
+}
+
+/// Usage counts as `count<TAB>key` lines; unreadable lines are ignored.
+pub(super) fn read_palette_usage(cache_dir: &Path) -> HashMap<String, u32> {
+    let Ok(content) = fs::read_to_string(palette_usage_path(cache_dir)) else {
+        return HashMap::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            let (count, key) = line.split_once('\t')?;
+            Some((key.to_string(), count.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Make room for `key` by halving every count until the table has space,
+/// so keys for renamed notes and forgotten commands drop out over time.
+fn age_palette_usage(usage: &mut HashMap<String, u32>, key: &str) {
+    if usage.contains_key(key) {
+        return;
+    }
+    while usage.len() >= PALETTE_USAGE_ENTRIES {
+        usage.values_mut().for_each(|count| *count /= 2);
+        usage.retain(|_, count| *count > 0);
+    }
+}
+
+fn write_palette_usage(cache_dir: &Path, usage: &HashMap<String, u32>) {
+    let mut entries: Vec<_> = usage.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let content: String = entries.into_iter().map(|(key, count)| format!("{count}\t{key}\n")).collect();
+    let _ = fs::create_dir_all(cache_dir);
+    let _ = fs::write(palette_usage_path(cache_dir), content);
+}
+
+impl App {
+    /// Open the command palette. From the editor it also lists ex commands.
+    pub fn open_command_palette(&mut self) {
+        let from_editor = self.mode == Mode::Edit;
+        let mut entries: Vec<PaletteEntry> = AppCommand::ALL
+            .into_iter()
+            .filter(|command| *command != AppCommand::OpenCommandPalette)
+            .map(|command| PaletteEntry {
+                target: PaletteTarget::Command(command),
+                label: command.title().to_string(),
+                detail: self.keymap.binding_label(command),
+                usage_key: format!("command:{}", command.id()),
+            })
+            .collect();
+
+        if from_editor {
+            entries.extend(PALETTE_EX_COMMANDS.iter().map(|&(command, title, prefill)| PaletteEntry {
+                target: PaletteTarget::Ex { command, prefill },
+                label: title.to_string(),
+                detail: format!(":{}", command.trim_end()),
+                usage_key: format!("ex:{}", command.trim_end()),
+            }));
+        }
+
+        let mut seen = HashSet::new();
+        let recent: Vec<usize> = self
+            .navigation_history
+            .iter()
+            .rev()
+            .filter_map(|entry| self.note_index_for_id(entry.note_id))
+            .filter(|index| seen.insert(*index))
+            .take(PALETTE_RECENT_NOTES)
+            .collect();
+        for index in recent {
+            let note = &self.notes[index];
+            let wiki_path = self.get_wiki_path_for_note(index).unwrap_or_else(|| note.title.clone());
+            entries.push(PaletteEntry {
+                target: PaletteTarget::Note(note.id),
+                label: note.title.clone(),
+                detail: wiki_path
+                    .rfind('/')
+                    .map_or_else(|| "Recent note".to_string(), |pos| wiki_path[..pos].to_string()),
+                usage_key: format!("note:{wiki_path}"),
+            });
+        }
+
+        entries.extend(self.config.actions.iter().enumerate().map(|(index, action)| PaletteEntry {
+            target: PaletteTarget::Action(index),
+            label: action.name.clone(),
+            detail: "Action".to_string(),
+            usage_key: format!("action:{}", action.name),
+        }));
+
+        let themes = ThemeFile::list_available_in(&Config::themes_dir_in(&self.dependencies.config_dir));
+        entries.extend(themes.into_iter().map(|theme| PaletteEntry {
+            label: format!("Theme: {}", theme.name),
+            detail: if theme.name == self.config.theme {
+                "active".to_string()
+            } else {
+                String::new()
+            },
+            usage_key: format!("theme:{}", theme.name),
+            target: PaletteTarget::Theme(theme.name),
+        }));
+
+        let selection = if from_editor {
+            match self.vim_mode {
+                VimMode::VisualLine => self.editor.visual_line_selected_text(),
+                VimMode::VisualBlock => self.editor.visual_block_selected_text(),
+                _ => self.editor.selected_text(),
+            }
+        } else {
+            None
+        };
+        self.command_palette = CommandPaletteState {
+            entries,
+            from_editor,
+            selection,
+            ..CommandPaletteState::default()
+        };
+        self.refresh_command_palette();
+        self.dialog = DialogState::CommandPalette;
+    }
+
+    pub fn close_command_palette(&mut self) {
+        self.command_palette = CommandPaletteState::default();
+        self.dialog = DialogState::None;
+    }
+
+    /// Re-rank entries for the current query.
+    pub fn refresh_command_palette(&mut self) {
+        let palette = &mut self.command_palette;
+        let query = palette.query.trim();
+        let mut scored: Vec<(usize, i32)> = palette
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let score = fuzzy_match(&entry.label, query).or_else(|| fuzzy_match(&entry.detail, query).map(|score| score / 2))?;
+                if score >= PALETTE_EXACT_SCORE {
+                    return Some((index, score));
+                }
+                let uses = self.palette_usage.get(&entry.usage_key).copied().unwrap_or(0);
+                let boosted = score + uses.min(PALETTE_USAGE_CAP) as i32 * PALETTE_USAGE_WEIGHT;
+                Some((index, boosted.min(PALETTE_EXACT_SCORE - 1)))
+            })
+            .collect();
+        // Stable, so equally ranked entries keep their listing order.
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        palette.matches = scored.into_iter().map(|(index, _)| index).collect();
+        palette.selected = 0;
+        palette.scroll_offset = 0;
+    }
+
+    pub fn command_palette_move(&mut self, down: bool) {
+        let palette = &mut self.command_palette;
+        let len = palette.matches.len();
+        if len == 0 {
+            return;
+        }
+        palette.selected = if down {
+            (palette.selected + 1) % len
+        } else {
+            (palette.selected + len - 1) % len
+        };
+        if palette.selected < palette.scroll_offset {
+            palette.scroll_offset = palette.selected;
+        } else if palette.selected >= palette.scroll_offset + PALETTE_VISIBLE_ITEMS {
+            palette.scroll_offset = palette.selected + 1 - PALETTE_VISIBLE_ITEMS;
+        }
+    }
+
+    /// Close the palette and return the chosen target, recording its use.
+    pub fn accept_command_palette(&mut self) -> Option<PaletteTarget> {
+        let palette = std::mem::take(&mut self.command_palette);
+        self.dialog = DialogState::None;
+        let entry = palette.matches.get(palette.selected).and_then(|index| palette.entries.get(*index))?;
+        age_palette_usage(&mut self.palette_usage, &entry.usage_key);
+        *self.palette_usage.entry(entry.usage_key.clone()).or_insert(0) += 1;
+        write_palette_usage(&self.dependencies.cache_dir, &self.palette_usage);
+        Some(entry.target.clone())
+    }
+
+    /// Switch to `name` and persist it, as confirming the theme selector does.
+    pub fn set_theme(&mut self, name: &str) {
+        self.config.theme = name.to_string();
+        let _ = self.config.save_to_dir(&self.dependencies.config_dir);
+        self.apply_theme_named(name);
+        self.status_message = Some(format!("Theme: {name}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[("alpha.md", "# Alpha\n")])
+    }
+
+    fn selected_label(app: &App) -> &str {
+        let palette = &app.command_palette;
+        &palette.entries[palette.matches[palette.selected]].label
+    }
+
+    #[test]
+    fn palette_lists_commands_with_bindings_and_filters_fuzzily() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        app.open_command_palette();
+
+        let graph = app
+            .command_palette
+            .entries
+            .iter()
+            .find(|entry| entry.target == PaletteTarget::Command(AppCommand::OpenGraph))
+            .unwrap();
+        assert_eq!(graph.detail, "Ctrl+g");
+        assert!(!app.command_palette.entries.iter().any(|entry| matches!(entry.target, PaletteTarget::Ex { .. })));
+
+        app.command_palette.query = "graph".to_string();
+        app.refresh_command_palette();
+        assert_eq!(selected_label(&app), "Open graph view");
+    }
+
+    #[test]
+    fn frequently_chosen_entries_rank_first_across_sessions() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+        for _ in 0..3 {
+            app.open_command_palette();
+            app.command_palette.query = "agenda".to_string();
+            app.refresh_command_palette();
+            assert_eq!(app.accept_command_palette(), Some(PaletteTarget::Command(AppCommand::OpenAgenda)));
+        }
+        assert_eq!(app.dialog, DialogState::None);
+
+        let mut app = vault.app();
+        app.open_command_palette();
+        assert_eq!(selected_label(&app), "Open task agenda");
+
+        app.command_palette.query = "open".to_string();
+        app.refresh_command_palette();
+        assert_eq!(selected_label(&app), "Open task agenda");
+    }
+
+    #[test]
+    fn usage_never_outranks_an_exact_match() {
+        let vault = sample_vault();
+        let action = |name: &str| crate::config::ActionConfig {
+            name: name.to_string(),
+            command: "true".to_string(),
+        };
+        let mut config = Config::default();
+        config.actions = vec![action("Weekly plan"), action("Planning"), action("Plan")];
+        let mut app = vault.app_with(config);
+        app.palette_usage.insert("action:Weekly plan".to_string(), 100);
+        app.palette_usage.insert("action:Planning".to_string(), 100);
+
+        app.open_command_palette();
+        app.command_palette.query = "plan".to_string();
+        app.refresh_command_palette();
+        let palette = &app.command_palette;
+        let labels: Vec<_> = palette.matches.iter().take(3).map(|index| palette.entries[*index].label.as_str()).collect();
+        assert_eq!(labels, ["Plan", "Planning", "Weekly plan"]);
+    }
+
+    #[test]
+    fn usage_table_ages_out_old_keys() {
+        let mut usage: HashMap<String, u32> = (0..PALETTE_USAGE_ENTRIES).map(|index| (format!("note:{index}"), 1)).collect();
+        usage.insert("note:0".to_string(), 6);
+        age_palette_usage(&mut usage, "note:0");
+        assert_eq!(usage.len(), PALETTE_USAGE_ENTRIES, "a known key needs no room");
+
+        age_palette_usage(&mut usage, "note:new");
+        assert_eq!(usage.into_iter().collect::<Vec<_>>(), [("note:0".to_string(), 3)]);
+    }
+}
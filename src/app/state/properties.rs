@@ -0,0 +1,411 @@
+use super::*;
+
+use chrono::{Datelike, Months, NaiveDate};
+use ekphos_vault::properties::{self, PropertyKind, PropertyValue};
+
+/// Suggestions shown under a property input.
+const PROPERTY_SUGGESTION_LIMIT: usize = 5;
+
+impl App {
+    /// Open the properties panel for the current note. Autocomplete draws on
+    /// the frontmatter of every note in the vault.
+    pub fn open_properties(&mut self) {
+        if self.mode != Mode::Normal {
+            return;
+        }
+        let Some(note_id) = self.current_note().map(|note| note.id) else {
+            self.show_error_toast("Open a note to edit its properties");
+            return;
+        };
+        self.ensure_frontmatter_corpus();
+        let vocabulary = self
+            .frontmatter_corpus
+            .as_ref()
+            .map(|(_, notes)| PropertyVocabulary::from_frontmatter(notes.iter().filter_map(|note| note.frontmatter.as_ref())))
+            .unwrap_or_default();
+        self.properties = PropertiesState {
+            note_id: Some(note_id),
+            vocabulary,
+            ..PropertiesState::default()
+        };
+        self.reload_properties();
+        self.dialog = DialogState::Properties;
+    }
+
+    pub fn close_properties(&mut self) {
+        self.properties = PropertiesState::default();
+        self.dialog = DialogState::None;
+    }
+
+    fn properties_body(&self) -> Option<&str> {
+        let note_id = self.properties.note_id?;
+        if self.current_note().map(|note| note.id) != Some(note_id) {
+            return None;
+        }
+        self.document().map(DocumentSnapshot::body)
+    }
+
+    fn reload_properties(&mut self) {
+        let Some(body) = self.properties_body() else {
+            self.properties.properties.clear();
+            return;
+        };
+        match properties::parse_properties(body) {
+            Some(parsed) => self.properties.properties = parsed,
+            None if ekphos_core::markdown::frontmatter_end(body).is_some() => {
+                self.properties.properties.clear();
+                self.properties.error = Some("Frontmatter is not valid YAML; fix it in the editor".to_owned());
+            }
+            None => self.properties.properties.clear(),
+        }
+        self.properties.selected = self.properties.selected.min(self.properties.properties.len().saturating_sub(1));
+    }
+
+    /// Rewrite the note through `edit` and reload the panel. Returns false
+    /// when nothing was written.
+    fn write_properties(&mut self, edit: impl FnOnce(&str) -> String) -> bool {
+        let Some(note_id) = self.properties.note_id else {
+            return false;
+        };
+        let Some(body) = self.properties_body() else {
+            self.properties.error = Some("The note is no longer open".to_owned());
+            return false;
+        };
+        if properties::parse_properties(body).is_none() && ekphos_core::markdown::frontmatter_end(body).is_some() {
+            self.properties.error = Some("Frontmatter is not valid YAML; fix it in the editor".to_owned());
+            return false;
+        }
+        let updated = edit(body);
+        if updated == body {
+            return false;
+        }
+        if !self.persist_note_body(note_id, updated) {
+            return false;
+        }
+        self.properties.error = None;
+        self.reload_properties();
+        true
+    }
+
+    fn set_selected_property(&mut self, value: PropertyValue) {
+        let Some(key) = self.selected_property().map(|property| property.key.clone()) else {
+            return;
+        };
+        self.write_properties(|body| properties::set_property(body, &key, &value));
+    }
+
+    pub fn selected_property(&self) -> Option<&Property> {
+        self.properties.properties.get(self.properties.selected)
+    }
+
+    pub fn properties_select_next(&mut self) {
+        if self.properties.selected + 1 < self.properties.properties.len() {
+            self.properties.selected += 1;
+        }
+    }
+
+    pub fn properties_select_prev(&mut self) {
+        self.properties.selected = self.properties.selected.saturating_sub(1);
+    }
+
+    /// Edit the selected value in place. Booleans toggle immediately.
+    pub fn properties_edit_selected(&mut self) {
+        let Some(property) = self.selected_property() else {
+            return;
+        };
+        let editor = match &property.value {
+            PropertyValue::Bool(value) => {
+                let toggled = PropertyValue::Bool(!value);
+                self.set_selected_property(toggled);
+                return;
+            }
+            PropertyValue::Raw(_) => {
+                self.properties.error = Some(format!("`{}` holds nested values; edit it in the editor", property.key));
+                return;
+            }
+            PropertyValue::Text(text) | PropertyValue::Number(text) => PropertyEditor::Text { input: text.clone() },
+            PropertyValue::Date(date) => PropertyEditor::Date { date: *date },
+            PropertyValue::List(items) => PropertyEditor::List {
+                items: items.clone(),
+                selected: 0,
+                input: None,
+            },
+        };
+        self.properties.error = None;
+        self.properties.editor = Some(editor);
+    }
+
+    pub fn properties_add(&mut self) {
+        self.properties.error = None;
+        self.properties.editor = Some(PropertyEditor::NewKey { input: String::new() });
+    }
+
+    pub fn properties_delete_selected(&mut self) {
+        let Some(key) = self.selected_property().map(|property| property.key.clone()) else {
+            return;
+        };
+        self.write_properties(|body| properties::remove_property(body, &key));
+    }
+
+    /// The text field the editor is typing into, if any.
+    pub fn properties_input_mut(&mut self) -> Option<&mut String> {
+        match self.properties.editor.as_mut()? {
+            PropertyEditor::NewKey { input } | PropertyEditor::Text { input } => Some(input),
+            PropertyEditor::List { input, .. } => input.as_mut(),
+            PropertyEditor::Date { .. } => None,
+        }
+    }
+
+    /// Keys or values used elsewhere in the vault that extend the current input.
+    pub fn property_suggestions(&self) -> Vec<String> {
+        let state = &self.properties;
+        let key = self.selected_property().map_or("", |property| property.key.as_str());
+        let candidates = match &state.editor {
+            Some(PropertyEditor::NewKey { input }) => state
+                .vocabulary
+                .keys(input.trim())
+                .into_iter()
+                .filter(|candidate| !state.properties.iter().any(|property| property.key == *candidate))
+                .collect(),
+            Some(PropertyEditor::Text { input }) => state.vocabulary.values(key, input),
+            Some(PropertyEditor::List { items, input: Some(input), .. }) => state
+                .vocabulary
+                .values(key, input.trim())
+                .into_iter()
+                .filter(|candidate| !items.iter().any(|item| item == candidate))
+                .collect(),
+            _ => Vec::new(),
+        };
+        candidates.into_iter().take(PROPERTY_SUGGESTION_LIMIT).map(str::to_owned).collect()
+    }
+
+    /// Replace the input with the best suggestion.
+    pub fn properties_complete(&mut self) {
+        if let Some(suggestion) = self.property_suggestions().into_iter().next() {
+            if let Some(input) = self.properties_input_mut() {
+                *input = suggestion;
+            }
+        }
+    }
+
+    pub fn properties_submit(&mut self) {
+        let Some(editor) = self.properties.editor.take() else {
+            return;
+        };
+        match editor {
+            PropertyEditor::NewKey { input } => self.add_property(input.trim()),
+            PropertyEditor::Text { input } => {
+                let value = match self.selected_property().map(|property| property.value.kind()) {
+                    Some(PropertyKind::Number) => {
+                        let input = input.trim();
+                        if input.parse::<f64>().is_err() {
+                            self.properties.error = Some(format!("`{input}` is not a number"));
+                            self.properties.editor = Some(PropertyEditor::Text { input: input.to_owned() });
+                            return;
+                        }
+                        PropertyValue::Number(input.to_owned())
+                    }
+                    _ => PropertyValue::Text(input),
+                };
+                self.set_selected_property(value);
+            }
+            PropertyEditor::Date { date } => self.set_selected_property(PropertyValue::Date(date)),
+            PropertyEditor::List {
+                mut items,
+                selected,
+                input: Some(input),
+            } => {
+                let item = input.trim();
+                if !item.is_empty() && !items.iter().any(|existing| existing == item) {
+                    items.push(item.to_owned());
+                }
+                let selected = if item.is_empty() { selected } else { items.len() - 1 };
+                self.properties.editor = Some(PropertyEditor::List { items, selected, input: None });
+            }
+            PropertyEditor::List { items, input: None, .. } => self.set_selected_property(PropertyValue::List(items)),
+        }
+    }
+
+    fn add_property(&mut self, key: &str) {
+        if key.is_empty() || key.contains(':') || key.starts_with(['#', '-']) {
+            self.properties.error = Some("Property names cannot be empty or contain `:`".to_owned());
+            self.properties.editor = Some(PropertyEditor::NewKey { input: key.to_owned() });
+            return;
+        }
+        if let Some(index) = self.properties.properties.iter().position(|property| property.key == key) {
+            self.properties.selected = index;
+            self.properties_edit_selected();
+            return;
+        }
+        let kind = self.properties.vocabulary.kind(key).unwrap_or(PropertyKind::Text);
+        let value = PropertyValue::empty(kind, self.dependencies.clock.today());
+        if !self.write_properties(|body| properties::set_property(body, key, &value)) {
+            return;
+        }
+        if let Some(index) = self.properties.properties.iter().position(|property| property.key == key) {
+            self.properties.selected = index;
+            if kind != PropertyKind::Bool {
+                self.properties_edit_selected();
+            }
+        }
+    }
+
+    /// Leave the innermost editing step: an item being typed, then the editor.
+    pub fn properties_cancel(&mut self) {
+        match &mut self.properties.editor {
+            Some(PropertyEditor::List { input: input @ Some(_), .. }) => *input = None,
+            _ => self.properties.editor = None,
+        }
+        self.properties.error = None;
+    }
+
+    pub fn properties_shift_date(&mut self, days: i64) {
+        if let Some(PropertyEditor::Date { date }) = &mut self.properties.editor {
+            *date = date.checked_add_signed(chrono::Duration::days(days)).unwrap_or(*date);
+        }
+    }
+
+    pub fn properties_shift_month(&mut self, forward: bool) {
+        if let Some(PropertyEditor::Date { date }) = &mut self.properties.editor {
+            let shifted = if forward {
+                date.checked_add_months(Months::new(1))
+            } else {
+                date.checked_sub_months(Months::new(1))
+            };
+            *date = shifted.unwrap_or(*date);
+        }
+    }
+
+    pub fn properties_date_today(&mut self) {
+        let today = self.dependencies.clock.today();
+        if let Some(PropertyEditor::Date { date }) = &mut self.properties.editor {
+            *date = today;
+        }
+    }
+
+    pub fn properties_list_move(&mut self, down: bool) {
+        if let Some(PropertyEditor::List { items, selected, .. }) = &mut self.properties.editor {
+            *selected = if down {
+                (*selected + 1).min(items.len().saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
+    }
+
+    pub fn properties_list_add(&mut self) {
+        if let Some(PropertyEditor::List { input, .. }) = &mut self.properties.editor {
+            *input = Some(String::new());
+        }
+    }
+
+    pub fn properties_list_delete(&mut self) {
+        if let Some(PropertyEditor::List { items, selected, .. }) = &mut self.properties.editor {
+            if *selected < items.len() {
+                items.remove(*selected);
+                *selected = (*selected).min(items.len().saturating_sub(1));
+            }
+        }
+    }
+}
+
+/// Days shown by the date picker: the weeks (Monday first) covering the
+/// month of `date`, with `None` padding outside the month.
+pub(crate) fn month_grid(date: NaiveDate) -> Vec<[Option<NaiveDate>; 7]> {
+    let Some(first) = date.with_day(1) else {
+        return Vec::new();
+    };
+    let offset = first.weekday().num_days_from_monday() as usize;
+    let mut weeks = Vec::new();
+    let mut week = [None; 7];
+    let mut day = Some(first);
+    let mut column = offset;
+    while let Some(current) = day.filter(|current| current.month() == first.month()) {
+        week[column] = Some(current);
+        column += 1;
+        if column == 7 {
+            weeks.push(week);
+            week = [None; 7];
+            column = 0;
+        }
+        day = current.succ_opt();
+    }
+    if column > 0 {
+        weeks.push(week);
+    }
+    weeks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    fn select(app: &mut App, key: &str) {
+        app.properties.selected = app.properties.properties.iter().position(|property| property.key == key).unwrap();
+    }
+
+    #[test]
+    fn edits_typed_values_and_writes_them_back_in_place() {
+        let vault = TempVault::with_files(&[
+            ("Other.md", "---\nstatus: blocked\nreviewed: 2026-01-01\ntags: [work, home]\n---\n"),
+            (
+                "Plan.md",
+                "---\nstatus: active # current\ndue: 2026-10-20\ndone: false\ntags: [work]\n---\nBody\n",
+            ),
+        ]);
+        let notes = vault.notes();
+        let mut app = vault.app_on(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert!(app.select_note_by_path(&notes.join("Plan.md")));
+        app.open_properties();
+        assert_eq!(app.dialog, DialogState::Properties);
+        assert_eq!(app.properties.properties.len(), 4);
+
+        select(&mut app, "status");
+        app.properties_edit_selected();
+        *app.properties_input_mut().unwrap() = "b".to_owned();
+        assert_eq!(app.property_suggestions(), ["blocked"]);
+        app.properties_complete();
+        app.properties_submit();
+
+        select(&mut app, "due");
+        app.properties_edit_selected();
+        app.properties_shift_date(7);
+        app.properties_shift_month(true);
+        app.properties_submit();
+
+        select(&mut app, "done");
+        app.properties_edit_selected();
+
+        select(&mut app, "tags");
+        app.properties_edit_selected();
+        app.properties_list_add();
+        *app.properties_input_mut().unwrap() = "h".to_owned();
+        app.properties_complete();
+        app.properties_submit();
+        app.properties_submit();
+
+        app.properties_add();
+        *app.properties_input_mut().unwrap() = "rev".to_owned();
+        app.properties_complete();
+        app.properties_submit();
+        assert!(matches!(app.properties.editor, Some(PropertyEditor::Date { .. })));
+        app.properties_date_today();
+        app.properties_submit();
+
+        assert_eq!(
+            fs::read_to_string(notes.join("Plan.md")).unwrap(),
+            "---\nstatus: blocked # current\ndue: 2026-11-27\ndone: true\ntags: [work, home]\nreviewed: 2026-10-18\n---\nBody\n"
+        );
+        assert_eq!(app.properties.error, None);
+    }
+
+    #[test]
+    fn month_grid_starts_weeks_on_monday() {
+        let grid = month_grid(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid[0][..3], [None, None, None]);
+        assert_eq!(grid[0][3], NaiveDate::from_ymd_opt(2026, 10, 1));
+        assert_eq!(grid[4][5], NaiveDate::from_ymd_opt(2026, 10, 31));
+    }
+}
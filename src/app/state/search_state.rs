@@ -30,9 +30,8 @@ impl App {
 
         std::thread::spawn(move || {
             let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                let cached = search::load_index(&index_path);
                 let mut loaded = 0usize;
-                let mut load = |source: &search::SearchSource| {
+                let index = load_or_build_search_index(&index_path, &notes_dir, &sources, |source| {
                     if generation_signal.load(Ordering::Acquire) != generation {
                         return None;
                     }
@@ -40,16 +39,7 @@ impl App {
                     loaded += 1;
                     progress.store(loaded, Ordering::Relaxed);
                     body
-                };
-                let index = if let Some(cached) = cached {
-                    if cached.matches_sources(&notes_dir, &sources) {
-                        Ok(cached)
-                    } else {
-                        cached.update_from_loader(&notes_dir, &sources, &mut load)
-                    }
-                } else {
-                    SearchIndex::build_from_loader(&notes_dir, &sources, &mut load)
-                };
+                });
                 progress.store(sources.len(), Ordering::Relaxed);
                 total.store(sources.len(), Ordering::Relaxed);
                 if let Ok(index) = index {
@@ -68,6 +58,21 @@ impl App {
         });
     }
 
+    /// Load or refresh the cached search index on the calling thread, for
+    /// command-line subcommands that have no event loop to poll.
+    pub fn load_search_index_blocking(&mut self) {
+        let notes_dir = self.config.notes_path();
+        let index_path = search::get_index_path_in(&self.dependencies.cache_dir, &notes_dir);
+        let sources = self.search_index_sources();
+        let index = load_or_build_search_index(&index_path, &notes_dir, &sources, |source| {
+            fs::read_to_string(&source.absolute_path).ok().map(Arc::<str>::from)
+        });
+        if let Ok(index) = index {
+            let _ = search::save_index(&index, &index_path);
+            self.search_index = Some(Arc::new(index));
+        }
+    }
+
     pub fn poll_index_build(&mut self) {
         if !self.indexing_in_progress {
             return;
@@ -453,12 +458,20 @@ impl App {
                             None
                         }
                     });
-                let score = score?;
+                // Fall back to frontmatter aliases, naming the note they belong to
+                let (score, display_name) = match score {
+                    Some(score) => (score, note.title.clone()),
+                    None => self
+                        .note_aliases(note.id)
+                        .iter()
+                        .filter_map(|alias| Some((fuzzy_match(alias, query)?, format!("{alias} → {}", note.title))))
+                        .max_by_key(|(score, _)| *score)?,
+                };
 
                 let folder_hint = wiki_path.and_then(|wp| wp.rfind('/').map(|pos| wp[..pos].to_string()));
 
                 Some(FilePickerResult {
-                    display_name: note.title.clone(),
+                    display_name,
                     folder_hint,
                     note_index: idx,
                     score,
@@ -901,3 +914,18 @@ impl App {
         }
     }
 }
+
+/// Reuse the cached index when it still matches `sources`, refresh it
+/// incrementally when it does not, and build from scratch without a cache.
+fn load_or_build_search_index(
+    index_path: &std::path::Path,
+    notes_dir: &std::path::Path,
+    sources: &[search::SearchSource],
+    mut load: impl FnMut(&search::SearchSource) -> Option<Arc<str>>,
+) -> Result<SearchIndex, search::SearchIndexError> {
+    match search::load_index(index_path) {
+        Some(cached) if cached.matches_sources(notes_dir, sources) => Ok(cached),
+        Some(cached) => cached.update_from_loader(notes_dir, sources, &mut load),
+        None => SearchIndex::build_from_loader(notes_dir, sources, &mut load),
+    }
+}
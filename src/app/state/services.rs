@@ -120,6 +120,7 @@ impl App {
     pub fn request_highlight_update(&mut self) {
         self.highlight_version += 1;
         self.highlight_pending = true;
+        self.update_git_signs();
 
         if let Some(ref worker) = self.highlight_worker {
             let content = self.editor.lines().join("\n");
@@ -8,8 +8,8 @@ pub enum BlockInsertMode {
 
 /// Severity of a transient [`Toast`] notification, used to pick its accent color.
 ///
-/// `Info`/`Success` round out the notification API for future callers; only
-/// `Error` is raised today (see [`App::show_error_toast`]).
+/// `Info` rounds out the notification API for future callers; errors (see
+/// [`App::show_error_toast`]) and finished palette actions raise the others.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub enum ToastKind {
@@ -98,6 +98,173 @@ pub enum DialogState {
     CreateWikiNote,
     GraphView,
     ThemeSelector,
+    Agenda,
+    Properties,
+    CommandPalette,
+    CommandLog,
+    GitCommit,
+    GitHistory,
+    NoteHistory,
+    Trash,
+    MergeNote,
+    HeadingRename,
+    VaultDoctor,
+}
+
+/// "Merge into…": pick a destination for `source`, then confirm a dry run.
+#[derive(Debug, Clone)]
+pub struct MergeState {
+    pub source: NoteId,
+    pub source_title: String,
+    pub query: String,
+    pub results: Vec<FilePickerResult>,
+    pub selected: usize,
+    /// Set once a destination is chosen; the preview shown before applying.
+    pub plan: Option<MergePlan>,
+}
+
+/// What a merge will do, computed without touching the vault.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergePlan {
+    pub destination: NoteId,
+    pub destination_title: String,
+    /// Heading the source body is appended under.
+    pub heading: String,
+    /// Frontmatter tags the destination gains.
+    pub new_tags: Vec<String>,
+    /// Vault-relative files whose links change, with the number of links.
+    pub affected: Vec<(String, usize)>,
+    /// Destination text after the merge.
+    pub merged: String,
+}
+
+/// Renaming a heading and the section links that point at it.
+#[derive(Debug, Clone)]
+pub struct HeadingRenameState {
+    pub note: NoteId,
+    /// Row of the heading to rewrite when renaming by command; `None` when
+    /// the rename was detected on save and is already written.
+    pub heading_row: Option<usize>,
+    /// `(old, new)` heading texts.
+    pub renames: Vec<(String, String)>,
+    /// New name being typed; the preview is shown once it is submitted.
+    pub input: Option<String>,
+    /// Vault-relative files whose links change, with the number of links.
+    pub affected: Vec<(String, usize)>,
+}
+
+/// Something the vault doctor found wrong with a note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DoctorIssueKind {
+    BrokenLink,
+    MissingImage,
+    InvalidFrontmatter,
+    InvalidEncoding,
+    DuplicateTitle,
+    EmptyNote,
+    Orphan,
+}
+
+impl DoctorIssueKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::BrokenLink => "broken link",
+            Self::MissingImage => "missing image",
+            Self::InvalidFrontmatter => "invalid frontmatter",
+            Self::InvalidEncoding => "not UTF-8",
+            Self::DuplicateTitle => "duplicate title",
+            Self::EmptyNote => "empty note",
+            Self::Orphan => "orphan",
+        }
+    }
+
+    /// Errors break links or rendering; the rest are worth a look but only
+    /// fail `ekphos check --strict`.
+    pub const fn is_error(self) -> bool {
+        matches!(self, Self::BrokenLink | Self::MissingImage | Self::InvalidFrontmatter | Self::InvalidEncoding)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorIssue {
+    pub kind: DoctorIssueKind,
+    pub path: PathBuf,
+    /// Zero-based source row, when the issue sits on one line.
+    pub line: Option<usize>,
+    pub detail: String,
+}
+
+/// Vault doctor: the last health report and the selected issue.
+#[derive(Debug, Clone)]
+pub struct DoctorState {
+    pub issues: Vec<DoctorIssue>,
+    pub selected: usize,
+    pub scroll: usize,
+}
+
+/// Where a `[[wiki link]]` target points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WikiLinkState {
+    Exists,
+    Missing,
+    /// The note was deleted and sits in the vault trash.
+    Trashed,
+}
+
+/// Trash browser: deleted notes and folders, newest first.
+#[derive(Debug, Clone)]
+pub struct TrashBrowserState {
+    pub entries: Vec<ekphos_vault::trash::TrashEntry>,
+    pub selected: usize,
+    pub scroll: usize,
+    /// Destructive action waiting for `y`.
+    pub confirm: Option<TrashConfirm>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrashConfirm {
+    Purge,
+    Empty,
+}
+
+/// Local snapshot browser: saved versions of a note compared side by side
+/// with the current buffer.
+#[derive(Debug, Clone)]
+pub struct NoteHistoryState {
+    pub note_id: NoteId,
+    pub title: String,
+    /// Vault-relative path the snapshots are stored under.
+    pub note: String,
+    pub snapshots: Vec<ekphos_vault::snapshots::Snapshot>,
+    pub selected: usize,
+    pub list_scroll: usize,
+    pub diff_scroll: usize,
+    pub version: Vec<String>,
+    pub current: Vec<String>,
+    /// Edit script from `version` to `current`.
+    pub diff: Vec<ekphos_core::diff::DiffOp>,
+    pub error: Option<String>,
+}
+
+/// Per-note history browser: commits touching the note and the selected
+/// version diffed against the current text.
+#[derive(Debug, Clone)]
+pub struct GitHistoryState {
+    pub note_id: NoteId,
+    pub title: String,
+    pub commits: Vec<crate::git::GitCommit>,
+    pub selected: usize,
+    pub list_scroll: usize,
+    pub diff_scroll: usize,
+    /// Lines of the selected version; empty when it failed to load.
+    pub version: Vec<String>,
+    /// Lines the version is compared against (the note as it is now).
+    pub current: Vec<String>,
+    /// Edit script from `version` to `current`.
+    pub diff: Vec<ekphos_core::diff::DiffOp>,
+    pub error: Option<String>,
+    /// The selected version is still being read.
+    pub loading: bool,
 }
 
 /// State for the theme selector modal (opened with Ctrl+T). Live-previews the
@@ -112,6 +279,180 @@ pub struct ThemePicker {
     pub original_theme_name: String,
 }
 
+/// How the agenda groups collected tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgendaGrouping {
+    #[default]
+    Note,
+    Tag,
+    DueDate,
+}
+
+impl AgendaGrouping {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Note => Self::Tag,
+            Self::Tag => Self::DueDate,
+            Self::DueDate => Self::Note,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Tag => "tag",
+            Self::DueDate => "due date",
+        }
+    }
+}
+
+/// Which tasks the agenda shows, relative to the clock's local date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AgendaFilter {
+    #[default]
+    All,
+    Overdue,
+    Today,
+    Upcoming,
+}
+
+impl AgendaFilter {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Overdue => "overdue",
+            Self::Today => "today",
+            Self::Upcoming => "upcoming",
+        }
+    }
+
+    pub fn matches(self, due: Option<chrono::NaiveDate>, today: chrono::NaiveDate) -> bool {
+        match self {
+            Self::All => true,
+            Self::Overdue => due.is_some_and(|due| due < today),
+            Self::Today => due == Some(today),
+            Self::Upcoming => due.is_some_and(|due| due > today),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgendaRow {
+    Group {
+        label: String,
+        count: usize,
+    },
+    /// Index into `AgendaState::tasks`.
+    Task(usize),
+}
+
+/// State for the vault-wide task agenda. Tasks are collected by a background
+/// [`TaskWorker`]; rows are rebuilt whenever grouping or filters change, so a
+/// task toggled in place stays visible until then.
+#[derive(Debug, Clone, Default)]
+pub struct AgendaState {
+    pub tasks: Vec<VaultTask>,
+    pub rows: Vec<AgendaRow>,
+    pub grouping: AgendaGrouping,
+    pub filter: AgendaFilter,
+    pub show_completed: bool,
+    /// Index into `rows`; always a task row while any exist.
+    pub selected: usize,
+    pub scroll_offset: usize,
+    pub loading: bool,
+    pub request_id: u64,
+}
+
+/// In-place editor for one property. Text inputs autocomplete from
+/// [`PropertiesState::vocabulary`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyEditor {
+    /// Naming a property to add.
+    NewKey {
+        input: String,
+    },
+    /// Text and number values.
+    Text {
+        input: String,
+    },
+    Date {
+        date: chrono::NaiveDate,
+    },
+    List {
+        items: Vec<String>,
+        selected: usize,
+        /// Item being typed, when adding one.
+        input: Option<String>,
+    },
+}
+
+/// Typed frontmatter panel for the current note.
+#[derive(Debug, Clone, Default)]
+pub struct PropertiesState {
+    pub note_id: Option<NoteId>,
+    pub properties: Vec<Property>,
+    pub selected: usize,
+    pub editor: Option<PropertyEditor>,
+    pub error: Option<String>,
+    pub vocabulary: PropertyVocabulary,
+}
+
+/// What a command palette entry runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteTarget {
+    Command(AppCommand),
+    /// Vim ex command; `prefill` opens the command line with it instead of
+    /// running it, for commands that take arguments.
+    Ex {
+        command: &'static str,
+        prefill: bool,
+    },
+    Theme(String),
+    Note(NoteId),
+    /// Index into the configured `[[actions]]`.
+    Action(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub target: PaletteTarget,
+    pub label: String,
+    /// Binding, ex syntax or folder shown right-aligned.
+    pub detail: String,
+    /// Stable identity under which usage is counted across sessions.
+    pub usage_key: String,
+}
+
+/// Fuzzy palette over app commands, ex commands, themes and recent notes,
+/// ranked by match quality and how often each entry was chosen.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteState {
+    pub entries: Vec<PaletteEntry>,
+    pub query: String,
+    /// Indices into `entries`, best first.
+    pub matches: Vec<usize>,
+    pub selected: usize,
+    pub scroll_offset: usize,
+    /// Opened from the editor, so ex commands are offered.
+    pub from_editor: bool,
+    /// Editor selection when the palette opened, piped to actions.
+    pub selection: Option<String>,
+}
+
+/// A finished hook or action as listed in the command log.
+#[derive(Debug, Clone)]
+pub struct CommandLogEntry {
+    /// Hook key (`on_save`) or action name.
+    pub source: String,
+    pub command: String,
+    /// Vault-relative path of the note the command ran for.
+    pub note: Option<String>,
+    pub status: crate::shell::ShellStatus,
+    /// Captured stdout followed by stderr, capped to the last lines.
+    pub output: Vec<String>,
+    pub finished_at: std::time::Instant,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum SortMode {
     #[default]
@@ -163,6 +504,24 @@ pub struct GraphViewState {
     pub filter_editing: bool,
     pub show_orphans: bool,
     pub help_visible: bool,
+    /// Waiting for a format key after `e`.
+    pub export_prompt: bool,
+    pub export_positions: bool,
+    /// Start of the highlighted shortest path, marked with `m`.
+    pub path_source: Option<NoteId>,
+    /// Notes from `path_source` to the selected node, empty when unreachable.
+    pub path: Vec<NoteId>,
+    pub color_clusters: bool,
+    pub centrality: Option<GraphCentralityPanel>,
+    /// Index into `config.graph.groups` for each projected node.
+    pub node_groups: Vec<Option<usize>>,
+    pub groups_editor: Option<GraphGroupsEditor>,
+    /// Hand-placed Global nodes, loaded when the graph opens.
+    pub pins: GraphPins,
+    /// Pins moved from the keyboard and not yet written out.
+    pub pins_dirty: bool,
+    /// Hides nodes dated after its cursor while open.
+    pub timeline: Option<GraphTimeline>,
     pub total_nodes: usize,
     pub total_edges: usize,
     pub index_pending: bool,
@@ -176,6 +535,8 @@ pub struct GraphViewState {
     pub drag_start: Option<(u16, u16)>,
     pub is_panning: bool,
     pub dragging_node: Option<usize>,
+    /// The grabbed node actually moved, so releasing it pins it.
+    pub node_dragged: bool,
     pub view_width: f32,
     pub view_height: f32,
     pub graph_area: Rect,
@@ -183,6 +544,74 @@ pub struct GraphViewState {
     pub last_click: Option<(std::time::Instant, usize)>,
 }
 
+/// The live local graph docked under the outline, reprojected whenever the
+/// current note or the link index changes.
+#[derive(Debug, Clone, Default)]
+pub struct LocalGraphPane {
+    /// Note, index fingerprint and depth the projection was built for.
+    pub root: Option<NoteId>,
+    pub fingerprint: Option<u64>,
+    pub depth: usize,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Titles in `nodes` order, kept so labels survive an index rebuild.
+    pub titles: Vec<String>,
+    /// Neighbour nodes in the order keys step through them: nearest first,
+    /// then by title.
+    pub order: Vec<usize>,
+    pub selected: Option<NoteId>,
+    /// Where each node was drawn, for mouse hits.
+    pub cells: Vec<(usize, u16, u16)>,
+    pub area: Rect,
+    /// Index generation the pane last started, so a failed build is not
+    /// retried on every tick.
+    pub requested_generation: Option<u64>,
+}
+
+/// A full timeline sweep takes this many steps, both for `,`/`.` and play.
+const GRAPH_TIMELINE_STEPS: i32 = 120;
+
+/// Timeline slider over the index's note dates, as day numbers.
+#[derive(Debug, Clone)]
+pub struct GraphTimeline {
+    pub first: i32,
+    pub last: i32,
+    pub cursor: i32,
+    pub playing: bool,
+    pub last_frame: Option<std::time::Instant>,
+}
+
+impl GraphTimeline {
+    /// Days per step; at least one so short spans still advance.
+    pub fn step(&self) -> i32 {
+        ((self.last - self.first) / GRAPH_TIMELINE_STEPS).max(1)
+    }
+}
+
+/// The "most central notes" list over the whole link index.
+#[derive(Debug, Clone)]
+pub struct GraphCentralityPanel {
+    pub metric: CentralityMetric,
+    pub entries: Vec<(NoteId, f32)>,
+    pub selected: usize,
+    /// Still being ranked on the graph worker; `entries` is empty until then.
+    pub loading: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphGroupField {
+    Query,
+    Color,
+}
+
+/// In-view editor for the `[[graph.groups]]` colour groups.
+#[derive(Debug, Clone, Default)]
+pub struct GraphGroupsEditor {
+    pub selected: usize,
+    pub editing: Option<GraphGroupField>,
+    pub draft: String,
+}
+
 impl Default for GraphViewState {
     fn default() -> Self {
         Self {
@@ -200,6 +629,17 @@ impl Default for GraphViewState {
             filter_editing: false,
             show_orphans: true,
             help_visible: false,
+            export_prompt: false,
+            export_positions: true,
+            path_source: None,
+            path: Vec::new(),
+            color_clusters: false,
+            centrality: None,
+            node_groups: Vec::new(),
+            groups_editor: None,
+            pins: GraphPins::default(),
+            pins_dirty: false,
+            timeline: None,
             total_nodes: 0,
             total_edges: 0,
             index_pending: false,
@@ -213,6 +653,7 @@ impl Default for GraphViewState {
             drag_start: None,
             is_panning: false,
             dragging_node: None,
+            node_dragged: false,
             view_width: 100.0,
             view_height: 50.0,
             graph_area: Rect::default(),
@@ -227,6 +668,7 @@ pub enum Focus {
     Sidebar,
     Content,
     Outline,
+    LocalGraph,
 }
 
 #[derive(Debug, Clone)]
@@ -315,6 +757,71 @@ pub enum ContentItem {
         source_line: u32,
     },
     TagBadges,
+    /// One rendered row of a ```` ```query ```` block. Cells live in
+    /// `App::document_queries` because they are computed, not source text.
+    QueryRow {
+        query: u32,
+        row: QueryRowKind,
+        source_line: u32,
+    },
+    /// One line of an `![[note#^id]]` embed, from `App::document_embeds`.
+    EmbedLine {
+        embed: u32,
+        line: u32,
+        source_line: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryRowKind {
+    Header,
+    Separator,
+    /// Index into `QueryBlock::rows`.
+    Row(u32),
+    /// The block's error or empty-result message.
+    Message,
+}
+
+/// Block transcluded by `![[note#^id]]`: its lines with the ID stripped, or
+/// why it could not be found.
+#[derive(Debug, Clone)]
+pub struct EmbedBlock {
+    pub target: Box<str>,
+    pub id: Box<str>,
+    pub lines: Result<Box<[Box<str>]>, Box<str>>,
+}
+
+impl EmbedBlock {
+    /// Rows shown: a caption linking to the source, then the block.
+    pub fn line_count(&self) -> usize {
+        1 + self.lines.as_ref().map_or(1, |lines| lines.len().max(1))
+    }
+
+    pub fn caption(&self) -> String {
+        format!("{}#^{}", self.target, self.id)
+    }
+}
+
+/// Evaluated query block, laid out like a source table.
+#[derive(Debug, Clone)]
+pub struct QueryBlock {
+    pub columns: Box<[Box<str>]>,
+    pub rows: Box<[Box<[Box<str>]>]>,
+    pub message: Option<Box<str>>,
+    pub metadata: TableMetadata,
+}
+
+impl QueryBlock {
+    pub fn cells(&self, row: QueryRowKind) -> Vec<&str> {
+        match row {
+            QueryRowKind::Header => self.columns.iter().map(AsRef::as_ref).collect(),
+            QueryRowKind::Row(index) => self
+                .rows
+                .get(index as usize)
+                .map_or_else(Vec::new, |cells| cells.iter().map(AsRef::as_ref).collect()),
+            QueryRowKind::Separator | QueryRowKind::Message => Vec::new(),
+        }
+    }
 }
 
 impl ContentItem {
@@ -328,7 +835,9 @@ impl ContentItem {
             | Self::TableRow { source_line, .. }
             | Self::Details { source_line, .. }
             | Self::FrontmatterLine { source_line, .. }
-            | Self::FrontmatterDelimiter { source_line } => *source_line as usize,
+            | Self::FrontmatterDelimiter { source_line }
+            | Self::QueryRow { source_line, .. }
+            | Self::EmbedLine { source_line, .. } => *source_line as usize,
             Self::TagBadges => 0,
         }
     }
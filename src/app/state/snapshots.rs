@@ -0,0 +1,198 @@
+use super::*;
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ekphos_core::diff::diff_lines;
+use ekphos_vault::snapshots::SnapshotStore;
+
+impl App {
+    /// The snapshot store for this vault, when `[snapshots]` is enabled.
+    fn snapshot_store(&self) -> Option<SnapshotStore> {
+        self.config
+            .snapshots
+            .enabled
+            .then(|| SnapshotStore::new(&self.dependencies.cache_dir, self.vault.root()))
+    }
+
+    /// Keep the version of `path` on disk before `body` overwrites it.
+    pub(super) fn snapshot_previous_version(&mut self, path: &Path, body: &str) {
+        let Some(store) = self.snapshot_store() else {
+            return;
+        };
+        let Ok(previous) = fs::read_to_string(path) else {
+            return;
+        };
+        if previous == body {
+            return;
+        }
+        let written = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or_else(|_| SystemTime::now());
+        let time = written.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let note = self.vault_relative(path);
+        if let Err(error) = store.record(&note, &previous, time, self.config.snapshots.policy()) {
+            self.show_error_toast(format!("Could not snapshot {note}: {error}"));
+        }
+    }
+
+    /// Move the snapshots of a renamed note along with it.
+    pub(super) fn rename_snapshots(&mut self, old_path: &Path, new_path: &Path) {
+        let Some(store) = self.snapshot_store() else {
+            return;
+        };
+        let (from, to) = (self.vault_relative(old_path), self.vault_relative(new_path));
+        if let Err(error) = store.rename(&from, &to) {
+            self.show_error_toast(format!("Could not move snapshots of {from}: {error}"));
+        }
+    }
+
+    /// Open the snapshot browser for the current note.
+    pub fn open_note_history(&mut self) {
+        let Some(store) = self.snapshot_store() else {
+            self.show_error_toast("Snapshots are disabled; set [snapshots] enabled = true");
+            return;
+        };
+        let Some(note) = self.current_note() else {
+            return;
+        };
+        let Some(path) = note.file_path.clone() else {
+            return;
+        };
+        let (note_id, title) = (note.id, note.title.clone());
+        let relative = self.vault_relative(&path);
+        let snapshots = match store.list(&relative) {
+            Ok(snapshots) => snapshots,
+            Err(error) => {
+                self.show_error_toast(format!("Could not read snapshots: {error}"));
+                return;
+            }
+        };
+        if snapshots.is_empty() {
+            self.show_toast(format!("\"{title}\" has no snapshots yet"), ToastKind::Info);
+            return;
+        }
+        let current: Vec<String> = if self.mode == Mode::Edit {
+            self.editor.lines().into_iter().map(str::to_string).collect()
+        } else {
+            fs::read_to_string(&path).unwrap_or_default().lines().map(str::to_string).collect()
+        };
+        self.note_history = Some(NoteHistoryState {
+            note_id,
+            title,
+            note: relative,
+            snapshots,
+            selected: 0,
+            list_scroll: 0,
+            diff_scroll: 0,
+            version: Vec::new(),
+            current,
+            diff: Vec::new(),
+            error: None,
+        });
+        self.select_note_snapshot(0);
+        self.dialog = DialogState::NoteHistory;
+    }
+
+    /// Load the `index`th snapshot and diff it against the current text.
+    pub fn select_note_snapshot(&mut self, index: usize) {
+        let Some(store) = self.snapshot_store() else {
+            return;
+        };
+        let Some(history) = &mut self.note_history else {
+            return;
+        };
+        let Some(snapshot) = history.snapshots.get(index) else {
+            return;
+        };
+        history.selected = index;
+        history.diff_scroll = 0;
+        match store.read(&history.note, snapshot) {
+            Ok(content) => {
+                history.version = content.lines().map(str::to_string).collect();
+                history.diff = diff_lines(&history.version, &history.current);
+                history.error = None;
+            }
+            Err(error) => {
+                history.version.clear();
+                history.diff.clear();
+                history.error = Some(format!("Could not read snapshot: {error}"));
+            }
+        }
+    }
+
+    pub fn close_note_history(&mut self) {
+        self.note_history = None;
+        self.dialog = DialogState::None;
+    }
+
+    /// Put the selected snapshot into the editor as one undoable edit.
+    pub fn restore_note_snapshot(&mut self) {
+        let Some(history) = self.note_history.take() else {
+            return;
+        };
+        self.dialog = DialogState::None;
+        if history.error.is_some() {
+            return;
+        }
+        let Some(snapshot) = history.snapshots.get(history.selected) else {
+            return;
+        };
+        let label = format!("{} from {}", history.title, format_snapshot_time(snapshot.time));
+        self.restore_note_lines(history.note_id, history.version, &label);
+    }
+}
+
+/// Local `YYYY-MM-DD HH:MM` for a snapshot timestamp.
+pub fn format_snapshot_time(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    #[test]
+    fn saving_snapshots_the_previous_version_and_restore_is_undoable() {
+        let vault = TempVault::with_files(&[("alpha.md", "# Alpha\nfirst draft\n")]);
+        let mut config = Config::default();
+        config.snapshots.enabled = true;
+        let mut app = vault.app_with(config);
+
+        app.enter_edit_mode();
+        app.editor.replace_lines(1, 1, vec!["second draft".to_string()]);
+        app.save_edit();
+        app.enter_edit_mode();
+        app.save_edit();
+
+        app.open_note_history();
+        let history = app.note_history.as_ref().unwrap();
+        assert_eq!(history.snapshots.len(), 1);
+        assert_eq!(history.version, ["# Alpha", "first draft"]);
+        assert_eq!(history.current, ["# Alpha", "second draft"]);
+
+        app.restore_note_snapshot();
+        assert_eq!(app.mode, Mode::Edit);
+        assert_eq!(app.editor.lines(), ["# Alpha", "first draft"]);
+        app.editor.undo();
+        assert_eq!(app.editor.lines(), ["# Alpha", "second draft"]);
+    }
+
+    #[test]
+    fn moving_a_folder_carries_its_notes_snapshots() {
+        let vault = TempVault::with_files(&[("drafts/alpha.md", "first draft\n"), ("archive/keep.md", "")]);
+        let mut config = Config::default();
+        config.snapshots.enabled = true;
+        let mut app = vault.app_with(config);
+        app.snapshot_previous_version(&vault.notes().join("drafts/alpha.md"), "second draft\n");
+
+        app.move_folder(&vault.notes().join("drafts"), &vault.notes().join("archive"), "drafts")
+            .unwrap();
+        let store = app.snapshot_store().unwrap();
+        assert!(store.list("drafts/alpha.md").unwrap().is_empty());
+        assert_eq!(store.list("archive/drafts/alpha.md").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,82 @@
+//! Throwaway vaults for the `App` state tests.
+
+use super::*;
+
+static NEXT_VAULT: AtomicU64 = AtomicU64::new(0);
+
+/// A vault under the temp directory, removed on drop. Notes live in
+/// `notes/`, with the config and cache directories beside it.
+pub(crate) struct TempVault {
+    pub(crate) path: PathBuf,
+}
+
+impl TempVault {
+    /// Paths are relative to the notes directory; parents are created.
+    pub(crate) fn with_files(files: &[(&str, &str)]) -> Self {
+        let path = std::env::temp_dir().join(format!("ekphos-test-{}-{}", std::process::id(), NEXT_VAULT.fetch_add(1, Ordering::Relaxed)));
+        fs::create_dir_all(path.join("notes")).unwrap();
+        let vault = Self { path };
+        for (relative, contents) in files {
+            vault.write(relative, contents);
+        }
+        vault
+    }
+
+    pub(crate) fn notes(&self) -> PathBuf {
+        self.path.join("notes")
+    }
+
+    pub(crate) fn write(&self, relative: &str, contents: impl AsRef<[u8]>) {
+        let path = self.notes().join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, contents).unwrap();
+    }
+
+    pub(crate) fn app(&self) -> App {
+        self.app_with(Config::default())
+    }
+
+    pub(crate) fn app_with(&self, config: Config) -> App {
+        self.app_with_dependencies(config, self.dependencies())
+    }
+
+    /// An app whose clock reports `today`.
+    pub(crate) fn app_on(&self, today: chrono::NaiveDate) -> App {
+        let mut dependencies = self.dependencies();
+        dependencies.clock = Arc::new(FixedClock(today));
+        self.app_with_dependencies(Config::default(), dependencies)
+    }
+
+    fn dependencies(&self) -> AppDependencies {
+        AppDependencies::headless(self.path.join("config"), self.path.join("cache"))
+    }
+
+    fn app_with_dependencies(&self, config: Config, dependencies: AppDependencies) -> App {
+        let config = Config {
+            welcome_shown: true,
+            check_updates: false,
+            ..config
+        };
+        App::new_injected(config, self.notes(), None, dependencies)
+    }
+}
+
+impl Drop for TempVault {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+struct FixedClock(chrono::NaiveDate);
+
+impl Clock for FixedClock {
+    fn now(&self) -> std::time::Instant {
+        std::time::Instant::now()
+    }
+
+    fn today(&self) -> chrono::NaiveDate {
+        self.0
+    }
+}
@@ -0,0 +1,183 @@
+use super::*;
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ekphos_vault::trash::Trash;
+
+impl App {
+    fn vault_trash(&self) -> Trash {
+        Trash::new(self.vault.root())
+    }
+
+    /// Move a note or folder to the vault trash. Returns `false` after
+    /// reporting a failure.
+    pub(super) fn move_to_trash(&mut self, path: &Path) -> bool {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64);
+        match self.vault_trash().trash(path, now) {
+            Ok(_) => true,
+            Err(error) => {
+                self.show_error_toast(format!("Could not move {} to the trash: {error}", self.vault_relative(path)));
+                false
+            }
+        }
+    }
+
+    /// Rebuild the names links are checked against. Called on every reload.
+    pub(super) fn refresh_trashed_notes(&mut self) {
+        let trash = self.vault_trash();
+        let entries = trash.entries().unwrap_or_default();
+        self.trashed_notes = entries
+            .iter()
+            .flat_map(|entry| trash.notes_in(entry))
+            .flat_map(|note| {
+                let relative = note.with_extension("").to_string_lossy().replace('\\', "/").to_lowercase();
+                let title = note.file_stem().map(|stem| stem.to_string_lossy().to_lowercase());
+                std::iter::once(relative).chain(title)
+            })
+            .collect();
+    }
+
+    /// Whether a wiki link target resolves, is missing, or was trashed.
+    pub fn wiki_link_state(&self, target: &str) -> WikiLinkState {
+        if target.is_empty() || self.wiki_link_exists(target) {
+            WikiLinkState::Exists
+        } else if self.trashed_notes.contains(&target.to_lowercase()) {
+            WikiLinkState::Trashed
+        } else {
+            WikiLinkState::Missing
+        }
+    }
+
+    /// Follow a link whose target does not exist: point at the trash when the
+    /// note was deleted, otherwise offer to create it.
+    pub fn open_missing_wiki_link(&mut self, target: String) {
+        if self.wiki_link_state(&target) != WikiLinkState::Trashed {
+            self.pending_wiki_target = Some(target);
+            self.dialog = DialogState::CreateWikiNote;
+            return;
+        }
+        self.open_trash();
+        let wanted = target.to_lowercase();
+        let trash = self.vault_trash();
+        if let Some(state) = &mut self.trash {
+            let matches = |note: &Path| {
+                note.with_extension("").to_string_lossy().replace('\\', "/").to_lowercase() == wanted
+                    || note.file_stem().is_some_and(|stem| stem.to_string_lossy().to_lowercase() == wanted)
+            };
+            if let Some(index) = state.entries.iter().position(|entry| trash.notes_in(entry).iter().any(|note| matches(note))) {
+                state.selected = index;
+            }
+        }
+        self.show_toast(format!("\"{target}\" is in the trash"), ToastKind::Info);
+    }
+
+    pub fn open_trash(&mut self) {
+        let entries = match self.vault_trash().entries() {
+            Ok(entries) => entries,
+            Err(error) => {
+                self.show_error_toast(format!("Could not read the trash: {error}"));
+                return;
+            }
+        };
+        self.trash = Some(TrashBrowserState {
+            entries,
+            selected: 0,
+            scroll: 0,
+            confirm: None,
+        });
+        self.dialog = DialogState::Trash;
+    }
+
+    pub fn close_trash(&mut self) {
+        self.trash = None;
+        self.dialog = DialogState::None;
+    }
+
+    /// Put the selected entry back into the vault and select it.
+    pub fn restore_trash_entry(&mut self) {
+        let Some(entry) = self.trash.as_ref().and_then(|state| state.entries.get(state.selected).cloned()) else {
+            return;
+        };
+        let restored = match self.vault_trash().restore(&entry) {
+            Ok(restored) => restored,
+            Err(error) => {
+                self.show_error_toast(format!("Could not restore {}: {error}", entry.display_name()));
+                return;
+            }
+        };
+        self.close_trash();
+        if entry.is_folder {
+            self.folder_states.insert(restored.clone(), true);
+        } else {
+            self.run_hook(HookEvent::Create, &restored, None);
+        }
+        // Rescanning re-indexes search and the graph on next use.
+        self.load_notes_from_dir();
+        if !entry.is_folder {
+            self.select_note_by_path(&restored);
+        }
+        self.show_toast(format!("Restored {}", self.vault_relative(&restored)), ToastKind::Success);
+    }
+
+    /// Ask before deleting the selected entry, or everything, for good.
+    pub fn confirm_trash_action(&mut self, action: TrashConfirm) {
+        if let Some(state) = &mut self.trash {
+            if !state.entries.is_empty() {
+                state.confirm = Some(action);
+            }
+        }
+    }
+
+    /// Carry out the confirmed purge.
+    pub fn purge_trash(&mut self) {
+        let Some(state) = &mut self.trash else {
+            return;
+        };
+        let Some(action) = state.confirm.take() else {
+            return;
+        };
+        let doomed: Vec<_> = match action {
+            TrashConfirm::Purge => state.entries.get(state.selected).cloned().into_iter().collect(),
+            TrashConfirm::Empty => state.entries.clone(),
+        };
+        let trash = self.vault_trash();
+        let failed = doomed.iter().filter(|entry| trash.purge(entry).is_err()).count();
+        if failed > 0 {
+            self.show_error_toast(format!("Could not purge {failed} trash item(s)"));
+        }
+        let entries = trash.entries().unwrap_or_default();
+        if let Some(state) = &mut self.trash {
+            state.selected = state.selected.min(entries.len().saturating_sub(1));
+            state.entries = entries;
+        }
+        self.refresh_trashed_notes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::test_support::TempVault;
+
+    #[test]
+    fn deleted_notes_go_to_the_trash_and_come_back() {
+        let vault = TempVault::with_files(&[("alpha.md", "# Alpha\nsee [[Beta]]\n"), ("beta.md", "# Beta\n")]);
+        let mut app = vault.app();
+        let beta = vault.notes().join("beta.md");
+        assert!(app.select_note_by_path(&beta));
+
+        app.delete_current_note();
+        assert!(!beta.exists());
+        assert!(app.notes.iter().all(|note| note.file_path.as_ref() != Some(&beta)));
+        assert_eq!(app.wiki_link_state("Beta"), WikiLinkState::Trashed);
+        assert_eq!(app.wiki_link_state("Gamma"), WikiLinkState::Missing);
+
+        app.open_missing_wiki_link("Beta".to_string());
+        assert_eq!(app.dialog, DialogState::Trash);
+        app.restore_trash_entry();
+        assert_eq!(fs::read_to_string(&beta).unwrap(), "# Beta\n");
+        assert_eq!(app.wiki_link_state("Beta"), WikiLinkState::Exists);
+        assert_eq!(app.current_note().and_then(|note| note.file_path.clone()), Some(beta));
+    }
+}
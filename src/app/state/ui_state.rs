@@ -99,10 +99,12 @@ impl App {
     pub fn toggle_focus(&mut self, backwards: bool) {
         self.focus = match self.focus {
             Focus::Sidebar => {
-                if backwards {
-                    Focus::Outline
-                } else {
+                if !backwards {
                     Focus::Content
+                } else if self.local_graph_visible() {
+                    Focus::LocalGraph
+                } else {
+                    Focus::Outline
                 }
             }
             Focus::Content => {
@@ -115,6 +117,15 @@ impl App {
             Focus::Outline => {
                 if backwards {
                     Focus::Content
+                } else if self.local_graph_visible() {
+                    Focus::LocalGraph
+                } else {
+                    Focus::Sidebar
+                }
+            }
+            Focus::LocalGraph => {
+                if backwards {
+                    Focus::Outline
                 } else {
                     Focus::Sidebar
                 }
@@ -146,7 +157,7 @@ impl App {
 
         let (panel_name, collapsed, current_width) = match self.focus {
             Focus::Sidebar => ("Sidebar", self.sidebar_collapsed, self.config.sidebar_width_percent),
-            Focus::Outline => ("Outline", self.outline_collapsed, self.config.outline_width_percent),
+            Focus::Outline | Focus::LocalGraph => ("Outline", self.outline_collapsed, self.config.outline_width_percent),
             Focus::Content => {
                 self.status_message = Some("Focus the sidebar or outline to resize".to_string());
                 return;
@@ -168,7 +179,7 @@ impl App {
 
         match self.focus {
             Focus::Sidebar => self.config.sidebar_width_percent = resized_width,
-            Focus::Outline => self.config.outline_width_percent = resized_width,
+            Focus::Outline | Focus::LocalGraph => self.config.outline_width_percent = resized_width,
             Focus::Content => unreachable!(),
         }
         let minimized = if Config::panel_width_is_minimized(resized_width) {
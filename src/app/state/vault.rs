@@ -41,6 +41,7 @@ impl App {
 
         let mut previous_notes: HashMap<NoteId, Note> = std::mem::take(&mut self.notes).into_iter().map(|note| (note.id, note)).collect();
         self.file_tree.clear();
+        self.note_aliases.clear();
         self.file_tree = self.build_tree_from_catalog(catalog, 0);
         for note in &mut self.notes {
             let unchanged = previous_fingerprints.get(&note.id).copied() == self.vault.fingerprint(note.id);
@@ -55,6 +56,8 @@ impl App {
         self.sort_tree();
 
         self.rebuild_sidebar_items();
+        self.discover_git_repo();
+        self.refresh_trashed_notes();
 
         let restored_note_id = selected_note_id.filter(|id| self.note_index_for_id(*id).is_some());
         if let Some(note_id) = restored_note_id {
@@ -123,7 +126,8 @@ impl App {
                     }
                 }
                 ekphos_vault::CatalogEntry::Note(note) => {
-                    let note = *note;
+                    let mut note = *note;
+                    self.record_aliases(note.metadata.id, std::mem::take(&mut note.metadata.frontmatter.aliases));
                     let frontmatter = note.has_frontmatter.then(|| note.metadata.frontmatter.into());
                     self.notes.push(Note {
                         id: note.metadata.id,
@@ -145,6 +149,14 @@ impl App {
         items
     }
 
+    fn record_aliases(&mut self, note_id: NoteId, aliases: Vec<String>) {
+        if aliases.is_empty() {
+            self.note_aliases.remove(&note_id);
+        } else {
+            self.note_aliases.insert(note_id, aliases.into_iter().map(String::into_boxed_str).collect());
+        }
+    }
+
     pub(crate) fn note_index_for_id(&self, note_id: NoteId) -> Option<usize> {
         self.notes.iter().position(|note| note.id == note_id)
     }
@@ -211,13 +223,17 @@ impl App {
         self.document_generation = self.document_generation.wrapping_add(1);
     }
 
-    pub(crate) fn refresh_current_note_after_save(&mut self) {
-        let Some(note_id) = self.current_note().map(|note| note.id) else {
-            return;
-        };
+    pub(crate) fn refresh_note_after_save(&mut self, note_id: NoteId) {
         self.body_cache.invalidate(note_id);
-        if let Ok(catalog_note) = self.vault.refresh_note(note_id) {
-            self.active_fingerprint = Some(catalog_note.fingerprint);
+        if let Ok(mut catalog_note) = self.vault.refresh_note(note_id) {
+            self.record_aliases(note_id, std::mem::take(&mut catalog_note.metadata.frontmatter.aliases));
+            // Frontmatter may have changed, which query blocks read.
+            let previous_generation = self.catalog_generation;
+            self.catalog_generation = self.catalog_generation.wrapping_add(1);
+            self.refresh_frontmatter_corpus_entry(note_id, previous_generation);
+            if self.active_note_id == Some(note_id) {
+                self.active_fingerprint = Some(catalog_note.fingerprint);
+            }
             if let Some(index) = self.note_index_for_id(note_id) {
                 let note = &mut self.notes[index];
                 note.file_size = catalog_note.metadata.file_size;
@@ -230,16 +246,33 @@ impl App {
     }
 
     pub(crate) fn persist_active_body(&mut self, body: String) -> bool {
-        let Some((note_id, path)) = self.current_note().and_then(|note| note.file_path.clone().map(|path| (note.id, path))) else {
+        let Some(note_id) = self.current_note().map(|note| note.id) else {
+            return false;
+        };
+        self.persist_note_body(note_id, body)
+    }
+
+    /// Save any note's body and drop every derived view of it. The active
+    /// document is replaced when the note is the one currently shown.
+    pub(crate) fn persist_note_body(&mut self, note_id: NoteId, body: String) -> bool {
+        let Some(path) = self.note_index_for_id(note_id).and_then(|index| self.notes[index].file_path.clone()) else {
             return false;
         };
+        self.snapshot_previous_version(&path, &body);
         if let Err(error) = ekphos_vault::save_note(&path, &body) {
             self.show_error_toast(format!("Could not save note: {error}"));
             return false;
         }
+        self.run_hook(HookEvent::Save, &path, None);
+        self.refresh_git_status();
         self.body_cache.invalidate(note_id);
-        self.replace_active_body(body);
-        self.refresh_current_note_after_save();
+        if self.current_note().is_some_and(|note| note.id == note_id) {
+            self.replace_active_body(body);
+        } else if self.active_note_id == Some(note_id) {
+            self.active_note_id = None;
+            self.active_document = None;
+        }
+        self.refresh_note_after_save(note_id);
         self.invalidate_graph_service();
         if let SearchPickerState::Open {
             content_results,
@@ -487,6 +520,7 @@ impl App {
 
         let content = format!("# {}\n\n", name);
         if fs::write(&file_path, &content).is_ok() {
+            self.run_hook(HookEvent::Create, &file_path, None);
             if let Some(ref folder_path) = self.target_folder {
                 self.folder_states.insert(folder_path.clone(), true);
             }
@@ -576,8 +610,10 @@ impl App {
     pub fn delete_current_note(&mut self) {
         if let Some(item) = self.sidebar_items.get(self.selected_sidebar_index) {
             if let SidebarItemKind::Note { note_id } = &item.kind {
-                if let Some(path) = self.notes.iter().find(|note| note.id == *note_id).and_then(|note| note.file_path.as_ref()) {
-                    let _ = fs::remove_file(path);
+                if let Some(path) = self.notes.iter().find(|note| note.id == *note_id).and_then(|note| note.file_path.clone()) {
+                    if self.move_to_trash(&path) {
+                        self.run_hook(HookEvent::Delete, &path, None);
+                    }
                 }
 
                 self.load_notes_from_dir();
@@ -595,7 +631,11 @@ impl App {
 
     pub fn delete_current_folder(&mut self) {
         if let Some(path) = self.get_selected_folder_path() {
-            if fs::remove_dir_all(&path).is_ok() {
+            let deleted = self.note_paths_under(&path);
+            if self.move_to_trash(&path) {
+                for note_path in deleted {
+                    self.run_hook(HookEvent::Delete, &note_path, None);
+                }
                 self.folder_states.remove(&path);
 
                 self.load_notes_from_dir();
@@ -611,6 +651,15 @@ impl App {
         }
     }
 
+    /// Files of the loaded notes inside `folder`, at any depth.
+    fn note_paths_under(&self, folder: &std::path::Path) -> Vec<PathBuf> {
+        self.notes
+            .iter()
+            .filter_map(|note| note.file_path.clone())
+            .filter(|path| path.starts_with(folder))
+            .collect()
+    }
+
     pub fn rename_note(&mut self, new_name: &str) {
         let new_name = new_name.trim();
         if new_name.is_empty() {
@@ -641,8 +690,10 @@ impl App {
                     return;
                 }
 
-                if let Some(ref old_path) = self.notes[note_index].file_path {
-                    if fs::rename(old_path, &new_file_path).is_ok() {
+                if let Some(old_path) = self.notes[note_index].file_path.clone() {
+                    if fs::rename(&old_path, &new_file_path).is_ok() {
+                        self.run_hook(HookEvent::Rename, &new_file_path, Some(&old_path));
+                        self.rename_snapshots(&old_path, &new_file_path);
                         self.load_notes_from_dir();
 
                         let new_name_owned = new_name.to_string();
@@ -686,7 +737,14 @@ impl App {
                     return;
                 }
 
+                let renamed = self.note_paths_under(&old_path);
                 if fs::rename(&old_path, &new_path).is_ok() {
+                    for note_path in renamed {
+                        let relative = note_path.strip_prefix(&old_path).unwrap_or(&note_path);
+                        let moved_to = new_path.join(relative);
+                        self.run_hook(HookEvent::Rename, &moved_to, Some(&note_path));
+                        self.rename_snapshots(&note_path, &moved_to);
+                    }
                     if let Some(expanded) = self.folder_states.remove(&old_path) {
                         self.folder_states.insert(new_path.clone(), expanded);
                     }
@@ -797,6 +855,8 @@ impl App {
         let old_wiki_path = Self::calculate_wiki_path(source, &notes_root);
         let new_wiki_path = Self::calculate_wiki_path(&dest_path, &notes_root);
         fs::rename(source, &dest_path).map_err(|e| format!("Failed to move file: {}", e))?;
+        self.run_hook(HookEvent::Rename, &dest_path, Some(source));
+        self.rename_snapshots(source, &dest_path);
         self.update_wiki_links_after_move(&old_wiki_path, &new_wiki_path, title);
         self.load_notes_from_dir();
         for (idx, item) in self.sidebar_items.iter().enumerate() {
@@ -851,7 +911,14 @@ impl App {
             }
         }
 
+        let moved = self.note_paths_under(source);
         fs::rename(source, &dest_path).map_err(|e| format!("Failed to move folder: {}", e))?;
+        for note_path in moved {
+            let relative = note_path.strip_prefix(source).unwrap_or(&note_path);
+            let moved_to = dest_path.join(relative);
+            self.run_hook(HookEvent::Rename, &moved_to, Some(&note_path));
+            self.rename_snapshots(&note_path, &moved_to);
+        }
 
         let keys_to_update: Vec<PathBuf> = self.folder_states.keys().filter(|k| k.starts_with(source)).cloned().collect();
 
@@ -885,16 +952,32 @@ impl App {
     }
 
     pub(super) fn update_wiki_links_after_move(&mut self, old_path: &str, new_path: &str, title: &str) {
+        self.rewrite_wiki_links_in_vault(None, |target, suffix| {
+            let target_lower = target.to_lowercase();
+            let should_replace = target_lower == old_path.to_lowercase() || target_lower == title.to_lowercase();
+            should_replace.then(|| {
+                let new_target = if new_path.contains('/') { new_path } else { title };
+                format!("{new_target}{suffix}")
+            })
+        });
+    }
+
+    /// Run `rewrite` over every wiki link in the vault's notes, skipping
+    /// `except`, and write back the files that changed.
+    pub(super) fn rewrite_wiki_links_in_vault(&self, except: Option<&std::path::Path>, mut rewrite: impl FnMut(&str, &str) -> Option<String>) {
         let notes_root = self.config.notes_path();
         let md_files = Self::collect_markdown_files(&notes_root);
 
         for file_path in md_files {
+            if except == Some(file_path.as_path()) {
+                continue;
+            }
             let content = match fs::read_to_string(&file_path) {
                 Ok(c) => c,
                 Err(_) => continue,
             };
 
-            let modified_content = self.replace_wiki_links_in_content(&content, old_path, new_path, title);
+            let modified_content = Self::rewrite_wiki_links(&content, &mut rewrite);
 
             if modified_content != content {
                 let _ = fs::write(&file_path, modified_content);
@@ -916,7 +999,10 @@ impl App {
         files
     }
 
-    pub(super) fn replace_wiki_links_in_content(&self, content: &str, old_path: &str, new_path: &str, old_title: &str) -> String {
+    /// Replace the inside of each `[[...]]` for which `rewrite` returns
+    /// something. It receives the link target and the rest of the link
+    /// (`#heading`, `|alias`, or empty).
+    pub(super) fn rewrite_wiki_links(content: &str, mut rewrite: impl FnMut(&str, &str) -> Option<String>) -> String {
         let mut result = String::new();
         let mut remaining = content;
 
@@ -935,28 +1021,13 @@ impl App {
                     (link_content, "")
                 };
 
-                let target_lower = target.to_lowercase();
-                let old_path_lower = old_path.to_lowercase();
-                let old_title_lower = old_title.to_lowercase();
-
-                let should_replace = target_lower == old_path_lower || target_lower == old_title_lower;
-
-                if should_replace {
-                    let new_target = if new_path.contains('/') {
-                        new_path.to_string()
-                    } else {
-                        old_title.to_string()
-                    };
-                    result.push_str("[[");
-                    result.push_str(&new_target);
-                    result.push_str(suffix);
-                    result.push_str("]]");
-                } else {
+                result.push_str("[[");
+                match rewrite(target, suffix) {
+                    Some(replacement) => result.push_str(&replacement),
                     // Keep original
-                    result.push_str("[[");
-                    result.push_str(link_content);
-                    result.push_str("]]");
+                    None => result.push_str(link_content),
                 }
+                result.push_str("]]");
 
                 remaining = &remaining[end + 2..];
             } else {
@@ -998,6 +1069,44 @@ impl App {
     }
 
     /// Create the notes directory when it doesn't exist
+    /// Create `<title>.md` under the vault root without touching the selection,
+    /// rendering `{{title}}` and `{{date}}` in an optional vault-relative
+    /// template. A slash in the title creates the note in that folder.
+    pub fn create_note_from_template(&mut self, title: &str, template: Option<&str>) -> Result<PathBuf, String> {
+        let title = title.trim().trim_end_matches(".md");
+        if title.is_empty() {
+            return Err("Note title is empty".to_string());
+        }
+        let relative = PathBuf::from(format!("{title}.md"));
+        if !relative.components().all(|component| matches!(component, std::path::Component::Normal(_))) {
+            return Err(format!("Not a vault-relative note name: {title}"));
+        }
+        let notes_path = self.config.notes_path();
+        let file_path = notes_path.join(&relative);
+        if file_path.exists() {
+            return Err(format!("{} already exists", relative.display()));
+        }
+
+        let name = title.rsplit('/').next().unwrap_or(title);
+        let content = match template {
+            Some(template) => {
+                let template_path = notes_path.join(template);
+                fs::read_to_string(&template_path)
+                    .map_err(|error| format!("Could not read template {}: {error}", template_path.display()))?
+                    .replace("{{title}}", name)
+                    .replace("{{date}}", &self.dependencies.clock.today().format("%Y-%m-%d").to_string())
+            }
+            None => format!("# {name}\n\n"),
+        };
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|error| format!("Could not create {}: {error}", parent.display()))?;
+        }
+        fs::write(&file_path, content).map_err(|error| format!("Could not write {}: {error}", file_path.display()))?;
+        self.run_hook(HookEvent::Create, &file_path, None);
+        self.load_notes_from_dir();
+        Ok(file_path)
+    }
+
     pub fn create_notes_directory(&mut self) {
         let notes_path = self.config.notes_path();
         if fs::create_dir_all(&notes_path).is_ok() {
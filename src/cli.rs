@@ -0,0 +1,561 @@
+//! Subcommands that run against a vault without starting the TUI.
+//!
+//! Plain output is one record per line on stdout; `--json` prints a single
+//! JSON document instead. Diagnostics go to stderr.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ekphos_graph::{GraphExportFormat, GraphExportOptions, GraphLinkScope, GraphMode};
+use serde::Serialize;
+
+use crate::app::{App, GraphViewState};
+use crate::config::Config;
+
+/// Exit code when a subcommand finds problems or nothing to report.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for bad arguments, a vault that cannot be opened, or a command
+/// that could not run.
+pub const EXIT_USAGE: i32 = 2;
+
+/// Subcommands dispatched by [`run`].
+pub const COMMANDS: [&str; 8] = ["check", "search", "new", "journal", "links", "backlinks", "tags", "graph"];
+
+/// Parsed subcommand arguments. Options not listed for a command are rejected.
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    json: bool,
+    strict: bool,
+    template: Option<String>,
+    date: Option<String>,
+    format: Option<String>,
+    mode: Option<String>,
+    depth: Option<String>,
+    scope: Option<String>,
+    filter: Option<String>,
+    no_orphans: bool,
+    positions: bool,
+    vault: Option<PathBuf>,
+    positional: Vec<String>,
+}
+
+impl Options {
+    fn parse(command: &str, args: &[String], allowed: &[&str]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                options.positional.extend(args.by_ref().cloned());
+                break;
+            }
+            if !arg.starts_with("--") || arg.len() == 2 {
+                options.positional.push(arg.clone());
+                continue;
+            }
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if !allowed.contains(&name) {
+                return Err(format!("Unknown option for {command}: {name}"));
+            }
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("{name} needs a value"))
+            };
+            match name {
+                "--json" => options.json = true,
+                "--strict" => options.strict = true,
+                "--template" => options.template = Some(value()?),
+                "--date" => options.date = Some(value()?),
+                "--format" => options.format = Some(value()?),
+                "--mode" => options.mode = Some(value()?),
+                "--depth" => options.depth = Some(value()?),
+                "--scope" => options.scope = Some(value()?),
+                "--filter" => options.filter = Some(value()?),
+                "--no-orphans" => options.no_orphans = true,
+                "--positions" => options.positions = true,
+                "--vault" => options.vault = Some(expand_path(&value()?)),
+                _ => unreachable!("allowed options are handled above"),
+            }
+        }
+        Ok(options)
+    }
+
+    /// The positional arguments joined back into one phrase, so titles and
+    /// queries with spaces work without quoting.
+    fn phrase(&self) -> Option<String> {
+        let phrase = self.positional.join(" ");
+        (!phrase.trim().is_empty()).then_some(phrase)
+    }
+
+    fn open_vault(&self) -> Result<App, String> {
+        let vault = self.vault.clone().unwrap_or_else(|| Config::load().notes_path());
+        if !vault.is_dir() {
+            return Err(format!("Not a notes folder: {}", vault.display()));
+        }
+        Ok(App::new_headless(vault))
+    }
+}
+
+/// Run `ekphos <command> [ARGS]` and return the process exit code.
+pub fn run(command: &str, args: &[String]) -> i32 {
+    let allowed: &[&str] = match command {
+        "check" => &["--json", "--strict", "--vault"],
+        "new" => &["--json", "--template", "--vault"],
+        "journal" => &["--json", "--date", "--vault"],
+        "graph" => &["--format", "--mode", "--depth", "--scope", "--filter", "--no-orphans", "--positions", "--vault"],
+        _ => &["--json", "--vault"],
+    };
+    let result = Options::parse(command, args, allowed).and_then(|options| match command {
+        "check" => check_command(options),
+        "search" => search(options),
+        "new" => new_note(options),
+        "journal" => journal(options),
+        "links" => links(options, false),
+        "backlinks" => links(options, true),
+        "tags" => tags(options),
+        "graph" => export_graph(options),
+        _ => Err(format!("Unknown command: {command}")),
+    });
+    result.unwrap_or_else(|error| {
+        eprintln!("{error}");
+        EXIT_USAGE
+    })
+}
+
+fn check_command(mut options: Options) -> Result<i32, String> {
+    if options.positional.len() > 1 {
+        return Err("check takes at most one PATH".to_string());
+    }
+    if let Some(path) = options.positional.pop() {
+        options.vault = Some(expand_path(&path));
+    }
+    let app = options.open_vault()?;
+    Ok(report_issues(&app, options.strict, options.json))
+}
+
+#[derive(Serialize)]
+struct IssueRecord<'a> {
+    path: String,
+    line: Option<usize>,
+    kind: &'a str,
+    severity: &'a str,
+    detail: &'a str,
+}
+
+/// `ekphos check`: print one `path:line: kind: detail` row per issue and
+/// return the process exit code. Warnings only fail the run with `strict`.
+fn report_issues(app: &App, strict: bool, json: bool) -> i32 {
+    let issues = app.vault_doctor_issues();
+    if json {
+        let records: Vec<_> = issues
+            .iter()
+            .map(|issue| IssueRecord {
+                path: app.vault_relative(&issue.path),
+                line: issue.line.map(|line| line + 1),
+                kind: issue.kind.label(),
+                severity: if issue.kind.is_error() { "error" } else { "warning" },
+                detail: &issue.detail,
+            })
+            .collect();
+        print_json(&records);
+    } else {
+        for issue in &issues {
+            let path = app.vault_relative(&issue.path);
+            let location = match issue.line {
+                Some(line) => format!("{path}:{}", line + 1),
+                None => path,
+            };
+            println!("{location}: {}: {}", issue.kind.label(), issue.detail);
+        }
+    }
+
+    let errors = issues.iter().filter(|issue| issue.kind.is_error()).count();
+    let warnings = issues.len() - errors;
+    eprintln!("Checked {} notes: {errors} errors, {warnings} warnings", app.notes.len());
+    if errors > 0 || (strict && warnings > 0) {
+        EXIT_FAILURE
+    } else {
+        0
+    }
+}
+
+#[derive(Serialize)]
+struct SearchRecord<'a> {
+    path: String,
+    title: &'a str,
+    line: u32,
+    text: String,
+    score: i32,
+}
+
+/// `ekphos search <query>`: `path:line:text` per matching line, best first.
+/// Exits 1 when nothing matches, like grep.
+fn search(options: Options) -> Result<i32, String> {
+    let query = options.phrase().ok_or("search needs a query")?;
+    let mut app = options.open_vault()?;
+    app.load_search_index_blocking();
+    let hits = app.headless_content_search_hits(&query);
+
+    let mut bodies: HashMap<_, Option<Arc<str>>> = HashMap::new();
+    let mut records = Vec::with_capacity(hits.len());
+    for hit in &hits {
+        let Some(note) = app.note_index_for_id(hit.note_id).map(|index| &app.notes[index]) else {
+            continue;
+        };
+        let Some(path) = &note.file_path else {
+            continue;
+        };
+        let body = bodies.entry(hit.note_id).or_insert_with(|| app.vault.load_body(hit.note_id).ok());
+        let Some(text) = body.as_deref().and_then(|body| body.lines().nth(hit.line_number as usize)) else {
+            continue;
+        };
+        records.push(SearchRecord {
+            path: app.vault_relative(path),
+            title: &note.title,
+            line: hit.line_number + 1,
+            text: text.to_string(),
+            score: hit.score,
+        });
+    }
+
+    if options.json {
+        print_json(&records);
+    } else {
+        for record in &records {
+            println!("{}:{}:{}", record.path, record.line, record.text);
+        }
+    }
+    Ok(if records.is_empty() { EXIT_FAILURE } else { 0 })
+}
+
+#[derive(Serialize)]
+struct CreatedRecord {
+    path: PathBuf,
+    created: bool,
+}
+
+fn print_created(record: CreatedRecord, json: bool) {
+    if json {
+        print_json(&record);
+    } else {
+        println!("{}", record.path.display());
+    }
+}
+
+/// `ekphos new <title> [--template PATH]`: create a note and print its path.
+fn new_note(options: Options) -> Result<i32, String> {
+    let title = options.phrase().ok_or("new needs a note title")?;
+    let mut app = options.open_vault()?;
+    let path = app.create_note_from_template(&title, options.template.as_deref())?;
+    report_hook_failures(&mut app);
+    print_created(CreatedRecord { path, created: true }, options.json);
+    Ok(0)
+}
+
+/// `ekphos journal [--date YYYY-MM-DD]`: print the daily note's path,
+/// creating it first when needed.
+fn journal(options: Options) -> Result<i32, String> {
+    if !options.positional.is_empty() {
+        return Err("journal takes no arguments; use --date YYYY-MM-DD".to_string());
+    }
+    let mut app = options.open_vault()?;
+    let date = match &options.date {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Not a YYYY-MM-DD date: {date}"))?,
+        None => app.dependencies.clock.today(),
+    };
+    let entry = app.ensure_journal_entry(date)?;
+    report_hook_failures(&mut app);
+    let created = entry.action == ekphos_vault::journal::JournalEntryAction::Created;
+    print_created(CreatedRecord { path: entry.path, created }, options.json);
+    Ok(0)
+}
+
+#[derive(Serialize)]
+struct NoteRecord {
+    path: String,
+    title: String,
+}
+
+/// `ekphos links <note>` and `ekphos backlinks <note>`: the notes a note
+/// links to, or the notes linking to it, one vault-relative path per line.
+fn links(options: Options, backlinks: bool) -> Result<i32, String> {
+    let target = options.phrase().ok_or("expected a note title, wiki path or file path")?;
+    let app = options.open_vault()?;
+    let note_index = resolve_note(&app, &target).ok_or_else(|| format!("No such note: {target}"))?;
+    let index = app.build_graph_index_blocking().ok_or("Could not build the link index")?;
+    let note_id = app.notes[note_index].id;
+    let neighbors: Vec<_> = if backlinks {
+        index.incoming_notes(note_id).collect()
+    } else {
+        index.outgoing_notes(note_id).collect()
+    };
+    let mut records: Vec<NoteRecord> = neighbors
+        .into_iter()
+        .filter_map(|node| {
+            let path = app.notes[app.note_index_for_id(node.note_id)?].file_path.as_deref()?;
+            Some(NoteRecord {
+                path: app.vault_relative(path),
+                title: node.title.clone(),
+            })
+        })
+        .collect();
+    records.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if options.json {
+        print_json(&records);
+    } else {
+        for record in &records {
+            println!("{}", record.path);
+        }
+    }
+    Ok(0)
+}
+
+/// A note named by file path (relative to the working directory or the
+/// vault) or by wiki link target, including aliases.
+fn resolve_note(app: &App, target: &str) -> Option<usize> {
+    let vault_root = app.config.notes_path();
+    let candidates = [expand_path(target), vault_root.join(target)];
+    for candidate in candidates.iter().filter(|candidate| candidate.is_file()) {
+        let candidate = candidate.canonicalize().unwrap_or_else(|_| candidate.clone());
+        let found = app.notes.iter().position(|note| {
+            note.file_path
+                .as_ref()
+                .is_some_and(|path| path.canonicalize().map_or(*path == candidate, |path| path == candidate))
+        });
+        if found.is_some() {
+            return found;
+        }
+    }
+    app.resolve_wiki_link(target.strip_suffix(".md").unwrap_or(target))
+}
+
+#[derive(Serialize)]
+struct TagRecord<'a> {
+    tag: &'a str,
+    count: usize,
+}
+
+/// `ekphos tags`: every frontmatter tag with its note count, `tag<TAB>count`,
+/// sorted by tag.
+fn tags(options: Options) -> Result<i32, String> {
+    if !options.positional.is_empty() {
+        return Err("tags takes no arguments".to_string());
+    }
+    let app = options.open_vault()?;
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for frontmatter in app.notes.iter().filter_map(|note| note.frontmatter.as_ref()) {
+        for tag in frontmatter.tags.iter() {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+
+    if options.json {
+        let records: Vec<_> = counts.iter().map(|(&tag, &count)| TagRecord { tag, count }).collect();
+        print_json(&records);
+    } else {
+        for (tag, count) in &counts {
+            println!("{tag}\t{count}");
+        }
+    }
+    Ok(0)
+}
+
+/// `ekphos graph --format dot|graphml|json [NOTE]`: print the link graph.
+/// With NOTE the default is NOTE's Local graph, otherwise the Global graph.
+fn export_graph(options: Options) -> Result<i32, String> {
+    let format = options.format.as_deref().ok_or("graph needs --format dot, graphml or json")?;
+    let format = GraphExportFormat::parse(format).ok_or_else(|| format!("Unknown graph format: {format}"))?;
+    let root = options.phrase();
+    let mode = match (options.mode.as_deref(), &root) {
+        (Some("local"), _) | (None, Some(_)) => GraphMode::Local,
+        (Some("global"), _) | (None, None) => GraphMode::Global,
+        (Some(mode), _) => return Err(format!("Unknown graph mode: {mode}")),
+    };
+    let link_scope = match options.scope.as_deref() {
+        None | Some("all") => GraphLinkScope::All,
+        Some("incoming") => GraphLinkScope::Incoming,
+        Some("outgoing") => GraphLinkScope::Outgoing,
+        Some(scope) => return Err(format!("Unknown link scope: {scope}")),
+    };
+    let depth = match options.depth.as_deref() {
+        Some(depth) => depth
+            .parse::<usize>()
+            .ok()
+            .filter(|depth| (1..=5).contains(depth))
+            .ok_or_else(|| format!("Depth must be 1 to 5: {depth}"))?,
+        None => 1,
+    };
+
+    let app = options.open_vault()?;
+    let root_note_index = match &root {
+        Some(target) => resolve_note(&app, target).ok_or_else(|| format!("No such note: {target}"))?,
+        None if mode == GraphMode::Local => return Err("a Local graph needs a NOTE".to_string()),
+        None => 0,
+    };
+    let index = app.build_graph_index_blocking().ok_or("Could not build the link index")?;
+    let view = GraphViewState {
+        root_note_index,
+        mode,
+        depth,
+        link_scope,
+        filter_query: options.filter.clone().unwrap_or_default(),
+        show_orphans: !options.no_orphans,
+        ..GraphViewState::default()
+    };
+    let projection = app.project_graph_blocking(&index, &view);
+    let export = ekphos_graph::export_graph(
+        &index,
+        &projection.nodes,
+        &projection.edges,
+        GraphExportOptions {
+            format,
+            positions: options.positions,
+        },
+    );
+    print!("{export}");
+    // The formats are directed: a bidirectional link is written as two edges.
+    let links: usize = projection.edges.iter().map(|edge| 1 + usize::from(edge.bidirectional)).sum();
+    eprintln!("Exported {} notes and {links} links", projection.nodes.len());
+    Ok(0)
+}
+
+/// Run queued hooks to completion; the process is about to exit.
+fn report_hook_failures(app: &mut App) {
+    for failure in app.wait_for_commands() {
+        eprintln!("{failure}");
+    }
+}
+
+fn print_json(value: &impl Serialize) {
+    match serde_json::to_string(value) {
+        Ok(json) => println!("{json}"),
+        Err(error) => eprintln!("Could not encode JSON: {error}"),
+    }
+}
+
+fn expand_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+    match std::env::current_dir() {
+        Ok(current) if path.is_relative() => current.join(path),
+        _ => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_support::TempVault;
+    use std::fs;
+
+    fn sample_vault() -> TempVault {
+        TempVault::with_files(&[
+            ("index.md", "# Index\n\nSee [[Topic]] about rpc calls\n"),
+            ("sub/Topic.md", "# Topic\n"),
+            ("plan.md", "# {{title}}\n\nDue {{date}}\n"),
+        ])
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn options_accept_listed_flags_and_keep_the_rest_positional() {
+        let options = Options::parse(
+            "new",
+            &args(&["Weekly", "plan", "--template=Templates/plan.md", "--json", "--", "--literal"]),
+            &["--json", "--template"],
+        )
+        .unwrap();
+        assert!(options.json);
+        assert_eq!(options.template.as_deref(), Some("Templates/plan.md"));
+        assert_eq!(options.phrase().as_deref(), Some("Weekly plan --literal"));
+
+        assert_eq!(
+            Options::parse("tags", &args(&["--strict"]), &["--json"]),
+            Err("Unknown option for tags: --strict".to_string())
+        );
+        assert_eq!(
+            Options::parse("journal", &args(&["--date"]), &["--date"]),
+            Err("--date needs a value".to_string())
+        );
+    }
+
+    #[test]
+    fn headless_helpers_create_resolve_link_and_search() {
+        let vault = sample_vault();
+        let mut app = vault.app();
+
+        let path = app.create_note_from_template("Projects/Launch", Some("plan.md")).unwrap();
+        let today = app.dependencies.clock.today().format("%Y-%m-%d");
+        assert_eq!(fs::read_to_string(&path).unwrap(), format!("# Launch\n\nDue {today}\n"));
+        assert_eq!(
+            app.create_note_from_template("Projects/Launch", None),
+            Err("Projects/Launch.md already exists".to_string())
+        );
+        assert!(app.create_note_from_template("../outside", None).is_err());
+
+        let index_note = resolve_note(&app, "index.md").unwrap();
+        assert_eq!(resolve_note(&app, "sub/Topic"), resolve_note(&app, "Topic"));
+        let graph = app.build_graph_index_blocking().unwrap();
+        let outgoing: Vec<_> = graph.outgoing_notes(app.notes[index_note].id).map(|node| node.title.as_str()).collect();
+        assert_eq!(outgoing, ["Topic"]);
+
+        app.load_search_index_blocking();
+        let hits = app.headless_content_search_hits("rpc");
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].note_id, hits[0].line_number), (app.notes[index_note].id, 2));
+    }
+
+    #[test]
+    fn graph_export_projects_like_the_graph_view() {
+        let vault = sample_vault();
+        let app = vault.app();
+        let index = app.build_graph_index_blocking().unwrap();
+        let root_note_index = resolve_note(&app, "Topic").unwrap();
+
+        let local = app.project_graph_blocking(
+            &index,
+            &GraphViewState {
+                root_note_index,
+                link_scope: GraphLinkScope::Incoming,
+                ..GraphViewState::default()
+            },
+        );
+        let json = ekphos_graph::export_graph(
+            &index,
+            &local.nodes,
+            &local.edges,
+            GraphExportOptions {
+                format: GraphExportFormat::Json,
+                positions: true,
+            },
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut ids: Vec<_> = value["nodes"].as_array().unwrap().iter().map(|node| node["id"].as_str().unwrap()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["index", "sub/Topic"]);
+        assert_eq!(
+            (value["links"][0]["source"].as_str(), value["links"][0]["target"].as_str()),
+            (Some("index"), Some("sub/Topic"))
+        );
+        assert!(value["nodes"][0]["x"].is_number());
+
+        let global = app.project_graph_blocking(
+            &index,
+            &GraphViewState {
+                mode: GraphMode::Global,
+                show_orphans: false,
+                ..GraphViewState::default()
+            },
+        );
+        assert_eq!(global.nodes.len(), 2);
+        assert!(global.nodes.iter().all(|node| node.x.is_finite() && node.y.is_finite()));
+    }
+}
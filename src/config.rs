@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-use crate::keybindings::KeybindingsConfig;
+use crate::keybindings::{KeybindingsConfig, VimConfig};
 pub use ekphos_editor::LineNumberMode;
+use ekphos_vault::journal::{PeriodKind, PeriodicNoteSpec};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -42,6 +43,9 @@ pub struct Config {
     pub show_tags: bool,
     #[serde(default = "default_check_updates")]
     pub check_updates: bool,
+    /// Show git status and line changes when the vault is in a repository.
+    #[serde(default = "default_git_integration")]
+    pub git_integration: bool,
     #[serde(default = "default_transparent_bg")]
     pub transparent_bg: bool,
     #[serde(default = "default_floating_cursor")]
@@ -49,7 +53,22 @@ pub struct Config {
     #[serde(default)]
     pub editor: EditorConfig,
     #[serde(default)]
+    pub periodic: PeriodicConfig,
+    #[serde(default)]
     pub keybindings: KeybindingsConfig,
+    #[serde(default)]
+    pub vim: VimConfig,
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    #[serde(default)]
+    pub snapshots: SnapshotsConfig,
+    #[serde(default)]
+    pub extract: ExtractConfig,
+    #[serde(default)]
+    pub graph: GraphConfig,
+    /// User commands listed in the command palette, each `[[actions]]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<ActionConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,6 +116,288 @@ impl Default for EditorConfig {
     }
 }
 
+/// Weekly, monthly, quarterly and yearly notes, each under `[periodic.<kind>]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodicConfig {
+    #[serde(default = "PeriodicNoteConfig::weekly")]
+    pub weekly: PeriodicNoteConfig,
+    #[serde(default = "PeriodicNoteConfig::monthly")]
+    pub monthly: PeriodicNoteConfig,
+    #[serde(default = "PeriodicNoteConfig::quarterly")]
+    pub quarterly: PeriodicNoteConfig,
+    #[serde(default = "PeriodicNoteConfig::yearly")]
+    pub yearly: PeriodicNoteConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodicNoteConfig {
+    /// Vault-relative directory; defaults to `journal_dir` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    /// strftime pattern below `directory`, e.g. `%G/week.%G-W%V.md`.
+    pub filename: String,
+    /// Vault-relative template file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl PeriodicNoteConfig {
+    fn for_kind(kind: PeriodKind) -> Self {
+        Self {
+            directory: None,
+            filename: kind.default_filename().to_string(),
+            template: None,
+        }
+    }
+
+    fn weekly() -> Self {
+        Self::for_kind(PeriodKind::Week)
+    }
+    fn monthly() -> Self {
+        Self::for_kind(PeriodKind::Month)
+    }
+    fn quarterly() -> Self {
+        Self::for_kind(PeriodKind::Quarter)
+    }
+    fn yearly() -> Self {
+        Self::for_kind(PeriodKind::Year)
+    }
+}
+
+impl Default for PeriodicConfig {
+    fn default() -> Self {
+        Self {
+            weekly: PeriodicNoteConfig::weekly(),
+            monthly: PeriodicNoteConfig::monthly(),
+            quarterly: PeriodicNoteConfig::quarterly(),
+            yearly: PeriodicNoteConfig::yearly(),
+        }
+    }
+}
+
+/// Vault events a `[hooks]` command can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Save,
+    Create,
+    Delete,
+    Rename,
+    JournalOpen,
+}
+
+impl HookEvent {
+    /// The config key, also exported to the command as `EKPHOS_EVENT`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Save => "on_save",
+            Self::Create => "on_create",
+            Self::Delete => "on_delete",
+            Self::Rename => "on_rename",
+            Self::JournalOpen => "on_journal_open",
+        }
+    }
+}
+
+/// `[snapshots]` section: local copies of previous versions, kept in the
+/// cache directory each time a note is saved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_snapshots_keep_last")]
+    pub keep_last: usize,
+    #[serde(default = "default_snapshots_keep_hourly")]
+    pub keep_hourly: usize,
+    #[serde(default = "default_snapshots_keep_daily")]
+    pub keep_daily: usize,
+}
+
+fn default_snapshots_keep_last() -> usize {
+    20
+}
+fn default_snapshots_keep_hourly() -> usize {
+    24
+}
+fn default_snapshots_keep_daily() -> usize {
+    30
+}
+
+impl Default for SnapshotsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep_last: default_snapshots_keep_last(),
+            keep_hourly: default_snapshots_keep_hourly(),
+            keep_daily: default_snapshots_keep_daily(),
+        }
+    }
+}
+
+impl SnapshotsConfig {
+    pub fn policy(&self) -> ekphos_vault::snapshots::RetentionPolicy {
+        ekphos_vault::snapshots::RetentionPolicy {
+            keep_last: self.keep_last,
+            keep_hourly: self.keep_hourly,
+            keep_daily: self.keep_daily,
+        }
+    }
+}
+
+/// `[extract]` section: how "extract to note" builds the new note and what
+/// it leaves behind.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtractConfig {
+    /// Vault-relative template with `{{title}}`, `{{content}}`, `{{source}}`
+    /// and `{{date}}` placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Leave `![[New Note]]` instead of `[[New Note]]` in the source note.
+    #[serde(default)]
+    pub embed: bool,
+}
+
+/// `[graph]` section: which links besides `[[wiki]]` links the graph view,
+/// export and `ekphos links` follow, and the colour groups the view paints.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GraphConfig {
+    /// `[text](other-note.md)` links. Off by default so turning the graph
+    /// sources on never changes an existing vault's graph unasked.
+    #[serde(default)]
+    pub markdown_links: bool,
+    /// A node per tag, linked from each note carrying it.
+    #[serde(default)]
+    pub tags: bool,
+    /// A node per linked or embedded image or PDF.
+    #[serde(default)]
+    pub attachments: bool,
+    /// Dock a live local graph of the current note under the outline.
+    #[serde(default)]
+    pub local_pane: bool,
+    /// Link hops the docked local graph shows, 1 or 2.
+    #[serde(default = "default_graph_local_pane_depth")]
+    pub local_pane_depth: usize,
+    /// `[[graph.groups]]`, tried in order; the first match colours a node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GraphColorGroup>,
+}
+
+/// A graph filter query (`path:projects/`, `#person`, …) and the colour of
+/// the nodes it matches: a theme slot such as `primary` or `tag`, a colour
+/// name such as `blue`, or `#rrggbb`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GraphColorGroup {
+    pub query: String,
+    pub color: String,
+}
+
+impl GraphColorGroup {
+    pub fn resolve_color(&self, theme: &Theme) -> Option<Color> {
+        let name = self.color.trim();
+        let slot = match name.to_ascii_lowercase().as_str() {
+            "primary" => Some(theme.primary),
+            "secondary" => Some(theme.secondary),
+            "error" => Some(theme.error),
+            "warning" => Some(theme.warning),
+            "success" => Some(theme.success),
+            "info" => Some(theme.info),
+            "muted" => Some(theme.muted),
+            "foreground" => Some(theme.foreground),
+            "tag" => Some(theme.content.tag),
+            "link" => Some(theme.content.link),
+            "code" => Some(theme.content.code),
+            _ => None,
+        };
+        slot.or_else(|| name.parse().ok())
+    }
+}
+
+fn default_graph_local_pane_depth() -> usize {
+    1
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        Self {
+            markdown_links: false,
+            tags: false,
+            attachments: false,
+            local_pane: false,
+            local_pane_depth: default_graph_local_pane_depth(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl GraphConfig {
+    pub fn sources(&self) -> ekphos_graph::GraphSources {
+        ekphos_graph::GraphSources {
+            markdown_links: self.markdown_links,
+            tags: self.tags,
+            attachments: self.attachments,
+        }
+    }
+
+    pub fn local_pane_depth(&self) -> usize {
+        self.local_pane_depth.clamp(1, 2)
+    }
+}
+
+/// `[hooks]` section: shell commands run in the vault root after vault
+/// events, with `EKPHOS_NOTE` and `EKPHOS_VAULT` set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HooksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_save: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_rename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_journal_open: Option<String>,
+    /// Hooks and actions still running after this long are killed.
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_hook_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            on_save: None,
+            on_create: None,
+            on_delete: None,
+            on_rename: None,
+            on_journal_open: None,
+            timeout_secs: default_hook_timeout_secs(),
+        }
+    }
+}
+
+impl HooksConfig {
+    pub fn command(&self, event: HookEvent) -> Option<&str> {
+        let command = match event {
+            HookEvent::Save => &self.on_save,
+            HookEvent::Create => &self.on_create,
+            HookEvent::Delete => &self.on_delete,
+            HookEvent::Rename => &self.on_rename,
+            HookEvent::JournalOpen => &self.on_journal_open,
+        };
+        command.as_deref().map(str::trim).filter(|command| !command.is_empty())
+    }
+}
+
+/// A named command run from the palette with the editor selection on stdin.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionConfig {
+    pub name: String,
+    pub command: String,
+}
+
 fn default_notes_dir() -> String {
     "~/Documents/ekphos".to_string()
 }
@@ -145,6 +446,9 @@ fn default_show_tags() -> bool {
 fn default_check_updates() -> bool {
     true
 }
+fn default_git_integration() -> bool {
+    true
+}
 fn default_transparent_bg() -> bool {
     false
 }
@@ -171,10 +475,18 @@ impl Default for Config {
             frontmatter_hidden: default_frontmatter_hidden(),
             show_tags: default_show_tags(),
             check_updates: default_check_updates(),
+            git_integration: default_git_integration(),
             transparent_bg: default_transparent_bg(),
             floating_cursor: default_floating_cursor(),
             editor: EditorConfig::default(),
+            periodic: PeriodicConfig::default(),
             keybindings: KeybindingsConfig::default(),
+            vim: VimConfig::default(),
+            hooks: HooksConfig::default(),
+            snapshots: SnapshotsConfig::default(),
+            extract: ExtractConfig::default(),
+            graph: GraphConfig::default(),
+            actions: Vec::new(),
         }
     }
 }
@@ -300,6 +612,26 @@ impl Config {
         let path = shellexpand::tilde(&self.notes_dir).to_string();
         PathBuf::from(path)
     }
+
+    pub fn periodic_note(&self, kind: PeriodKind) -> &PeriodicNoteConfig {
+        match kind {
+            PeriodKind::Week => &self.periodic.weekly,
+            PeriodKind::Month => &self.periodic.monthly,
+            PeriodKind::Quarter => &self.periodic.quarterly,
+            PeriodKind::Year => &self.periodic.yearly,
+        }
+    }
+
+    /// Resolve a periodic note kind against `journal_dir` for the vault layer.
+    pub fn periodic_spec(&self, kind: PeriodKind) -> PeriodicNoteSpec<'_> {
+        let note = self.periodic_note(kind);
+        PeriodicNoteSpec {
+            kind,
+            directory: note.directory.as_deref().unwrap_or(&self.journal_dir),
+            filename: &note.filename,
+            template: note.template.as_deref(),
+        }
+    }
 }
 
 // ============================================================================
@@ -1062,6 +1394,35 @@ mod tests {
         assert_eq!(keymap.binding_label(AppCommand::Quit), "q");
     }
 
+    #[test]
+    fn vim_mappings_load_from_toml_and_skip_invalid_entries() {
+        let config: Config = toml::from_str(
+            "[vim]\nleader = '<Space>'\n[vim.mappings.inoremap]\njk = '<Esc>'\n[vim.mappings.nnoremap]\n'<leader>g' = '<Cmd>open_graph<CR>'\n'<leader>x' = '<Cmd>no_such_command<CR>'\n[vim.mappings.omap]\nx = 'y'\n",
+        )
+        .unwrap();
+        let (mapper, issues) = config.vim.mapper();
+
+        assert_eq!(config.vim.timeout_ms, 1000);
+        assert_eq!(mapper.list(&[ekphos_vim::MapMode::Normal], ""), ["n <Space>g *<Cmd>open_graph<CR>"]);
+        assert_eq!(mapper.list(&[ekphos_vim::MapMode::Insert], ""), ["i jk *<Esc>"]);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("no_such_command"));
+        assert!(issues[1].contains("omap"));
+    }
+
+    #[test]
+    fn hooks_and_actions_load_from_toml() {
+        let config: Config =
+            toml::from_str("[hooks]\non_save = 'git commit -am save'\non_delete = '  '\n\n[[actions]]\nname = 'Word count'\ncommand = 'wc -w'\n").unwrap();
+
+        assert_eq!(config.hooks.command(HookEvent::Save), Some("git commit -am save"));
+        assert_eq!(config.hooks.command(HookEvent::Delete), None);
+        assert_eq!(config.hooks.timeout_secs, 10);
+        assert_eq!(config.actions[0].name, "Word count");
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("[[actions]]"));
+    }
+
     #[test]
     fn journal_directory_defaults_when_missing_from_toml() {
         let config: Config = toml::from_str("notes_dir = '/tmp/notes'").unwrap();
@@ -1078,6 +1439,30 @@ mod tests {
         assert!(serialized.contains("journal_dir = \"Personal/Daily Notes\""));
     }
 
+    #[test]
+    fn periodic_notes_default_to_the_journal_directory() {
+        let config: Config = toml::from_str("journal_dir = 'Daily'").unwrap();
+        let spec = config.periodic_spec(PeriodKind::Week);
+
+        assert_eq!(spec.directory, "Daily");
+        assert_eq!(spec.filename, "%G/week.%G-W%V.md");
+        assert_eq!(spec.template, None);
+    }
+
+    #[test]
+    fn partial_periodic_table_keeps_other_kinds() {
+        let config: Config = toml::from_str("[periodic.monthly]\ndirectory = 'Reviews'\nfilename = '%Y-%m.md'\ntemplate = 'Templates/month.md'").unwrap();
+        let monthly = config.periodic_spec(PeriodKind::Month);
+
+        assert_eq!(monthly.directory, "Reviews");
+        assert_eq!(monthly.filename, "%Y-%m.md");
+        assert_eq!(monthly.template, Some("Templates/month.md"));
+        assert_eq!(config.periodic.yearly.filename, "%Y/year.%Y.md");
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("[periodic.monthly]"));
+        assert!(serialized.contains("template = \"Templates/month.md\""));
+    }
+
     #[test]
     fn panel_widths_default_when_missing_from_toml() {
         let config: Config = toml::from_str("notes_dir = '/tmp/notes'").unwrap();
@@ -1196,4 +1581,34 @@ mod tests {
         assert_eq!(parse_hex_color("aé234"), Color::White); // 6 bytes, 5 chars
         assert_eq!(parse_hex_color("世界AB"), Color::White);
     }
+
+    #[test]
+    fn graph_groups_round_trip_and_resolve_theme_slots_and_names() {
+        let config: Config = toml::from_str(
+            "notes_dir = '/tmp/notes'\n[[graph.groups]]\nquery = 'path:projects/'\ncolor = 'blue'\n[[graph.groups]]\nquery = '#person'\ncolor = 'Tag'\n",
+        )
+        .unwrap();
+        let theme = Theme::default();
+        let colors: Vec<_> = config.graph.groups.iter().map(|group| group.resolve_color(&theme)).collect();
+        assert_eq!(colors, [Some(Color::Blue), Some(theme.content.tag)]);
+        let invalid = GraphColorGroup {
+            query: "x".to_string(),
+            color: "not a colour".to_string(),
+        };
+        assert_eq!(invalid.resolve_color(&theme), None);
+
+        let saved: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved.graph, config.graph);
+    }
+
+    #[test]
+    fn graph_local_pane_defaults_off_and_clamps_depth() {
+        let config: Config = toml::from_str("notes_dir = '/tmp/notes'\n").unwrap();
+        assert!(!config.graph.local_pane);
+        assert_eq!(config.graph.local_pane_depth(), 1);
+
+        let config: Config = toml::from_str("notes_dir = '/tmp/notes'\n[graph]\nlocal_pane = true\nlocal_pane_depth = 5\n").unwrap();
+        assert!(config.graph.local_pane);
+        assert_eq!(config.graph.local_pane_depth(), 2);
+    }
 }
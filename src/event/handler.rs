@@ -1,20 +1,23 @@
+use std::collections::VecDeque;
 use std::io;
+use std::time::Instant;
 
 use crossterm::cursor::SetCursorStyle;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use crate::app::{
-    App, BlockInsertMode, BlockInsertState, ContextMenuItem, ContextMenuState, DeleteType, DialogState, Focus, LinkInfo, Mode, SearchPickerState,
-    SidebarItemKind, VimMode, WikiAutocompleteMode, WikiAutocompleteState,
+    AgendaFilter, App, BlockInsertMode, BlockInsertState, ContextMenuItem, ContextMenuState, DeleteType, DialogState, Focus, LinkInfo, Mode, PaletteTarget,
+    PropertyEditor, SearchPickerState, SidebarItemKind, TrashConfirm, VimMode, WikiAutocompleteMode, WikiAutocompleteState,
 };
 use crate::clipboard::{self, ClipboardContent};
 use crate::config::Config;
 use crate::keybindings::{AppCommand, KeyResolution};
 use crate::ui;
 use ekphos_editor::{CursorMove, CursorShape, Position};
+use ekphos_vault::journal::PeriodKind;
 use ekphos_vim::command::{parse_command, Command};
-use ekphos_vim::{FindState, PendingFind, PendingMacro, PendingMark, TextObject, TextObjectScope, VimMode as VimModeNew};
+use ekphos_vim::{FindState, MapAction, MapMode, PendingFind, PendingMacro, PendingMark, TextObject, TextObjectScope, VimMode as VimModeNew};
 
 mod commands;
 mod dialogs;
@@ -15,11 +15,31 @@ pub(super) fn handle_normal_mode(app: &mut App, key: crossterm::event::KeyEvent)
 pub(super) fn app_command_available(app: &App, command: AppCommand) -> bool {
     match command {
         AppCommand::FocusNext | AppCommand::FocusPrevious => !app.zen_mode,
-        AppCommand::OpenJournal | AppCommand::CreateNote | AppCommand::CreateFolder | AppCommand::DeleteItem | AppCommand::RenameItem => !app.zen_mode,
+        AppCommand::OpenJournal
+        | AppCommand::OpenWeeklyNote
+        | AppCommand::OpenMonthlyNote
+        | AppCommand::OpenQuarterlyNote
+        | AppCommand::OpenYearlyNote
+        | AppCommand::CreateNote
+        | AppCommand::CreateFolder
+        | AppCommand::DeleteItem
+        | AppCommand::RenameItem => !app.zen_mode,
         AppCommand::CutItem => !app.zen_mode && app.focus == Focus::Sidebar,
         AppCommand::PasteItem => !app.zen_mode && app.focus == Focus::Sidebar && app.cut_buffer.is_some(),
         AppCommand::HistoryBack | AppCommand::HistoryForward => app.focus != Focus::Sidebar,
-        AppCommand::OpenSelected => matches!(app.focus, Focus::Content | Focus::Outline),
+        AppCommand::EditProperties => app.current_note().is_some(),
+        AppCommand::GitCommit => app.git.is_some(),
+        AppCommand::GitHistory => app.git.is_some() && app.current_note().is_some(),
+        AppCommand::NoteHistory => app.config.snapshots.enabled && app.current_note().is_some(),
+        AppCommand::ExtractSection | AppCommand::RenameHeading | AppCommand::CopyBlockLink => app.focus == Focus::Content && app.current_note().is_some(),
+        AppCommand::MergeNote => {
+            app.focus == Focus::Sidebar
+                && matches!(
+                    app.sidebar_items.get(app.selected_sidebar_index).map(|item| &item.kind),
+                    Some(SidebarItemKind::Note { .. })
+                )
+        }
+        AppCommand::OpenSelected => matches!(app.focus, Focus::Content | Focus::Outline | Focus::LocalGraph),
         AppCommand::ContentAction
         | AppCommand::NextTarget
         | AppCommand::PreviousTarget
@@ -115,9 +135,27 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
         AppCommand::OpenQuickSearch => app.open_search_picker(),
         AppCommand::OpenThemeSelector => app.open_theme_selector(),
         AppCommand::OpenJournal => app.open_or_create_journal(),
+        AppCommand::OpenWeeklyNote => app.open_or_create_periodic_note(PeriodKind::Week),
+        AppCommand::OpenMonthlyNote => app.open_or_create_periodic_note(PeriodKind::Month),
+        AppCommand::OpenQuarterlyNote => app.open_or_create_periodic_note(PeriodKind::Quarter),
+        AppCommand::OpenYearlyNote => app.open_or_create_periodic_note(PeriodKind::Year),
+        AppCommand::OpenAgenda => app.open_agenda(),
+        AppCommand::EditProperties => app.open_properties(),
+        AppCommand::OpenCommandPalette => app.open_command_palette(),
+        AppCommand::OpenCommandLog => app.open_command_log(),
+        AppCommand::GitCommit => app.open_git_commit_dialog(),
+        AppCommand::GitHistory => app.open_git_history(),
+        AppCommand::NoteHistory => app.open_note_history(),
+        AppCommand::OpenTrash => app.open_trash(),
+        AppCommand::ExtractSection => app.extract_section_to_note(),
+        AppCommand::MergeNote => app.open_merge_for_selected_note(),
+        AppCommand::RenameHeading => app.open_heading_rename(),
+        AppCommand::CopyBlockLink => app.copy_block_link(),
+        AppCommand::VaultDoctor => app.open_vault_doctor(),
         AppCommand::MoveDown => match app.focus {
             Focus::Sidebar => app.next_sidebar_item(),
             Focus::Outline => app.next_outline(),
+            Focus::LocalGraph => app.move_local_graph_selection(1),
             Focus::Content => {
                 if app.floating_cursor_mode {
                     app.floating_move_down();
@@ -130,6 +168,7 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
         AppCommand::MoveUp => match app.focus {
             Focus::Sidebar => app.previous_sidebar_item(),
             Focus::Outline => app.previous_outline(),
+            Focus::LocalGraph => app.move_local_graph_selection(-1),
             Focus::Content => {
                 if app.floating_cursor_mode {
                     app.floating_move_up();
@@ -146,9 +185,11 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
                 }
             }
             Focus::Outline => app.jump_to_outline(),
+            Focus::LocalGraph => app.open_local_graph_selection(),
             Focus::Sidebar => app.handle_sidebar_enter(),
         },
         AppCommand::ToggleOutline => app.toggle_outline_collapsed(),
+        AppCommand::ToggleLocalGraph => app.toggle_local_graph(),
         AppCommand::HistoryBack => {
             app.navigate_back();
         }
@@ -163,6 +204,8 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
             } else if app.focus == Focus::Outline {
                 // 'o' on outline just jumps to content view without edit
                 app.jump_to_outline();
+            } else if app.focus == Focus::LocalGraph {
+                app.open_local_graph_selection();
             }
         }
         AppCommand::ShowHelp => app.dialog = DialogState::Help,
@@ -208,6 +251,7 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
         AppCommand::GoFirst => match app.focus {
             Focus::Sidebar => app.goto_first_sidebar_item(),
             Focus::Outline => app.goto_first_outline(),
+            Focus::LocalGraph => app.select_local_graph_edge(false),
             Focus::Content => {
                 app.goto_first_content_line();
                 app.sync_outline_to_content();
@@ -216,6 +260,7 @@ pub(super) fn execute_app_command(app: &mut App, command: AppCommand) -> bool {
         AppCommand::GoLast => match app.focus {
             Focus::Sidebar => app.goto_last_sidebar_item(),
             Focus::Outline => app.goto_last_outline(),
+            Focus::LocalGraph => app.select_local_graph_edge(true),
             Focus::Content => {
                 app.goto_last_content_line();
                 app.sync_outline_to_content();
@@ -113,6 +113,60 @@ pub(super) fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) -
             handle_theme_selector_dialog(app, key);
             return Ok(false);
         }
+        DialogState::Agenda => {
+            app.keymap.reset_pending();
+            handle_agenda_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::Properties => {
+            app.keymap.reset_pending();
+            handle_properties_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::CommandPalette => {
+            app.keymap.reset_pending();
+            return Ok(handle_command_palette_dialog(app, key));
+        }
+        DialogState::CommandLog => {
+            app.keymap.reset_pending();
+            handle_command_log_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::GitCommit => {
+            app.keymap.reset_pending();
+            handle_git_commit_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::GitHistory => {
+            app.keymap.reset_pending();
+            handle_git_history_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::NoteHistory => {
+            app.keymap.reset_pending();
+            handle_note_history_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::Trash => {
+            app.keymap.reset_pending();
+            handle_trash_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::MergeNote => {
+            app.keymap.reset_pending();
+            handle_merge_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::HeadingRename => {
+            app.keymap.reset_pending();
+            handle_heading_rename_dialog(app, key);
+            return Ok(false);
+        }
+        DialogState::VaultDoctor => {
+            app.keymap.reset_pending();
+            handle_vault_doctor_dialog(app, key);
+            return Ok(false);
+        }
         DialogState::None => {}
     }
 
@@ -152,7 +206,9 @@ pub(super) fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) -
         }
         Mode::Edit => {
             app.keymap.reset_pending();
-            handle_edit_mode(app, key);
+            if handle_edit_mode(app, key) {
+                return Ok(true);
+            }
         }
     }
 
@@ -708,6 +764,297 @@ pub(super) fn handle_help_dialog(app: &mut App, key: crossterm::event::KeyEvent)
     }
 }
 
+pub(super) fn handle_agenda_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_agenda(),
+        KeyCode::Enter => app.agenda_open_selected(),
+        KeyCode::Char(' ') | KeyCode::Char('x') => app.agenda_toggle_selected(),
+        KeyCode::Down | KeyCode::Char('j') => app.agenda_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.agenda_select_prev(),
+        KeyCode::Tab => app.agenda_cycle_grouping(),
+        KeyCode::Char('f') => app.agenda_cycle_filter(),
+        KeyCode::Char('a') => app.agenda_set_filter(AgendaFilter::All),
+        KeyCode::Char('o') => app.agenda_set_filter(AgendaFilter::Overdue),
+        KeyCode::Char('t') => app.agenda_set_filter(AgendaFilter::Today),
+        KeyCode::Char('u') => app.agenda_set_filter(AgendaFilter::Upcoming),
+        KeyCode::Char('c') => app.agenda_toggle_completed(),
+        KeyCode::Char('r') => app.refresh_agenda(),
+        _ => {}
+    }
+}
+
+/// Returns true if the chosen command quits the app.
+pub(super) fn handle_command_palette_dialog(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Down | KeyCode::Tab => app.command_palette_move(true),
+        KeyCode::Up | KeyCode::BackTab => app.command_palette_move(false),
+        KeyCode::Char('n' | 'j') if ctrl => app.command_palette_move(true),
+        KeyCode::Char('p' | 'k') if ctrl => app.command_palette_move(false),
+        _ => {
+            let mut error = None;
+            match apply_text_dialog_key(&mut app.command_palette.query, &mut error, key, false) {
+                DialogCommand::Submit => {
+                    let selection = app.command_palette.selection.take();
+                    if let Some(target) = app.accept_command_palette() {
+                        return run_palette_target(app, target, selection);
+                    }
+                }
+                DialogCommand::Cancel => app.close_command_palette(),
+                DialogCommand::Edited => app.refresh_command_palette(),
+                DialogCommand::Ignore => {}
+            }
+        }
+    }
+    false
+}
+
+fn run_palette_target(app: &mut App, target: PaletteTarget, selection: Option<String>) -> bool {
+    match target {
+        PaletteTarget::Command(command) => {
+            // Commands act on the main view; leave the editor as `:w` does.
+            if app.mode == Mode::Edit {
+                app.save_edit();
+            }
+            if app_command_available(app, command) {
+                return execute_app_command(app, command);
+            }
+            app.status_message = Some(format!("{} is not available here", command.title()));
+        }
+        PaletteTarget::Ex { command, prefill } => {
+            if app.mode != Mode::Edit {
+                return false;
+            }
+            if prefill {
+                app.vim.enter_command_mode();
+                app.vim.command_buffer = command.to_string();
+            } else if let Some(command) = parse_command(command) {
+                execute_vim_command(app, command);
+            }
+            app.update_editor_block();
+        }
+        PaletteTarget::Theme(name) => app.set_theme(&name),
+        PaletteTarget::Note(note_id) => {
+            if app.mode == Mode::Edit {
+                app.save_edit();
+            }
+            if let Some(index) = app.note_index_for_id(note_id) {
+                app.navigate_to_note(index);
+                app.focus = Focus::Content;
+            }
+        }
+        PaletteTarget::Action(index) => app.run_action(index, selection),
+    }
+    false
+}
+
+pub(super) fn handle_command_log_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.close_command_log(),
+        KeyCode::Char('j') | KeyCode::Down => app.command_log_scroll = app.command_log_scroll.saturating_add(1),
+        KeyCode::Char('k') | KeyCode::Up => app.command_log_scroll = app.command_log_scroll.saturating_sub(1),
+        KeyCode::Char('d') if ctrl => app.command_log_scroll = app.command_log_scroll.saturating_add(10),
+        KeyCode::Char('u') if ctrl => app.command_log_scroll = app.command_log_scroll.saturating_sub(10),
+        KeyCode::Char('g') => app.command_log_scroll = 0,
+        // Clamped to the content height when rendered.
+        KeyCode::Char('G') => app.command_log_scroll = usize::MAX,
+        KeyCode::Char('c') => {
+            app.command_log.clear();
+            app.command_log_scroll = 0;
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn handle_git_commit_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    match apply_text_dialog_key(&mut app.input_buffer, &mut app.dialog_error, key, true) {
+        DialogCommand::Submit => {
+            let message = app.input_buffer.clone();
+            app.commit_all(&message);
+        }
+        DialogCommand::Cancel => {
+            app.input_buffer.clear();
+            app.dialog_error = None;
+            app.dialog = DialogState::None;
+        }
+        DialogCommand::Edited | DialogCommand::Ignore => {}
+    }
+}
+
+pub(super) fn handle_git_history_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let Some(history) = app.git_history.as_mut() else {
+        app.close_git_history();
+        return;
+    };
+    let (selected, last) = (history.selected, history.commits.len().saturating_sub(1));
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_git_history(),
+        KeyCode::Enter | KeyCode::Char('r') => app.restore_git_version(),
+        KeyCode::Char('j') | KeyCode::Down => app.select_git_history_commit(selected + 1),
+        KeyCode::Char('k') | KeyCode::Up => app.select_git_history_commit(selected.saturating_sub(1)),
+        KeyCode::Char('g') => app.select_git_history_commit(0),
+        KeyCode::Char('G') => app.select_git_history_commit(last),
+        KeyCode::Char('d') if ctrl => history.diff_scroll = history.diff_scroll.saturating_add(10),
+        KeyCode::Char('u') if ctrl => history.diff_scroll = history.diff_scroll.saturating_sub(10),
+        KeyCode::Char('J') | KeyCode::PageDown => history.diff_scroll = history.diff_scroll.saturating_add(1),
+        KeyCode::Char('K') | KeyCode::PageUp => history.diff_scroll = history.diff_scroll.saturating_sub(1),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_note_history_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let Some(history) = app.note_history.as_mut() else {
+        app.close_note_history();
+        return;
+    };
+    let (selected, last) = (history.selected, history.snapshots.len().saturating_sub(1));
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_note_history(),
+        KeyCode::Enter | KeyCode::Char('r') => app.restore_note_snapshot(),
+        KeyCode::Char('j') | KeyCode::Down => app.select_note_snapshot(selected + 1),
+        KeyCode::Char('k') | KeyCode::Up => app.select_note_snapshot(selected.saturating_sub(1)),
+        KeyCode::Char('g') => app.select_note_snapshot(0),
+        KeyCode::Char('G') => app.select_note_snapshot(last),
+        KeyCode::Char('d') if ctrl => history.diff_scroll = history.diff_scroll.saturating_add(10),
+        KeyCode::Char('u') if ctrl => history.diff_scroll = history.diff_scroll.saturating_sub(10),
+        KeyCode::Char('J') | KeyCode::PageDown => history.diff_scroll = history.diff_scroll.saturating_add(1),
+        KeyCode::Char('K') | KeyCode::PageUp => history.diff_scroll = history.diff_scroll.saturating_sub(1),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_trash_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let Some(trash) = app.trash.as_mut() else {
+        app.close_trash();
+        return;
+    };
+    if trash.confirm.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.purge_trash(),
+            _ => trash.confirm = None,
+        }
+        return;
+    }
+    let last = trash.entries.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_trash(),
+        KeyCode::Enter | KeyCode::Char('r') => app.restore_trash_entry(),
+        KeyCode::Char('d') => app.confirm_trash_action(TrashConfirm::Purge),
+        KeyCode::Char('E') => app.confirm_trash_action(TrashConfirm::Empty),
+        KeyCode::Char('j') | KeyCode::Down => trash.selected = (trash.selected + 1).min(last),
+        KeyCode::Char('k') | KeyCode::Up => trash.selected = trash.selected.saturating_sub(1),
+        KeyCode::Char('g') => trash.selected = 0,
+        KeyCode::Char('G') => trash.selected = last,
+        _ => {}
+    }
+}
+
+pub(super) fn handle_merge_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let Some(merge) = app.merge.as_ref() else {
+        app.close_merge_dialog();
+        return;
+    };
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    if merge.plan.is_some() {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => app.apply_merge(),
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => app.cancel_merge_preview(),
+            _ => {}
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc => app.close_merge_dialog(),
+        KeyCode::Enter => app.preview_merge(),
+        KeyCode::Down => app.merge_select(true),
+        KeyCode::Up => app.merge_select(false),
+        KeyCode::Char('j') | KeyCode::Char('n') if ctrl => app.merge_select(true),
+        KeyCode::Char('k') | KeyCode::Char('p') if ctrl => app.merge_select(false),
+        KeyCode::Backspace => app.merge_query_pop(),
+        KeyCode::Char(c) if !ctrl => app.merge_query_push(c),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_heading_rename_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let Some(state) = app.heading_rename.as_mut() else {
+        app.close_heading_rename();
+        return;
+    };
+    let Some(input) = state.input.as_mut() else {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => app.apply_heading_rename(),
+            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => app.close_heading_rename(),
+            _ => {}
+        }
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.close_heading_rename(),
+        KeyCode::Enter => app.submit_heading_rename(),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_properties_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    match &app.properties.editor {
+        None => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_properties(),
+            KeyCode::Down | KeyCode::Char('j') => app.properties_select_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.properties_select_prev(),
+            KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char(' ') => app.properties_edit_selected(),
+            KeyCode::Char('a') => app.properties_add(),
+            KeyCode::Char('d') => app.properties_delete_selected(),
+            _ => {}
+        },
+        Some(PropertyEditor::Date { .. }) => match key.code {
+            KeyCode::Esc => app.properties_cancel(),
+            KeyCode::Enter => app.properties_submit(),
+            KeyCode::Left | KeyCode::Char('h') => app.properties_shift_date(-1),
+            KeyCode::Right | KeyCode::Char('l') => app.properties_shift_date(1),
+            KeyCode::Up | KeyCode::Char('k') => app.properties_shift_date(-7),
+            KeyCode::Down | KeyCode::Char('j') => app.properties_shift_date(7),
+            KeyCode::PageUp | KeyCode::Char('[') => app.properties_shift_month(false),
+            KeyCode::PageDown | KeyCode::Char(']') => app.properties_shift_month(true),
+            KeyCode::Char('t') => app.properties_date_today(),
+            _ => {}
+        },
+        Some(PropertyEditor::List { input: None, .. }) => match key.code {
+            KeyCode::Esc => app.properties_cancel(),
+            KeyCode::Enter => app.properties_submit(),
+            KeyCode::Down | KeyCode::Char('j') => app.properties_list_move(true),
+            KeyCode::Up | KeyCode::Char('k') => app.properties_list_move(false),
+            KeyCode::Char('a') => app.properties_list_add(),
+            KeyCode::Char('d') => app.properties_list_delete(),
+            _ => {}
+        },
+        Some(_) => {
+            if key.code == KeyCode::Tab {
+                app.properties_complete();
+                return;
+            }
+            let mut error = app.properties.error.take();
+            let command = match app.properties_input_mut() {
+                Some(input) => apply_text_dialog_key(input, &mut error, key, true),
+                None => DialogCommand::Ignore,
+            };
+            app.properties.error = error;
+            match command {
+                DialogCommand::Submit => app.properties_submit(),
+                DialogCommand::Cancel => app.properties_cancel(),
+                DialogCommand::Edited | DialogCommand::Ignore => {}
+            }
+        }
+    }
+}
+
 /// Zoom the graph view, anchoring on the selected node or graph center
 pub(super) fn handle_empty_directory_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
     match key.code {
@@ -744,6 +1091,25 @@ pub(super) fn handle_welcome_dialog(app: &mut App, key: crossterm::event::KeyEve
     }
 }
 
+pub(super) fn handle_vault_doctor_dialog(app: &mut App, key: crossterm::event::KeyEvent) {
+    let Some(doctor) = app.doctor.as_mut() else {
+        app.close_vault_doctor();
+        return;
+    };
+    let last = doctor.issues.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_vault_doctor(),
+        KeyCode::Enter => app.open_doctor_issue(),
+        KeyCode::Char('f') => app.fix_doctor_issue(),
+        KeyCode::Char('r') => app.refresh_vault_doctor(),
+        KeyCode::Char('j') | KeyCode::Down => doctor.selected = (doctor.selected + 1).min(last),
+        KeyCode::Char('k') | KeyCode::Up => doctor.selected = doctor.selected.saturating_sub(1),
+        KeyCode::Char('g') => doctor.selected = 0,
+        KeyCode::Char('G') => doctor.selected = last,
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,19 @@
 use super::*;
 
-pub(super) fn handle_edit_mode(app: &mut App, key: crossterm::event::KeyEvent) {
+/// Returns true if the app should quit.
+pub(super) fn handle_edit_mode(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    // The buffer is frozen while an external filter runs.
+    if app.filter_job.is_some() {
+        let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+        if key.code == KeyCode::Esc || ctrl_c {
+            app.cancel_filter_job();
+        }
+        return false;
+    }
+
     if handle_wiki_autocomplete(app, key) {
         app.request_highlight_update();
-        return;
+        return false;
     }
 
     // Handle context menu keyboard navigation first
@@ -37,7 +47,7 @@ pub(super) fn handle_edit_mode(app: &mut App, key: crossterm::event::KeyEvent) {
             }
             _ => {}
         }
-        return;
+        return false;
     }
 
     // Handle pending delete confirmation
@@ -67,9 +77,97 @@ pub(super) fn handle_edit_mode(app: &mut App, key: crossterm::event::KeyEvent) {
         }
         app.request_highlight_update();
         app.update_editor_block();
-        return;
+        return false;
+    }
+
+    if let Some(mode) = mapping_mode(app) {
+        return match app.vim_mappings.feed(mode, key, Instant::now()) {
+            Some(actions) => run_mapped_actions(app, actions),
+            // Held until the rest of a mapped sequence arrives or times out.
+            None => false,
+        };
+    }
+    dispatch_vim_key(app, key);
+    false
+}
+
+/// Resolves a mapped sequence prefix once its timeout has passed, e.g. a lone
+/// `j` when `jk` is mapped. Returns true when the app should quit.
+pub(super) fn flush_vim_mappings(app: &mut App) -> bool {
+    if app.mode != Mode::Edit {
+        app.vim_mappings.cancel();
+        return false;
+    }
+    match app.vim_mappings.expire(Instant::now()) {
+        Some(actions) => run_mapped_actions(app, actions),
+        None => false,
+    }
+}
+
+/// Mapping table for the current editor state, or `None` when the key belongs
+/// to the command line, a search prompt or a command awaiting its argument.
+fn mapping_mode(app: &App) -> Option<MapMode> {
+    if app.vim_mappings.is_empty()
+        || app.vim.mode.is_command()
+        || app.vim.mode.is_search()
+        || matches!(app.vim.mode, VimModeNew::SearchLocked { .. })
+        || app.vim.awaiting_argument()
+    {
+        return None;
+    }
+    Some(match app.vim_mode {
+        VimMode::Normal => MapMode::Normal,
+        VimMode::Insert | VimMode::Replace => MapMode::Insert,
+        VimMode::Visual | VimMode::VisualLine | VimMode::VisualBlock => MapMode::Visual,
+    })
+}
+
+/// Upper bound on remapped keys per typed key, like Vim's `maxmapdepth`.
+const MAX_REMAPPED_KEYS: usize = 1000;
+
+fn run_mapped_actions(app: &mut App, actions: Vec<MapAction>) -> bool {
+    let mut queue: VecDeque<MapAction> = actions.into();
+    let mut remapped = 0;
+    while let Some(action) = queue.pop_front() {
+        match action {
+            MapAction::Key(key) => dispatch_vim_key(app, key),
+            MapAction::Remap(key) => {
+                remapped += 1;
+                if remapped > MAX_REMAPPED_KEYS {
+                    app.vim_mappings.cancel();
+                    app.vim.status_message = Some("E223: recursive mapping".to_string());
+                    break;
+                }
+                let Some(mode) = mapping_mode(app) else {
+                    dispatch_vim_key(app, key);
+                    continue;
+                };
+                if let Some(actions) = app.vim_mappings.feed(mode, key, Instant::now()) {
+                    for action in actions.into_iter().rev() {
+                        queue.push_front(action);
+                    }
+                }
+            }
+            MapAction::Command(id) => {
+                app.vim_mappings.cancel();
+                // App commands act on the main view, so leave the editor the
+                // way `:w` does before running one. The palette works in both.
+                if app.mode == Mode::Edit && id != AppCommand::OpenCommandPalette.id() {
+                    app.save_edit();
+                }
+                match AppCommand::from_id(&id) {
+                    Some(command) if app_command_available(app, command) => return execute_app_command(app, command),
+                    Some(_) => app.status_message = Some(format!("Command not available here: {id}")),
+                    None => app.status_message = Some(format!("Unknown command: {id}")),
+                }
+                return false;
+            }
+        }
     }
+    false
+}
 
+fn dispatch_vim_key(app: &mut App, key: crossterm::event::KeyEvent) {
     // Check the new vim state mode for command mode
     if app.vim.mode.is_command() {
         handle_vim_command_mode(app, key);
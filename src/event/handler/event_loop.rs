@@ -55,8 +55,7 @@ pub(super) fn open_selected_content_target(app: &mut App) -> bool {
             if is_valid {
                 app.navigate_to_wiki_link_with_heading(&target, heading.as_deref());
             } else {
-                app.pending_wiki_target = Some(target);
-                app.dialog = DialogState::CreateWikiNote;
+                app.open_missing_wiki_link(target);
             }
         }
     }
@@ -80,6 +79,18 @@ pub fn run_app(terminal: &mut Terminal<CrosstermBackend<Box<dyn io::Write>>>, ap
             needs_render = true;
         }
 
+        if app.sync_local_graph() {
+            needs_render = true;
+        }
+
+        if app.tick_graph_timeline() {
+            needs_render = true;
+        }
+
+        if app.poll_agenda() {
+            needs_render = true;
+        }
+
         if app.poll_highlight_worker() {
             needs_render = true;
         }
@@ -96,11 +107,34 @@ pub fn run_app(terminal: &mut Terminal<CrosstermBackend<Box<dyn io::Write>>>, ap
             needs_render = true;
         }
 
+        if app.poll_filter_job() {
+            needs_render = true;
+        }
+
+        if app.poll_command_runner() {
+            needs_render = true;
+        }
+
+        if app.poll_git_status() {
+            needs_render = true;
+        }
+
+        if app.poll_git_jobs() {
+            needs_render = true;
+        }
+
         // Auto-dismiss an expired toast and redraw to clear it.
         if app.tick_toast() {
             needs_render = true;
         }
 
+        if app.vim_mappings.is_pending() {
+            needs_render = true;
+            if flush_vim_mappings(app) {
+                return Ok(());
+            }
+        }
+
         if needs_render {
             terminal.draw(|f| ui::render(f, app))?;
             needs_render = false;
@@ -112,7 +146,13 @@ pub fn run_app(terminal: &mut Terminal<CrosstermBackend<Box<dyn io::Write>>>, ap
             || app.is_content_search_in_progress()
             || app.indexing_in_progress
             || app.graph_has_background_work()
+            || app.agenda_has_background_work()
             || app.has_highlight_work()
+            // Wake up to resolve a mapped key sequence that timed out.
+            || app.vim_mappings.is_pending()
+            || app.filter_job.is_some()
+            || app.command_runner.is_busy()
+            || app.git_has_background_work()
             // Keep ticking while a toast is visible so it can self-expire.
             || app.toast.is_some();
 
@@ -1,4 +1,6 @@
 use super::*;
+use crate::app::GraphGroupField;
+use ekphos_graph::{CentralityMetric, GraphExportFormat, GraphNodeKind};
 
 pub(super) fn zoom_graph(app: &mut App, factor: f32) {
     let old_zoom = app.graph_view.zoom;
@@ -124,32 +126,81 @@ pub(super) fn handle_graph_view_dialog(app: &mut App, key: crossterm::event::Key
         return;
     }
 
+    if app.graph_view.export_prompt {
+        match key.code {
+            KeyCode::Char('d') => app.export_graph_view(GraphExportFormat::Dot),
+            KeyCode::Char('g') => app.export_graph_view(GraphExportFormat::GraphMl),
+            KeyCode::Char('j') => app.export_graph_view(GraphExportFormat::Json),
+            KeyCode::Char('p') => app.graph_view.export_positions = !app.graph_view.export_positions,
+            _ => app.graph_view.export_prompt = false,
+        }
+        return;
+    }
+
+    if let Some(editor) = app.graph_view.groups_editor.as_mut() {
+        if editor.editing.is_some() {
+            match key.code {
+                KeyCode::Esc => app.cancel_graph_group_edit(),
+                KeyCode::Enter => app.commit_graph_group_edit(),
+                KeyCode::Backspace => {
+                    editor.draft.pop();
+                }
+                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => editor.draft.clear(),
+                KeyCode::Char(ch) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => editor.draft.push(ch),
+                _ => {}
+            }
+            return;
+        }
+        let group_count = app.config.graph.groups.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => editor.selected = (editor.selected + 1).min(group_count.saturating_sub(1)),
+            KeyCode::Char('k') | KeyCode::Up => editor.selected = editor.selected.saturating_sub(1),
+            KeyCode::Char('J') => app.move_graph_group(1),
+            KeyCode::Char('K') => app.move_graph_group(-1),
+            KeyCode::Char('a') => app.add_graph_group(),
+            KeyCode::Enter | KeyCode::Char('e') => app.edit_graph_group(GraphGroupField::Query),
+            KeyCode::Char('c') => app.edit_graph_group(GraphGroupField::Color),
+            KeyCode::Char('d') => app.delete_graph_group(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s') => app.graph_view.groups_editor = None,
+            _ => {}
+        }
+        return;
+    }
+
+    if let Some(panel) = app.graph_view.centrality.as_mut() {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => panel.selected = (panel.selected + 1).min(panel.entries.len().saturating_sub(1)),
+            KeyCode::Char('k') | KeyCode::Up => panel.selected = panel.selected.saturating_sub(1),
+            KeyCode::Tab => {
+                let metric = panel.metric.toggled();
+                app.open_graph_centrality(metric);
+            }
+            KeyCode::Enter => app.select_graph_centrality_entry(),
+            _ => app.graph_view.centrality = None,
+        }
+        return;
+    }
+
     if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
         if let Some(node_idx) = app.graph_view.selected_node {
             if node_idx < app.graph_view.nodes.len() {
                 let move_amount = 2.0;
-                match key.code {
-                    KeyCode::Char('h') => {
-                        app.graph_view.nodes[node_idx].x -= move_amount;
-                        return;
-                    }
-                    KeyCode::Char('j') => {
-                        app.graph_view.nodes[node_idx].y += move_amount;
-                        return;
-                    }
-                    KeyCode::Char('k') => {
-                        app.graph_view.nodes[node_idx].y -= move_amount;
-                        return;
-                    }
-                    KeyCode::Char('l') => {
-                        app.graph_view.nodes[node_idx].x += move_amount;
-                        return;
-                    }
-                    _ => {}
+                let offset = match key.code {
+                    KeyCode::Char('h') => Some((-move_amount, 0.0)),
+                    KeyCode::Char('j') => Some((0.0, move_amount)),
+                    KeyCode::Char('k') => Some((0.0, -move_amount)),
+                    KeyCode::Char('l') => Some((move_amount, 0.0)),
+                    _ => None,
+                };
+                if let Some((dx, dy)) = offset {
+                    // Moved Global nodes keep their new place across layouts.
+                    app.nudge_graph_node(node_idx, dx, dy);
+                    return;
                 }
             }
         }
     }
+    app.flush_graph_pins();
 
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') => {
@@ -214,6 +265,7 @@ pub(super) fn handle_graph_view_dialog(app: &mut App, key: crossterm::event::Key
             // Unselect current node
             app.graph_view.selected_node = None;
             app.graph_view.selected_note_index = None;
+            app.update_graph_path();
         }
         KeyCode::Char('v') => app.toggle_graph_mode(),
         KeyCode::Char('[') => app.change_graph_depth(-1),
@@ -225,6 +277,19 @@ pub(super) fn handle_graph_view_dialog(app: &mut App, key: crossterm::event::Key
             app.graph_view.filter_editing = true;
         }
         KeyCode::Char('o') => app.toggle_graph_orphans(),
+        KeyCode::Char('e') => app.graph_view.export_prompt = true,
+        KeyCode::Char('m') => app.toggle_graph_path_mark(),
+        KeyCode::Char('c') => app.toggle_graph_cluster_colors(),
+        KeyCode::Char('C') => app.open_graph_centrality(CentralityMetric::default()),
+        KeyCode::Char('s') => app.open_graph_groups_editor(),
+        KeyCode::Char('p') => app.toggle_graph_pin(),
+        KeyCode::Char('P') => app.reset_graph_pins(),
+        KeyCode::Char('t') => app.toggle_graph_timeline(),
+        KeyCode::Char('T') => app.toggle_graph_timeline_play(),
+        KeyCode::Char(',') => app.step_graph_timeline(-1),
+        KeyCode::Char('.') => app.step_graph_timeline(1),
+        KeyCode::Char('<') => app.jump_graph_timeline(false),
+        KeyCode::Char('>') => app.jump_graph_timeline(true),
         KeyCode::Char('r') => app.reset_graph_view(),
         KeyCode::Char('?') => app.graph_view.help_visible = !app.graph_view.help_visible,
         KeyCode::Char(' ') => app.reroot_graph_on_selected(),
@@ -246,6 +311,17 @@ pub(super) fn open_selected_graph_node(app: &mut App) {
     };
     let note_id = node.note_id;
     let Some(note_idx) = app.note_index_for_id(note_id) else {
+        // A tag node has no note to open; filter the view to its notes instead.
+        let tag = app
+            .graph_index
+            .as_ref()
+            .and_then(|index| index.metadata_for_note(note_id))
+            .filter(|metadata| metadata.kind == GraphNodeKind::Tag)
+            .map(|metadata| metadata.path.clone());
+        if let Some(tag) = tag {
+            app.graph_view.filter_draft = tag.clone();
+            app.update_graph_filter(tag, true);
+        }
         return;
     };
     if app.navigate_to_note(note_idx) {
@@ -258,6 +334,7 @@ pub(super) fn select_graph_node(app: &mut App, idx: usize, center: bool) {
     if let Some(note_id) = app.graph_view.nodes.get(idx).map(|node| node.note_id) {
         app.graph_view.selected_node = Some(idx);
         app.graph_view.selected_note_index = app.note_index_for_id(note_id);
+        app.update_graph_path();
         if center {
             center_on_selected_node(app);
         }
@@ -381,6 +458,10 @@ pub(super) fn handle_graph_view_mouse(app: &mut App, mouse: crossterm::event::Mo
             }
         }
         MouseEventKind::Up(MouseButton::Left) => {
+            if let Some(node_idx) = app.graph_view.dragging_node.filter(|_| app.graph_view.node_dragged) {
+                app.pin_graph_node(node_idx);
+            }
+            app.graph_view.node_dragged = false;
             app.graph_view.is_panning = false;
             app.graph_view.dragging_node = None;
             app.graph_view.drag_start = None;
@@ -397,6 +478,7 @@ pub(super) fn handle_graph_view_mouse(app: &mut App, mouse: crossterm::event::Mo
                         app.graph_view.nodes[node_idx].y += dy / app.graph_view.zoom;
                         app.graph_view.nodes[node_idx].home_x = app.graph_view.nodes[node_idx].x;
                         app.graph_view.nodes[node_idx].home_y = app.graph_view.nodes[node_idx].y;
+                        app.graph_view.node_dragged = true;
                     }
                 } else if app.graph_view.is_panning {
                     // Panning the viewport
@@ -133,7 +133,18 @@ pub(super) fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEv
                     && mouse_y >= app.outline_area.y
                     && mouse_y < app.outline_area.y + app.outline_area.height;
 
-                if in_sidebar_area {
+                let in_local_graph_area = app.local_graph.area.width > 0
+                    && mouse_x >= app.local_graph.area.x
+                    && mouse_x < app.local_graph.area.x + app.local_graph.area.width
+                    && mouse_y >= app.local_graph.area.y
+                    && mouse_y < app.local_graph.area.y + app.local_graph.area.height;
+
+                if in_local_graph_area {
+                    app.focus = Focus::LocalGraph;
+                    if let Some(note_id) = app.local_graph_node_at(mouse_x, mouse_y) {
+                        app.open_local_graph_note(note_id);
+                    }
+                } else if in_sidebar_area {
                     let inner_y = mouse_y.saturating_sub(app.sidebar_area.y + 1); // +1 for top border
                     let clicked_index = inner_y as usize;
 
@@ -215,8 +226,7 @@ pub(super) fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEv
                             if wiki_link.is_valid {
                                 app.navigate_to_wiki_link_with_heading(&wiki_link.target, wiki_link.heading.as_deref());
                             } else {
-                                app.pending_wiki_target = Some(wiki_link.target);
-                                app.dialog = DialogState::CreateWikiNote;
+                                app.open_missing_wiki_link(wiki_link.target);
                             }
                         } else if let Some(path) = app.item_is_image_at(idx) {
                             let normalized = crate::app::normalize_image_destination(path);
@@ -253,6 +263,7 @@ pub(super) fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEv
                     app.sync_outline_to_content();
                 }
                 Focus::Outline => app.next_outline(),
+                Focus::LocalGraph => app.move_local_graph_selection(1),
             },
             MouseEventKind::ScrollUp => match app.focus {
                 Focus::Sidebar => app.previous_sidebar_item(),
@@ -265,6 +276,7 @@ pub(super) fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEv
                     app.sync_outline_to_content();
                 }
                 Focus::Outline => app.previous_outline(),
+                Focus::LocalGraph => app.move_local_graph_selection(-1),
             },
             _ => {}
         }
@@ -29,6 +29,9 @@ pub(super) fn handle_search_picker_input(app: &mut App, key: crossterm::event::K
         KeyCode::Char('p') if key.modifiers == KeyModifiers::CONTROL => {
             app.search_picker_select_prev();
         }
+        KeyCode::Char('e') if key.modifiers == KeyModifiers::CONTROL => {
+            app.open_merge_from_search_picker();
+        }
         KeyCode::Backspace => {
             app.search_picker_pop_char();
         }
@@ -350,6 +350,30 @@ pub(super) fn handle_vim_visual_mode(app: &mut App, key: crossterm::event::KeyEv
             app.visual_line_current = None;
             app.visual_block_anchor = None;
         }
+        KeyCode::Char(command @ ('!' | ':')) => {
+            let cursor_row = app.editor.cursor().0;
+            let anchor_row = match app.vim_mode {
+                VimMode::VisualLine => app.visual_line_anchor,
+                VimMode::VisualBlock => app.visual_block_anchor.map(|anchor| anchor.row),
+                _ => app
+                    .editor
+                    .selection_range()
+                    .map(|(start, end)| if start.row == cursor_row { end.row } else { start.row }),
+            }
+            .unwrap_or(cursor_row);
+            app.editor.clear_visual_line_selection();
+            app.editor.clear_visual_block_selection();
+            app.vim_mode = VimMode::Normal;
+            update_cursor_style(app);
+            app.visual_line_anchor = None;
+            app.visual_line_current = None;
+            app.visual_block_anchor = None;
+            if command == '!' {
+                begin_filter_command(app, anchor_row, cursor_row);
+            } else {
+                begin_range_command(app, anchor_row, cursor_row);
+            }
+        }
         KeyCode::Char('s') if key.modifiers == KeyModifiers::CONTROL => {
             app.editor.cancel_selection();
             app.editor.clear_visual_line_selection();
@@ -681,5 +705,38 @@ pub(super) fn execute_vim_command(app: &mut App, command: Command) {
 
             app.update_editor_highlights();
         }
+        Command::Map { modes, lhs, rhs, recursive } => {
+            if let Some(id) = ekphos_vim::mapping::command_target(&rhs) {
+                if AppCommand::from_id(id).is_none() {
+                    app.vim.status_message = Some(format!("Unknown command: {id}"));
+                    return;
+                }
+            }
+            if let Err(error) = app.vim_mappings.map(modes, &lhs, &rhs, recursive) {
+                app.vim.status_message = Some(error);
+            }
+        }
+        Command::Unmap { modes, lhs } => {
+            if let Err(error) = app.vim_mappings.unmap(modes, &lhs) {
+                app.vim.status_message = Some(error);
+            }
+        }
+        Command::Filter { range, command } => {
+            let (start, end) = range.resolve(app.editor.cursor().0, app.editor.line_count());
+            app.start_filter(start, end, command);
+        }
+        Command::ReadCommand(command) => app.start_read_command(command),
+        Command::Extract { range, title } => {
+            let rows = range.map(|range| range.resolve(app.editor.cursor().0, app.editor.line_count()));
+            app.extract_to_note(rows, title);
+        }
+        Command::ListMappings { modes, prefix } => {
+            let mappings = app.vim_mappings.list(modes, &prefix);
+            app.vim.status_message = Some(if mappings.is_empty() {
+                "No mapping found".to_string()
+            } else {
+                mappings.join("  |  ")
+            });
+        }
     }
 }
@@ -163,6 +163,7 @@ pub(super) fn handle_vim_normal_mode(app: &mut App, key: crossterm::event::KeyEv
                             app.editor.cancel_selection();
                             app.editor.set_cursor(current_row, 0);
                         }
+                        '!' => begin_filter_command(app, start_row, end_row),
                         _ => {
                             app.editor.cancel_selection();
                         }
@@ -421,6 +422,7 @@ pub(super) fn handle_vim_normal_mode(app: &mut App, key: crossterm::event::KeyEv
                         app.editor.cancel_selection();
                         app.editor.set_cursor(current_row, 0);
                     }
+                    '!' => begin_filter_command(app, start_row, end_row),
                     _ => {
                         app.editor.cancel_selection();
                     }
@@ -591,6 +593,19 @@ pub(super) fn handle_vim_normal_mode(app: &mut App, key: crossterm::event::KeyEv
             }
         }
 
+        KeyCode::Char('!') => {
+            if app.pending_operator == Some('!') {
+                // !!: filter count lines
+                app.pending_operator = None;
+                let count = app.vim.get_count();
+                let row = app.editor.cursor().0;
+                let last_row = app.editor.line_count().saturating_sub(1);
+                begin_filter_command(app, row, (row + count - 1).min(last_row));
+            } else {
+                app.pending_operator = Some('!');
+            }
+        }
+
         // Quick actions
         KeyCode::Char('x') => {
             let count = app.vim.get_count();
@@ -682,6 +697,11 @@ pub(super) fn handle_vim_normal_mode(app: &mut App, key: crossterm::event::KeyEv
             app.vim.reset_pending();
         }
 
+        KeyCode::Char('p') if key.modifiers == KeyModifiers::CONTROL => {
+            app.vim.reset_pending();
+            app.open_command_palette();
+        }
+
         // Paste
         KeyCode::Char('p') => {
             app.editor.paste_after();
@@ -847,9 +867,36 @@ pub(super) fn execute_motion_n(app: &mut App, movement: CursorMove) {
     let count = app.vim.get_count();
     app.vim.reset_pending();
     app.editor.cancel_selection();
+    let start_row = app.editor.cursor().0;
     for _ in 0..count {
         app.editor.move_cursor(movement);
     }
+    if app.pending_operator == Some('!') {
+        app.pending_operator = None;
+        let end_row = app.editor.cursor().0;
+        begin_filter_command(app, start_row, end_row);
+    }
+}
+
+/// `!{motion}` opens the command line prefilled with the covered lines, as
+/// Vim does, so the user only types the filter command.
+pub(super) fn begin_filter_command(app: &mut App, start_row: usize, end_row: usize) {
+    begin_range_command(app, start_row, end_row);
+    app.vim.command_buffer.push('!');
+}
+
+/// `:` from visual mode opens the command line prefilled with the selected
+/// lines, e.g. for `:.,.+3extract`.
+pub(super) fn begin_range_command(app: &mut App, start_row: usize, end_row: usize) {
+    let (start_row, end_row) = (start_row.min(end_row), start_row.max(end_row));
+    app.editor.cancel_selection();
+    app.editor.set_cursor(start_row, 0);
+    app.vim.enter_command_mode();
+    app.vim.command_buffer = if start_row == end_row {
+        ".".to_string()
+    } else {
+        format!(".,.+{}", end_row - start_row)
+    };
 }
 
 pub(super) fn execute_motion_or_operator(app: &mut App, movement: CursorMove) {
@@ -945,6 +992,10 @@ pub(super) fn execute_motion_or_operator(app: &mut App, movement: CursorMove) {
                     app.editor.insert_str("    ");
                 }
             }
+            '!' => {
+                let end_row = app.editor.cursor().0;
+                begin_filter_command(app, start_row, end_row);
+            }
             '<' => {
                 if let Some((start, _)) = app.editor.selection_range() {
                     app.editor.cancel_selection();
@@ -1050,6 +1101,7 @@ pub(super) fn execute_text_object(app: &mut App, scope: TextObjectScope, obj: Te
                     app.editor.cancel_selection();
                     app.editor.set_cursor(start.row, start.col);
                 }
+                '!' => begin_filter_command(app, start.row, end.row),
                 _ => {
                     app.editor.cancel_selection();
                 }
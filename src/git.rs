@@ -0,0 +1,268 @@
+//! Thin wrapper over the local `git` binary for vaults inside a repository.
+//!
+//! Every call shells out with `git -C <root>`; nothing links libgit2. Paths
+//! given to and returned from [`GitRepo`] are absolute.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Working-tree state of a file relative to `HEAD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Untracked,
+    Conflicted,
+}
+
+impl FileStatus {
+    /// One-letter marker as `git status --short` prints it.
+    pub fn marker(self) -> char {
+        match self {
+            Self::Modified => 'M',
+            Self::Added => 'A',
+            Self::Deleted => 'D',
+            Self::Renamed => 'R',
+            Self::Untracked => 'U',
+            Self::Conflicted => '!',
+        }
+    }
+}
+
+/// A commit that touched a note, newest first in [`GitRepo::log`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    /// Author date as `YYYY-MM-DD HH:MM`.
+    pub date: String,
+    pub subject: String,
+    /// Repository-relative path of the note in this commit; differs from
+    /// the current one when the note was renamed since.
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitRepo {
+    root: PathBuf,
+}
+
+impl GitRepo {
+    /// The repository containing `dir`, if `git` is installed and finds one.
+    pub fn discover(dir: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        // Canonicalize so prefix checks against vault paths agree on symlinks.
+        let root = PathBuf::from(root).canonicalize().ok()?;
+        Some(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|error| format!("failed to run git: {error}"))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.trim();
+            Err(if message.is_empty() {
+                format!("git {} failed", args.first().copied().unwrap_or_default())
+            } else {
+                message.lines().next().unwrap_or(message).to_string()
+            })
+        }
+    }
+
+    /// `path` relative to the repository root, with `/` separators.
+    pub fn relative(&self, path: &Path) -> Option<String> {
+        let path = path.canonicalize().ok().or_else(|| {
+            // Deleted files cannot be canonicalized; resolve their parent.
+            let parent = path.parent()?.canonicalize().ok()?;
+            Some(parent.join(path.file_name()?))
+        })?;
+        let relative = path.strip_prefix(&self.root).ok()?;
+        Some(
+            relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// Every changed or untracked file, keyed by absolute path.
+    pub fn status(&self) -> Result<HashMap<PathBuf, FileStatus>, String> {
+        let output = self.run(&["status", "--porcelain=v1", "-z", "--untracked-files=all"])?;
+        Ok(parse_status(&output).into_iter().map(|(path, status)| (self.root.join(path), status)).collect())
+    }
+
+    /// Content of `path` as committed in `HEAD`, or `None` when untracked.
+    pub fn head_content(&self, path: &Path) -> Option<String> {
+        let relative = self.relative(path)?;
+        self.run(&["show", &format!("HEAD:{relative}")]).ok()
+    }
+
+    /// Content of a repository-relative `path` at `commit`.
+    pub fn content_at(&self, commit: &str, path: &str) -> Result<String, String> {
+        self.run(&["show", &format!("{commit}:{path}")])
+    }
+
+    /// Commits touching `path`, following renames, newest first.
+    pub fn log(&self, path: &Path) -> Result<Vec<GitCommit>, String> {
+        let relative = self.relative(path).ok_or_else(|| "note is outside the repository".to_string())?;
+        let output = self.run(&[
+            "log",
+            "--follow",
+            "--name-only",
+            "--date=format:%Y-%m-%d %H:%M",
+            "--format=%x1e%H%x1f%h%x1f%an%x1f%ad%x1f%s",
+            "--",
+            &relative,
+        ])?;
+        Ok(parse_log(&output))
+    }
+
+    /// Stage every change under `dir` and commit only those, leaving the
+    /// rest of the repository as it was. Returns the new commit's short hash.
+    pub fn commit_under(&self, dir: &Path, message: &str) -> Result<String, String> {
+        let relative = self.relative(dir).ok_or_else(|| "folder is outside the repository".to_string())?;
+        let pathspec = if relative.is_empty() { "." } else { relative.as_str() };
+        self.run(&["add", "--all", "--", pathspec])?;
+        self.run(&["commit", "--quiet", "--message", message, "--", pathspec])?;
+        Ok(self.run(&["rev-parse", "--short", "HEAD"])?.trim().to_string())
+    }
+}
+
+/// Parse `git status --porcelain=v1 -z` into repository-relative paths.
+fn parse_status(output: &str) -> Vec<(String, FileStatus)> {
+    let mut entries = Vec::new();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        if field.len() < 4 {
+            continue;
+        }
+        let (code, path) = field.split_at(3);
+        let mut code = code.chars();
+        let (index, worktree) = (code.next().unwrap_or(' '), code.next().unwrap_or(' '));
+        let status = match (index, worktree) {
+            ('?', '?') => FileStatus::Untracked,
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => FileStatus::Conflicted,
+            ('R' | 'C', _) => FileStatus::Renamed,
+            ('D', _) | (_, 'D') => FileStatus::Deleted,
+            ('A', _) => FileStatus::Added,
+            _ => FileStatus::Modified,
+        };
+        if matches!(index, 'R' | 'C') {
+            // Renames are followed by the original path.
+            fields.next();
+        }
+        entries.push((path.to_string(), status));
+    }
+    entries
+}
+
+fn parse_log(output: &str) -> Vec<GitCommit> {
+    output
+        .split('\u{1e}')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut fields = lines.next()?.split('\u{1f}');
+            let mut field = || fields.next().map(str::to_string);
+            let commit = GitCommit {
+                hash: field()?,
+                short_hash: field()?,
+                author: field()?,
+                date: field()?,
+                subject: field()?,
+                path: String::new(),
+            };
+            let path = lines.map(str::trim).find(|line| !line.is_empty())?.to_string();
+            Some(GitCommit { path, ..commit })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parses_porcelain_status_with_renames() {
+        let output = " M notes/a.md\0?? notes/new.md\0R  b2.md\0b.md\0A  added.md\0 D gone.md\0";
+        assert_eq!(
+            parse_status(output),
+            [
+                ("notes/a.md".to_string(), FileStatus::Modified),
+                ("notes/new.md".to_string(), FileStatus::Untracked),
+                ("b2.md".to_string(), FileStatus::Renamed),
+                ("added.md".to_string(), FileStatus::Added),
+                ("gone.md".to_string(), FileStatus::Deleted),
+            ]
+        );
+    }
+
+    #[test]
+    fn commits_and_reads_history_of_a_real_repository() {
+        let root = std::env::temp_dir().join(format!("ekphos-git-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(&root)
+                .args(args)
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "--quiet"]) {
+            // No usable git binary in this environment.
+            let _ = fs::remove_dir_all(&root);
+            return;
+        }
+        git(&["config", "user.name", "Test"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "commit.gpgsign", "false"]);
+
+        let note = root.join("note.md");
+        fs::write(&note, "first\n").unwrap();
+        let repo = GitRepo::discover(&root).unwrap();
+        assert_eq!(repo.status().unwrap().get(&repo.root().join("note.md")), Some(&FileStatus::Untracked));
+        repo.commit_under(&root, "Add note").unwrap();
+        fs::write(&note, "second\n").unwrap();
+        assert_eq!(repo.status().unwrap().get(&repo.root().join("note.md")), Some(&FileStatus::Modified));
+        repo.commit_under(&root, "Edit note").unwrap();
+
+        let log = repo.log(&note).unwrap();
+        assert_eq!(log.iter().map(|commit| commit.subject.as_str()).collect::<Vec<_>>(), ["Edit note", "Add note"]);
+        assert_eq!(repo.content_at(&log[1].hash, &log[1].path).unwrap(), "first\n");
+        assert_eq!(repo.head_content(&note).as_deref(), Some("second\n"));
+        assert!(repo.status().unwrap().is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ekphos_vim::mapping::{command_target, map_command, KeyMapper};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -22,10 +23,28 @@ pub enum AppCommand {
     OpenGraph,
     OpenThemeSelector,
     OpenJournal,
+    OpenWeeklyNote,
+    OpenMonthlyNote,
+    OpenQuarterlyNote,
+    OpenYearlyNote,
+    OpenAgenda,
+    EditProperties,
+    OpenCommandPalette,
+    OpenCommandLog,
+    GitCommit,
+    GitHistory,
+    NoteHistory,
+    OpenTrash,
+    ExtractSection,
+    MergeNote,
+    RenameHeading,
+    CopyBlockLink,
+    VaultDoctor,
     HistoryBack,
     HistoryForward,
     ToggleSidebar,
     ToggleOutline,
+    ToggleLocalGraph,
     ShrinkPanel,
     GrowPanel,
     ToggleZen,
@@ -53,7 +72,7 @@ pub enum AppCommand {
 }
 
 impl AppCommand {
-    pub const ALL: [Self; 44] = [
+    pub const ALL: [Self; 62] = [
         Self::Quit,
         Self::FocusNext,
         Self::FocusPrevious,
@@ -70,10 +89,28 @@ impl AppCommand {
         Self::OpenGraph,
         Self::OpenThemeSelector,
         Self::OpenJournal,
+        Self::OpenWeeklyNote,
+        Self::OpenMonthlyNote,
+        Self::OpenQuarterlyNote,
+        Self::OpenYearlyNote,
+        Self::OpenAgenda,
+        Self::EditProperties,
+        Self::OpenCommandPalette,
+        Self::OpenCommandLog,
+        Self::GitCommit,
+        Self::GitHistory,
+        Self::NoteHistory,
+        Self::OpenTrash,
+        Self::ExtractSection,
+        Self::MergeNote,
+        Self::RenameHeading,
+        Self::CopyBlockLink,
+        Self::VaultDoctor,
         Self::HistoryBack,
         Self::HistoryForward,
         Self::ToggleSidebar,
         Self::ToggleOutline,
+        Self::ToggleLocalGraph,
         Self::ShrinkPanel,
         Self::GrowPanel,
         Self::ToggleZen,
@@ -118,10 +155,28 @@ impl AppCommand {
             Self::OpenGraph => "open_graph",
             Self::OpenThemeSelector => "open_theme_selector",
             Self::OpenJournal => "open_journal",
+            Self::OpenWeeklyNote => "open_weekly_note",
+            Self::OpenMonthlyNote => "open_monthly_note",
+            Self::OpenQuarterlyNote => "open_quarterly_note",
+            Self::OpenYearlyNote => "open_yearly_note",
+            Self::OpenAgenda => "open_agenda",
+            Self::EditProperties => "edit_properties",
+            Self::OpenCommandPalette => "open_command_palette",
+            Self::OpenCommandLog => "open_command_log",
+            Self::GitCommit => "git_commit",
+            Self::GitHistory => "git_history",
+            Self::NoteHistory => "note_history",
+            Self::OpenTrash => "open_trash",
+            Self::ExtractSection => "extract_section",
+            Self::MergeNote => "merge_note",
+            Self::RenameHeading => "rename_heading",
+            Self::CopyBlockLink => "copy_block_link",
+            Self::VaultDoctor => "vault_doctor",
             Self::HistoryBack => "history_back",
             Self::HistoryForward => "history_forward",
             Self::ToggleSidebar => "toggle_sidebar",
             Self::ToggleOutline => "toggle_outline",
+            Self::ToggleLocalGraph => "toggle_local_graph",
             Self::ShrinkPanel => "shrink_panel",
             Self::GrowPanel => "grow_panel",
             Self::ToggleZen => "toggle_zen",
@@ -153,6 +208,74 @@ impl AppCommand {
         Self::ALL.into_iter().find(|command| command.id() == id)
     }
 
+    /// Human-readable name shown in the command palette.
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::FocusNext => "Focus next panel",
+            Self::FocusPrevious => "Focus previous panel",
+            Self::MoveDown => "Move down",
+            Self::MoveUp => "Move up",
+            Self::GoFirst => "Go to start",
+            Self::GoLast => "Go to end",
+            Self::Activate => "Open / activate",
+            Self::ShowHelp => "Show help",
+            Self::ReloadFiles => "Reload files",
+            Self::ReloadConfig => "Reload config",
+            Self::OpenQuickSearch => "Search notes",
+            Self::FindInBuffer => "Find in note",
+            Self::OpenGraph => "Open graph view",
+            Self::OpenThemeSelector => "Select theme",
+            Self::OpenJournal => "Open today's journal",
+            Self::OpenWeeklyNote => "Open this week's note",
+            Self::OpenMonthlyNote => "Open this month's note",
+            Self::OpenQuarterlyNote => "Open this quarter's note",
+            Self::OpenYearlyNote => "Open this year's note",
+            Self::OpenAgenda => "Open task agenda",
+            Self::EditProperties => "Edit note properties",
+            Self::OpenCommandPalette => "Open command palette",
+            Self::OpenCommandLog => "Open hook and action log",
+            Self::GitCommit => "Commit vault changes",
+            Self::GitHistory => "Browse note history",
+            Self::NoteHistory => "Browse local snapshots",
+            Self::OpenTrash => "Open trash",
+            Self::ExtractSection => "Extract section to note",
+            Self::MergeNote => "Merge note into…",
+            Self::RenameHeading => "Rename heading and its links",
+            Self::CopyBlockLink => "Copy block link",
+            Self::VaultDoctor => "Vault doctor",
+            Self::HistoryBack => "Go back",
+            Self::HistoryForward => "Go forward",
+            Self::ToggleSidebar => "Toggle sidebar",
+            Self::ToggleOutline => "Toggle outline",
+            Self::ToggleLocalGraph => "Toggle local graph pane",
+            Self::ShrinkPanel => "Shrink panel",
+            Self::GrowPanel => "Grow panel",
+            Self::ToggleZen => "Toggle zen mode",
+            Self::OpenSelected => "Open selected link",
+            Self::ContentAction => "Toggle task / fold / follow link",
+            Self::NextTarget => "Next link",
+            Self::PreviousTarget => "Previous link",
+            Self::ToggleFloatingCursor => "Toggle floating cursor",
+            Self::HalfPageDown => "Half page down",
+            Self::HalfPageUp => "Half page up",
+            Self::ToggleFrontmatter => "Toggle frontmatter",
+            Self::ToggleFold => "Toggle fold",
+            Self::FoldAll => "Fold all headings",
+            Self::UnfoldAll => "Unfold all headings",
+            Self::EditNote => "Edit note",
+            Self::CreateNote => "Create note",
+            Self::CreateFolder => "Create folder",
+            Self::DeleteItem => "Delete note or folder",
+            Self::RenameItem => "Rename note or folder",
+            Self::CutItem => "Cut note or folder",
+            Self::PasteItem => "Paste note or folder",
+            Self::CancelCut => "Cancel cut",
+            Self::SidebarSearch => "Filter sidebar",
+            Self::CycleSort => "Cycle sidebar sort",
+        }
+    }
+
     pub const fn default_bindings(self) -> &'static [&'static str] {
         match self {
             Self::Quit => &["q"],
@@ -171,10 +294,28 @@ impl AppCommand {
             Self::OpenGraph => &["ctrl+g"],
             Self::OpenThemeSelector => &["ctrl+t"],
             Self::OpenJournal => &["t"],
+            Self::OpenWeeklyNote => &["shift+t w"],
+            Self::OpenMonthlyNote => &["shift+t m"],
+            Self::OpenQuarterlyNote => &["shift+t q"],
+            Self::OpenYearlyNote => &["shift+t y"],
+            Self::OpenAgenda => &["shift+a"],
+            Self::EditProperties => &["shift+p"],
+            Self::OpenCommandPalette => &[":", "ctrl+p"],
+            Self::OpenCommandLog => &["shift+l"],
+            Self::GitCommit => &["shift+c"],
+            Self::GitHistory => &["shift+h"],
+            Self::NoteHistory => &["shift+v"],
+            Self::OpenTrash => &["shift+d"],
+            Self::ExtractSection => &["shift+x"],
+            Self::MergeNote => &["shift+m"],
+            Self::RenameHeading => &["g r"],
+            Self::CopyBlockLink => &["y b"],
+            Self::VaultDoctor => &["g h"],
             Self::HistoryBack => &["-"],
             Self::HistoryForward => &["="],
             Self::ToggleSidebar => &["ctrl+b"],
             Self::ToggleOutline => &["ctrl+o"],
+            Self::ToggleLocalGraph => &["g l"],
             Self::ShrinkPanel => &["<"],
             Self::GrowPanel => &[">"],
             Self::ToggleZen => &["ctrl+z"],
@@ -222,6 +363,67 @@ impl Default for KeybindingsConfig {
     }
 }
 
+/// `[vim]` section: leader key, sequence timeout and user mappings keyed by
+/// map command, e.g. `[vim.mappings.inoremap] jk = "<Esc>"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VimConfig {
+    #[serde(default = "default_vim_leader")]
+    pub leader: String,
+    #[serde(default = "default_vim_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub mappings: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn default_vim_leader() -> String {
+    "\\".to_string()
+}
+
+fn default_vim_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for VimConfig {
+    fn default() -> Self {
+        Self {
+            leader: default_vim_leader(),
+            timeout_ms: default_vim_timeout_ms(),
+            mappings: BTreeMap::new(),
+        }
+    }
+}
+
+impl VimConfig {
+    /// Build the mapper, skipping invalid entries. Every skipped entry is
+    /// reported so the rest of the configuration still applies.
+    pub fn mapper(&self) -> (KeyMapper, Vec<String>) {
+        let mut issues = Vec::new();
+        let timeout = std::time::Duration::from_millis(self.timeout_ms);
+        let mut mapper = KeyMapper::new(&self.leader, timeout).unwrap_or_else(|error| {
+            issues.push(format!("[vim] leader: {error}"));
+            KeyMapper::new(&default_vim_leader(), timeout).expect("default leader must be valid")
+        });
+        for (name, entries) in &self.mappings {
+            let Some((modes, recursive)) = map_command(name) else {
+                issues.push(format!("[vim.mappings] unknown map command `{name}`"));
+                continue;
+            };
+            for (lhs, rhs) in entries {
+                if let Some(id) = command_target(rhs) {
+                    if AppCommand::from_id(id).is_none() {
+                        issues.push(format!("[vim.mappings.{name}] {lhs}: unknown command `{id}`"));
+                        continue;
+                    }
+                }
+                if let Err(error) = mapper.map(modes, lhs, rhs, recursive) {
+                    issues.push(format!("[vim.mappings.{name}] {lhs}: {error}"));
+                }
+            }
+        }
+        (mapper, issues)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct KeyChord {
     code: KeyCode,
@@ -400,6 +602,8 @@ pub struct KeybindingValidationError {
 pub enum KeybindingFallback {
     Defaults,
     Previous,
+    /// Only Vim mappings were rejected; the valid ones are active.
+    SkippedVimMappings,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -417,6 +621,24 @@ impl KeybindingWarning {
             scroll: 0,
         }
     }
+
+    /// Fold Vim mapping issues into an existing warning, or raise one of their own.
+    pub fn with_vim_issues(warning: Option<Self>, issues: Vec<String>) -> Option<Self> {
+        if issues.is_empty() {
+            return warning;
+        }
+        match warning {
+            Some(mut warning) => {
+                warning.issues.extend(issues);
+                Some(warning)
+            }
+            None => Some(Self {
+                issues,
+                fallback: KeybindingFallback::SkippedVimMappings,
+                scroll: 0,
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,8 +1,11 @@
 pub mod app;
+pub mod cli;
 pub mod clipboard;
 pub mod config;
 pub mod event;
+pub mod git;
 pub mod highlight;
 pub mod highlight_worker;
 pub mod keybindings;
+pub mod shell;
 pub mod ui;
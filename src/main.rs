@@ -12,7 +12,7 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use ekphos::app::App;
-use ekphos::{config, event::run_app};
+use ekphos::{cli, config, event::run_app};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -120,11 +120,14 @@ fn print_help() {
     println!();
     println!("USAGE:");
     println!("    ekphos [OPTIONS] [PATH]");
+    println!("    ekphos <COMMAND> [ARGS]");
     println!();
     println!("ARGUMENTS:");
     println!("    [PATH]           Open a file or folder directly");
     println!("                     - If PATH is a folder, opens it as the notes directory");
     println!("                     - If PATH is a .md file, opens it and its parent folder");
+    println!("                     - A PATH named like a COMMAND opens when it exists and is");
+    println!("                       the only argument; write ./PATH to be explicit");
     println!();
     println!("OPTIONS:");
     println!("    -h, --help       Print help information");
@@ -134,10 +137,36 @@ fn print_help() {
     println!("    --reset          Reset config and themes to defaults");
     println!("    --clean-cache    Clear the search index cache");
     println!();
+    println!("COMMANDS:");
+    println!("    check [PATH] [--strict]");
+    println!("                     Report broken links, missing images, invalid frontmatter,");
+    println!("                     duplicate titles, empty and orphan notes. Exits 1 on errors,");
+    println!("                     or on any issue with --strict");
+    println!("    search <QUERY>   Print matching lines as path:line:text; exits 1 on no match");
+    println!("    new <TITLE> [--template PATH]");
+    println!("                     Create a note, optionally from a vault-relative template");
+    println!("    journal [--date YYYY-MM-DD]");
+    println!("                     Print the daily note's path, creating it if needed");
+    println!("    links <NOTE>     Print the notes NOTE links to");
+    println!("    backlinks <NOTE> Print the notes linking to NOTE");
+    println!("    tags             Print each frontmatter tag and its note count");
+    println!("    graph --format dot|graphml|json [NOTE]");
+    println!("                     Print NOTE's Local graph, or the Global graph without NOTE.");
+    println!("                     Shape it with --mode local|global, --depth 1-5,");
+    println!("                     --scope all|incoming|outgoing, --filter QUERY and");
+    println!("                     --no-orphans; add --positions for layout coordinates");
+    println!();
+    println!("    Every command takes --vault PATH (default: the configured notes folder)");
+    println!("    and, except graph, --json for a single JSON document instead of plain lines.");
+    println!();
     println!("EXAMPLES:");
     println!("    ekphos ~/notes           Open the ~/notes folder");
     println!("    ekphos ./my-note.md      Open a specific markdown file");
     println!("    ekphos .                 Open current directory as notes folder");
+    println!("    ekphos check ~/notes     Audit a vault, e.g. in CI");
+    println!("    ekphos search --json rpc Search the configured vault from a script");
+    println!("    ekphos graph --format graphml > vault.graphml");
+    println!("                             Open the link graph in Gephi");
 }
 
 fn reset_config_and_themes() {
@@ -314,6 +343,11 @@ fn main() -> io::Result<()> {
                 clean_cache();
                 return Ok(());
             }
+            // A lone word naming an existing file or folder opens it, so a
+            // vault called `journal` still works without `./`.
+            command if cli::COMMANDS.contains(&command) && !(args.len() == 2 && PathBuf::from(command).exists()) => {
+                std::process::exit(cli::run(command, &args[2..]))
+            }
             arg if arg.starts_with('-') => {
                 eprintln!("Unknown option: {}", arg);
                 eprintln!("Run 'ekphos --help' for usage information");
@@ -0,0 +1,245 @@
+//! Background runner for external shell commands.
+//!
+//! Commands run through the platform shell on a dedicated thread so the event
+//! loop never blocks on them. The caller polls [`ShellJob::try_finish`] and may
+//! cancel at any time; a timeout kills the process the same way.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Default)]
+pub struct ShellCommand {
+    pub command: String,
+    pub stdin: Option<String>,
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+    pub timeout: Option<Duration>,
+}
+
+impl ShellCommand {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellStatus {
+    /// Exit code, or `None` when the process was killed by a signal.
+    Exited(Option<i32>),
+    TimedOut,
+    Cancelled,
+    /// The shell could not be started.
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellOutput {
+    pub status: ShellStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ShellOutput {
+    pub fn success(&self) -> bool {
+        self.status == ShellStatus::Exited(Some(0))
+    }
+
+    /// One-line reason the command did not succeed, preferring its stderr.
+    pub fn failure_message(&self) -> Option<String> {
+        if self.success() {
+            return None;
+        }
+        let stderr = self.stderr.trim();
+        Some(match &self.status {
+            ShellStatus::Exited(_) if !stderr.is_empty() => stderr.to_string(),
+            ShellStatus::Exited(Some(code)) => format!("exited with status {code}"),
+            ShellStatus::Exited(None) => "terminated by a signal".to_string(),
+            ShellStatus::TimedOut => "timed out".to_string(),
+            ShellStatus::Cancelled => "cancelled".to_string(),
+            ShellStatus::Failed(error) => error.clone(),
+        })
+    }
+}
+
+/// Handle to a running command.
+pub struct ShellJob {
+    cancel: Arc<AtomicBool>,
+    receiver: Receiver<ShellOutput>,
+}
+
+impl ShellJob {
+    pub fn spawn(command: ShellCommand) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let cancel_flag = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let _ = sender.send(run(command, &cancel_flag));
+        });
+        Self { cancel, receiver }
+    }
+
+    /// Ask the command to stop; the job still reports a `Cancelled` result.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The finished output, or `None` while the command is still running.
+    pub fn try_finish(&self) -> Option<ShellOutput> {
+        match self.receiver.try_recv() {
+            Ok(output) => Some(output),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(ShellOutput {
+                status: ShellStatus::Failed("command runner stopped".to_string()),
+                stdout: String::new(),
+                stderr: String::new(),
+            }),
+        }
+    }
+
+    /// Block until the command finishes.
+    pub fn wait(self) -> ShellOutput {
+        self.receiver.recv().unwrap_or_else(|_| ShellOutput {
+            status: ShellStatus::Failed("command runner stopped".to_string()),
+            stdout: String::new(),
+            stderr: String::new(),
+        })
+    }
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+fn run(command: ShellCommand, cancel: &AtomicBool) -> ShellOutput {
+    let mut process = shell(&command.command);
+    process
+        .stdin(if command.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .envs(command.env.iter().map(|(key, value)| (key, value)));
+    if let Some(cwd) = &command.cwd {
+        process.current_dir(cwd);
+    }
+    let mut child = match process.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            return ShellOutput {
+                status: ShellStatus::Failed(format!("failed to start `{}`: {error}", command.command)),
+                stdout: String::new(),
+                stderr: String::new(),
+            }
+        }
+    };
+
+    // Feed stdin and drain both pipes on their own threads so a command that
+    // fills one pipe while we write the other cannot deadlock.
+    if let (Some(input), Some(mut stdin)) = (command.stdin, child.stdin.take()) {
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let status = wait(&mut child, cancel, command.timeout);
+    if matches!(status, ShellStatus::Cancelled | ShellStatus::TimedOut) {
+        // Grandchildren may still hold the pipes open; don't wait for them.
+        return ShellOutput {
+            status,
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+    }
+    let collect = |reader: Option<thread::JoinHandle<String>>| reader.and_then(|reader| reader.join().ok()).unwrap_or_default();
+    ShellOutput {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    }
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+fn wait(child: &mut Child, cancel: &AtomicBool, timeout: Option<Duration>) -> ShellStatus {
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return ShellStatus::Exited(status.code()),
+            Ok(None) => {}
+            Err(error) => return ShellStatus::Failed(error.to_string()),
+        }
+        let stop = if cancel.load(Ordering::Relaxed) {
+            Some(ShellStatus::Cancelled)
+        } else if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+            Some(ShellStatus::TimedOut)
+        } else {
+            None
+        };
+        if let Some(status) = stop {
+            let _ = child.kill();
+            let _ = child.wait();
+            return status;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipes_stdin_through_the_command() {
+        let output = ShellJob::spawn(ShellCommand {
+            stdin: Some("b\na\n".to_string()),
+            ..ShellCommand::new("sort")
+        })
+        .wait();
+
+        assert!(output.success());
+        assert_eq!(output.stdout, "a\nb\n");
+    }
+
+    #[test]
+    fn reports_failures_timeouts_and_cancellation() {
+        let output = ShellJob::spawn(ShellCommand::new("echo oops >&2; exit 3")).wait();
+        assert_eq!(output.status, ShellStatus::Exited(Some(3)));
+        assert_eq!(output.failure_message().as_deref(), Some("oops"));
+
+        let output = ShellJob::spawn(ShellCommand {
+            timeout: Some(Duration::from_millis(50)),
+            ..ShellCommand::new("sleep 5")
+        })
+        .wait();
+        assert_eq!(output.status, ShellStatus::TimedOut);
+
+        let job = ShellJob::spawn(ShellCommand::new("sleep 5"));
+        job.cancel();
+        assert_eq!(job.wait().status, ShellStatus::Cancelled);
+    }
+}
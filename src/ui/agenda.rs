@@ -0,0 +1,176 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::app::{AgendaGrouping, AgendaRow, App};
+
+const MIN_WIDTH: u16 = 50;
+const MAX_WIDTH: u16 = 110;
+
+/// Centered vault-wide task list. Group headers are not selectable; the
+/// selection always rests on a task row.
+pub fn render_agenda(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 4 / 5).clamp(MIN_WIDTH, MAX_WIDTH).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    // borders(2) + status line(1) + gap(1)
+    let visible = popup_height.saturating_sub(4) as usize;
+    let selected = app.agenda.selected;
+    let mut scroll = app.agenda.scroll_offset;
+    if selected < scroll {
+        // Keep the group header of the first visible task on screen.
+        scroll = selected.saturating_sub(1);
+    } else if visible > 0 && selected >= scroll + visible {
+        scroll = selected + 1 - visible;
+    }
+    app.agenda.scroll_offset = scroll;
+
+    let theme = &app.theme;
+    let agenda = &app.agenda;
+    let today = app.dependencies.clock.today();
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            " Agenda ",
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                " ␣ toggle · ⏎ open · tab group · f/a/o/t/u filter · c done · r refresh · esc close ",
+                Style::default().fg(theme.muted),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    f.render_widget(block, popup_area);
+
+    let inner_width = popup_width.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::with_capacity(visible + 2);
+    let task_count = agenda.rows.iter().filter(|row| matches!(row, AgendaRow::Task(_))).count();
+    let mut status = vec![
+        Span::styled(" group ", Style::default().fg(theme.muted)),
+        Span::styled(agenda.grouping.label(), Style::default().fg(theme.info)),
+        Span::styled(" · filter ", Style::default().fg(theme.muted)),
+        Span::styled(agenda.filter.label(), Style::default().fg(theme.info)),
+        Span::styled(" · completed ", Style::default().fg(theme.muted)),
+        Span::styled(if agenda.show_completed { "shown" } else { "hidden" }, Style::default().fg(theme.info)),
+        Span::styled(format!(" · {task_count} tasks"), Style::default().fg(theme.muted)),
+    ];
+    if agenda.loading {
+        status.push(Span::styled(" · collecting…", Style::default().fg(theme.warning)));
+    }
+    lines.push(Line::from(status));
+    lines.push(Line::from(""));
+
+    if agenda.rows.is_empty() && !agenda.loading {
+        lines.push(Line::from(Span::styled(" No tasks match this filter.", Style::default().fg(theme.muted))));
+    }
+
+    for (row_index, row) in agenda.rows.iter().enumerate().skip(scroll).take(visible) {
+        match row {
+            AgendaRow::Group { label, count } => {
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {label}"), Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!(" ({count})"), Style::default().fg(theme.muted)),
+                ]));
+            }
+            AgendaRow::Task(task_index) => {
+                let Some(task) = agenda.tasks.get(*task_index) else {
+                    continue;
+                };
+                let is_selected = row_index == selected;
+
+                let mut meta = Vec::new();
+                if let Some(priority) = task.priority {
+                    meta.push(Span::styled(format!(" {}", priority.id()), Style::default().fg(theme.warning)));
+                }
+                if let Some(due) = task.due {
+                    let color = if task.checked {
+                        theme.muted
+                    } else if due < today {
+                        theme.error
+                    } else if due == today {
+                        theme.warning
+                    } else {
+                        theme.info
+                    };
+                    meta.push(Span::styled(format!(" {}", due.format("%Y-%m-%d")), Style::default().fg(color)));
+                }
+                if agenda.grouping != AgendaGrouping::Note {
+                    if let Some(note) = app.notes.iter().find(|note| note.id == task.note_id) {
+                        meta.push(Span::styled(format!(" {}", note.title), Style::default().fg(theme.muted)));
+                    }
+                }
+                let meta_width: usize = meta.iter().map(|span| span.content.width()).sum();
+
+                let marker = if is_selected { " ▶ " } else { "   " };
+                let checkbox = if task.checked { "[x] " } else { "[ ] " };
+                let available = inner_width.saturating_sub(marker.width() + checkbox.width() + meta_width + 1);
+                let description = truncate_to_width(&task.description, available);
+                let gap = available.saturating_sub(description.width()) + 1;
+
+                let text_style = if task.checked {
+                    Style::default().fg(theme.muted).add_modifier(Modifier::CROSSED_OUT)
+                } else if is_selected {
+                    Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.dialog.text)
+                };
+                let mut spans = vec![
+                    Span::styled(marker, Style::default().fg(theme.dialog.title)),
+                    Span::styled(checkbox, Style::default().fg(if task.checked { theme.success } else { theme.dialog.text })),
+                    Span::styled(description, text_style),
+                    Span::raw(" ".repeat(gap)),
+                ];
+                spans.extend(meta);
+                let line_style = if is_selected {
+                    Style::default().bg(theme.selection)
+                } else {
+                    Style::default()
+                };
+                lines.push(Line::from(spans).style(line_style));
+            }
+        }
+    }
+
+    let inner = Rect::new(
+        popup_area.x + 1,
+        popup_area.y + 1,
+        popup_area.width.saturating_sub(2),
+        popup_area.height.saturating_sub(2),
+    );
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut width = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if width + ch_width + 1 > max_width {
+            break;
+        }
+        out.push(ch);
+        width += ch_width;
+    }
+    out.push('…');
+    out
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::shell::ShellStatus;
+
+/// Log of finished hooks and palette actions with their captured output,
+/// newest first.
+pub fn render_command_log(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 4 / 5).max(40).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let now = app.dependencies.clock.now();
+    let pending = app.pending_command_count();
+    let title = if pending > 0 {
+        format!(" Command log · {pending} running ")
+    } else {
+        " Command log ".to_string()
+    };
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(Line::from(Span::styled(" j/k scroll · c clear · esc close ", Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if app.command_log.is_empty() {
+        lines.push(Line::from(Span::styled(" No hooks or actions have run yet.", Style::default().fg(theme.muted))));
+    }
+    for entry in &app.command_log {
+        let (marker, status, color) = match &entry.status {
+            ShellStatus::Exited(Some(0)) => ("✓", String::new(), theme.success),
+            ShellStatus::Exited(Some(code)) => ("✗", format!("exit {code}"), theme.error),
+            ShellStatus::Exited(None) => ("✗", "killed".to_string(), theme.error),
+            ShellStatus::TimedOut => ("✗", "timed out".to_string(), theme.error),
+            ShellStatus::Cancelled => ("✗", "cancelled".to_string(), theme.warning),
+            ShellStatus::Failed(error) => ("✗", error.clone(), theme.error),
+        };
+        let mut header = vec![
+            Span::styled(format!(" {marker} "), Style::default().fg(color)),
+            Span::styled(entry.source.clone(), Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)),
+        ];
+        if let Some(note) = &entry.note {
+            header.push(Span::styled(format!("  {note}"), Style::default().fg(theme.info)));
+        }
+        if !status.is_empty() {
+            header.push(Span::styled(format!("  {status}"), Style::default().fg(color)));
+        }
+        header.push(Span::styled(
+            format!("  {}", ago(now.saturating_duration_since(entry.finished_at))),
+            Style::default().fg(theme.muted),
+        ));
+        lines.push(Line::from(header));
+        lines.push(Line::from(Span::styled(format!("   $ {}", entry.command), Style::default().fg(theme.muted))));
+        lines.extend(
+            entry
+                .output
+                .iter()
+                .map(|line| Line::from(Span::styled(format!("     {line}"), Style::default().fg(theme.dialog.text)))),
+        );
+        lines.push(Line::from(""));
+    }
+
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    app.command_log_scroll = app.command_log_scroll.min(max_scroll);
+    let visible: Vec<Line> = lines.into_iter().skip(app.command_log_scroll).collect();
+    f.render_widget(Paragraph::new(visible), inner);
+}
+
+fn ago(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    match seconds {
+        0..=59 => format!("{seconds}s ago"),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
+    }
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::file_picker::picker_input_lines;
+use crate::app::{App, PaletteTarget, PALETTE_VISIBLE_ITEMS};
+use crate::keybindings::AppCommand;
+
+const POPUP_MAX_WIDTH: u16 = 80;
+
+pub fn render_command_palette(f: &mut Frame, app: &mut App) {
+    let theme = &app.theme;
+    let palette = &app.command_palette;
+    let area = f.area();
+
+    let popup_width = POPUP_MAX_WIDTH.min((area.width as f32 * 0.9) as u16).min(area.width.saturating_sub(4));
+    let visible = palette.matches.len().clamp(1, PALETTE_VISIBLE_ITEMS);
+    // Borders, top padding, input and separator around the rows.
+    let popup_height = (visible as u16 + 5).min(area.height.saturating_sub(4));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 3,
+        popup_width,
+        popup_height,
+    );
+
+    f.render_widget(Clear, popup_area);
+    let footer = if palette.matches.is_empty() {
+        " No matches ".to_string()
+    } else {
+        format!(" {}/{} ", palette.selected + 1, palette.matches.len())
+    };
+    let block = Block::default()
+        .title(format!(" Commands ({}) ", app.keymap.binding_label(AppCommand::OpenCommandPalette)))
+        .title_bottom(Line::from(footer).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.background_secondary));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let width = inner.width as usize;
+    let mut lines = vec![Line::from("")];
+    lines.extend(picker_input_lines(&palette.query, "Run a command...", inner.width, theme));
+
+    for (row, &index) in palette.matches.iter().enumerate().skip(palette.scroll_offset).take(PALETTE_VISIBLE_ITEMS) {
+        let entry = &palette.entries[index];
+        let is_selected = row == palette.selected;
+        let kind = match entry.target {
+            PaletteTarget::Command(_) => "  ",
+            PaletteTarget::Ex { .. } => ": ",
+            PaletteTarget::Theme(_) => "◐ ",
+            PaletteTarget::Note(_) => "▤ ",
+            PaletteTarget::Action(_) => "» ",
+        };
+        let detail_width = entry.detail.width();
+        let label_width = width.saturating_sub(detail_width + kind.width() + 3);
+        let label = truncate(&entry.label, label_width);
+        let padding = width.saturating_sub(1 + kind.width() + label.width() + detail_width + 1);
+
+        let (label_style, detail_style, row_style) = if is_selected {
+            (
+                Style::default().fg(theme.background).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.background),
+                Style::default().bg(theme.primary),
+            )
+        } else {
+            (Style::default().fg(theme.foreground), Style::default().fg(theme.muted), Style::default())
+        };
+        lines.push(
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(kind, detail_style),
+                Span::styled(label, label_style),
+                Span::raw(" ".repeat(padding)),
+                Span::styled(entry.detail.clone(), detail_style),
+                Span::raw(" "),
+            ])
+            .style(row_style),
+        );
+    }
+    if palette.matches.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("No matching commands", Style::default().fg(theme.muted)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).style(Style::default().bg(theme.background_secondary)), inner);
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_width.saturating_sub(1)).collect();
+    format!("{truncated}…")
+}
@@ -13,6 +13,7 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::app::{
     normalize_image_destination, App, ContentItem, DialogState, DocumentRange, DocumentSnapshot, Focus, ImageState, InlineImageRect, LinkInfo, Mode,
+    QueryRowKind, WikiLinkState,
 };
 use crate::config::Theme;
 
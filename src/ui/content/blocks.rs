@@ -26,7 +26,7 @@ pub(super) fn render_content_line<F>(
     wiki_link_validator: Option<F>,
     fold_state: Option<bool>, // None = not foldable, Some(true) = folded, Some(false) = expanded
 ) where
-    F: Fn(&str) -> bool,
+    F: Fn(&str) -> WikiLinkState,
 {
     let RenderContext {
         theme,
@@ -244,7 +244,7 @@ pub(super) fn render_code_fence(f: &mut Frame, theme: &Theme, _lang: &str, area:
 
 pub(super) fn render_task_item<F>(f: &mut Frame, text: &str, checked: bool, indent: usize, context: RenderContext<'_>, wiki_link_validator: Option<F>)
 where
-    F: Fn(&str) -> bool,
+    F: Fn(&str) -> WikiLinkState,
 {
     let RenderContext {
         theme,
@@ -329,13 +329,13 @@ where
 
 pub(super) fn render_table_row(
     f: &mut Frame,
-    document: &DocumentSnapshot,
-    cells: &[DocumentRange],
+    cells: &[&str],
     is_separator: bool,
     is_header: bool,
     natural_widths: &[u16],
     alignments: &[crate::app::Alignment],
     context: RenderContext<'_>,
+    wiki_link_validator: Option<&dyn Fn(&str) -> WikiLinkState>,
 ) {
     let RenderContext {
         theme,
@@ -394,10 +394,10 @@ pub(super) fn render_table_row(
         .enumerate()
         .map(|(i, c)| {
             let w = widths.get(i).copied().unwrap_or(0);
-            let expanded = expand_tabs(document.slice(*c));
+            let expanded = expand_tabs(c);
             let mut all_visual_lines: Vec<Vec<Span<'static>>> = Vec::new();
             for logical in split_cell_by_br(&expanded) {
-                let spans = parse_inline_formatting::<fn(&str) -> bool>(logical, theme, None, None);
+                let spans = parse_inline_formatting(logical, theme, None, wiki_link_validator);
                 all_visual_lines.extend(distribute_spans_across_lines(spans, w, text_color));
             }
             if all_visual_lines.is_empty() {
@@ -467,6 +467,32 @@ pub(super) fn render_table_row(
     }
 }
 
+/// Error or empty-result line of a query block.
+pub(super) fn render_query_message(f: &mut Frame, theme: &Theme, message: &str, area: Rect, is_cursor: bool) {
+    let cursor_indicator = if is_cursor { "▶ " } else { "  " };
+    let line = Line::from(vec![
+        Span::styled(cursor_indicator, Style::default().fg(theme.warning)),
+        Span::styled(message.to_owned(), Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC)),
+    ]);
+    let style = if is_cursor { Style::default().bg(theme.selection) } else { Style::default() };
+    f.render_widget(Paragraph::new(line).style(style), area);
+}
+
+/// First row of an `![[note#^id]]` embed, naming the block it shows.
+pub(super) fn render_embed_caption(f: &mut Frame, theme: &Theme, caption: &str, area: Rect, is_cursor: bool) {
+    let cursor_indicator = if is_cursor { "▶ " } else { "  " };
+    let mut caption_style = Style::default().fg(theme.content.link).add_modifier(Modifier::ITALIC);
+    if is_cursor {
+        caption_style = caption_style.add_modifier(Modifier::UNDERLINED);
+    }
+    let line = Line::from(vec![
+        Span::styled(cursor_indicator, Style::default().fg(theme.warning)),
+        Span::styled(format!("↪ {caption}"), caption_style),
+    ]);
+    let style = if is_cursor { Style::default().bg(theme.selection) } else { Style::default() };
+    f.render_widget(Paragraph::new(line).style(style), area);
+}
+
 pub(super) fn render_inline_image_with_cursor(
     f: &mut Frame,
     app: &mut App,
@@ -94,7 +94,7 @@ pub(super) fn visible_item_height(total_height: u16, viewport_height: u16, item_
 }
 
 pub(super) fn inline_prose_text(text: &str, theme: &Theme) -> String {
-    parse_inline_formatting::<fn(&str) -> bool>(text, theme, None, None)
+    parse_inline_formatting::<fn(&str) -> WikiLinkState>(text, theme, None, None)
         .iter()
         .map(|span| span.content.as_ref())
         .collect::<String>()
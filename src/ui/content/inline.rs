@@ -164,7 +164,7 @@ pub(super) fn find_single_marker(chars: &[char], start: usize, marker: char) ->
 
 pub(super) fn parse_inline_formatting<'a, F>(text: &'a str, theme: &Theme, selected_link: Option<usize>, wiki_link_validator: Option<F>) -> Vec<Span<'a>>
 where
-    F: Fn(&str) -> bool,
+    F: Fn(&str) -> WikiLinkState,
 {
     let mut spans = Vec::new();
     let mut chars = text.char_indices().peekable();
@@ -456,13 +456,21 @@ where
                 }
 
                 let is_selected = selected_link == Some(link_index);
-                let is_valid = wiki_link_validator.as_ref().map(|validator| validator(link.target)).unwrap_or(false);
+                let link_state = wiki_link_validator
+                    .as_ref()
+                    .map(|validator| validator(link.target))
+                    .unwrap_or(WikiLinkState::Missing);
                 let style = if is_selected {
                     Style::default().fg(theme.background).bg(theme.warning).add_modifier(Modifier::BOLD)
-                } else if is_valid {
-                    Style::default().fg(content_theme.link).add_modifier(Modifier::UNDERLINED)
                 } else {
-                    Style::default().fg(content_theme.link_invalid).add_modifier(Modifier::UNDERLINED)
+                    match link_state {
+                        WikiLinkState::Exists => Style::default().fg(content_theme.link).add_modifier(Modifier::UNDERLINED),
+                        WikiLinkState::Missing => Style::default().fg(content_theme.link_invalid).add_modifier(Modifier::UNDERLINED),
+                        // Struck through so a deleted note reads differently from one never written.
+                        WikiLinkState::Trashed => Style::default()
+                            .fg(content_theme.link_invalid)
+                            .add_modifier(Modifier::UNDERLINED | Modifier::CROSSED_OUT),
+                    }
                 };
 
                 spans.push(Span::styled(link.display_text().to_string(), style));
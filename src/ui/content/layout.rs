@@ -108,6 +108,7 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
     let inline_image_height = app.config.effective_inline_image_height();
     let document = app.active_document.as_ref().expect("normal-mode content requires a document snapshot");
     let document_tables = &app.document_tables;
+    let document_queries = &app.document_queries;
     let document_link_ranges = &app.document_link_ranges;
     let mut scratch = std::mem::take(&mut app.content_render_scratch);
 
@@ -185,6 +186,10 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
                         calc_wrapped_height(&prose, 6 + *indent as usize)
                     }
                 }
+                ContentItem::EmbedLine { embed, line, .. } => match app.embed_block(*embed).map(|block| &block.lines) {
+                    Some(Ok(lines)) if *line > 0 => lines.get(*line as usize - 1).map_or(1, |text| calc_wrapped_height(text, 6)),
+                    _ => 1,
+                },
                 _ => 0,
             }));
         scratch.height_generation = app.document_generation;
@@ -227,33 +232,17 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
                     // carries a 4-char list-prefix margin that tables don't need.
                     let metadata = document_tables.get(*table as usize);
                     let column_widths = metadata.map_or(&[][..], |metadata| metadata.column_widths.as_ref());
-                    let n = column_widths.len();
-                    let overhead = 3 + 3 * n;
-                    let budget = (inner_area.width as usize).saturating_sub(overhead);
-                    let natural: Vec<usize> = column_widths.iter().map(|width| *width as usize).collect();
-                    let capped = cap_column_widths(&natural, budget);
-                    let text_color = theme.content.text;
-                    let row_lines = cells
-                        .iter()
-                        .enumerate()
-                        .map(|(i, cell)| {
-                            let w = capped.get(i).copied().unwrap_or(0);
-                            let expanded = expand_tabs(document.slice(*cell));
-                            // `<br>` inside a cell opens a new logical line; each logical line
-                            // wraps independently and stacks vertically within the cell.
-                            let mut total: usize = 0;
-                            for logical in split_cell_by_br(&expanded) {
-                                let spans = parse_inline_formatting::<fn(&str) -> bool>(logical, theme, None, None);
-                                total += distribute_spans_across_lines(spans, w, text_color).len();
-                            }
-                            total.max(1)
-                        })
-                        .max()
-                        .unwrap_or(1)
-                        .max(1);
-                    (row_lines as u16).min(max_item_height)
+                    let cells: Vec<&str> = cells.iter().map(|cell| document.slice(*cell)).collect();
+                    (table_row_line_count(&cells, column_widths, inner_area.width, theme) as u16).min(max_item_height)
                 }
             }
+            ContentItem::QueryRow { query, row, .. } => match (document_queries.get(*query as usize), row) {
+                (Some(block), QueryRowKind::Header | QueryRowKind::Row(_)) => {
+                    (table_row_line_count(&block.cells(*row), &block.metadata.column_widths, inner_area.width, theme) as u16).min(max_item_height)
+                }
+                _ => 1u16,
+            },
+            ContentItem::EmbedLine { .. } => item_text_heights[idx].max(1),
             ContentItem::Details {
                 content_lines, source_line, ..
             } => {
@@ -425,7 +414,7 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
                 let hovered_image = app.mouse_hover_inline_image.map(|(hovered_item, _)| hovered_item == item_idx).unwrap_or(false);
                 let has_link = (is_cursor_line || is_hovered) && has_text_link && !selected_is_image && !hovered_image;
                 let selected_link = if is_cursor_line { app.selected_link_index } else { 0 };
-                let wiki_validator = |target: &str| app.wiki_link_exists(target);
+                let wiki_validator = |target: &str| app.wiki_link_state(target);
                 // Get fold state for H1-H3 headings
                 let fold_state = if app.is_heading_at(item_idx) {
                     Some(app.is_heading_folded(item_idx))
@@ -466,7 +455,7 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
                 let text = app.document_slice(*text);
                 let selected_link = if is_cursor_line { app.selected_link_index } else { 0 };
                 let has_links = !app.item_all_links_at(item_idx).is_empty();
-                let wiki_validator = |target: &str| app.wiki_link_exists(target);
+                let wiki_validator = |target: &str| app.wiki_link_state(target);
                 let context = RenderContext::new(&app.theme, chunks[chunk_idx], is_cursor_line, selected_link, has_links);
                 render_task_item(f, text, *checked, *indent as usize, context, Some(wiki_validator));
                 if !skip_images {
@@ -495,18 +484,55 @@ pub fn render_content(f: &mut Frame, app: &mut App, area: Rect) {
                 let has_link = !*is_separator && (is_cursor_line || is_hovered) && !app.item_all_links_at(item_idx).is_empty();
                 let context = RenderContext::new(&app.theme, chunks[chunk_idx], is_cursor_line, 0, has_link);
                 if let (Some(document), Some(metadata)) = (app.document(), app.table_metadata(*table)) {
+                    let cells: Vec<&str> = cells.iter().map(|cell| document.slice(*cell)).collect();
                     render_table_row(
                         f,
-                        document,
-                        cells,
+                        &cells,
                         *is_separator,
                         *is_header,
                         &metadata.column_widths,
                         &metadata.alignments,
                         context,
+                        None,
                     );
                 }
             }
+            ContentItem::QueryRow { query, row, .. } => {
+                if let Some(block) = app.query_block(*query) {
+                    if *row == QueryRowKind::Message {
+                        let message = block.message.as_deref().unwrap_or_default();
+                        render_query_message(f, &app.theme, message, chunks[chunk_idx], is_cursor_line);
+                    } else {
+                        let has_link = (is_cursor_line || is_hovered) && !app.item_all_links_at(item_idx).is_empty();
+                        let context = RenderContext::new(&app.theme, chunks[chunk_idx], is_cursor_line, 0, has_link);
+                        let wiki_validator = |target: &str| app.wiki_link_state(target);
+                        render_table_row(
+                            f,
+                            &block.cells(*row),
+                            *row == QueryRowKind::Separator,
+                            *row == QueryRowKind::Header,
+                            &block.metadata.column_widths,
+                            &block.metadata.alignments,
+                            context,
+                            Some(&wiki_validator),
+                        );
+                    }
+                }
+            }
+            ContentItem::EmbedLine { embed, line, .. } => {
+                if let Some(block) = app.embed_block(*embed) {
+                    match (&block.lines, *line) {
+                        (_, 0) => render_embed_caption(f, &app.theme, &block.caption(), chunks[chunk_idx], is_cursor_line),
+                        (Ok(lines), line) => {
+                            let text = format!("> {}", lines.get(line as usize - 1).map_or("", AsRef::as_ref));
+                            let context = RenderContext::new(&app.theme, chunks[chunk_idx], is_cursor_line, 0, false);
+                            let wiki_validator = |target: &str| app.wiki_link_state(target);
+                            render_content_line(f, &text, context, Some(wiki_validator), None);
+                        }
+                        (Err(message), _) => render_query_message(f, &app.theme, message, chunks[chunk_idx], is_cursor_line),
+                    }
+                }
+            }
             ContentItem::Details {
                 summary,
                 content_lines,
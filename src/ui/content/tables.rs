@@ -16,6 +16,36 @@ pub(crate) fn cell_visible_width(cell: &str) -> usize {
     display_width.saturating_sub(marker_chars)
 }
 
+/// Visual lines a non-separator row occupies once its columns are capped to
+/// `area_width`. Must agree with `render_table_row`, which uses the same
+/// budget and wrapping.
+pub(super) fn table_row_line_count(cells: &[&str], natural_widths: &[u16], area_width: u16, theme: &Theme) -> usize {
+    // Row overhead: "  " (2) + leading │ (1) + per cell " content " (+2) + per-cell │ = 3 + 3N.
+    let overhead = 3 + 3 * natural_widths.len();
+    let budget = (area_width as usize).saturating_sub(overhead);
+    let natural: Vec<usize> = natural_widths.iter().map(|width| *width as usize).collect();
+    let capped = cap_column_widths(&natural, budget);
+    let text_color = theme.content.text;
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let w = capped.get(i).copied().unwrap_or(0);
+            let expanded = expand_tabs(cell);
+            // `<br>` inside a cell opens a new logical line; each logical line
+            // wraps independently and stacks vertically within the cell.
+            let mut total: usize = 0;
+            for logical in split_cell_by_br(&expanded) {
+                let spans = parse_inline_formatting::<fn(&str) -> WikiLinkState>(logical, theme, None, None);
+                total += distribute_spans_across_lines(spans, w, text_color).len();
+            }
+            total.max(1)
+        })
+        .max()
+        .unwrap_or(1)
+        .max(1)
+}
+
 /// Per-column minimum width when shrinking a wide table to fit the terminal.
 pub(super) const TABLE_COLUMN_MIN_WIDTH: usize = 8;
 
@@ -13,7 +13,7 @@ use super::*;
 /// Use this downstream of `parse_inline_formatting` so the parser stays the
 /// single source of truth for what counts as a markdown construct:
 /// ```ignore
-/// let spans = parse_inline_formatting(cell, theme, None, None::<fn(&str) -> bool>);
+/// let spans = parse_inline_formatting(cell, theme, None, None::<fn(&str) -> WikiLinkState>);
 /// let lines = distribute_spans_across_lines(spans, width, theme.content.text);
 /// ```
 ///
@@ -245,15 +245,15 @@ pub(crate) fn content_item_click_col(app: &App, index: usize, item_area: Rect, m
             let line = normalize_whitespace(raw_line);
             let mut spans = if line.starts_with("- ") || line.starts_with("* ") {
                 let mut spans = vec![Span::styled(cursor_indicator, Style::default()), Span::styled("• ", Style::default())];
-                spans.extend(parse_inline_formatting::<fn(&str) -> bool>(&line[2..], &app.theme, None, None));
+                spans.extend(parse_inline_formatting::<fn(&str) -> WikiLinkState>(&line[2..], &app.theme, None, None));
                 spans
             } else if line.starts_with("> ") {
                 let mut spans = vec![Span::styled(cursor_indicator, Style::default()), Span::styled("┃ ", Style::default())];
-                spans.extend(parse_inline_formatting::<fn(&str) -> bool>(&line[2..], &app.theme, None, None));
+                spans.extend(parse_inline_formatting::<fn(&str) -> WikiLinkState>(&line[2..], &app.theme, None, None));
                 spans
             } else {
                 let mut spans = vec![Span::styled(cursor_indicator, Style::default())];
-                spans.extend(parse_inline_formatting::<fn(&str) -> bool>(&line, &app.theme, None, None));
+                spans.extend(parse_inline_formatting::<fn(&str) -> WikiLinkState>(&line, &app.theme, None, None));
                 spans
             };
 
@@ -278,7 +278,7 @@ pub(crate) fn content_item_click_col(app: &App, index: usize, item_area: Rect, m
                 Span::styled("]", Style::default()),
                 Span::styled(" ", Style::default()),
             ]);
-            spans.extend(parse_inline_formatting::<fn(&str) -> bool>(&expanded_text, &app.theme, None, None));
+            spans.extend(parse_inline_formatting::<fn(&str) -> WikiLinkState>(&expanded_text, &app.theme, None, None));
 
             rendered_col_for_wrapped_click(spans, available_width, visual_row, visual_col, &app.theme)
         }
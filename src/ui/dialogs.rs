@@ -47,6 +47,7 @@ pub fn render_keybinding_warning(f: &mut Frame, app: &App) {
     let fallback = match warning.fallback {
         KeybindingFallback::Defaults => "Custom keybindings were not applied; built-in defaults are active.",
         KeybindingFallback::Previous => "Custom keybindings were not applied; the previous valid bindings remain active.",
+        KeybindingFallback::SkippedVimMappings => "Some Vim mappings were skipped; the remaining mappings are active.",
     };
     let sections = Layout::default()
         .direction(Direction::Vertical)
@@ -329,7 +330,10 @@ pub fn render_delete_confirm_dialog(f: &mut Frame, app: &App) {
 
     let content = vec![
         Line::from(""),
-        Line::from(Span::styled("Delete note?", Style::default().fg(theme.error).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(
+            "Move note to trash?",
+            Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+        )),
         Line::from(""),
         Line::from(Span::styled(note_name, Style::default().fg(theme.foreground))),
         Line::from(""),
@@ -463,13 +467,13 @@ pub fn render_delete_folder_confirm_dialog(f: &mut Frame, app: &App) {
     let content = vec![
         Line::from(""),
         Line::from(Span::styled(
-            "Delete folder and all contents?",
+            "Move folder and all contents to trash?",
             Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(folder_name, Style::default().fg(theme.foreground))),
         Line::from(""),
-        Line::from(Span::styled("This will delete all notes inside!", Style::default().fg(theme.warning))),
+        Line::from(Span::styled("Restore it from the trash browser", Style::default().fg(theme.muted))),
         Line::from(""),
         Line::from(Span::styled(
             "y: Yes  |  n: No",
@@ -850,6 +854,10 @@ pub fn render_help_dialog(f: &mut Frame, app: &mut App) {
             Span::styled(keys(AppCommand::ToggleOutline), key_style),
             Span::styled("Toggle outline", desc_style),
         ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::ToggleLocalGraph), key_style),
+            Span::styled("Toggle local graph pane", desc_style),
+        ]),
         Line::from(vec![
             Span::styled(keys(AppCommand::ShrinkPanel), key_style),
             Span::styled("Shrink focused side panel", desc_style),
@@ -878,6 +886,71 @@ pub fn render_help_dialog(f: &mut Frame, app: &mut App) {
             Span::styled(keys(AppCommand::OpenJournal), key_style),
             Span::styled("Open today's journal", desc_style),
         ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenWeeklyNote), key_style),
+            Span::styled("Open this week's note", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenMonthlyNote), key_style),
+            Span::styled("Open this month's note", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenQuarterlyNote), key_style),
+            Span::styled("Open this quarter's note", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenYearlyNote), key_style),
+            Span::styled("Open this year's note", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenAgenda), key_style),
+            Span::styled("Open task agenda", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::EditProperties), key_style),
+            Span::styled("Edit note properties", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenCommandPalette), key_style),
+            Span::styled("Command palette", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::OpenCommandLog), key_style),
+            Span::styled("Hook and action log", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::GitCommit), key_style),
+            Span::styled("Git commit vault changes", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::GitHistory), key_style),
+            Span::styled("Git note history", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::NoteHistory), key_style),
+            Span::styled("Local snapshots", desc_style),
+        ]),
+        Line::from(vec![Span::styled(keys(AppCommand::OpenTrash), key_style), Span::styled("Trash", desc_style)]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::ExtractSection), key_style),
+            Span::styled("Extract section to note (:extract in edit)", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::MergeNote), key_style),
+            Span::styled("Merge note into… (ctrl+e in file picker)", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::RenameHeading), key_style),
+            Span::styled("Rename heading and its links", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::CopyBlockLink), key_style),
+            Span::styled("Copy block link ([[note#^id]])", desc_style),
+        ]),
+        Line::from(vec![
+            Span::styled(keys(AppCommand::VaultDoctor), key_style),
+            Span::styled("Vault doctor (broken links, orphans…)", desc_style),
+        ]),
         Line::from(vec![
             Span::styled(keys(AppCommand::ToggleZen), key_style),
             Span::styled("Toggle zen mode", desc_style),
@@ -0,0 +1,90 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Vault health report: one row per issue, errors before warnings.
+pub fn render_vault_doctor(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 3 / 4).max(50).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 3 / 5).max(8).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let vault_root = app.config.notes_path();
+    let theme = &app.theme;
+    let Some(doctor) = app.doctor.as_mut() else {
+        return;
+    };
+
+    let errors = doctor.issues.iter().filter(|issue| issue.kind.is_error()).count();
+    let warnings = doctor.issues.len() - errors;
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            format!(" Vault doctor · {errors} errors · {warnings} warnings "),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                " j/k select · enter open · f fix · r recheck · esc close ",
+                Style::default().fg(theme.muted),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if doctor.issues.is_empty() {
+        f.render_widget(Paragraph::new(Span::styled(" No problems found.", Style::default().fg(theme.success))), inner);
+        return;
+    }
+
+    let visible = (inner.height as usize).max(1);
+    if doctor.selected < doctor.scroll {
+        doctor.scroll = doctor.selected;
+    } else if doctor.selected >= doctor.scroll + visible {
+        doctor.scroll = doctor.selected + 1 - visible;
+    }
+    let label_width = doctor.issues.iter().map(|issue| issue.kind.label().len()).max().unwrap_or(0);
+    let lines: Vec<Line> = doctor
+        .issues
+        .iter()
+        .enumerate()
+        .skip(doctor.scroll)
+        .take(visible)
+        .map(|(index, issue)| {
+            let selected = index == doctor.selected;
+            let location = issue.path.strip_prefix(&vault_root).unwrap_or(&issue.path).to_string_lossy().replace('\\', "/");
+            let location = match issue.line {
+                Some(line) => format!("{location}:{}", line + 1),
+                None => location,
+            };
+            let text = if selected {
+                Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog.text)
+            };
+            let severity = if issue.kind.is_error() { theme.error } else { theme.warning };
+            Line::from(vec![
+                Span::styled(if selected { "▸ " } else { "  " }, Style::default().fg(theme.info)),
+                Span::styled(format!("{:label_width$}  ", issue.kind.label()), Style::default().fg(severity)),
+                Span::styled(location, text),
+                Span::styled(format!("  {}", issue.detail), Style::default().fg(theme.muted)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -73,8 +73,11 @@ pub fn render_search_picker(f: &mut Frame, app: &mut App) {
         f.render_widget(Clear, popup_area);
 
         // Render the main popup border first
-        let popup_block = Block::default()
-            .title(format!(" Search ({}) ", app.keymap.binding_label(AppCommand::OpenQuickSearch)))
+        let mut popup_block = Block::default().title(format!(" Search ({}) ", app.keymap.binding_label(AppCommand::OpenQuickSearch)));
+        if *mode == SearchPickerMode::Files && results_len > 0 {
+            popup_block = popup_block.title_bottom(Line::from(" ctrl+e merge into… ").style(Style::default().fg(theme.muted)));
+        }
+        let popup_block = popup_block
             .title_bottom(
                 Line::from(if results_len == 0 {
                     if *search_in_progress {
@@ -138,22 +141,7 @@ pub fn render_search_picker(f: &mut Frame, app: &mut App) {
             "Search content..."
         };
 
-        let input_line = if query.is_empty() {
-            Line::from(vec![Span::raw(" "), Span::styled(placeholder, Style::default().fg(theme.muted))])
-        } else {
-            Line::from(vec![
-                Span::raw(" "),
-                Span::styled(query.clone(), Style::default().fg(theme.foreground)),
-                Span::styled("█", Style::default().fg(theme.primary)),
-            ])
-        };
-        header_lines.push(input_line);
-
-        // Separator line
-        header_lines.push(Line::from(Span::styled(
-            "─".repeat(inner_area.width as usize),
-            Style::default().fg(theme.muted),
-        )));
+        header_lines.extend(picker_input_lines(query, placeholder, inner_area.width, theme));
 
         let header_height = header_lines.len() as u16;
         let header_area = Rect::new(inner_area.x, inner_area.y, inner_area.width, header_height);
@@ -296,6 +284,21 @@ pub fn render_search_picker(f: &mut Frame, app: &mut App) {
     }
 }
 
+/// Query input followed by a full-width separator, shared by the pickers.
+pub(super) fn picker_input_lines<'a>(query: &str, placeholder: &'a str, width: u16, theme: &crate::config::Theme) -> [Line<'a>; 2] {
+    let input_line = if query.is_empty() {
+        Line::from(vec![Span::raw(" "), Span::styled(placeholder, Style::default().fg(theme.muted))])
+    } else {
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(query.to_string(), Style::default().fg(theme.foreground)),
+            Span::styled("█", Style::default().fg(theme.primary)),
+        ])
+    };
+    let separator = Line::from(Span::styled("─".repeat(width as usize), Style::default().fg(theme.muted)));
+    [input_line, separator]
+}
+
 fn render_file_results(
     lines: &mut Vec<Line>,
     results: &[crate::app::FilePickerResult],
@@ -0,0 +1,194 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, GitHistoryState};
+use crate::config::Theme;
+use ekphos_core::diff::DiffOp;
+
+/// Unchanged runs longer than this are folded in the history diff.
+const DIFF_CONTEXT: usize = 3;
+
+pub fn render_git_commit_dialog(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let theme = &app.theme;
+
+    let has_error = app.dialog_error.is_some();
+    let dialog_height = if has_error { 11 } else { 9 };
+    let dialog_width = 60.min(area.width.saturating_sub(4));
+    let dialog_height = dialog_height.min(area.height.saturating_sub(4));
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    f.render_widget(Clear, dialog_area);
+
+    let changed = app.git_change_count();
+    let mut content = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Commit {changed} changed file{}:", if changed == 1 { "" } else { "s" }),
+            Style::default().fg(theme.foreground),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.info)),
+            Span::styled(&app.input_buffer, Style::default().fg(theme.foreground)),
+            Span::styled("█", Style::default().fg(theme.cursor)),
+        ]),
+    ];
+
+    if let Some(ref error) = app.dialog_error {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(error.as_str(), Style::default().fg(theme.error))));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        if app.git_committing() {
+            "Committing…"
+        } else {
+            "Enter: Commit vault changes  |  Esc: Cancel"
+        },
+        Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+    )));
+
+    let border_color = if has_error { theme.error } else { theme.info };
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(" Git Commit ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color))
+                .style(Style::default().bg(theme.background)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(dialog, dialog_area);
+}
+
+/// Commits touching the current note on the left, the selected version
+/// diffed against the note as it is now on the right.
+pub fn render_git_history(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 9 / 10).max(40).min(area.width.saturating_sub(2));
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let Some(history) = app.git_history.as_mut() else {
+        return;
+    };
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            format!(" History · {} ", history.title),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                " j/k commit · J/K ctrl+d/u scroll diff · enter restore · esc close ",
+                Style::default().fg(theme.muted),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(38.min(inner.width / 2)), Constraint::Min(10)])
+        .split(inner);
+
+    // Commit list, two rows per commit.
+    let visible_commits = (columns[0].height as usize / 2).max(1);
+    if history.selected < history.list_scroll {
+        history.list_scroll = history.selected;
+    } else if history.selected >= history.list_scroll + visible_commits {
+        history.list_scroll = history.selected + 1 - visible_commits;
+    }
+    let mut commit_lines = Vec::new();
+    for (index, commit) in history.commits.iter().enumerate().skip(history.list_scroll).take(visible_commits) {
+        let selected = index == history.selected;
+        let marker = if selected { "▸ " } else { "  " };
+        let subject_style = if selected {
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dialog.text)
+        };
+        commit_lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(theme.info)),
+            Span::styled(commit.subject.clone(), subject_style),
+        ]));
+        commit_lines.push(Line::from(Span::styled(
+            format!("  {} {} · {}", commit.short_hash, commit.date, commit.author),
+            Style::default().fg(theme.muted),
+        )));
+    }
+    f.render_widget(
+        Paragraph::new(commit_lines).block(Block::default().borders(Borders::RIGHT).border_style(Style::default().fg(theme.dialog.border))),
+        columns[0],
+    );
+
+    let mut diff_lines: Vec<Line> = Vec::new();
+    if let Some(error) = &history.error {
+        diff_lines.push(Line::from(Span::styled(format!(" {error}"), Style::default().fg(theme.error))));
+    } else if history.loading {
+        diff_lines.push(Line::from(Span::styled(" Loading…", Style::default().fg(theme.muted))));
+    } else if !history.diff.iter().any(|op| !matches!(op, DiffOp::Equal { .. })) {
+        diff_lines.push(Line::from(Span::styled(" Identical to the current note.", Style::default().fg(theme.muted))));
+    }
+    let mut index = 0;
+    while index < history.diff.len() {
+        let run = history.diff[index..].iter().take_while(|op| matches!(op, DiffOp::Equal { .. })).count();
+        let keep_before = if index == 0 { 0 } else { DIFF_CONTEXT };
+        let keep_after = if index + run == history.diff.len() { 0 } else { DIFF_CONTEXT };
+        if run > keep_before + keep_after + 1 {
+            for op in &history.diff[index..index + keep_before] {
+                diff_lines.push(diff_line(op, history, theme));
+            }
+            diff_lines.push(Line::from(Span::styled(
+                format!(" ⋯ {} unchanged lines", run - keep_before - keep_after),
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+            )));
+            for op in &history.diff[index + run - keep_after..index + run] {
+                diff_lines.push(diff_line(op, history, theme));
+            }
+            index += run;
+            continue;
+        }
+        diff_lines.push(diff_line(&history.diff[index], history, theme));
+        index += 1;
+    }
+
+    let max_scroll = diff_lines.len().saturating_sub(columns[1].height as usize);
+    history.diff_scroll = history.diff_scroll.min(max_scroll);
+    let visible: Vec<Line> = diff_lines.into_iter().skip(history.diff_scroll).collect();
+    f.render_widget(Paragraph::new(visible), columns[1]);
+}
+
+/// `-` lines exist only in the selected version, `+` lines only now.
+fn diff_line(op: &DiffOp, history: &GitHistoryState, theme: &Theme) -> Line<'static> {
+    let (sign, line, color) = match *op {
+        DiffOp::Equal { new, .. } => (' ', &history.current[new], theme.dialog.text),
+        DiffOp::Delete { old } => ('-', &history.version[old], theme.error),
+        DiffOp::Insert { new } => ('+', &history.current[new], theme.success),
+    };
+    Line::from(Span::styled(format!(" {sign} {line}"), Style::default().fg(color)))
+}
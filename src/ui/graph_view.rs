@@ -12,9 +12,10 @@ use ratatui::{
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::app::App;
+use crate::app::{App, GraphGroupField};
 use crate::config::Theme;
-use ekphos_graph::{fit_zoom_for_bounds, GraphIndexNode, GraphMode, GraphNode, GraphRelation};
+use ekphos_core::NoteId;
+use ekphos_graph::{fit_zoom_for_bounds, format_day, CentralityMetric, GraphIndexNode, GraphMode, GraphNode, GraphRelation};
 
 const NODE_WIDTH: u16 = 3;
 const NODE_HEIGHT: u16 = 2;
@@ -91,6 +92,13 @@ pub fn render_graph_view(frame: &mut Frame, app: &mut App) {
     }
 
     render_bottom_status(frame, app, inner, bottom_rows);
+    render_group_legend(frame, app, graph_area);
+    if app.graph_view.centrality.is_some() {
+        render_centrality_panel(frame, app, graph_area);
+    }
+    if app.graph_view.groups_editor.is_some() {
+        render_groups_editor(frame, app, area);
+    }
     if app.graph_view.help_visible {
         render_help_overlay(frame, app, area);
     }
@@ -124,6 +132,21 @@ fn render_top_status(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("▏", Style::default().fg(theme.primary)),
             Span::styled("  Enter apply · Esc cancel", Style::default().fg(theme.muted)),
         ])
+    } else if app.graph_view.export_prompt {
+        Line::from(vec![
+            Span::styled(" Export › ", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+            key("d", theme),
+            hint(" DOT  ", theme),
+            key("g", theme),
+            hint(" GraphML  ", theme),
+            key("j", theme),
+            hint(" JSON  ", theme),
+            key("p", theme),
+            hint(if app.graph_view.export_positions { " positions on" } else { " positions off" }, theme),
+            hint("  Esc cancel", theme),
+        ])
+    } else if let Some(source) = app.graph_view.path_source {
+        path_status_line(app, source)
     } else if let Some(selected) = app.graph_view.selected_node.and_then(|idx| app.graph_view.nodes.get(idx)) {
         let metadata = graph_node_metadata(app, selected);
         Line::from(vec![
@@ -136,6 +159,7 @@ fn render_top_status(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(theme.muted),
             ),
             Span::styled(format!("←{}  →{}", selected.in_degree, selected.out_degree), Style::default().fg(theme.info)),
+            Span::styled(if is_pinned(app, selected) { "  pinned" } else { "" }, Style::default().fg(theme.muted)),
         ])
     } else {
         Line::from(Span::styled(
@@ -146,6 +170,43 @@ fn render_top_status(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(line), area);
 }
 
+fn path_status_line(app: &App, source: NoteId) -> Line<'static> {
+    let theme = &app.theme;
+    let title = |note_id: NoteId| {
+        app.graph_index
+            .as_ref()
+            .and_then(|index| index.metadata_for_note(note_id))
+            .map_or_else(String::new, |node| node.title.clone())
+    };
+    let mut spans = vec![Span::styled(" Path › ", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))];
+    let target = app
+        .graph_view
+        .selected_node
+        .and_then(|idx| app.graph_view.nodes.get(idx))
+        .map(|node| node.note_id);
+    match target {
+        Some(target) if target != source && app.graph_view.path.is_empty() => {
+            spans.push(Span::styled(
+                format!("no route from {} to {}", title(source), title(target)),
+                Style::default().fg(theme.error),
+            ));
+        }
+        Some(target) if target != source => {
+            let route = app.graph_view.path.iter().map(|&note_id| title(note_id)).collect::<Vec<_>>().join(" → ");
+            spans.push(Span::styled(route, Style::default().fg(theme.dialog.text)));
+            spans.push(Span::styled(
+                format!("  {} hops", app.graph_view.path.len().saturating_sub(1)),
+                Style::default().fg(theme.info),
+            ));
+        }
+        _ => {
+            spans.push(Span::styled(format!("from {}", title(source)), Style::default().fg(theme.dialog.text)));
+            spans.push(Span::styled("  select another note · m clears", Style::default().fg(theme.muted)));
+        }
+    }
+    Line::from(spans)
+}
+
 fn render_bottom_status(frame: &mut Frame, app: &App, inner: Rect, rows: u16) {
     let theme = &app.theme;
     let status_y = inner.y + inner.height.saturating_sub(rows);
@@ -163,9 +224,14 @@ fn render_bottom_status(frame: &mut Frame, app: &App, inner: Rect, rows: u16) {
             Style::default().fg(theme.muted),
         ));
     }
+    if app.graph_view.color_clusters {
+        status.push(Span::styled("  clusters", Style::default().fg(theme.muted)));
+    }
     frame.render_widget(Paragraph::new(Line::from(status)), Rect::new(inner.x, status_y, inner.width, 1));
 
-    if rows >= 2 {
+    if rows >= 2 && app.graph_view.timeline.is_some() {
+        render_timeline(frame, app, Rect::new(inner.x, status_y + 1, inner.width, 1));
+    } else if rows >= 2 {
         let help = Line::from(vec![
             key("Enter", theme),
             hint(" open  ", theme),
@@ -188,6 +254,40 @@ fn render_bottom_status(frame: &mut Frame, app: &App, inner: Rect, rows: u16) {
     }
 }
 
+/// `▶ 2024-03-01 ━━━━━━●────── 2021-01-04 … 2026-10-18`, then key hints.
+fn render_timeline(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(timeline) = &app.graph_view.timeline else {
+        return;
+    };
+    let theme = &app.theme;
+    let (first, last) = (format_day(timeline.first), format_day(timeline.last));
+    let hints = vec![
+        key("T", theme),
+        hint(if timeline.playing { " pause  " } else { " play  " }, theme),
+        key(",/.", theme),
+        hint(" step  ", theme),
+        key("t", theme),
+        hint(" close", theme),
+    ];
+    let hints_width: usize = hints.iter().map(|span| span.content.width()).sum();
+    let fixed = 1 + 2 + 10 + 1 + 1 + first.width() + 3 + last.width() + 2 + hints_width;
+    let track = (area.width as usize).saturating_sub(fixed).max(4);
+    let span = (timeline.last - timeline.first).max(1) as f32;
+    let filled = (((timeline.cursor - timeline.first) as f32 / span) * (track - 1) as f32).round() as usize;
+
+    let mut spans = vec![
+        Span::styled(if timeline.playing { " ▶ " } else { " ‖ " }, Style::default().fg(theme.warning)),
+        Span::styled(format_day(timeline.cursor), Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+        Span::raw(" "),
+        Span::styled("━".repeat(filled), Style::default().fg(theme.primary)),
+        Span::styled("●", Style::default().fg(theme.warning)),
+        Span::styled("─".repeat(track - 1 - filled), Style::default().fg(theme.muted)),
+        Span::styled(format!(" {first} … {last}  "), Style::default().fg(theme.muted)),
+    ];
+    spans.extend(hints);
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 fn key(text: &'static str, theme: &Theme) -> Span<'static> {
     Span::styled(text, Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
 }
@@ -204,6 +304,7 @@ fn render_graph(buffer: &mut Buffer, app: &App, area: Rect) {
     let connected = connected_nodes(app, selected);
 
     render_edges(buffer, app, area, level, selected);
+    render_path(buffer, app, area);
     match level {
         DetailLevel::Overview => render_overview_nodes(buffer, app, area, &connected),
         DetailLevel::Compact => render_compact_nodes(buffer, app, area, &connected),
@@ -334,6 +435,28 @@ fn render_edges(buffer: &mut Buffer, app: &App, area: Rect, level: DetailLevel,
     }
 }
 
+/// The traced shortest path is painted over every other link. Hops whose
+/// endpoints fall outside the projection are left to the status row.
+fn render_path(buffer: &mut Buffer, app: &App, area: Rect) {
+    if app.graph_view.path.len() < 2 {
+        return;
+    }
+    let positions: HashMap<NoteId, &GraphNode> = app
+        .graph_view
+        .nodes
+        .iter()
+        .filter(|node| app.graph_view.path.contains(&node.note_id))
+        .map(|node| (node.note_id, node))
+        .collect();
+    for hop in app.graph_view.path.windows(2) {
+        if let (Some(from), Some(to)) = (positions.get(&hop[0]), positions.get(&hop[1])) {
+            let from = screen_position(app, area, from);
+            let to = screen_position(app, area, to);
+            draw_clipped_line(buffer, from, to, area, app.theme.warning, true);
+        }
+    }
+}
+
 fn render_overview_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected: &HashSet<usize>) {
     let mut bins: HashMap<(i32, i32), OverviewBin> = HashMap::new();
     for (idx, node) in app.graph_view.nodes.iter().enumerate() {
@@ -363,12 +486,15 @@ fn render_overview_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected:
     }
     for ((x, y), bin) in bins {
         let glyph = overview_glyph(bin);
-        let color = if bin.selected || bin.connected {
+        let representative = &app.graph_view.nodes[bin.representative];
+        let color = if on_path(app, representative) {
+            app.theme.warning
+        } else if bin.selected || bin.connected {
             app.theme.primary
         } else if bin.root {
             app.theme.warning
         } else {
-            relation_color(&app.graph_view.nodes[bin.representative], &app.theme, false)
+            node_color(app, bin.representative, false)
         };
         put(buffer, x, y, glyph, color, area);
     }
@@ -426,10 +552,12 @@ fn render_compact_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected: &
     for ((x, y), bin) in bins {
         let node = &app.graph_view.nodes[bin.representative];
         let dimmed = app.graph_view.selected_node.is_some() && !bin.connected;
-        let color = if bin.connected {
+        let color = if on_path(app, node) {
+            app.theme.warning
+        } else if bin.connected {
             app.theme.primary
         } else {
-            relation_color(node, &app.theme, dimmed)
+            node_color(app, bin.representative, dimmed)
         };
         let glyph = if bin.selected {
             '◆'
@@ -460,12 +588,17 @@ fn render_detail_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected: &H
                 continue;
             }
             let dimmed = app.graph_view.selected_node.is_some() && !connected.contains(&idx);
-            let color = if connected.contains(&idx) {
+            let color = if on_path(app, node) {
+                app.theme.warning
+            } else if connected.contains(&idx) {
                 app.theme.primary
             } else {
-                relation_color(node, &app.theme, dimmed)
+                node_color(app, idx, dimmed)
             };
             draw_box_node(buffer, x, y, color, selected, node.relation == GraphRelation::Root, area);
+            if is_pinned(app, node) {
+                put(buffer, x + 1, y + 1, '•', color, area);
+            }
             for px in x..x + NODE_WIDTH as i32 {
                 for py in y..y + NODE_HEIGHT as i32 {
                     if contains(area, px, py) {
@@ -491,15 +624,18 @@ fn render_detail_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected: &H
             break;
         }
         let node = &app.graph_view.nodes[idx];
-        let dimmed = app.graph_view.selected_node.is_some() && !connected.contains(&idx);
+        let path_member = on_path(app, node);
+        let dimmed = app.graph_view.selected_node.is_some() && !connected.contains(&idx) && !path_member;
         if dimmed {
             continue;
         }
         let (x, y) = screen_position(app, area, node);
-        let color = if connected.contains(&idx) {
+        let color = if path_member {
+            app.theme.warning
+        } else if connected.contains(&idx) {
             app.theme.primary
         } else {
-            relation_color(node, &app.theme, false)
+            node_color(app, idx, false)
         };
         let title = graph_node_metadata(app, node).map_or("", |metadata| metadata.title.as_str());
         if place_label(buffer, title, x + 1, y + 1, color, area, &mut occupied) {
@@ -508,7 +644,47 @@ fn render_detail_nodes(buffer: &mut Buffer, app: &App, area: Rect, connected: &H
     }
 }
 
-fn relation_color(node: &GraphNode, theme: &Theme, dimmed: bool) -> Color {
+fn is_pinned(app: &App, node: &GraphNode) -> bool {
+    app.graph_view.mode == GraphMode::Global
+        && !app.graph_view.pins.is_empty()
+        && graph_node_metadata(app, node).is_some_and(|metadata| app.graph_view.pins.get(&metadata.path).is_some())
+}
+
+fn on_path(app: &App, node: &GraphNode) -> bool {
+    app.graph_view.path.len() > 1 && app.graph_view.path.contains(&node.note_id)
+}
+
+/// Cluster colouring, when toggled on, wins over the configured colour
+/// groups, which win over relation colours. Tag and attachment nodes belong
+/// to no cluster and fall through.
+fn node_color(app: &App, idx: usize, dimmed: bool) -> Color {
+    let node = &app.graph_view.nodes[idx];
+    if dimmed {
+        return app.theme.muted;
+    }
+    if !app.graph_view.color_clusters || node.cluster == 0 {
+        return group_color(app, idx).unwrap_or_else(|| relation_color(node, &app.theme, false));
+    }
+    let theme = &app.theme;
+    let palette = [
+        theme.info,
+        theme.success,
+        theme.secondary,
+        theme.content.heading1,
+        theme.content.heading2,
+        theme.content.heading3,
+        theme.content.heading4,
+        theme.content.tag,
+    ];
+    palette[(node.cluster as usize - 1) % palette.len()]
+}
+
+fn group_color(app: &App, idx: usize) -> Option<Color> {
+    let group = app.graph_view.node_groups.get(idx).copied().flatten()?;
+    app.config.graph.groups.get(group)?.resolve_color(&app.theme)
+}
+
+pub(super) fn relation_color(node: &GraphNode, theme: &Theme, dimmed: bool) -> Color {
     if dimmed {
         return theme.muted;
     }
@@ -518,6 +694,8 @@ fn relation_color(node: &GraphNode, theme: &Theme, dimmed: bool) -> Color {
         GraphRelation::Outgoing => theme.info,
         GraphRelation::Bidirectional => theme.success,
         GraphRelation::Neutral => theme.foreground,
+        GraphRelation::Tag => theme.content.tag,
+        GraphRelation::Attachment => theme.content.code,
     }
 }
 
@@ -620,7 +798,7 @@ fn draw_arrow(buffer: &mut Buffer, from: (i32, i32), to: (i32, i32), area: Rect,
     put(buffer, position.0, position.1, arrow, color, area);
 }
 
-fn draw_clipped_line(buffer: &mut Buffer, from: (i32, i32), to: (i32, i32), area: Rect, color: Color, overwrite: bool) {
+pub(super) fn draw_clipped_line(buffer: &mut Buffer, from: (i32, i32), to: (i32, i32), area: Rect, color: Color, overwrite: bool) {
     let Some(((mut x0, mut y0), (x1, y1))) = clip_line(from, to, area) else {
         return;
     };
@@ -687,7 +865,7 @@ fn clip_line(from: (i32, i32), to: (i32, i32), area: Rect) -> Option<((i32, i32)
     ))
 }
 
-fn put(buffer: &mut Buffer, x: i32, y: i32, ch: char, color: Color, area: Rect) {
+pub(super) fn put(buffer: &mut Buffer, x: i32, y: i32, ch: char, color: Color, area: Rect) {
     if contains(area, x, y) {
         if let Some(cell) = buffer.cell_mut((x as u16, y as u16)) {
             cell.set_char(ch);
@@ -737,9 +915,181 @@ fn render_centered_message(frame: &mut Frame, area: Rect, message: &str, color:
     );
 }
 
+fn render_centrality_panel(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(panel) = &app.graph_view.centrality else {
+        return;
+    };
+    let theme = &app.theme;
+    let width = area.width.min(44);
+    let height = area.height.min(panel.entries.len().max(1) as u16 + 2);
+    if width < 12 || height < 3 {
+        return;
+    }
+    let popup = Rect::new(area.right() - width, area.y, width, height);
+    let visible = height.saturating_sub(2) as usize;
+    let offset = panel.selected.saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = if panel.loading {
+        vec![Line::from(Span::styled(" Ranking…", Style::default().fg(theme.muted)))]
+    } else if panel.entries.is_empty() {
+        vec![Line::from(Span::styled(" No linked notes", Style::default().fg(theme.muted)))]
+    } else {
+        panel
+            .entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(rank, &(note_id, score))| {
+                let title = app
+                    .graph_index
+                    .as_ref()
+                    .and_then(|index| index.metadata_for_note(note_id))
+                    .map_or("", |node| node.title.as_str());
+                let score = match panel.metric {
+                    CentralityMetric::PageRank => format!("{:.4}", score),
+                    CentralityMetric::Betweenness => format!("{:.0}", score),
+                };
+                let selected = rank == panel.selected;
+                let style = if selected {
+                    Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.dialog.text)
+                };
+                let title_width = (width as usize).saturating_sub(score.len() + 9);
+                let title: String = title
+                    .chars()
+                    .scan(0usize, |used, ch| {
+                        *used += ch.width().unwrap_or(1);
+                        (*used <= title_width).then_some(ch)
+                    })
+                    .collect();
+                Line::from(vec![
+                    Span::styled(format!("{}{:>3}. ", if selected { "›" } else { " " }, rank + 1), style),
+                    Span::styled(format!("{title}{}", " ".repeat(title_width.saturating_sub(title.width()))), style),
+                    Span::styled(format!(" {score}"), Style::default().fg(theme.muted)),
+                ])
+            })
+            .collect()
+    };
+    let block = Block::default()
+        .title(format!(" Most central · {} ", panel.metric.label()))
+        .title_bottom(Line::from(Span::styled(" Tab metric · Enter select ", Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary))
+        .style(Style::default().bg(theme.dialog.background));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// One row per colour group in the bottom-left corner, with how many of the
+/// visible nodes it claimed.
+fn render_group_legend(frame: &mut Frame, app: &App, area: Rect) {
+    let groups = &app.config.graph.groups;
+    if groups.is_empty() || app.graph_view.nodes.is_empty() {
+        return;
+    }
+    let mut counts = vec![0usize; groups.len()];
+    for group in app.graph_view.node_groups.iter().flatten() {
+        counts[*group] += 1;
+    }
+    let lines: Vec<Line> = groups
+        .iter()
+        .zip(&counts)
+        .map(|(group, count)| {
+            let color = group.resolve_color(&app.theme).unwrap_or(app.theme.muted);
+            Line::from(vec![
+                Span::styled(" ● ", Style::default().fg(color)),
+                Span::styled(group.query.clone(), Style::default().fg(app.theme.dialog.text)),
+                Span::styled(format!(" {count} "), Style::default().fg(app.theme.muted)),
+            ])
+        })
+        .collect();
+    let width = lines.iter().map(Line::width).max().unwrap_or(0).min(area.width as usize) as u16;
+    let height = (lines.len() as u16).min(area.height / 2);
+    if width == 0 || height == 0 {
+        return;
+    }
+    let legend = Rect::new(area.x, area.bottom() - height, width, height);
+    frame.render_widget(Clear, legend);
+    frame.render_widget(Paragraph::new(lines).style(Style::default().bg(app.theme.dialog.background)), legend);
+}
+
+fn render_groups_editor(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(editor) = &app.graph_view.groups_editor else {
+        return;
+    };
+    let theme = &app.theme;
+    let groups = &app.config.graph.groups;
+    let width = area.width.saturating_sub(4).min(64);
+    let height = area.height.saturating_sub(4).min(groups.len().max(1) as u16 + 4);
+    if width < 20 || height < 5 {
+        return;
+    }
+    let popup = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    let mut lines: Vec<Line> = Vec::new();
+    if groups.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No colour groups yet; press a to add one",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for (idx, group) in groups.iter().enumerate() {
+        let selected = idx == editor.selected;
+        let editing = editor.editing.filter(|_| selected);
+        let text_style = if selected {
+            Style::default().fg(theme.primary).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dialog.text)
+        };
+        let field = |value: &str, field: GraphGroupField, style: Style| -> Vec<Span<'static>> {
+            if editing == Some(field) {
+                vec![
+                    Span::styled(editor.draft.clone(), Style::default().fg(theme.search.input)),
+                    Span::styled("▏", Style::default().fg(theme.primary)),
+                ]
+            } else {
+                vec![Span::styled(value.to_string(), style)]
+            }
+        };
+        let mut spans = vec![
+            Span::styled(if selected { " › " } else { "   " }, text_style),
+            Span::styled("● ", Style::default().fg(group.resolve_color(theme).unwrap_or(theme.muted))),
+        ];
+        spans.extend(field(&group.query, GraphGroupField::Query, text_style));
+        spans.push(Span::raw("  "));
+        spans.extend(field(&group.color, GraphGroupField::Color, Style::default().fg(theme.muted)));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(
+        " First matching group colours a node; uses the / filter syntax.",
+        Style::default().fg(theme.muted),
+    )));
+    let visible = height.saturating_sub(2) as usize;
+    let offset = (editor.selected + 3).saturating_sub(visible);
+    let footer = if editor.editing.is_some() {
+        " Enter save · Esc cancel "
+    } else {
+        " a add · Enter query · c colour · J/K reorder · d delete · Esc close "
+    };
+    let block = Block::default()
+        .title(" Graph colour groups ")
+        .title_bottom(Line::from(Span::styled(footer, Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.primary))
+        .style(Style::default().bg(theme.dialog.background));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((offset as u16, 0)), popup);
+}
+
 fn render_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
     let width = area.width.saturating_sub(2).min(72);
-    let height = area.height.saturating_sub(2).min(22);
+    let height = area.height.saturating_sub(2).min(42);
     if width < 4 || height < 4 {
         return;
     }
@@ -750,7 +1100,7 @@ fn render_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
         height,
     );
     frame.render_widget(Clear, popup);
-    let text = "EXPLORE\n\n  hjkl / arrows   select spatial neighbor\n  HJKL             pan camera\n  + / -             bounded zoom\n  f / 0             fit all, center\n  Enter             open selected note\n  Space             make selected note the Local root\n\nSHAPE THE VIEW\n\n  v / Tab           Local ↔ Global\n  [ / ]             Local depth (1–5)\n  d                 all → incoming → outgoing\n  /                 title, path, #tag filter\n  n / N             next / previous match\n  o                 toggle Global orphans\n  r                 reset graph controls\n\n  ?                 close this help";
+    let text = "EXPLORE\n\n  hjkl / arrows   select spatial neighbor\n  HJKL             pan camera\n  + / -             bounded zoom\n  f / 0             fit all, center\n  Enter             open selected note\n  Space             make selected note the Local root\n\nSHAPE THE VIEW\n\n  v / Tab           Local ↔ Global\n  [ / ]             Local depth (1–5)\n  d                 all → incoming → outgoing\n  /                 title, path:, #tag, kind:, cluster: filter\n                    before: / after: a date, month or year\n  n / N             next / previous match\n  o                 toggle Global orphans\n  r                 reset graph controls\n  e                 export as DOT, GraphML or JSON\n\nARRANGE GLOBAL\n\n  Ctrl+hjkl / drag  move node and pin it there\n  p                 pin / unpin selected node\n  P                 release every pin and re-layout\n\nANALYZE\n\n  m                 mark path start, trace to selection\n  c                 colour notes by cluster\n  C                 most central notes (Tab: metric)\n  s                 edit colour groups\n\nTIMELINE\n\n  t                 hide notes dated after the cursor\n  , / .             step back / forward\n  < / >             first / last date\n  T                 play vault growth / pause\n\n  ?                 close this help";
     let block = Block::default()
         .title(" Graph controls ")
         .borders(Borders::ALL)
@@ -788,6 +1138,7 @@ mod tests {
             relation: GraphRelation::Root,
             in_degree: 0,
             out_degree: 0,
+            cluster: 0,
         };
         assert_eq!(graph_bounds(std::slice::from_mut(&mut node)), (2.0, 3.0, 5.0, 6.0));
     }
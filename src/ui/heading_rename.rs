@@ -0,0 +1,92 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Heading rename: the new name prompt, then the links it will rewrite.
+pub fn render_heading_rename(f: &mut Frame, app: &App) {
+    let Some(state) = app.heading_rename.as_ref() else {
+        return;
+    };
+    let theme = &app.theme;
+    let area = f.area();
+    let popup_width = (area.width * 3 / 5).max(40).min(area.width.saturating_sub(4));
+    let popup_height = match state.input {
+        Some(_) => 5,
+        None => (state.renames.len() + state.affected.len() + 5) as u16,
+    }
+    .min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let (title, footer) = match state.input {
+        Some(_) => (" Rename heading ", " enter preview · esc cancel "),
+        None if state.heading_row.is_some() => (" Rename heading ", " enter/y rename · esc cancel "),
+        None => (" Heading renamed ", " enter/y update links · n keep "),
+    };
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(Line::from(Span::styled(footer, Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let label = Style::default().fg(theme.muted);
+    let text = Style::default().fg(theme.dialog.text);
+    if let Some(input) = &state.input {
+        let lines = vec![
+            Line::from(vec![Span::styled(" From ", label), Span::styled(state.renames[0].0.clone(), text)]),
+            Line::from(vec![
+                Span::styled(" To   ", label),
+                Span::styled(input.clone(), text),
+                Span::styled("█", Style::default().fg(theme.info)),
+            ]),
+        ];
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    let mut lines: Vec<Line> = state
+        .renames
+        .iter()
+        .map(|(old, new)| {
+            Line::from(vec![
+                Span::styled(format!(" #{old}"), label),
+                Span::styled(" → ", Style::default().fg(theme.info)),
+                Span::styled(format!("#{new}"), text),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    if state.affected.is_empty() {
+        lines.push(Line::from(Span::styled(" No section links to rewrite.", label)));
+    } else {
+        let total: usize = state.affected.iter().map(|(_, count)| count).sum();
+        lines.push(Line::from(Span::styled(
+            format!(" Rewrites {total} link(s) in {} file(s):", state.affected.len()),
+            label,
+        )));
+        lines.extend(state.affected.iter().map(|(file, count)| {
+            Line::from(vec![
+                Span::styled(format!("   {file}"), text),
+                Span::styled(format!("  ×{count}"), Style::default().fg(theme.info)),
+            ])
+        }));
+    }
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::graph_view::{draw_clipped_line, put, relation_color};
+use crate::app::{App, Focus, Mode};
+use ekphos_graph::GraphRelation;
+use unicode_width::UnicodeWidthStr;
+
+/// The outline column must be at least this tall before the pane shares it.
+const MIN_COLUMN_HEIGHT: u16 = 20;
+const MIN_COLUMN_WIDTH: u16 = 16;
+
+/// Split the outline column into the outline and, when the docked local graph
+/// is on and there is room, the pane below it.
+pub fn split_outline_column(app: &mut App, area: Rect) -> (Rect, Option<Rect>) {
+    if !app.local_graph_visible() || area.height < MIN_COLUMN_HEIGHT || area.width < MIN_COLUMN_WIDTH {
+        app.local_graph.area = Rect::default();
+        app.local_graph.cells.clear();
+        return (area, None);
+    }
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Percentage(45)])
+        .split(area);
+    (parts[0], Some(parts[1]))
+}
+
+pub fn render_local_graph(f: &mut Frame, app: &mut App, area: Rect) {
+    app.local_graph.area = area;
+    app.local_graph.cells.clear();
+    let theme = &app.theme;
+    let pane = &app.local_graph;
+    let focused = app.focus == Focus::LocalGraph && app.mode == Mode::Normal;
+    let border_style = Style::default().fg(if focused { theme.primary } else { theme.border });
+
+    let footer = match pane.selected.and_then(|note_id| pane.nodes.iter().position(|node| node.note_id == note_id)) {
+        Some(idx) => format!(" → {} ", pane.titles[idx]),
+        None if pane.order.is_empty() => String::new(),
+        None => format!(" {} linked ", pane.order.len()),
+    };
+    let block = Block::default()
+        .title(format!(" Links · depth {} ", app.config.graph.local_pane_depth()))
+        .title_bottom(Line::from(Span::styled(footer, Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.width < 3 || inner.height < 3 {
+        return;
+    }
+
+    if pane.nodes.len() <= 1 {
+        let message = if pane.fingerprint.is_none() {
+            "Indexing links…"
+        } else if pane.root.is_none() {
+            "No note open"
+        } else {
+            "No links"
+        };
+        let y = inner.y + inner.height / 2;
+        let paragraph = Paragraph::new(Span::styled(message, Style::default().fg(theme.muted))).alignment(Alignment::Center);
+        f.render_widget(paragraph, Rect::new(inner.x, y, inner.width, 1));
+        return;
+    }
+
+    // Fit the layout to the pane on each axis separately; the pane is far too
+    // small for the full view's zoom and box nodes.
+    let (min_x, min_y, max_x, max_y) = pane.nodes.iter().fold(
+        (f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+        |(min_x, min_y, max_x, max_y), node| (min_x.min(node.x), min_y.min(node.y), max_x.max(node.x), max_y.max(node.y)),
+    );
+    let span_x = (inner.width.saturating_sub(3)) as f32;
+    let span_y = (inner.height.saturating_sub(3)) as f32;
+    let project = |value: f32, min: f32, max: f32, span: f32, origin: u16| -> i32 {
+        let offset = if (max - min).abs() < f32::EPSILON {
+            span / 2.0
+        } else {
+            (value - min) / (max - min) * span
+        };
+        origin as i32 + 1 + offset.round() as i32
+    };
+    let positions: Vec<(i32, i32)> = pane
+        .nodes
+        .iter()
+        .map(|node| (project(node.x, min_x, max_x, span_x, inner.x), project(node.y, min_y, max_y, span_y, inner.y)))
+        .collect();
+    let selected = pane.selected.and_then(|note_id| pane.nodes.iter().position(|node| node.note_id == note_id));
+
+    let buffer = f.buffer_mut();
+    for edge in &pane.edges {
+        let touches_selected = selected.is_some_and(|idx| edge.from_index() == idx || edge.to_index() == idx);
+        let color = if touches_selected { theme.primary } else { theme.border };
+        draw_clipped_line(buffer, positions[edge.from_index()], positions[edge.to_index()], inner, color, touches_selected);
+    }
+
+    let mut occupied = HashSet::new();
+    let mut cells = Vec::with_capacity(pane.nodes.len());
+    for (idx, (node, &(x, y))) in pane.nodes.iter().zip(&positions).enumerate() {
+        let (glyph, color) = if node.relation == GraphRelation::Root {
+            ('◆', theme.warning)
+        } else if selected == Some(idx) {
+            ('●', theme.primary)
+        } else {
+            (if node.depth > 1 { '∘' } else { '○' }, relation_color(node, theme, node.depth > 1))
+        };
+        put(buffer, x, y, glyph, color, inner);
+        if let Some(cell) = buffer.cell_mut((x as u16, y as u16)).filter(|_| selected == Some(idx)) {
+            cell.set_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+        }
+        occupied.insert((x as u16, y as u16));
+        cells.push((idx, x as u16, y as u16));
+    }
+
+    // Labels for the root and the selection first, then neighbours nearest
+    // first as room allows.
+    let max_label = (inner.width as usize / 2).max(6);
+    let labelled = pane
+        .root
+        .and_then(|root| pane.nodes.iter().position(|node| node.note_id == root))
+        .into_iter()
+        .chain(selected)
+        .chain(pane.order.iter().copied());
+    let mut seen = HashSet::new();
+    for idx in labelled.filter(|idx| seen.insert(*idx)) {
+        let node = &pane.nodes[idx];
+        let color = if node.relation == GraphRelation::Root {
+            theme.warning
+        } else if selected == Some(idx) {
+            theme.primary
+        } else {
+            relation_color(node, theme, false)
+        };
+        let (x, y) = positions[idx];
+        place_pane_label(buffer, &short_title(&pane.titles[idx], max_label), (x, y), color, inner, &mut occupied);
+    }
+    app.local_graph.cells = cells;
+}
+
+/// Beside the node, else below or above it, shifted to stay inside the pane.
+/// Unlike the full view's labels these may cross edges but never a node or
+/// another label.
+fn place_pane_label(buffer: &mut Buffer, title: &str, (x, y): (i32, i32), color: Color, area: Rect, occupied: &mut HashSet<(u16, u16)>) {
+    let width = title.width() as i32;
+    if width == 0 || width > area.width as i32 {
+        return;
+    }
+    let clamp_x = |x: i32| x.clamp(area.x as i32, area.right() as i32 - width);
+    let candidates = [(x + 2, y), (x - width - 1, y), (clamp_x(x - width / 2), y + 1), (clamp_x(x - width / 2), y - 1)];
+    let Some((x, y)) = candidates.into_iter().find(|&(x, y)| {
+        x >= area.x as i32
+            && x + width <= area.right() as i32
+            && y >= area.y as i32
+            && y < area.bottom() as i32
+            && (0..width).all(|offset| !occupied.contains(&((x + offset) as u16, y as u16)))
+    }) else {
+        return;
+    };
+    buffer.set_stringn(x as u16, y as u16, title, width as usize, Style::default().fg(color));
+    occupied.extend((0..width).map(|offset| ((x + offset) as u16, y as u16)));
+}
+
+fn short_title(title: &str, max_chars: usize) -> String {
+    if title.chars().count() <= max_chars {
+        return title.to_string();
+    }
+    let mut short: String = title.chars().take(max_chars.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// "Merge into…": pick a destination, then review the dry run.
+pub fn render_merge(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let popup_width = (area.width * 3 / 5).max(40).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 3 / 5).max(8).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let Some(merge) = app.merge.as_ref() else {
+        return;
+    };
+
+    let footer = if merge.plan.is_some() {
+        " enter/y merge · esc back "
+    } else {
+        " ↑/↓ select · enter preview · esc close "
+    };
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            format!(" Merge {} into… ", merge.source_title),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(Line::from(Span::styled(footer, Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let label = Style::default().fg(theme.muted);
+    let text = Style::default().fg(theme.dialog.text);
+    if let Some(plan) = &merge.plan {
+        let mut lines = vec![
+            Line::from(vec![Span::styled(" Into     ", label), Span::styled(plan.destination_title.clone(), text)]),
+            Line::from(vec![Span::styled(" Section  ", label), Span::styled(format!("## {}", plan.heading), text)]),
+            Line::from(vec![
+                Span::styled(" New tags ", label),
+                Span::styled(
+                    if plan.new_tags.is_empty() {
+                        "none".to_string()
+                    } else {
+                        plan.new_tags.join(", ")
+                    },
+                    text,
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(" Deletes  ", label),
+                Span::styled(format!("{} (moved to the trash)", merge.source_title), Style::default().fg(theme.error)),
+            ]),
+            Line::from(""),
+        ];
+        if plan.affected.is_empty() {
+            lines.push(Line::from(Span::styled(" No links to rewrite.", label)));
+        } else {
+            let total: usize = plan.affected.iter().map(|(_, count)| count).sum();
+            lines.push(Line::from(Span::styled(
+                format!(" Rewrites {total} link(s) in {} file(s):", plan.affected.len()),
+                label,
+            )));
+            lines.extend(plan.affected.iter().map(|(file, count)| {
+                Line::from(vec![
+                    Span::styled(format!("   {file}"), text),
+                    Span::styled(format!("  ×{count}"), Style::default().fg(theme.info)),
+                ])
+            }));
+        }
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.info)),
+            Span::styled(merge.query.clone(), text),
+            Span::styled("█", Style::default().fg(theme.info)),
+        ]),
+        Line::from(""),
+    ];
+    if merge.results.is_empty() {
+        lines.push(Line::from(Span::styled(" No matching notes.", label)));
+    }
+    let visible = (inner.height as usize).saturating_sub(2).max(1);
+    let scroll = (merge.selected + 1).saturating_sub(visible);
+    lines.extend(merge.results.iter().enumerate().skip(scroll).take(visible).map(|(index, result)| {
+        let selected = index == merge.selected;
+        let style = if selected {
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+        } else {
+            text
+        };
+        let mut spans = vec![
+            Span::styled(if selected { "▸ " } else { "  " }, Style::default().fg(theme.info)),
+            Span::styled(result.display_name.clone(), style),
+        ];
+        if let Some(folder) = &result.folder_hint {
+            spans.push(Span::styled(format!("  {folder}"), label));
+        }
+        Line::from(spans)
+    }));
+    f.render_widget(Paragraph::new(lines), inner);
+}
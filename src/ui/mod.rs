@@ -1,15 +1,26 @@
+mod agenda;
+mod command_log;
+mod command_palette;
 mod content;
 mod context_menu;
 mod dialogs;
+mod doctor;
 mod editor;
 mod file_picker;
+mod git;
 mod graph_view;
+mod heading_rename;
+mod local_graph;
+mod merge;
+mod note_history;
 mod outline;
+mod properties;
 mod search_dialog;
 mod sidebar;
 mod status_bar;
 mod theme_picker;
 mod toast;
+mod trash;
 mod wiki_autocomplete;
 
 use ratatui::{
@@ -99,8 +110,12 @@ pub fn render(f: &mut Frame, app: &mut App) {
         Mode::Edit => render_editor(f, app, chunks[1]),
     }
 
-    // Render right sidebar (outline)
-    render_outline(f, app, chunks[2]);
+    // Render right sidebar (outline), with the docked local graph below it
+    let (outline_area, local_graph_area) = local_graph::split_outline_column(app, chunks[2]);
+    render_outline(f, app, outline_area);
+    if let Some(area) = local_graph_area {
+        local_graph::render_local_graph(f, app, area);
+    }
 
     // Render status bar
     render_status_bar(f, app, vertical_chunks[1]);
@@ -122,6 +137,17 @@ pub fn render(f: &mut Frame, app: &mut App) {
         DialogState::CreateWikiNote => render_create_wiki_note_dialog(f, app),
         DialogState::GraphView => graph_view::render_graph_view(f, app),
         DialogState::ThemeSelector => theme_picker::render_theme_picker(f, app),
+        DialogState::Agenda => agenda::render_agenda(f, app),
+        DialogState::Properties => properties::render_properties(f, app),
+        DialogState::CommandPalette => command_palette::render_command_palette(f, app),
+        DialogState::CommandLog => command_log::render_command_log(f, app),
+        DialogState::GitCommit => git::render_git_commit_dialog(f, app),
+        DialogState::GitHistory => git::render_git_history(f, app),
+        DialogState::NoteHistory => note_history::render_note_history(f, app),
+        DialogState::Trash => trash::render_trash(f, app),
+        DialogState::MergeNote => merge::render_merge(f, app),
+        DialogState::HeadingRename => heading_rename::render_heading_rename(f, app),
+        DialogState::VaultDoctor => doctor::render_vault_doctor(f, app),
         DialogState::None => {
             // Render welcome dialog on top if active
             if app.show_welcome {
@@ -0,0 +1,225 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{format_snapshot_time, App, NoteHistoryState};
+use ekphos_core::diff::DiffOp;
+
+/// Unchanged lines shown around each change before the rest are folded.
+const DIFF_CONTEXT: usize = 3;
+
+/// One row of the side-by-side view. Deletions and insertions of a hunk are
+/// paired up so a modified line sits next to its replacement.
+enum Row {
+    Lines { old: Option<usize>, new: Option<usize> },
+    Fold(usize),
+}
+
+/// Snapshot timeline on the left, the selected snapshot and the current
+/// buffer side by side on the right.
+pub fn render_note_history(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 9 / 10).max(50).min(area.width.saturating_sub(2));
+    let popup_height = (area.height * 4 / 5).max(10).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let Some(history) = app.note_history.as_mut() else {
+        return;
+    };
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            format!(" Snapshots · {} ", history.title),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(
+            Line::from(Span::styled(
+                " j/k snapshot · J/K ctrl+d/u scroll · enter restore · esc close ",
+                Style::default().fg(theme.muted),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(22.min(inner.width / 4)), Constraint::Min(20)])
+        .split(inner);
+
+    // Timeline, newest first.
+    let visible_snapshots = (columns[0].height as usize).max(1);
+    if history.selected < history.list_scroll {
+        history.list_scroll = history.selected;
+    } else if history.selected >= history.list_scroll + visible_snapshots {
+        history.list_scroll = history.selected + 1 - visible_snapshots;
+    }
+    let timeline: Vec<Line> = history
+        .snapshots
+        .iter()
+        .enumerate()
+        .skip(history.list_scroll)
+        .take(visible_snapshots)
+        .map(|(index, snapshot)| {
+            let selected = index == history.selected;
+            let style = if selected {
+                Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { "▸ " } else { "  " }, Style::default().fg(theme.info)),
+                Span::styled(format_snapshot_time(snapshot.time), style),
+            ])
+        })
+        .collect();
+    f.render_widget(
+        Paragraph::new(timeline).block(Block::default().borders(Borders::RIGHT).border_style(Style::default().fg(theme.dialog.border))),
+        columns[0],
+    );
+
+    let sides = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(columns[1]);
+    let halves = |area: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area)
+    };
+    let headers = halves(sides[0]);
+    let header_style = Style::default().fg(theme.muted).add_modifier(Modifier::BOLD);
+    let snapshot_label = history
+        .snapshots
+        .get(history.selected)
+        .map(|snapshot| format!(" Snapshot {}", format_snapshot_time(snapshot.time)))
+        .unwrap_or_default();
+    f.render_widget(Paragraph::new(Span::styled(snapshot_label, header_style)), headers[0]);
+    f.render_widget(Paragraph::new(Span::styled(" Current", header_style)), headers[1]);
+
+    let bodies = halves(sides[1]);
+    if let Some(error) = &history.error {
+        f.render_widget(Paragraph::new(Span::styled(format!(" {error}"), Style::default().fg(theme.error))), sides[1]);
+        return;
+    }
+
+    let rows = side_by_side_rows(&history.diff);
+    let max_scroll = rows.len().saturating_sub(sides[1].height as usize);
+    history.diff_scroll = history.diff_scroll.min(max_scroll);
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for row in rows.iter().skip(history.diff_scroll).take(sides[1].height as usize) {
+        match *row {
+            Row::Lines { old, new } => {
+                let (old_color, new_color) = if history.diff_changed(old, new) {
+                    (theme.error, theme.success)
+                } else {
+                    (theme.dialog.text, theme.dialog.text)
+                };
+                left.push(numbered(old, &history.version, old_color, theme.muted));
+                right.push(numbered(new, &history.current, new_color, theme.muted));
+            }
+            Row::Fold(count) => {
+                let fold = Line::from(Span::styled(
+                    format!(" ⋯ {count} unchanged lines"),
+                    Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+                ));
+                left.push(fold.clone());
+                right.push(fold);
+            }
+        }
+    }
+    f.render_widget(
+        Paragraph::new(left).block(Block::default().borders(Borders::RIGHT).border_style(Style::default().fg(theme.dialog.border))),
+        bodies[0],
+    );
+    f.render_widget(Paragraph::new(right), bodies[1]);
+}
+
+impl NoteHistoryState {
+    /// Whether a paired row shows different text on each side.
+    fn diff_changed(&self, old: Option<usize>, new: Option<usize>) -> bool {
+        match (old, new) {
+            (Some(old), Some(new)) => self.version.get(old) != self.current.get(new),
+            _ => true,
+        }
+    }
+}
+
+fn numbered(index: Option<usize>, lines: &[String], color: Color, muted: Color) -> Line<'static> {
+    match index {
+        Some(index) => Line::from(vec![
+            Span::styled(format!("{:>4} ", index + 1), Style::default().fg(muted)),
+            Span::styled(lines.get(index).cloned().unwrap_or_default(), Style::default().fg(color)),
+        ]),
+        None => Line::from(""),
+    }
+}
+
+fn side_by_side_rows(diff: &[DiffOp]) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut index = 0;
+    while index < diff.len() {
+        let equal = diff[index..].iter().take_while(|op| matches!(op, DiffOp::Equal { .. })).count();
+        if equal > 0 {
+            let keep_before = if index == 0 { 0 } else { DIFF_CONTEXT };
+            let keep_after = if index + equal == diff.len() { 0 } else { DIFF_CONTEXT };
+            let equal_row = |op: &DiffOp| match *op {
+                DiffOp::Equal { old, new } => Some(Row::Lines {
+                    old: Some(old),
+                    new: Some(new),
+                }),
+                _ => None,
+            };
+            let run = &diff[index..index + equal];
+            if equal > keep_before + keep_after + 1 {
+                rows.extend(run[..keep_before].iter().filter_map(equal_row));
+                rows.push(Row::Fold(equal - keep_before - keep_after));
+                rows.extend(run[equal - keep_after..].iter().filter_map(equal_row));
+            } else {
+                rows.extend(run.iter().filter_map(equal_row));
+            }
+            index += equal;
+            continue;
+        }
+
+        let hunk = diff[index..].iter().take_while(|op| !matches!(op, DiffOp::Equal { .. })).count();
+        let deleted: Vec<usize> = diff[index..index + hunk]
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Delete { old } => Some(*old),
+                _ => None,
+            })
+            .collect();
+        let inserted: Vec<usize> = diff[index..index + hunk]
+            .iter()
+            .filter_map(|op| match op {
+                DiffOp::Insert { new } => Some(*new),
+                _ => None,
+            })
+            .collect();
+        for pair in 0..deleted.len().max(inserted.len()) {
+            rows.push(Row::Lines {
+                old: deleted.get(pair).copied(),
+                new: inserted.get(pair).copied(),
+            });
+        }
+        index += hunk;
+    }
+    rows
+}
@@ -0,0 +1,237 @@
+use chrono::Datelike;
+use ekphos_vault::properties::PropertyValue;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::app::{month_grid, App, PropertyEditor};
+
+const MIN_WIDTH: u16 = 50;
+const MAX_WIDTH: u16 = 90;
+
+/// Centered properties panel: one row per frontmatter key, with the active
+/// editor (text input, date picker or list editor) below the rows.
+pub fn render_properties(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 3 / 5).clamp(MIN_WIDTH, MAX_WIDTH).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 4 / 5).max(12).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let state = &app.properties;
+    let title = app.current_note().map_or_else(String::new, |note| format!(" Properties · {} ", note.title));
+    let hint = match &state.editor {
+        None => " ⏎ edit · a add · d delete · esc close ",
+        Some(PropertyEditor::Date { .. }) => " h/l day · j/k week · [/] month · t today · ⏎ save · esc cancel ",
+        Some(PropertyEditor::List { input: None, .. }) => " a add · d remove · ⏎ save · esc cancel ",
+        Some(_) => " tab complete · ⏎ confirm · esc cancel ",
+    };
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(Line::from(Span::styled(hint, Style::default().fg(theme.muted))).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    f.render_widget(block, popup_area);
+
+    let inner_width = popup_width.saturating_sub(2) as usize;
+    let key_width = state
+        .properties
+        .iter()
+        .map(|property| property.key.width())
+        .max()
+        .unwrap_or(0)
+        .clamp(4, inner_width / 3);
+    let mut lines: Vec<Line> = Vec::new();
+
+    if state.properties.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No properties. Press a to add one.",
+            Style::default().fg(theme.muted),
+        )));
+    }
+    for (index, property) in state.properties.iter().enumerate() {
+        let is_selected = index == state.selected;
+        let kind = property.value.kind().label();
+        let value_width = inner_width.saturating_sub(key_width + kind.len() + 7);
+        let value_spans = match &property.value {
+            PropertyValue::List(items) => items
+                .iter()
+                .flat_map(|item| {
+                    [
+                        Span::styled(format!(" {item} "), Style::default().fg(theme.dialog.text).bg(theme.selection)),
+                        Span::raw(" "),
+                    ]
+                })
+                .collect(),
+            PropertyValue::Bool(value) => vec![Span::styled(
+                if *value { "☑ true" } else { "☐ false" },
+                Style::default().fg(if *value { theme.success } else { theme.dialog.text }),
+            )],
+            PropertyValue::Date(date) => vec![
+                Span::styled(date.format("%Y-%m-%d").to_string(), Style::default().fg(theme.info)),
+                Span::styled(date.format(" %a").to_string(), Style::default().fg(theme.muted)),
+            ],
+            PropertyValue::Raw(_) => vec![Span::styled(truncate(&property.value.display(), value_width), Style::default().fg(theme.muted))],
+            PropertyValue::Text(_) | PropertyValue::Number(_) => vec![Span::styled(
+                truncate(&property.value.display(), value_width),
+                Style::default().fg(theme.dialog.text),
+            )],
+        };
+        let marker = if is_selected { " ▶ " } else { "   " };
+        let key_style = if is_selected {
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.info)
+        };
+        let mut spans = vec![
+            Span::styled(marker, Style::default().fg(theme.dialog.title)),
+            Span::styled(format!("{:<key_width$}", truncate(&property.key, key_width)), key_style),
+            Span::styled(format!(" {kind:<6} "), Style::default().fg(theme.muted)),
+        ];
+        spans.extend(value_spans);
+        let line_style = if is_selected && state.editor.is_none() {
+            Style::default().bg(theme.selection)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(spans).style(line_style));
+    }
+
+    if let Some(editor) = &state.editor {
+        lines.push(Line::from(""));
+        let label = match editor {
+            PropertyEditor::NewKey { .. } => "New property".to_owned(),
+            _ => app.selected_property().map_or_else(String::new, |property| property.key.clone()),
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {label}"),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )));
+        match editor {
+            PropertyEditor::NewKey { input } | PropertyEditor::Text { input } => {
+                lines.push(input_line(app, input));
+            }
+            PropertyEditor::Date { date } => {
+                let today = app.dependencies.clock.today();
+                lines.push(Line::from(Span::styled(
+                    format!(" {:^27}", date.format("%B %Y").to_string()),
+                    Style::default().fg(theme.dialog.text),
+                )));
+                lines.push(Line::from(Span::styled(" Mo  Tu  We  Th  Fr  Sa  Su", Style::default().fg(theme.muted))));
+                for week in month_grid(*date) {
+                    let mut spans = vec![Span::raw(" ")];
+                    for day in week {
+                        let Some(day) = day else {
+                            spans.push(Span::raw("    "));
+                            continue;
+                        };
+                        let style = if day == *date {
+                            Style::default().fg(theme.dialog.background).bg(theme.dialog.title).add_modifier(Modifier::BOLD)
+                        } else if day == today {
+                            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(theme.dialog.text)
+                        };
+                        spans.push(Span::styled(format!("{:>2}", day.day()), style));
+                        spans.push(Span::raw("  "));
+                    }
+                    lines.push(Line::from(spans));
+                }
+            }
+            PropertyEditor::List { items, selected, input } => {
+                if items.is_empty() && input.is_none() {
+                    lines.push(Line::from(Span::styled("   (empty)", Style::default().fg(theme.muted))));
+                }
+                for (index, item) in items.iter().enumerate() {
+                    let is_selected = input.is_none() && index == *selected;
+                    let marker = if is_selected { " ▶ " } else { "   " };
+                    let style = if is_selected {
+                        Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.dialog.text)
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(marker, Style::default().fg(theme.dialog.title)),
+                        Span::styled(format!("- {item}"), style),
+                    ]));
+                }
+                if let Some(input) = input {
+                    lines.push(input_line(app, input));
+                }
+            }
+        }
+        let suggestions = app.property_suggestions();
+        if !suggestions.is_empty() {
+            let mut spans = vec![Span::styled("   ", Style::default())];
+            for (index, suggestion) in suggestions.iter().enumerate() {
+                let style = if index == 0 {
+                    Style::default().fg(theme.info).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.muted)
+                };
+                spans.push(Span::styled(suggestion.clone(), style));
+                spans.push(Span::raw("  "));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    if let Some(error) = &state.error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" {error}"), Style::default().fg(theme.error))));
+    }
+
+    let inner = Rect::new(
+        popup_area.x + 1,
+        popup_area.y + 1,
+        popup_area.width.saturating_sub(2),
+        popup_area.height.saturating_sub(2),
+    );
+    // Keep the selection and the editor visible by dropping leading rows.
+    let visible = inner.height as usize;
+    let overflow = lines.len().saturating_sub(visible);
+    let skip = overflow.min(state.selected);
+    f.render_widget(Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()), inner);
+}
+
+fn input_line<'a>(app: &App, input: &str) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(" > ", Style::default().fg(app.theme.dialog.title)),
+        Span::styled(input.to_owned(), Style::default().fg(app.theme.dialog.text)),
+        Span::styled("▏", Style::default().fg(app.theme.warning)),
+    ])
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_owned();
+    }
+    let mut out = String::new();
+    let mut width = 0;
+    for character in text.chars() {
+        let character_width = character.width().unwrap_or(0);
+        if width + character_width + 1 > max_width {
+            break;
+        }
+        out.push(character);
+        width += character_width;
+    }
+    out.push('…');
+    out
+}
@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::{App, CutItem, Focus, Mode, SidebarItemKind};
+use crate::git::FileStatus;
 
 pub fn render_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
     let theme = &app.theme;
@@ -118,7 +119,28 @@ pub fn render_sidebar(f: &mut Frame, app: &mut App, area: Rect) {
                 SidebarItemKind::Folder(_) => item.display_name.as_str(),
             };
             let display = format!("{}{}{}", indent, icon, display_name);
-            ListItem::new(Line::from(Span::styled(display, style)))
+            let git_marker = match &item.kind {
+                SidebarItemKind::Note { note_id } => app
+                    .notes
+                    .iter()
+                    .find(|note| note.id == *note_id)
+                    .and_then(|note| note.file_path.as_deref())
+                    .and_then(|path| app.git_file_status(path))
+                    .map(|status| {
+                        let color = match status {
+                            FileStatus::Modified | FileStatus::Renamed => theme.warning,
+                            FileStatus::Added | FileStatus::Untracked => theme.success,
+                            FileStatus::Deleted | FileStatus::Conflicted => theme.error,
+                        };
+                        Span::styled(format!(" {}", status.marker()), Style::default().fg(color))
+                    }),
+                SidebarItemKind::Folder(folder) => app
+                    .git_folder_changed(&folder.path)
+                    .then(|| Span::styled(" •", Style::default().fg(theme.warning))),
+            };
+            let mut spans = vec![Span::styled(display, style)];
+            spans.extend(git_marker);
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
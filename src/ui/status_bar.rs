@@ -56,6 +56,7 @@ pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
                 Focus::Sidebar => "sidebar",
                 Focus::Content => "content",
                 Focus::Outline => "outline",
+                Focus::LocalGraph => "graph",
             };
             let status = app.status_message.clone();
             (mode.to_string(), String::new(), None, status)
@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::{format_snapshot_time, App, TrashConfirm};
+
+/// Deleted notes and folders, newest first, with restore and purge keys.
+pub fn render_trash(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_width = (area.width * 3 / 5).max(40).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 3 / 5).max(8).min(area.height.saturating_sub(2));
+    let popup_area = Rect::new(
+        area.width.saturating_sub(popup_width) / 2,
+        area.height.saturating_sub(popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    let theme = &app.theme;
+    let Some(trash) = app.trash.as_mut() else {
+        return;
+    };
+
+    let footer = match trash.confirm {
+        Some(TrashConfirm::Purge) => Span::styled(" Delete this item for good? y/n ", Style::default().fg(theme.error)),
+        Some(TrashConfirm::Empty) => Span::styled(
+            format!(" Delete all {} items for good? y/n ", trash.entries.len()),
+            Style::default().fg(theme.error),
+        ),
+        None => Span::styled(" j/k select · enter restore · d purge · E empty · esc close ", Style::default().fg(theme.muted)),
+    };
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(Line::from(Span::styled(
+            format!(" Trash · {} ", trash.entries.len()),
+            Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD),
+        )))
+        .title_bottom(Line::from(footer).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog.border))
+        .style(Style::default().bg(theme.dialog.background));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if trash.entries.is_empty() {
+        f.render_widget(Paragraph::new(Span::styled(" The trash is empty.", Style::default().fg(theme.muted))), inner);
+        return;
+    }
+
+    let visible = (inner.height as usize).max(1);
+    if trash.selected < trash.scroll {
+        trash.scroll = trash.selected;
+    } else if trash.selected >= trash.scroll + visible {
+        trash.scroll = trash.selected + 1 - visible;
+    }
+    let lines: Vec<Line> = trash
+        .entries
+        .iter()
+        .enumerate()
+        .skip(trash.scroll)
+        .take(visible)
+        .map(|(index, entry)| {
+            let selected = index == trash.selected;
+            let style = if selected {
+                Style::default().fg(theme.dialog.title).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog.text)
+            };
+            let deleted = format_snapshot_time(entry.deleted_at);
+            let name = entry.display_name();
+            let padding = (inner.width as usize).saturating_sub(name.chars().count() + deleted.len() + 4);
+            Line::from(vec![
+                Span::styled(if selected { "▸ " } else { "  " }, Style::default().fg(theme.info)),
+                Span::styled(name, style),
+                Span::raw(" ".repeat(padding)),
+                Span::styled(deleted, Style::default().fg(theme.muted)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}